@@ -10,6 +10,7 @@ use std::{
     ops::{Bound, RangeBounds},
 };
 
+use noodles_bgzf as bgzf;
 use noodles_csi::{
     binning_index::optimize_chunks, index::reference_sequence::bin::Chunk, BinningIndex,
 };
@@ -112,15 +113,7 @@ impl BinningIndex<ReferenceSequence> for Index {
     where
         B: RangeBounds<i32> + Clone,
     {
-        let reference_sequence = self
-            .reference_sequences()
-            .get(reference_sequence_id)
-            .ok_or_else(|| {
-                io::Error::new(
-                    io::ErrorKind::InvalidInput,
-                    format!("invalid reference sequence ID: {}", reference_sequence_id),
-                )
-            })?;
+        let reference_sequence = self.get_reference_sequence(reference_sequence_id)?;
 
         let query_bins = reference_sequence
             .query(interval.clone())
@@ -143,4 +136,16 @@ impl BinningIndex<ReferenceSequence> for Index {
 
         Ok(merged_chunks)
     }
+
+    /// Returns the minimum start offset of records that may overlap the given start position.
+    ///
+    /// This is the offset of the linear index window that includes the start position.
+    fn min_offset(
+        &self,
+        reference_sequence_id: usize,
+        start: i32,
+    ) -> io::Result<bgzf::VirtualPosition> {
+        self.get_reference_sequence(reference_sequence_id)
+            .map(|rs| rs.min_offset(start))
+    }
 }
@@ -0,0 +1,231 @@
+//! BAM coordinate-sorted index (CSI) building.
+//!
+//! A CSI can be used instead of a BAM index (BAI) to [query] a BAM file. Unlike a BAI, which is
+//! limited to reference sequences of length 2^29 - 1, the bin sizes of a CSI are set by its number
+//! of bits for the minimum interval (`min_shift`) and depth.
+//!
+//! [query]: crate::Reader::query
+//!
+//! # Examples
+//!
+//! ```no_run
+//! # use std::io;
+//! use noodles_bam as bam;
+//! let index = bam::csi::index("sample.bam", 14, 6)?;
+//! # Ok::<(), io::Error>(())
+//! ```
+
+use std::{fs::File, io, path::Path};
+
+use noodles_csi::{self as csi, index::reference_sequence::bin::Chunk};
+
+use crate::{Reader, Record};
+
+/// A BAM coordinate-sorted index (CSI) indexer.
+#[derive(Debug, Default)]
+pub struct Indexer {
+    inner: csi::index::Indexer,
+}
+
+impl Indexer {
+    /// Creates a BAM CSI indexer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bam::csi::Indexer;
+    /// let indexer = Indexer::new(14, 6);
+    /// ```
+    pub fn new(min_shift: i32, depth: i32) -> Self {
+        Self {
+            inner: csi::index::Indexer::new(min_shift, depth),
+        }
+    }
+
+    /// Adds a record.
+    ///
+    /// The record must have an associated chunk denoting its start and end position in the file.
+    /// Records must be coordinate-sorted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bam::{self as bam, csi::Indexer};
+    /// use noodles_bgzf as bgzf;
+    /// use noodles_csi::index::reference_sequence::bin::Chunk;
+    ///
+    /// let mut indexer = Indexer::new(14, 5);
+    ///
+    /// let record = bam::Record::default();
+    /// let chunk = Chunk::new(
+    ///     bgzf::VirtualPosition::from(233),
+    ///     bgzf::VirtualPosition::from(377),
+    /// );
+    ///
+    /// indexer.add_record(&record, chunk)?;
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn add_record(&mut self, record: &Record, chunk: Chunk) -> io::Result<()> {
+        let alignment_context = alignment_context(record)?;
+        self.inner.add_record(alignment_context, chunk)
+    }
+
+    /// Builds a coordinate-sorted index (CSI).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bam::csi::Indexer;
+    /// let indexer = Indexer::new(14, 5);
+    /// let index = indexer.build(1);
+    /// ```
+    pub fn build(self, reference_sequence_count: usize) -> csi::Index {
+        self.inner.build(reference_sequence_count)
+    }
+}
+
+/// Builds a coordinate-sorted index (CSI) from a BAM file.
+///
+/// The BAM file must be coordinate-sorted. `min_shift` is the number of bits for the minimum
+/// interval, and `depth` is the depth of the binning index. A BAI-compatible binning uses a
+/// `min_shift` of 14 and `depth` of 5.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use std::io;
+/// use noodles_bam as bam;
+/// use noodles_csi as csi;
+///
+/// let index = bam::csi::index("sample.bam", 14, 6)?;
+/// csi::write("sample.bam.csi", &index)?;
+/// # Ok::<(), io::Error>(())
+/// ```
+pub fn index<P>(src: P, min_shift: i32, depth: i32) -> io::Result<csi::Index>
+where
+    P: AsRef<Path>,
+{
+    let mut reader = File::open(src).map(Reader::new)?;
+    reader.read_header()?;
+    let reference_sequences = reader.read_reference_sequences()?;
+
+    let mut indexer = Indexer::new(min_shift, depth);
+    let mut record = Record::default();
+    let mut start_position = reader.virtual_position();
+
+    while reader.read_record(&mut record)? != 0 {
+        let end_position = reader.virtual_position();
+        indexer.add_record(&record, Chunk::new(start_position, end_position))?;
+        start_position = end_position;
+    }
+
+    Ok(indexer.build(reference_sequences.len()))
+}
+
+// Returns the reference sequence ID, 1-based start and end (inclusive) positions, and whether the
+// record is mapped.
+//
+// Unmapped records that are placed have an end position equal to their start position.
+pub(crate) fn alignment_context(record: &Record) -> io::Result<Option<(usize, i64, i64, bool)>> {
+    let (reference_sequence_id, start) = match (record.reference_sequence_id(), record.position()) {
+        (Some(id), Some(position)) => (id, i64::from(i32::from(position))),
+        _ => return Ok(None),
+    };
+
+    let i = usize::try_from(i32::from(reference_sequence_id))
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+    let reference_len = record.cigar().reference_len().map(|len| len as i64)?;
+    let end = start + reference_len.max(1) - 1;
+
+    let is_mapped = !record.flags().is_unmapped();
+
+    Ok(Some((i, start, end, is_mapped)))
+}
+
+#[cfg(test)]
+mod tests {
+    use noodles_sam::record::{cigar::op::Kind, Flags};
+
+    use super::*;
+    use crate::record::{cigar::Op, ReferenceSequenceId};
+
+    #[test]
+    fn test_alignment_context() -> Result<(), Box<dyn std::error::Error>> {
+        let mut record = Record::default();
+        assert!(alignment_context(&record)?.is_none());
+
+        *record.reference_sequence_id_mut() = Some(ReferenceSequenceId::try_from(1)?);
+        record.pos = 7;
+        assert_eq!(alignment_context(&record)?, Some((1, 8, 8, false)));
+
+        *record.flags_mut() = Flags::empty();
+        record.cigar_mut().push(Op::new(Kind::Match, 4)?);
+        assert_eq!(alignment_context(&record)?, Some((1, 8, 11, true)));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_query_with_long_reference_sequence() -> Result<(), Box<dyn std::error::Error>> {
+        use std::io::Cursor;
+
+        use noodles_core::Region;
+        use noodles_sam::{self as sam, header::ReferenceSequence, record::Position};
+
+        use crate::Writer;
+
+        // This is longer than the max position addressable by a BAI (2^29 - 1).
+        const START: i32 = 1 << 30;
+
+        let header = sam::Header::builder()
+            .add_reference_sequence(ReferenceSequence::new("sq0".parse()?, i32::MAX)?)
+            .build();
+
+        let reference_sequences = header.reference_sequences();
+
+        let mut writer = Writer::builder(Vec::new()).build();
+        writer.write_header(&header)?;
+        writer.write_reference_sequences(reference_sequences)?;
+
+        for position in [8, START, START + 5] {
+            let record = sam::Record::builder()
+                .set_flags(Flags::empty())
+                .set_reference_sequence_name("sq0".parse()?)
+                .set_position(Position::try_from(position)?)
+                .set_cigar("4M".parse()?)
+                .build()?;
+
+            writer.write_sam_record(reference_sequences, &record)?;
+        }
+
+        writer.try_finish()?;
+
+        let data = writer.get_ref().get_ref();
+        let mut reader = Reader::new(Cursor::new(&data[..]));
+        reader.read_header()?;
+        reader.read_reference_sequences()?;
+
+        let mut indexer = Indexer::new(14, 6);
+        let mut record = Record::default();
+        let mut start_position = reader.virtual_position();
+
+        while reader.read_record(&mut record)? != 0 {
+            let end_position = reader.virtual_position();
+            indexer.add_record(&record, Chunk::new(start_position, end_position))?;
+            start_position = end_position;
+        }
+
+        let index = indexer.build(reference_sequences.len());
+
+        let region = Region::mapped("sq0", (START + 2)..=(START + 6));
+        let positions: Vec<_> = reader
+            .query(reference_sequences, &index, &region)?
+            .map(|result| result.map(|record| record.position().map(i32::from)))
+            .collect::<io::Result<_>>()?;
+
+        assert_eq!(positions, [Some(START), Some(START + 5)]);
+
+        Ok(())
+    }
+}
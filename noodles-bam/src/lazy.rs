@@ -0,0 +1,5 @@
+//! Lazily-evaluated BAM records.
+
+pub mod record;
+
+pub use self::record::Record;
@@ -0,0 +1,429 @@
+//! Lazily-evaluated BAM record and fields.
+
+mod bounds;
+mod cigar;
+mod data;
+mod sequence;
+
+pub use self::{cigar::Cigar, data::Data, sequence::Sequence};
+
+use std::{
+    ffi::{self, CStr},
+    fmt, io,
+};
+
+use byteorder::{ByteOrder, LittleEndian};
+use noodles_sam as sam;
+
+use self::bounds::Bounds;
+use crate::record::{data::Bounds as DataBounds, ReferenceSequenceId, UNMAPPED_POSITION};
+
+/// A lazily-evaluated BAM record.
+///
+/// The record wraps the raw record buffer. The positions of the variable-length fields and the
+/// bounds of each data field are computed once when the record is read, and fields are only
+/// decoded when requested.
+///
+/// This is useful for scanning records where only a few fields are used, e.g., counting records
+/// by their flags. A lazy record can be converted to a [`crate::Record`] using [`TryFrom`].
+#[derive(Clone, Eq, PartialEq)]
+pub struct Record {
+    pub(crate) buf: Vec<u8>,
+    bounds: Bounds,
+    data_bounds: DataBounds,
+}
+
+impl Record {
+    /// Returns the reference sequence ID.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_bam::lazy;
+    /// let record = lazy::Record::default();
+    /// assert!(record.reference_sequence_id()?.is_none());
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn reference_sequence_id(&self) -> io::Result<Option<ReferenceSequenceId>> {
+        let n = LittleEndian::read_i32(&self.buf[bounds::REFERENCE_SEQUENCE_ID_RANGE]);
+        decode_reference_sequence_id(n)
+    }
+
+    /// Returns the start position.
+    ///
+    /// Despite the BAM format using 0-based positions, this normalizes the value as a 1-based
+    /// position.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_bam::lazy;
+    /// let record = lazy::Record::default();
+    /// assert!(record.position()?.is_none());
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn position(&self) -> io::Result<Option<sam::record::Position>> {
+        let n = LittleEndian::read_i32(&self.buf[bounds::POSITION_RANGE]);
+        decode_position(n)
+    }
+
+    /// Returns the mapping quality.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bam::lazy;
+    /// let record = lazy::Record::default();
+    /// assert!(record.mapping_quality().is_none());
+    /// ```
+    pub fn mapping_quality(&self) -> Option<sam::record::MappingQuality> {
+        let n = self.buf[bounds::MAPPING_QUALITY_INDEX];
+        sam::record::MappingQuality::try_from(n).ok()
+    }
+
+    /// Returns the index bin.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bam::lazy;
+    /// let record = lazy::Record::default();
+    /// assert_eq!(record.bin(), 4680);
+    /// ```
+    pub fn bin(&self) -> u16 {
+        LittleEndian::read_u16(&self.buf[bounds::BIN_RANGE])
+    }
+
+    /// Returns the SAM flags.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bam::lazy;
+    /// use noodles_sam as sam;
+    /// let record = lazy::Record::default();
+    /// assert_eq!(record.flags(), sam::record::Flags::UNMAPPED);
+    /// ```
+    pub fn flags(&self) -> sam::record::Flags {
+        let n = LittleEndian::read_u16(&self.buf[bounds::FLAGS_RANGE]);
+        sam::record::Flags::from(n)
+    }
+
+    /// Returns the mate reference sequence ID.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_bam::lazy;
+    /// let record = lazy::Record::default();
+    /// assert!(record.mate_reference_sequence_id()?.is_none());
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn mate_reference_sequence_id(&self) -> io::Result<Option<ReferenceSequenceId>> {
+        let n = LittleEndian::read_i32(&self.buf[bounds::MATE_REFERENCE_SEQUENCE_ID_RANGE]);
+        decode_reference_sequence_id(n)
+    }
+
+    /// Returns the mate start position.
+    ///
+    /// Despite the BAM format using 0-based positions, this normalizes the value as a 1-based
+    /// position.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_bam::lazy;
+    /// let record = lazy::Record::default();
+    /// assert!(record.mate_position()?.is_none());
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn mate_position(&self) -> io::Result<Option<sam::record::Position>> {
+        let n = LittleEndian::read_i32(&self.buf[bounds::MATE_POSITION_RANGE]);
+        decode_position(n)
+    }
+
+    /// Returns the template length.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bam::lazy;
+    /// let record = lazy::Record::default();
+    /// assert_eq!(record.template_length(), 0);
+    /// ```
+    pub fn template_length(&self) -> i32 {
+        LittleEndian::read_i32(&self.buf[bounds::TEMPLATE_LENGTH_RANGE])
+    }
+
+    /// Returns the read name.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::ffi;
+    /// use noodles_bam::lazy;
+    /// let record = lazy::Record::default();
+    /// assert_eq!(record.read_name()?.to_bytes(), b"*");
+    /// # Ok::<(), ffi::FromBytesWithNulError>(())
+    /// ```
+    pub fn read_name(&self) -> Result<&CStr, ffi::FromBytesWithNulError> {
+        CStr::from_bytes_with_nul(&self.buf[self.bounds.read_name_range()])
+    }
+
+    /// Returns the raw CIGAR operations.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bam::lazy;
+    /// let record = lazy::Record::default();
+    /// assert!(record.cigar().is_empty());
+    /// ```
+    pub fn cigar(&self) -> Cigar<'_> {
+        Cigar::new(&self.buf[self.bounds.cigar_range()])
+    }
+
+    /// Returns the raw sequence.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bam::lazy;
+    /// let record = lazy::Record::default();
+    /// assert!(record.sequence().is_empty());
+    /// ```
+    pub fn sequence(&self) -> Sequence<'_> {
+        let buf = &self.buf[self.bounds.sequence_range()];
+        let len = self.bounds.quality_scores_range().len();
+        Sequence::new(buf, len)
+    }
+
+    /// Returns the raw quality scores.
+    ///
+    /// Each score is a Phred quality score without an offset. If the quality scores are missing,
+    /// each score is 0xff.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bam::lazy;
+    /// let record = lazy::Record::default();
+    /// assert!(record.quality_scores().is_empty());
+    /// ```
+    pub fn quality_scores(&self) -> &[u8] {
+        &self.buf[self.bounds.quality_scores_range()]
+    }
+
+    /// Returns the raw data.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bam::lazy;
+    /// let record = lazy::Record::default();
+    /// assert!(record.data().is_empty());
+    /// ```
+    pub fn data(&self) -> Data<'_> {
+        Data::new(&self.buf[self.bounds.data_range()], &self.data_bounds)
+    }
+
+    pub(crate) fn index(&mut self) -> io::Result<()> {
+        self.bounds.update(&self.buf)?;
+        self.data_bounds.update(&self.buf[self.bounds.data_range()])
+    }
+}
+
+impl AsRef<[u8]> for Record {
+    fn as_ref(&self) -> &[u8] {
+        &self.buf
+    }
+}
+
+impl Default for Record {
+    fn default() -> Self {
+        let buf = vec![
+            0xff, 0xff, 0xff, 0xff, // ref_id = -1
+            0xff, 0xff, 0xff, 0xff, // pos = -1
+            0x02, // l_read_name = 2
+            0xff, // mapq = 255
+            0x48, 0x12, // bin = 4680
+            0x00, 0x00, // n_cigar_op = 0
+            0x04, 0x00, // flag = 4
+            0x00, 0x00, 0x00, 0x00, // l_seq = 0
+            0xff, 0xff, 0xff, 0xff, // next_ref_id = -1
+            0xff, 0xff, 0xff, 0xff, // next_pos = -1
+            0x00, 0x00, 0x00, 0x00, // tlen = 0
+            b'*', 0x00, // read_name = "*\x00"
+        ];
+
+        let mut record = Self {
+            buf,
+            bounds: Bounds::default(),
+            data_bounds: DataBounds::default(),
+        };
+
+        record.index().expect("invalid default record");
+
+        record
+    }
+}
+
+impl fmt::Debug for Record {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Record")
+            .field("reference_sequence_id", &self.reference_sequence_id())
+            .field("position", &self.position())
+            .field("mapping_quality", &self.mapping_quality())
+            .field("bin", &self.bin())
+            .field("flags", &self.flags())
+            .field(
+                "mate_reference_sequence_id",
+                &self.mate_reference_sequence_id(),
+            )
+            .field("mate_position", &self.mate_position())
+            .field("template_length", &self.template_length())
+            .field("read_name", &self.read_name())
+            .field("cigar", &self.cigar())
+            .field("sequence", &self.sequence())
+            .field("quality_scores", &self.quality_scores())
+            .field("data", &self.data())
+            .finish()
+    }
+}
+
+impl TryFrom<&Record> for crate::Record {
+    type Error = io::Error;
+
+    fn try_from(lazy_record: &Record) -> Result<Self, Self::Error> {
+        use crate::reader::record::decode_record;
+
+        let mut record = Self::default();
+        decode_record(&lazy_record.buf, &mut record)?;
+        Ok(record)
+    }
+}
+
+fn decode_reference_sequence_id(n: i32) -> io::Result<Option<ReferenceSequenceId>> {
+    use crate::record::reference_sequence_id::UNMAPPED;
+
+    match n {
+        UNMAPPED => Ok(None),
+        _ => ReferenceSequenceId::try_from(n)
+            .map(Some)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e)),
+    }
+}
+
+fn decode_position(n: i32) -> io::Result<Option<sam::record::Position>> {
+    if n == UNMAPPED_POSITION {
+        Ok(None)
+    } else {
+        n.checked_add(1)
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "invalid position"))
+            .and_then(|m| {
+                sam::record::Position::try_from(m)
+                    .map(Some)
+                    .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use noodles_sam::record::{data::field::Tag, Flags};
+
+    use super::*;
+    use crate::record::{data::field::Value, sequence::Base};
+
+    fn build_record() -> io::Result<Record> {
+        let buf = vec![
+            0x0a, 0x00, 0x00, 0x00, // ref_id = 10
+            0x85, 0xee, 0x00, 0x00, // pos = 61061
+            0x03, // l_read_name = 3
+            0x0c, // mapq = 12
+            0x4c, 0x12, // bin = 4684
+            0x01, 0x00, // n_cigar_op = 1
+            0x41, 0x00, // flag = 65
+            0x04, 0x00, 0x00, 0x00, // l_seq = 4
+            0x0a, 0x00, 0x00, 0x00, // next_ref_id = 10
+            0xe0, 0xee, 0x00, 0x00, // next_pos = 61152
+            0xa6, 0x00, 0x00, 0x00, // tlen = 166
+            b'r', b'0', 0x00, // read_name = "r0\x00"
+            0x40, 0x00, 0x00, 0x00, // cigar = 4M
+            0x18, 0x42, // seq = ATGC
+            0x1f, 0x1d, 0x1e, 0x20, // qual = @>?A
+            b'N', b'M', b'C', 0x00, // NM:C:0
+            b'P', b'G', b'Z', b'S', b'N', b'A', b'P', 0x00, // PG:Z:SNAP
+        ];
+
+        let mut record = Record {
+            buf,
+            bounds: Bounds::default(),
+            data_bounds: DataBounds::default(),
+        };
+
+        record.index()?;
+
+        Ok(record)
+    }
+
+    #[test]
+    fn test_fields() -> io::Result<()> {
+        let record = build_record()?;
+
+        assert_eq!(record.reference_sequence_id()?.map(i32::from), Some(10));
+        assert_eq!(record.position()?.map(i32::from), Some(61062));
+        assert_eq!(record.mapping_quality().map(u8::from), Some(12));
+        assert_eq!(record.bin(), 4684);
+        assert_eq!(record.flags(), Flags::SEGMENTED | Flags::FIRST_SEGMENT);
+        assert_eq!(
+            record.mate_reference_sequence_id()?.map(i32::from),
+            Some(10)
+        );
+        assert_eq!(record.mate_position()?.map(i32::from), Some(61153));
+        assert_eq!(record.template_length(), 166);
+
+        assert_eq!(
+            record.read_name().map(|name| name.to_bytes()),
+            Ok(&b"r0"[..])
+        );
+
+        assert_eq!(record.cigar().reference_len()?, 4);
+
+        let bases: Vec<_> = record.sequence().bases().collect();
+        assert_eq!(bases, [Base::A, Base::T, Base::G, Base::C]);
+
+        assert_eq!(record.quality_scores(), [0x1f, 0x1d, 0x1e, 0x20]);
+
+        let data = record.data();
+        assert_eq!(data.len(), 2);
+        assert_eq!(
+            data.get(Tag::Program)
+                .transpose()?
+                .map(|field| field.value().clone()),
+            Some(Value::String(String::from("SNAP")))
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_try_from_record_for_crate_record() -> io::Result<()> {
+        let lazy_record = build_record()?;
+        let record = crate::Record::try_from(&lazy_record)?;
+
+        assert_eq!(record.position().map(i32::from), Some(61062));
+        assert_eq!(record.cigar().as_ref(), [0x00000040]);
+        assert_eq!(record.sequence().as_ref(), [0x18, 0x42]);
+        assert_eq!(record.data().len(), 2);
+
+        let record = crate::Record::try_from(&Record::default())?;
+        assert_eq!(record, crate::Record::default());
+
+        Ok(())
+    }
+}
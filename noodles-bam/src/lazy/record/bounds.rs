@@ -0,0 +1,143 @@
+use std::{
+    io, mem,
+    ops::{Range, RangeFrom},
+};
+
+use byteorder::{ByteOrder, LittleEndian};
+
+pub(super) const REFERENCE_SEQUENCE_ID_RANGE: Range<usize> = 0..4;
+pub(super) const POSITION_RANGE: Range<usize> = 4..8;
+const READ_NAME_LENGTH_INDEX: usize = 8;
+pub(super) const MAPPING_QUALITY_INDEX: usize = 9;
+pub(super) const BIN_RANGE: Range<usize> = 10..12;
+const CIGAR_OP_COUNT_RANGE: Range<usize> = 12..14;
+pub(super) const FLAGS_RANGE: Range<usize> = 14..16;
+const SEQUENCE_LENGTH_RANGE: Range<usize> = 16..20;
+pub(super) const MATE_REFERENCE_SEQUENCE_ID_RANGE: Range<usize> = 20..24;
+pub(super) const MATE_POSITION_RANGE: Range<usize> = 24..28;
+pub(super) const TEMPLATE_LENGTH_RANGE: Range<usize> = 28..32;
+
+const READ_NAME_START: usize = 32;
+
+/// The end positions of the variable-length fields of a BAM record.
+///
+/// The data field ends at the end of the record.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) struct Bounds {
+    pub read_name_end: usize,
+    pub cigar_end: usize,
+    pub sequence_end: usize,
+    pub quality_scores_end: usize,
+}
+
+impl Bounds {
+    pub fn update(&mut self, buf: &[u8]) -> io::Result<()> {
+        if buf.len() < READ_NAME_START {
+            return Err(io::Error::from(io::ErrorKind::UnexpectedEof));
+        }
+
+        let l_read_name = usize::from(buf[READ_NAME_LENGTH_INDEX]);
+        self.read_name_end = READ_NAME_START + l_read_name;
+
+        let n_cigar_op = usize::from(LittleEndian::read_u16(&buf[CIGAR_OP_COUNT_RANGE]));
+        self.cigar_end = self.read_name_end + mem::size_of::<u32>() * n_cigar_op;
+
+        let l_seq = usize::try_from(LittleEndian::read_u32(&buf[SEQUENCE_LENGTH_RANGE]))
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+        self.sequence_end = self
+            .cigar_end
+            .checked_add(l_seq / 2 + l_seq % 2)
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "invalid sequence length"))?;
+
+        self.quality_scores_end = self
+            .sequence_end
+            .checked_add(l_seq)
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "invalid sequence length"))?;
+
+        if buf.len() < self.quality_scores_end {
+            return Err(io::Error::from(io::ErrorKind::UnexpectedEof));
+        }
+
+        Ok(())
+    }
+
+    pub fn read_name_range(&self) -> Range<usize> {
+        READ_NAME_START..self.read_name_end
+    }
+
+    pub fn cigar_range(&self) -> Range<usize> {
+        self.read_name_end..self.cigar_end
+    }
+
+    pub fn sequence_range(&self) -> Range<usize> {
+        self.cigar_end..self.sequence_end
+    }
+
+    pub fn quality_scores_range(&self) -> Range<usize> {
+        self.sequence_end..self.quality_scores_end
+    }
+
+    pub fn data_range(&self) -> RangeFrom<usize> {
+        self.quality_scores_end..
+    }
+}
+
+impl Default for Bounds {
+    fn default() -> Self {
+        Self {
+            read_name_end: READ_NAME_START,
+            cigar_end: READ_NAME_START,
+            sequence_end: READ_NAME_START,
+            quality_scores_end: READ_NAME_START,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_update() -> io::Result<()> {
+        let buf = [
+            0xff, 0xff, 0xff, 0xff, // ref_id = -1
+            0xff, 0xff, 0xff, 0xff, // pos = -1
+            0x03, // l_read_name = 3
+            0xff, // mapq = 255
+            0x48, 0x12, // bin = 4680
+            0x01, 0x00, // n_cigar_op = 1
+            0x04, 0x00, // flag = 4
+            0x03, 0x00, 0x00, 0x00, // l_seq = 3
+            0xff, 0xff, 0xff, 0xff, // next_ref_id = -1
+            0xff, 0xff, 0xff, 0xff, // next_pos = -1
+            0x00, 0x00, 0x00, 0x00, // tlen = 0
+            b'r', b'0', 0x00, // read_name = "r0\x00"
+            0x34, 0x00, 0x00, 0x00, // cigar = 3S
+            0x12, 0x40, // seq = ACG
+            0x2d, 0x23, 0x2b, // qual = NDL
+            b'N', b'H', b'C', 0x01, // NH:C:1
+        ];
+
+        let mut bounds = Bounds::default();
+        bounds.update(&buf)?;
+
+        assert_eq!(bounds.read_name_range(), 32..35);
+        assert_eq!(bounds.cigar_range(), 35..39);
+        assert_eq!(bounds.sequence_range(), 39..41);
+        assert_eq!(bounds.quality_scores_range(), 41..44);
+        assert_eq!(bounds.data_range(), 44..);
+
+        assert!(matches!(
+            bounds.update(&buf[..40]),
+            Err(ref e) if e.kind() == io::ErrorKind::UnexpectedEof
+        ));
+
+        assert!(matches!(
+            bounds.update(&buf[..16]),
+            Err(ref e) if e.kind() == io::ErrorKind::UnexpectedEof
+        ));
+
+        Ok(())
+    }
+}
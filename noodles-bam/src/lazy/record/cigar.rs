@@ -0,0 +1,137 @@
+use std::{io, mem};
+
+use byteorder::{ByteOrder, LittleEndian};
+use noodles_sam::record::cigar::op::Kind;
+
+use crate::record::cigar::Op;
+
+/// Raw BAM record CIGAR operations.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Cigar<'a>(&'a [u8]);
+
+impl<'a> Cigar<'a> {
+    pub(super) fn new(buf: &'a [u8]) -> Self {
+        Self(buf)
+    }
+
+    /// Returns the number of CIGAR operations.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bam::lazy;
+    /// let record = lazy::Record::default();
+    /// assert_eq!(record.cigar().len(), 0);
+    /// ```
+    pub fn len(&self) -> usize {
+        self.0.len() / mem::size_of::<u32>()
+    }
+
+    /// Returns whether there are any CIGAR operations.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bam::lazy;
+    /// let record = lazy::Record::default();
+    /// assert!(record.cigar().is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns an iterator over the CIGAR operations.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bam::lazy;
+    /// let record = lazy::Record::default();
+    /// assert!(record.cigar().ops().next().is_none());
+    /// ```
+    pub fn ops(&self) -> impl Iterator<Item = io::Result<Op>> + 'a {
+        self.0.chunks_exact(mem::size_of::<u32>()).map(|buf| {
+            let n = LittleEndian::read_u32(buf);
+            Op::try_from(n).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+        })
+    }
+
+    /// Calculates the alignment span over the reference sequence.
+    ///
+    /// This sums the lengths of the CIGAR operations that consume the reference sequence.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_bam::lazy;
+    /// let record = lazy::Record::default();
+    /// assert_eq!(record.cigar().reference_len()?, 0);
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn reference_len(&self) -> io::Result<u32> {
+        let mut len = 0;
+
+        for result in self.ops() {
+            let op = result?;
+
+            match op.kind() {
+                Kind::Match | Kind::Deletion | Kind::Skip | Kind::SeqMatch | Kind::SeqMismatch => {
+                    len += op.len();
+                }
+                _ => {}
+            }
+        }
+
+        Ok(len)
+    }
+}
+
+impl<'a> AsRef<[u8]> for Cigar<'a> {
+    fn as_ref(&self) -> &[u8] {
+        self.0
+    }
+}
+
+impl<'a> TryFrom<Cigar<'a>> for crate::record::Cigar {
+    type Error = io::Error;
+
+    fn try_from(cigar: Cigar<'a>) -> Result<Self, Self::Error> {
+        cigar.ops().collect::<io::Result<Vec<_>>>().map(Self::from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ops() -> io::Result<()> {
+        let buf = [
+            0x40, 0x02, 0x00, 0x00, // 36M
+            0x42, 0x00, 0x00, 0x00, // 4D
+            0x84, 0x00, 0x00, 0x00, // 8S
+        ];
+
+        let cigar = Cigar::new(&buf);
+        assert_eq!(cigar.len(), 3);
+
+        let ops: Vec<_> = cigar.ops().collect::<io::Result<_>>()?;
+
+        assert_eq!(
+            ops,
+            [
+                Op::new(Kind::Match, 36)
+                    .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?,
+                Op::new(Kind::Deletion, 4)
+                    .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?,
+                Op::new(Kind::SoftClip, 8)
+                    .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?,
+            ]
+        );
+
+        assert_eq!(cigar.reference_len()?, 40);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,140 @@
+use std::io;
+
+use noodles_sam::record::data::field::Tag;
+
+use crate::{
+    reader::record::data::read_field,
+    record::data::{Bounds, Field},
+};
+
+/// Raw BAM record data.
+///
+/// Field boundaries are precomputed when the record is read, so fields can be looked up by tag
+/// without decoding the preceding fields.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Data<'a> {
+    buf: &'a [u8],
+    bounds: &'a Bounds,
+}
+
+impl<'a> Data<'a> {
+    pub(super) fn new(buf: &'a [u8], bounds: &'a Bounds) -> Self {
+        Self { buf, bounds }
+    }
+
+    /// Returns the number of data fields.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bam::lazy;
+    /// let record = lazy::Record::default();
+    /// assert_eq!(record.data().len(), 0);
+    /// ```
+    pub fn len(&self) -> usize {
+        self.bounds.len()
+    }
+
+    /// Returns whether there are any data fields.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bam::lazy;
+    /// let record = lazy::Record::default();
+    /// assert!(record.data().is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.buf.is_empty()
+    }
+
+    /// Returns a field by the given tag.
+    ///
+    /// Only the value of the matching field is decoded.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bam::lazy;
+    /// use noodles_sam::record::data::field::Tag;
+    /// let record = lazy::Record::default();
+    /// assert!(record.data().get(Tag::AlignmentHitCount).is_none());
+    /// ```
+    pub fn get(&self, tag: Tag) -> Option<io::Result<Field>> {
+        let raw_tag: &[u8; 2] = tag.as_ref();
+
+        (0..self.len())
+            .find(|&i| {
+                self.bounds
+                    .get(i)
+                    .map(|range| self.buf[range].starts_with(raw_tag))
+                    .unwrap_or_default()
+            })
+            .and_then(|i| self.get_index(i))
+    }
+
+    /// Returns a field by an index.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bam::lazy;
+    /// let record = lazy::Record::default();
+    /// assert!(record.data().get_index(0).is_none());
+    /// ```
+    pub fn get_index(&self, i: usize) -> Option<io::Result<Field>> {
+        self.bounds.get(i).map(|range| {
+            let mut reader = &self.buf[range];
+
+            read_field(&mut reader).and_then(|field| {
+                field.ok_or_else(|| io::Error::from(io::ErrorKind::UnexpectedEof))
+            })
+        })
+    }
+}
+
+impl<'a> AsRef<[u8]> for Data<'a> {
+    fn as_ref(&self) -> &[u8] {
+        self.buf
+    }
+}
+
+impl<'a> TryFrom<Data<'a>> for crate::record::Data {
+    type Error = io::Error;
+
+    fn try_from(data: Data<'a>) -> Result<Self, Self::Error> {
+        Self::try_from(data.buf.to_vec())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::record::data::field::Value;
+
+    #[test]
+    fn test_get() -> io::Result<()> {
+        let buf = [
+            b'N', b'H', b'i', 0x01, 0x00, 0x00, 0x00, // NH:i:1
+            b'R', b'G', b'Z', b'r', b'g', b'0', 0x00, // RG:Z:rg0
+        ];
+
+        let mut bounds = Bounds::default();
+        bounds.update(&buf[..])?;
+
+        let data = Data::new(&buf, &bounds);
+        assert_eq!(data.len(), 2);
+
+        assert_eq!(
+            data.get(Tag::ReadGroup).transpose()?,
+            Some(Field::new(
+                Tag::ReadGroup,
+                Value::String(String::from("rg0"))
+            ))
+        );
+
+        assert!(data.get(Tag::AlignmentScore).is_none());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,116 @@
+use crate::record::sequence::{Base, BASES};
+
+/// A raw BAM record sequence.
+///
+/// Bases are packed as 4-bit values, two per byte.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Sequence<'a> {
+    seq: &'a [u8],
+    len: usize,
+}
+
+impl<'a> Sequence<'a> {
+    pub(super) fn new(seq: &'a [u8], len: usize) -> Self {
+        Self { seq, len }
+    }
+
+    /// Returns the number of bases in the sequence.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bam::lazy;
+    /// let record = lazy::Record::default();
+    /// assert_eq!(record.sequence().len(), 0);
+    /// ```
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns whether the sequence contains any bases.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bam::lazy;
+    /// let record = lazy::Record::default();
+    /// assert!(record.sequence().is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the base at the given index.
+    ///
+    /// If the index is out of bounds, this returns `None`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bam::lazy;
+    /// let record = lazy::Record::default();
+    /// assert!(record.sequence().get(0).is_none());
+    /// ```
+    pub fn get(&self, i: usize) -> Option<Base> {
+        if i >= self.len {
+            return None;
+        }
+
+        let b = self.seq.get(i / 2)?;
+
+        let k = match i % 2 {
+            0 => b >> 4,
+            _ => b & 0x0f,
+        };
+
+        Some(BASES[usize::from(k)])
+    }
+
+    /// Returns an iterator over the bases in the sequence.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bam::lazy;
+    /// let record = lazy::Record::default();
+    /// assert!(record.sequence().bases().next().is_none());
+    /// ```
+    pub fn bases(&self) -> impl Iterator<Item = Base> + 'a {
+        let sequence = *self;
+        (0..self.len).filter_map(move |i| sequence.get(i))
+    }
+}
+
+impl<'a> AsRef<[u8]> for Sequence<'a> {
+    fn as_ref(&self) -> &[u8] {
+        self.seq
+    }
+}
+
+impl<'a> From<Sequence<'a>> for crate::record::Sequence {
+    fn from(sequence: Sequence<'a>) -> Self {
+        Self::new(sequence.seq.to_vec(), sequence.len)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_get() {
+        let sequence = Sequence::new(&[0x12, 0x40], 3); // ACG
+
+        assert_eq!(sequence.get(0), Some(Base::A));
+        assert_eq!(sequence.get(1), Some(Base::C));
+        assert_eq!(sequence.get(2), Some(Base::G));
+        assert!(sequence.get(3).is_none());
+    }
+
+    #[test]
+    fn test_bases() {
+        let sequence = Sequence::new(&[0x12, 0x48], 4); // ACGT
+        let bases: Vec<_> = sequence.bases().collect();
+        assert_eq!(bases, [Base::A, Base::C, Base::G, Base::T]);
+    }
+}
@@ -53,9 +53,11 @@
 mod r#async;
 
 pub mod bai;
+pub mod csi;
+pub mod lazy;
 pub mod reader;
 pub mod record;
-mod writer;
+pub mod writer;
 
 pub use self::{reader::Reader, record::Record, writer::Writer};
 
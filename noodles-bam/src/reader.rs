@@ -1,11 +1,14 @@
 //! BAM reader and iterators.
 
+mod lazy_records;
 pub(crate) mod query;
 pub mod record;
 mod records;
 mod unmapped_records;
 
-pub use self::{query::Query, records::Records, unmapped_records::UnmappedRecords};
+pub use self::{
+    lazy_records::LazyRecords, query::Query, records::Records, unmapped_records::UnmappedRecords,
+};
 
 use std::{
     ffi::CStr,
@@ -18,8 +21,8 @@ use noodles_core::{region::Interval, Region};
 use noodles_csi::{binning_index::ReferenceSequenceExt, BinningIndex};
 use noodles_sam::header::{ReferenceSequence, ReferenceSequences};
 
-use self::record::read_record;
-use super::{bai, Record, MAGIC_NUMBER};
+use self::record::{read_lazy_record, read_record};
+use super::{lazy, Record, MAGIC_NUMBER};
 
 /// A BAM reader.
 ///
@@ -207,6 +210,63 @@ where
     pub fn records(&mut self) -> Records<'_, R> {
         Records::new(self)
     }
+
+    /// Reads a single record without decoding its fields.
+    ///
+    /// This reuses the record buffer and only computes the bounds of the record fields. Fields
+    /// are decoded when they are accessed.
+    ///
+    /// If successful, the record block size is returned. If a block size of 0 is returned, the
+    /// stream reached EOF. If the record is invalid, an error is returned, and the record is reset
+    /// to the default record.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::{fs::File, io};
+    /// use noodles_bam::{self as bam, lazy};
+    ///
+    /// let mut reader = File::open("sample.bam").map(bam::Reader::new)?;
+    /// reader.read_header()?;
+    /// reader.read_reference_sequences()?;
+    ///
+    /// let mut record = lazy::Record::default();
+    /// reader.read_lazy_record(&mut record)?;
+    /// # Ok::<(), io::Error>(())
+    /// ```
+    pub fn read_lazy_record(&mut self, record: &mut lazy::Record) -> io::Result<usize> {
+        read_lazy_record(&mut self.inner, record)
+    }
+
+    /// Returns a borrowing iterator over lazy records starting from the current stream position.
+    ///
+    /// The stream is expected to be directly after the reference sequences or at the start of
+    /// another record.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::{fs::File, io};
+    /// use noodles_bam as bam;
+    /// use noodles_sam::record::Flags;
+    ///
+    /// let mut reader = File::open("sample.bam").map(bam::Reader::new)?;
+    /// reader.read_header()?;
+    /// reader.read_reference_sequences()?;
+    ///
+    /// let mut records = reader.lazy_records();
+    /// let mut unmapped_count = 0;
+    ///
+    /// while let Some(record) = records.next_record().transpose()? {
+    ///     if record.flags().contains(Flags::UNMAPPED) {
+    ///         unmapped_count += 1;
+    ///     }
+    /// }
+    /// # Ok::<(), io::Error>(())
+    /// ```
+    pub fn lazy_records(&mut self) -> LazyRecords<'_, R> {
+        LazyRecords::new(self)
+    }
 }
 
 impl<R> Reader<bgzf::Reader<R>>
@@ -338,7 +398,11 @@ where
     /// }
     /// # Ok::<(), io::Error>(())
     /// ```
-    pub fn query_unmapped(&mut self, index: &bai::Index) -> io::Result<UnmappedRecords<'_, R>> {
+    pub fn query_unmapped<I, RS>(&mut self, index: &I) -> io::Result<UnmappedRecords<'_, R>>
+    where
+        I: BinningIndex<RS>,
+        RS: ReferenceSequenceExt,
+    {
         if let Some(pos) = index.first_record_in_last_linear_bin_start_position() {
             self.seek(pos)?;
         } else {
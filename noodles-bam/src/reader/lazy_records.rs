@@ -0,0 +1,60 @@
+use std::io::{self, Read};
+
+use super::Reader;
+use crate::lazy;
+
+/// A borrowing iterator over lazy records of a BAM reader.
+///
+/// Unlike [`super::Records`], this reuses a single record buffer and lends out a reference to it,
+/// so reading a record does not allocate or copy it. Because each record borrows the iterator,
+/// this does not implement [`Iterator`].
+///
+/// This is created by calling [`Reader::lazy_records`].
+pub struct LazyRecords<'a, R>
+where
+    R: Read,
+{
+    reader: &'a mut Reader<R>,
+    record: lazy::Record,
+}
+
+impl<'a, R> LazyRecords<'a, R>
+where
+    R: Read,
+{
+    pub(crate) fn new(reader: &'a mut Reader<R>) -> Self {
+        Self {
+            reader,
+            record: lazy::Record::default(),
+        }
+    }
+
+    /// Reads the next record and returns a reference to it.
+    ///
+    /// This returns `None` when the stream reaches EOF.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::{fs::File, io};
+    /// use noodles_bam as bam;
+    ///
+    /// let mut reader = File::open("sample.bam").map(bam::Reader::new)?;
+    /// reader.read_header()?;
+    /// reader.read_reference_sequences()?;
+    ///
+    /// let mut records = reader.lazy_records();
+    ///
+    /// while let Some(record) = records.next_record().transpose()? {
+    ///     println!("{:?}", record.flags());
+    /// }
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn next_record(&mut self) -> Option<io::Result<&lazy::Record>> {
+        match self.reader.read_lazy_record(&mut self.record) {
+            Ok(0) => None,
+            Ok(_) => Some(Ok(&self.record)),
+            Err(e) => Some(Err(e)),
+        }
+    }
+}
@@ -11,6 +11,7 @@ use byteorder::{LittleEndian, ReadBytesExt};
 use noodles_sam as sam;
 
 use crate::{
+    lazy,
     record::{Cigar, Data, QualityScores, ReferenceSequenceId, Sequence},
     Record,
 };
@@ -32,7 +33,40 @@ where
     buf.resize(block_size, Default::default());
     reader.read_exact(buf)?;
 
-    let mut reader = &buf[..];
+    decode_record(buf, record)?;
+
+    Ok(block_size)
+}
+
+pub(crate) fn read_lazy_record<R>(reader: &mut R, record: &mut lazy::Record) -> io::Result<usize>
+where
+    R: Read,
+{
+    let block_size = match reader.read_u32::<LittleEndian>() {
+        Ok(bs) => usize::try_from(bs).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?,
+        Err(ref e) if e.kind() == io::ErrorKind::UnexpectedEof => return Ok(0),
+        Err(e) => return Err(e),
+    };
+
+    record.buf.resize(block_size, Default::default());
+
+    if let Err(e) = reader
+        .read_exact(&mut record.buf)
+        .and_then(|_| record.index())
+    {
+        // The record buffer no longer matches its bounds, so the record is reset to keep its
+        // accessors valid.
+        *record = lazy::Record::default();
+        return Err(e);
+    }
+
+    Ok(block_size)
+}
+
+pub(crate) fn decode_record(buf: &[u8], record: &mut Record) -> io::Result<()> {
+    let block_size = buf.len();
+
+    let mut reader = buf;
     let reader = &mut reader;
 
     *record.reference_sequence_id_mut() = read_reference_sequence_id(reader)?;
@@ -73,7 +107,7 @@ where
         l_seq,
     )?;
 
-    Ok(block_size)
+    Ok(())
 }
 
 fn read_reference_sequence_id<R>(reader: &mut R) -> io::Result<Option<ReferenceSequenceId>>
@@ -212,4 +246,52 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_read_lazy_record() -> io::Result<()> {
+        let data = [
+            0x22, 0x00, 0x00, 0x00, // block_size = 34
+            0xff, 0xff, 0xff, 0xff, // ref_id = -1
+            0xff, 0xff, 0xff, 0xff, // pos = -1
+            0x02, // l_read_name = 2
+            0xff, // mapq = 255
+            0x48, 0x12, // bin = 4680
+            0x00, 0x00, // n_cigar_op = 0
+            0x04, 0x00, // flag = 4
+            0x00, 0x00, 0x00, 0x00, // l_seq = 0
+            0xff, 0xff, 0xff, 0xff, // next_ref_id = -1
+            0xff, 0xff, 0xff, 0xff, // next_pos = -1
+            0x00, 0x00, 0x00, 0x00, // tlen = 0
+            0x2a, 0x00, // read_name = "*\x00"
+        ];
+
+        let mut reader = &data[..];
+        let mut record = lazy::Record::default();
+
+        let block_size = read_lazy_record(&mut reader, &mut record)?;
+        assert_eq!(block_size, 34);
+        assert_eq!(record, lazy::Record::default());
+
+        assert_eq!(read_lazy_record(&mut reader, &mut record)?, 0);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_lazy_record_with_invalid_record() {
+        let data = [
+            0x04, 0x00, 0x00, 0x00, // block_size = 4
+            0xff, 0xff, 0xff, 0xff, // ref_id = -1
+        ];
+
+        let mut reader = &data[..];
+        let mut record = lazy::Record::default();
+
+        assert!(matches!(
+            read_lazy_record(&mut reader, &mut record),
+            Err(ref e) if e.kind() == io::ErrorKind::UnexpectedEof
+        ));
+        assert_eq!(record, lazy::Record::default());
+        assert_eq!(record.flags(), sam::record::Flags::UNMAPPED);
+    }
 }
@@ -103,6 +103,10 @@ where
         Type::Array => size_of_array_value(buf)?,
     };
 
+    if buf.remaining() < len {
+        return Err(io::Error::from(io::ErrorKind::UnexpectedEof));
+    }
+
     buf.advance(len);
 
     Ok(())
@@ -127,6 +131,12 @@ where
 {
     use super::field::value::Subtype;
 
+    const LENGTH: usize = mem::size_of::<u8>() + mem::size_of::<u32>();
+
+    if buf.remaining() < LENGTH {
+        return Err(io::Error::from(io::ErrorKind::UnexpectedEof));
+    }
+
     let subtype = Subtype::try_from(buf.get_u8())
         .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
 
@@ -142,7 +152,8 @@ where
         Subtype::Float => mem::size_of::<f32>(),
     };
 
-    Ok(n * size)
+    n.checked_mul(size)
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "invalid array length"))
 }
 
 #[cfg(test)]
@@ -186,6 +197,29 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_update_with_truncated_data() {
+        let mut bounds = Bounds::default();
+
+        let data = [b'N', b'H', b'i', 0x01, 0x00]; // NH:i:1
+        assert!(matches!(
+            bounds.update(&data[..]),
+            Err(ref e) if e.kind() == io::ErrorKind::UnexpectedEof
+        ));
+
+        let data = [b'b', b'C', b'B', b'C', 0x02, 0x00, 0x00, 0x00, 0x00]; // bC:B:C,0,0
+        assert!(matches!(
+            bounds.update(&data[..]),
+            Err(ref e) if e.kind() == io::ErrorKind::UnexpectedEof
+        ));
+
+        let data = [b'b', b'C', b'B', b'C']; // bC:B:C
+        assert!(matches!(
+            bounds.update(&data[..]),
+            Err(ref e) if e.kind() == io::ErrorKind::UnexpectedEof
+        ));
+    }
+
     #[test]
     fn test_get() {
         let bounds = Bounds(vec![2, 3, 5, 8]);
@@ -9,7 +9,7 @@ use std::fmt;
 
 use noodles_sam as sam;
 
-static BASES: &[Base] = &[
+pub(crate) static BASES: &[Base] = &[
     Base::Eq,
     Base::A,
     Base::C,
@@ -1,6 +1,13 @@
+//! BAM writer.
+
+mod builder;
+mod index;
+mod indexer;
 mod record;
 pub(crate) mod sam_record;
 
+pub use self::{builder::Builder, index::Index};
+
 use std::{
     ffi::CString,
     io::{self, Write},
@@ -13,6 +20,7 @@ use noodles_sam::{
     header::{ReferenceSequence, ReferenceSequences},
 };
 
+use self::indexer::Indexer;
 use super::Record;
 
 /// A BAM writer.
@@ -39,6 +47,7 @@ use super::Record;
 /// ```
 pub struct Writer<W> {
     inner: W,
+    indexer: Option<Indexer<W>>,
 }
 
 impl<W> Writer<W>
@@ -129,7 +138,13 @@ where
         &mut self,
         reference_sequences: &ReferenceSequences,
     ) -> io::Result<()> {
-        write_reference_sequences(&mut self.inner, reference_sequences)
+        write_reference_sequences(&mut self.inner, reference_sequences)?;
+
+        if let Some(indexer) = self.indexer.as_mut() {
+            indexer.set_reference_sequence_count(reference_sequences.len());
+        }
+
+        Ok(())
     }
 
     /// Writes a BAM record.
@@ -145,7 +160,20 @@ where
     /// # Ok::<(), io::Error>(())
     /// ```
     pub fn write_record(&mut self, record: &Record) -> io::Result<()> {
-        record::write_record(&mut self.inner, record)
+        match self.indexer.as_mut() {
+            Some(indexer) => {
+                // The record is validated before it is written so that it is never written
+                // without being indexed.
+                indexer.validate_record(record)?;
+
+                let start_position = indexer.block_position(&self.inner);
+                record::write_record(&mut self.inner, record)?;
+                let end_position = indexer.block_position(&self.inner);
+
+                indexer.add_record(&mut self.inner, record, start_position, end_position)
+            }
+            None => record::write_record(&mut self.inner, record),
+        }
     }
 
     /// Writes a SAM record.
@@ -169,7 +197,12 @@ where
         reference_sequences: &ReferenceSequences,
         record: &sam::Record,
     ) -> io::Result<()> {
-        sam_record::write_sam_record(&mut self.inner, reference_sequences, record)
+        if self.indexer.is_some() {
+            let record = Record::try_from_sam_record(reference_sequences, record)?;
+            self.write_record(&record)
+        } else {
+            sam_record::write_sam_record(&mut self.inner, reference_sequences, record)
+        }
     }
 }
 
@@ -191,6 +224,19 @@ where
         Self::from(bgzf::Writer::new(writer))
     }
 
+    /// Creates a BAM writer builder.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bam as bam;
+    /// let builder = bam::Writer::builder(Vec::new());
+    /// let writer = builder.build();
+    /// ```
+    pub fn builder(inner: W) -> Builder<W> {
+        Builder::new(inner)
+    }
+
     /// Attempts to finish the output stream.
     ///
     /// This is typically only manually called if the underlying stream is needed before the writer
@@ -208,11 +254,50 @@ where
     pub fn try_finish(&mut self) -> io::Result<()> {
         self.inner.try_finish()
     }
+
+    /// Attempts to finish the output stream and returns the index built from the written records.
+    ///
+    /// The index is either a BAM index (BAI) or a coordinate-sorted index (CSI), depending on how
+    /// the writer was built. This returns `None` if the writer was not built to create an index
+    /// (see [`Builder::set_build_index`] and [`Builder::set_build_csi_index`]) or if the index was
+    /// already returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_bam as bam;
+    /// use noodles_sam as sam;
+    ///
+    /// let mut writer = bam::Writer::builder(Vec::new())
+    ///     .set_build_index(true)
+    ///     .build();
+    ///
+    /// let header = sam::Header::default();
+    /// writer.write_header(&header)?;
+    /// writer.write_reference_sequences(header.reference_sequences())?;
+    /// writer.write_record(&bam::Record::default())?;
+    ///
+    /// let index = writer.try_finish_with_index()?;
+    /// assert!(index.is_some());
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn try_finish_with_index(&mut self) -> io::Result<Option<Index>> {
+        self.try_finish()?;
+
+        self.indexer
+            .take()
+            .map(|indexer| indexer.build(&mut self.inner))
+            .transpose()
+    }
 }
 
 impl<W> From<W> for Writer<W> {
     fn from(inner: W) -> Self {
-        Self { inner }
+        Self {
+            inner,
+            indexer: None,
+        }
     }
 }
 
@@ -276,6 +361,7 @@ where
 
 #[cfg(test)]
 mod tests {
+    use noodles_csi::index::reference_sequence::bin::Chunk;
     use noodles_sam::record::Data;
 
     use crate::{record::sequence::Base, Reader, Record};
@@ -361,6 +447,284 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_try_finish_with_index() -> Result<(), Box<dyn std::error::Error>> {
+        use noodles_sam::record::{Flags, Position};
+
+        let header = sam::Header::builder()
+            .add_reference_sequence(ReferenceSequence::new("sq0".parse()?, 8)?)
+            .add_reference_sequence(ReferenceSequence::new("sq1".parse()?, 13)?)
+            .build();
+
+        let reference_sequences = header.reference_sequences();
+
+        let mut writer = Writer::builder(Vec::new()).set_build_index(true).build();
+        writer.write_header(&header)?;
+        writer.write_reference_sequences(reference_sequences)?;
+
+        for (reference_sequence_name, position) in [("sq0", 1), ("sq0", 3), ("sq1", 5)] {
+            let record = sam::Record::builder()
+                .set_flags(Flags::empty())
+                .set_reference_sequence_name(reference_sequence_name.parse()?)
+                .set_position(Position::try_from(position)?)
+                .set_cigar("4M".parse()?)
+                .build()?;
+
+            writer.write_sam_record(reference_sequences, &record)?;
+        }
+
+        writer.write_sam_record(reference_sequences, &sam::Record::default())?;
+
+        let actual = writer.try_finish_with_index()?.expect("missing index");
+        assert!(writer.try_finish_with_index()?.is_none());
+
+        let data = writer.get_ref().get_ref();
+        let mut reader = Reader::new(&data[..]);
+        reader.read_header()?;
+        reader.read_reference_sequences()?;
+
+        let mut builder = crate::bai::Index::builder();
+        let mut record = Record::default();
+        let mut start_position = reader.virtual_position();
+
+        while reader.read_record(&mut record)? != 0 {
+            let end_position = reader.virtual_position();
+            builder.add_record(&record, Chunk::new(start_position, end_position))?;
+            start_position = end_position;
+        }
+
+        let expected = builder.build(reference_sequences.len());
+
+        assert_eq!(actual, Index::Bai(expected));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_try_finish_with_index_with_workers() -> Result<(), Box<dyn std::error::Error>> {
+        use std::collections::HashSet;
+
+        use noodles_sam::record::{Flags, Position};
+
+        const RECORD_COUNT: usize = 16384;
+
+        let header = sam::Header::builder()
+            .add_reference_sequence(ReferenceSequence::new("sq0".parse()?, 1 << 20)?)
+            .build();
+
+        let reference_sequences = header.reference_sequences();
+
+        let mut writer = Writer::builder(Vec::new())
+            .set_worker_count(4)
+            .set_build_index(true)
+            .build();
+        writer.write_header(&header)?;
+        writer.write_reference_sequences(reference_sequences)?;
+
+        let sequence: String = "ACGT".repeat(25);
+
+        for i in 0..RECORD_COUNT {
+            let record = sam::Record::builder()
+                .set_flags(Flags::empty())
+                .set_reference_sequence_name("sq0".parse()?)
+                .set_position(Position::try_from(i32::try_from(i * 32 + 1)?)?)
+                .set_cigar("100M".parse()?)
+                .set_sequence(sequence.parse()?)
+                .build()?;
+
+            writer.write_sam_record(reference_sequences, &record)?;
+        }
+
+        writer.write_sam_record(reference_sequences, &sam::Record::default())?;
+
+        let actual = writer.try_finish_with_index()?.expect("missing index");
+
+        let data = writer.get_ref().get_ref();
+        let mut reader = Reader::new(&data[..]);
+        reader.read_header()?;
+        reader.read_reference_sequences()?;
+
+        let mut builder = crate::bai::Index::builder();
+        let mut record = Record::default();
+        let mut start_position = reader.virtual_position();
+        let mut block_offsets = HashSet::new();
+        let mut n = 0;
+
+        while reader.read_record(&mut record)? != 0 {
+            let end_position = reader.virtual_position();
+            builder.add_record(&record, Chunk::new(start_position, end_position))?;
+            block_offsets.insert(start_position.compressed());
+            start_position = end_position;
+            n += 1;
+        }
+
+        assert_eq!(n, RECORD_COUNT + 1);
+        // The records span more blocks than there are workers.
+        assert!(block_offsets.len() > 8);
+
+        let expected = builder.build(reference_sequences.len());
+
+        match actual {
+            Index::Bai(actual) => assert_eq!(sort_bins(&actual), sort_bins(&expected)),
+            Index::Csi(_) => panic!("expected BAI"),
+        }
+
+        Ok(())
+    }
+
+    // Sorts the bins of each reference sequence, which are otherwise in an arbitrary order.
+    fn sort_bins(index: &crate::bai::Index) -> crate::bai::Index {
+        use noodles_csi::{binning_index::ReferenceSequenceExt, BinningIndex};
+
+        use crate::bai::index::ReferenceSequence;
+
+        let reference_sequences = index
+            .reference_sequences()
+            .iter()
+            .map(|reference_sequence| {
+                let mut bins = reference_sequence.bins().to_vec();
+                bins.sort_by_key(|bin| bin.id());
+
+                ReferenceSequence::new(
+                    bins,
+                    reference_sequence.intervals().to_vec(),
+                    reference_sequence.metadata().cloned(),
+                )
+            })
+            .collect();
+
+        crate::bai::Index::new(reference_sequences, index.unplaced_unmapped_record_count())
+    }
+
+    #[test]
+    fn test_try_finish_with_index_with_csi() -> Result<(), Box<dyn std::error::Error>> {
+        use noodles_sam::record::{Flags, Position};
+
+        use crate::csi;
+
+        // This is greater than the max position addressable by a BAI (2^29 - 1).
+        const START: i32 = 1 << 30;
+
+        let header = sam::Header::builder()
+            .add_reference_sequence(ReferenceSequence::new("sq0".parse()?, START + 8)?)
+            .build();
+
+        let reference_sequences = header.reference_sequences();
+
+        let mut writer = Writer::builder(Vec::new())
+            .set_build_csi_index(14, 6)
+            .build();
+        writer.write_header(&header)?;
+        writer.write_reference_sequences(reference_sequences)?;
+
+        for position in [1, START] {
+            let record = sam::Record::builder()
+                .set_flags(Flags::empty())
+                .set_reference_sequence_name("sq0".parse()?)
+                .set_position(Position::try_from(position)?)
+                .set_cigar("4M".parse()?)
+                .build()?;
+
+            writer.write_sam_record(reference_sequences, &record)?;
+        }
+
+        writer.write_sam_record(reference_sequences, &sam::Record::default())?;
+
+        let actual = writer.try_finish_with_index()?.expect("missing index");
+
+        let data = writer.get_ref().get_ref();
+        let mut reader = Reader::new(&data[..]);
+        reader.read_header()?;
+        reader.read_reference_sequences()?;
+
+        let mut indexer = csi::Indexer::new(14, 6);
+        let mut record = Record::default();
+        let mut start_position = reader.virtual_position();
+
+        while reader.read_record(&mut record)? != 0 {
+            let end_position = reader.virtual_position();
+            indexer.add_record(&record, Chunk::new(start_position, end_position))?;
+            start_position = end_position;
+        }
+
+        let expected = indexer.build(reference_sequences.len());
+
+        assert_eq!(actual, Index::Csi(expected));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_write_record_with_index_and_position_out_of_range(
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        use noodles_sam::record::{Flags, Position};
+
+        const START: i32 = 1 << 30;
+
+        let header = sam::Header::builder()
+            .add_reference_sequence(ReferenceSequence::new("sq0".parse()?, START + 8)?)
+            .build();
+
+        let reference_sequences = header.reference_sequences();
+
+        let mut writer = Writer::builder(Vec::new()).set_build_index(true).build();
+        writer.write_header(&header)?;
+        writer.write_reference_sequences(reference_sequences)?;
+
+        let record = sam::Record::builder()
+            .set_flags(Flags::empty())
+            .set_reference_sequence_name("sq0".parse()?)
+            .set_position(Position::try_from(START)?)
+            .build()?;
+
+        let virtual_position = writer.get_ref().virtual_position()?;
+
+        assert!(matches!(
+            writer.write_sam_record(reference_sequences, &record),
+            Err(ref e) if e.kind() == io::ErrorKind::InvalidInput
+        ));
+
+        // The record is not written.
+        assert_eq!(writer.get_ref().virtual_position()?, virtual_position);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_write_record_with_index_and_unsorted_records() -> Result<(), Box<dyn std::error::Error>>
+    {
+        use noodles_sam::record::{Flags, Position};
+
+        let header = sam::Header::builder()
+            .add_reference_sequence(ReferenceSequence::new("sq0".parse()?, 8)?)
+            .build();
+
+        let reference_sequences = header.reference_sequences();
+
+        let mut writer = Writer::builder(Vec::new()).set_build_index(true).build();
+        writer.write_header(&header)?;
+        writer.write_reference_sequences(reference_sequences)?;
+
+        let build_record = |position| -> Result<_, Box<dyn std::error::Error>> {
+            let record = sam::Record::builder()
+                .set_flags(Flags::empty())
+                .set_reference_sequence_name("sq0".parse()?)
+                .set_position(Position::try_from(position)?)
+                .build()?;
+
+            Ok(record)
+        };
+
+        writer.write_sam_record(reference_sequences, &build_record(3)?)?;
+
+        assert!(matches!(
+            writer.write_sam_record(reference_sequences, &build_record(1)?),
+            Err(ref e) if e.kind() == io::ErrorKind::InvalidInput
+        ));
+
+        Ok(())
+    }
+
     #[test]
     fn test_write_sam_record_with_sequence_length_less_than_quality_scores_length(
     ) -> Result<(), Box<dyn std::error::Error>> {
@@ -0,0 +1,154 @@
+use std::io::Write;
+
+use noodles_bgzf::{self as bgzf, writer::CompressionLevel};
+
+use super::{indexer::Indexer, Writer};
+
+/// A BAM writer builder.
+pub struct Builder<W> {
+    inner: W,
+    compression_level: Option<CompressionLevel>,
+    worker_count: Option<usize>,
+    index_parameters: Option<IndexParameters>,
+}
+
+// The type of index to build and, for a CSI, its min shift and depth.
+enum IndexParameters {
+    Bai,
+    Csi(i32, i32),
+}
+
+impl<W> Builder<W>
+where
+    W: Write,
+{
+    pub(crate) fn new(inner: W) -> Self {
+        Self {
+            inner,
+            compression_level: None,
+            worker_count: None,
+            index_parameters: None,
+        }
+    }
+
+    /// Sets a compression level.
+    ///
+    /// By default, the compression level is set to level 6.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bam as bam;
+    /// use noodles_bgzf::writer::CompressionLevel;
+    ///
+    /// let builder = bam::Writer::builder(Vec::new())
+    ///     .set_compression_level(CompressionLevel::best());
+    /// ```
+    pub fn set_compression_level(mut self, compression_level: CompressionLevel) -> Self {
+        self.compression_level = Some(compression_level);
+        self
+    }
+
+    /// Sets a worker count.
+    ///
+    /// By default, the worker count is set to 1, i.e., blocks are compressed on the calling
+    /// thread.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bam as bam;
+    /// let builder = bam::Writer::builder(Vec::new()).set_worker_count(8);
+    /// ```
+    pub fn set_worker_count(mut self, worker_count: usize) -> Self {
+        self.worker_count = Some(worker_count);
+        self
+    }
+
+    /// Sets whether to build a BAM index (BAI) while writing records.
+    ///
+    /// When enabled, the writer tracks the virtual position of each written record. Records must
+    /// be written in coordinate-sorted order, and the index is returned by
+    /// [`Writer::try_finish_with_index`]. A record that cannot be indexed, e.g., one with an
+    /// alignment end greater than 2^29 - 1, is not written, and an error is returned. By default,
+    /// no index is built.
+    ///
+    /// Determining the virtual position of a record waits for blocks that are pending
+    /// compression, which limits the parallelism of compression workers.
+    ///
+    /// This overrides [`Self::set_build_csi_index`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bam as bam;
+    /// let builder = bam::Writer::builder(Vec::new()).set_build_index(true);
+    /// ```
+    pub fn set_build_index(mut self, build_index: bool) -> Self {
+        self.index_parameters = if build_index {
+            Some(IndexParameters::Bai)
+        } else {
+            None
+        };
+
+        self
+    }
+
+    /// Sets the writer to build a coordinate-sorted index (CSI) while writing records.
+    ///
+    /// `min_shift` is the number of bits for the minimum interval, and `depth` is the depth of
+    /// the binning index. Unlike a BAI, a CSI can index reference sequences longer than 2^29 - 1
+    /// bases. Records must be written in coordinate-sorted order, and the index is returned by
+    /// [`Writer::try_finish_with_index`].
+    ///
+    /// This overrides [`Self::set_build_index`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bam as bam;
+    /// let builder = bam::Writer::builder(Vec::new()).set_build_csi_index(14, 6);
+    /// ```
+    pub fn set_build_csi_index(mut self, min_shift: i32, depth: i32) -> Self {
+        self.index_parameters = Some(IndexParameters::Csi(min_shift, depth));
+        self
+    }
+
+    /// Builds a BAM writer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bam as bam;
+    /// let writer = bam::Writer::builder(Vec::new()).build();
+    /// ```
+    pub fn build(self) -> Writer<bgzf::Writer<W>> {
+        let mut builder = bgzf::Writer::builder(self.inner);
+
+        if let Some(compression_level) = self.compression_level {
+            builder = builder.set_compression_level(compression_level);
+        }
+
+        if let Some(worker_count) = self.worker_count {
+            builder = builder.set_worker_count(worker_count);
+        }
+
+        let indexer = self.index_parameters.map(|parameters| match parameters {
+            IndexParameters::Bai => Indexer::bai(
+                bgzf::Writer::block_position,
+                bgzf::Writer::resolve_block_position,
+            ),
+            IndexParameters::Csi(min_shift, depth) => Indexer::csi(
+                bgzf::Writer::block_position,
+                bgzf::Writer::resolve_block_position,
+                min_shift,
+                depth,
+            ),
+        });
+
+        Writer {
+            inner: builder.build(),
+            indexer,
+        }
+    }
+}
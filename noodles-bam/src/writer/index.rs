@@ -0,0 +1,12 @@
+use noodles_csi as csi;
+
+use crate::bai;
+
+/// An index built by a BAM writer.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Index {
+    /// A BAM index (BAI).
+    Bai(bai::Index),
+    /// A coordinate-sorted index (CSI).
+    Csi(csi::Index),
+}
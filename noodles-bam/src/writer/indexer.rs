@@ -0,0 +1,298 @@
+use std::{collections::VecDeque, io};
+
+use noodles_bgzf::{self as bgzf, writer::BlockPosition};
+use noodles_csi::index::reference_sequence::bin::Chunk;
+
+use super::Index;
+use crate::{bai, csi, Record};
+
+// The min shift and depth of a BAM index (BAI).
+const BAI_MIN_SHIFT: i32 = 14;
+const BAI_DEPTH: i32 = 5;
+
+type BlockPositionFn<W> = fn(&W) -> BlockPosition;
+type ResolveBlockPositionFn<W> = fn(&mut W, BlockPosition) -> Option<bgzf::VirtualPosition>;
+
+enum Inner {
+    Bai(bai::index::Builder),
+    Csi(csi::Indexer),
+}
+
+// Builds a BAM index from records as they are written.
+//
+// The indexer is only created for BGZF-compressed streams, which are able to report the block
+// position of the next record. Block positions are resolved to virtual positions once their
+// blocks are written, so records are only added to the index after their blocks are compressed.
+pub(super) struct Indexer<W> {
+    block_position: BlockPositionFn<W>,
+    resolve_block_position: ResolveBlockPositionFn<W>,
+    inner: Inner,
+    max_position: i64,
+    reference_sequence_count: usize,
+    last_sort_key: Option<(usize, i64)>,
+    pending_records: VecDeque<(Record, BlockPosition, BlockPosition)>,
+}
+
+impl<W> Indexer<W> {
+    pub fn bai(
+        block_position: BlockPositionFn<W>,
+        resolve_block_position: ResolveBlockPositionFn<W>,
+    ) -> Self {
+        Self::new(
+            block_position,
+            resolve_block_position,
+            Inner::Bai(bai::Index::builder()),
+            BAI_MIN_SHIFT,
+            BAI_DEPTH,
+        )
+    }
+
+    pub fn csi(
+        block_position: BlockPositionFn<W>,
+        resolve_block_position: ResolveBlockPositionFn<W>,
+        min_shift: i32,
+        depth: i32,
+    ) -> Self {
+        Self::new(
+            block_position,
+            resolve_block_position,
+            Inner::Csi(csi::Indexer::new(min_shift, depth)),
+            min_shift,
+            depth,
+        )
+    }
+
+    fn new(
+        block_position: BlockPositionFn<W>,
+        resolve_block_position: ResolveBlockPositionFn<W>,
+        inner: Inner,
+        min_shift: i32,
+        depth: i32,
+    ) -> Self {
+        Self {
+            block_position,
+            resolve_block_position,
+            inner,
+            max_position: max_position(min_shift, depth),
+            reference_sequence_count: 0,
+            last_sort_key: None,
+            pending_records: VecDeque::new(),
+        }
+    }
+
+    // Returns the block position of the next record.
+    //
+    // This never waits for blocks that are still being compressed.
+    pub fn block_position(&self, inner: &W) -> BlockPosition {
+        (self.block_position)(inner)
+    }
+
+    pub fn set_reference_sequence_count(&mut self, reference_sequence_count: usize) {
+        self.reference_sequence_count = reference_sequence_count;
+    }
+
+    // Checks whether the record can be indexed, i.e., its reference sequence ID is defined, its
+    // alignment end is addressable by the index, and it is coordinate-sorted relative to the
+    // previously added record.
+    //
+    // A record that passes validation is guaranteed to be added to the index.
+    pub fn validate_record(&self, record: &Record) -> io::Result<()> {
+        if let Some(reference_sequence_id) = record.reference_sequence_id() {
+            let i = usize::try_from(i32::from(reference_sequence_id))
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+
+            if i >= self.reference_sequence_count {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "invalid reference sequence ID",
+                ));
+            }
+        }
+
+        let sort_key = match csi::alignment_context(record)? {
+            Some((reference_sequence_id, start, end, _)) => {
+                if end > self.max_position {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidInput,
+                        format!(
+                            "invalid alignment end: expected <= {}, got {}",
+                            self.max_position, end
+                        ),
+                    ));
+                }
+
+                (reference_sequence_id, start)
+            }
+            None => UNPLACED_SORT_KEY,
+        };
+
+        if let Some(last_sort_key) = self.last_sort_key {
+            if sort_key < last_sort_key {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "records must be coordinate-sorted to be indexed",
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    // Adds a record that was validated using `validate_record`.
+    //
+    // The record is added to the index once its start and end positions are resolved. This also
+    // adds previously written records whose blocks were written since.
+    pub fn add_record(
+        &mut self,
+        inner: &mut W,
+        record: &Record,
+        start_position: BlockPosition,
+        end_position: BlockPosition,
+    ) -> io::Result<()> {
+        self.last_sort_key = csi::alignment_context(record)?
+            .map(|(reference_sequence_id, start, _, _)| (reference_sequence_id, start))
+            .or(Some(UNPLACED_SORT_KEY));
+
+        self.pending_records
+            .push_back((record.clone(), start_position, end_position));
+
+        self.add_resolved_records(inner)
+    }
+
+    // Adds pending records, in order, whose positions can be resolved.
+    fn add_resolved_records(&mut self, inner: &mut W) -> io::Result<()> {
+        while let Some((record, start_position, end_position)) = self.pending_records.pop_front() {
+            let resolved_start_position = (self.resolve_block_position)(inner, start_position);
+            let resolved_end_position = (self.resolve_block_position)(inner, end_position);
+
+            let chunk = match (resolved_start_position, resolved_end_position) {
+                (Some(start), Some(end)) => Chunk::new(start, end),
+                _ => {
+                    self.pending_records
+                        .push_front((record, start_position, end_position));
+                    break;
+                }
+            };
+
+            match &mut self.inner {
+                Inner::Bai(builder) => builder.add_record(&record, chunk)?,
+                Inner::Csi(indexer) => indexer.add_record(&record, chunk)?,
+            }
+        }
+
+        Ok(())
+    }
+
+    // Builds the index.
+    //
+    // This must be called after all blocks are written.
+    pub fn build(mut self, inner: &mut W) -> io::Result<Index> {
+        self.add_resolved_records(inner)?;
+
+        if !self.pending_records.is_empty() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "unresolved record positions",
+            ));
+        }
+
+        let index = match self.inner {
+            Inner::Bai(builder) => Index::Bai(builder.build(self.reference_sequence_count)),
+            Inner::Csi(indexer) => Index::Csi(indexer.build(self.reference_sequence_count)),
+        };
+
+        Ok(index)
+    }
+}
+
+// Unplaced records are sorted last.
+const UNPLACED_SORT_KEY: (usize, i64) = (usize::MAX, 0);
+
+// Returns the max 1-based position addressable by an index with the given min shift and depth.
+fn max_position(min_shift: i32, depth: i32) -> i64 {
+    (1 << (min_shift + 3 * depth)) - 1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn block_position(_: &()) -> BlockPosition {
+        BlockPosition::default()
+    }
+
+    fn resolve_block_position(_: &mut (), _: BlockPosition) -> Option<bgzf::VirtualPosition> {
+        Some(bgzf::VirtualPosition::default())
+    }
+
+    #[test]
+    fn test_validate_record() -> Result<(), Box<dyn std::error::Error>> {
+        use crate::record::ReferenceSequenceId;
+
+        let mut indexer = Indexer::bai(block_position, resolve_block_position);
+        indexer.set_reference_sequence_count(1);
+
+        let mut record = Record::default();
+        *record.reference_sequence_id_mut() = Some(ReferenceSequenceId::try_from(0)?);
+        record.pos = 8;
+        indexer.validate_record(&record)?;
+        indexer.add_record(&mut (), &record, Default::default(), Default::default())?;
+
+        record.pos = 5;
+        assert!(matches!(
+            indexer.validate_record(&record),
+            Err(e) if e.kind() == io::ErrorKind::InvalidInput
+        ));
+
+        *record.reference_sequence_id_mut() = Some(ReferenceSequenceId::try_from(1)?);
+        assert!(matches!(
+            indexer.validate_record(&record),
+            Err(e) if e.kind() == io::ErrorKind::InvalidInput
+        ));
+
+        indexer.validate_record(&Record::default())?;
+        indexer.add_record(
+            &mut (),
+            &Record::default(),
+            Default::default(),
+            Default::default(),
+        )?;
+
+        *record.reference_sequence_id_mut() = Some(ReferenceSequenceId::try_from(0)?);
+        record.pos = 13;
+        assert!(matches!(
+            indexer.validate_record(&record),
+            Err(e) if e.kind() == io::ErrorKind::InvalidInput
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_validate_record_with_position_out_of_range() -> Result<(), Box<dyn std::error::Error>> {
+        use crate::record::ReferenceSequenceId;
+
+        let mut record = Record::default();
+        *record.reference_sequence_id_mut() = Some(ReferenceSequenceId::try_from(0)?);
+        record.pos = 1 << 29;
+
+        let mut indexer = Indexer::bai(block_position, resolve_block_position);
+        indexer.set_reference_sequence_count(1);
+        assert!(matches!(
+            indexer.validate_record(&record),
+            Err(e) if e.kind() == io::ErrorKind::InvalidInput
+        ));
+
+        let mut indexer = Indexer::csi(block_position, resolve_block_position, 14, 6);
+        indexer.set_reference_sequence_count(1);
+        indexer.validate_record(&record)?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_max_position() {
+        assert_eq!(max_position(14, 5), (1 << 29) - 1);
+        assert_eq!(max_position(14, 6), (1 << 32) - 1);
+    }
+}
@@ -0,0 +1,256 @@
+//! BCF coordinate-sorted index (CSI) building.
+//!
+//! A CSI is the index used to [query] a BCF file. The bin sizes of a CSI are set by its number of
+//! bits for the minimum interval (`min_shift`) and depth.
+//!
+//! [query]: crate::Reader::query
+//!
+//! # Examples
+//!
+//! ```no_run
+//! # use std::io;
+//! use noodles_bcf as bcf;
+//! let index = bcf::csi::index("sample.bcf", 14, 5)?;
+//! # Ok::<(), io::Error>(())
+//! ```
+
+use std::{
+    fs::File,
+    io::{self, Read},
+    path::Path,
+};
+
+use noodles_bgzf as bgzf;
+use noodles_csi::{self as csi, index::reference_sequence::bin::Chunk};
+use noodles_vcf as vcf;
+
+use crate::{Reader, Record};
+
+/// A BCF coordinate-sorted index (CSI) indexer.
+#[derive(Debug, Default)]
+pub struct Indexer {
+    inner: csi::index::Indexer,
+}
+
+impl Indexer {
+    /// Creates a BCF CSI indexer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bcf::csi::Indexer;
+    /// let indexer = Indexer::new(14, 5);
+    /// ```
+    pub fn new(min_shift: i32, depth: i32) -> Self {
+        Self {
+            inner: csi::index::Indexer::new(min_shift, depth),
+        }
+    }
+
+    /// Adds a record.
+    ///
+    /// The record must have an associated chunk denoting its start and end position in the file.
+    /// Records must be coordinate-sorted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bcf::{self as bcf, csi::Indexer};
+    /// use noodles_bgzf as bgzf;
+    /// use noodles_csi::index::reference_sequence::bin::Chunk;
+    ///
+    /// let mut indexer = Indexer::new(14, 5);
+    ///
+    /// let record = bcf::Record::default();
+    /// let chunk = Chunk::new(
+    ///     bgzf::VirtualPosition::from(233),
+    ///     bgzf::VirtualPosition::from(377),
+    /// );
+    ///
+    /// indexer.add_record(&record, chunk)?;
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn add_record(&mut self, record: &Record, chunk: Chunk) -> io::Result<()> {
+        let context = record_context(record)?;
+        self.inner.add_record(Some(context), chunk)
+    }
+
+    /// Builds a coordinate-sorted index (CSI).
+    ///
+    /// `reference_sequence_count` is the number of contigs in the VCF header.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bcf::csi::Indexer;
+    /// let indexer = Indexer::new(14, 5);
+    /// let index = indexer.build(1);
+    /// ```
+    pub fn build(self, reference_sequence_count: usize) -> csi::Index {
+        self.inner.build(reference_sequence_count)
+    }
+}
+
+/// Builds a coordinate-sorted index (CSI) from a BCF file.
+///
+/// The BCF file must be coordinate-sorted. `min_shift` is the number of bits for the minimum
+/// interval, and `depth` is the depth of the binning index. bcftools uses a `min_shift` of 14 and
+/// `depth` of 5 by default.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use std::io;
+/// use noodles_bcf as bcf;
+/// use noodles_csi as csi;
+///
+/// let index = bcf::csi::index("sample.bcf", 14, 5)?;
+/// csi::write("sample.bcf.csi", &index)?;
+/// # Ok::<(), io::Error>(())
+/// ```
+pub fn index<P>(src: P, min_shift: i32, depth: i32) -> io::Result<csi::Index>
+where
+    P: AsRef<Path>,
+{
+    let mut reader = File::open(src).map(Reader::new)?;
+    reader.read_file_format()?;
+
+    let header: vcf::Header = reader
+        .read_header()?
+        .parse()
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+    index_records(&mut reader, min_shift, depth, header.contigs().len())
+}
+
+// Builds an index from the records of a reader, starting from the current stream position, which
+// is expected to be directly after the header or at the start of another record.
+fn index_records<R>(
+    reader: &mut Reader<bgzf::Reader<R>>,
+    min_shift: i32,
+    depth: i32,
+    reference_sequence_count: usize,
+) -> io::Result<csi::Index>
+where
+    R: Read,
+{
+    let mut indexer = Indexer::new(min_shift, depth);
+    let mut record = Record::default();
+    let mut start_position = reader.virtual_position();
+
+    while reader.read_record(&mut record)? != 0 {
+        let end_position = reader.virtual_position();
+        indexer.add_record(&record, Chunk::new(start_position, end_position))?;
+        start_position = end_position;
+    }
+
+    Ok(indexer.build(reference_sequence_count))
+}
+
+// Returns the chromosome ID, 1-based start and end (inclusive) positions, and whether the record
+// is mapped.
+//
+// The end position is calculated from the reference length (rlen). Records are always mapped.
+fn record_context(record: &Record) -> io::Result<(usize, i64, i64, bool)> {
+    let i = usize::try_from(record.chromosome_id())
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+    let start = i64::from(i32::from(record.position()));
+    let end = start + i64::from(record.rlen().max(1)) - 1;
+
+    Ok((i, start, end, true))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_context() -> Result<(), Box<dyn std::error::Error>> {
+        let mut record = Record::default();
+        assert_eq!(record_context(&record)?, (0, 1, 1, true));
+
+        *record.chromosome_id_mut() = 1;
+        *record.position_mut() = vcf::record::Position::try_from(8)?;
+        *record.rlen_mut() = 5;
+        assert_eq!(record_context(&record)?, (1, 8, 12, true));
+
+        *record.rlen_mut() = 0;
+        assert_eq!(record_context(&record)?, (1, 8, 8, true));
+
+        *record.chromosome_id_mut() = -1;
+        assert!(matches!(
+            record_context(&record),
+            Err(e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_query() -> Result<(), Box<dyn std::error::Error>> {
+        use std::io::Cursor;
+
+        use noodles_core::Region;
+        use noodles_csi::BinningIndex;
+        use vcf::{header::Contig, record::Position};
+
+        use crate::{header::StringMap, Writer};
+
+        let header = vcf::Header::builder()
+            .add_contig(Contig::new("sq0"))
+            .add_contig(Contig::new("sq1"))
+            .build();
+
+        let string_map = StringMap::from(&header);
+
+        let mut writer = Writer::new(Vec::new());
+        writer.write_file_format()?;
+        writer.write_header(&header)?;
+
+        for (chromosome, position, reference_bases) in [
+            ("sq0", 8, "A"),
+            ("sq0", 13, "ACGT"),
+            ("sq0", 1 << 20, "A"),
+            ("sq1", 21, "A"),
+        ] {
+            let record = vcf::Record::builder()
+                .set_chromosome(chromosome.parse()?)
+                .set_position(Position::try_from(position)?)
+                .set_reference_bases(reference_bases.parse()?)
+                .build()?;
+
+            writer.write_vcf_record(&header, &string_map, &record)?;
+        }
+
+        writer.try_finish()?;
+
+        let data = writer.get_ref().get_ref();
+        let mut reader = Reader::new(Cursor::new(&data[..]));
+        reader.read_file_format()?;
+        reader.read_header()?;
+
+        let index = index_records(&mut reader, 14, 5, header.contigs().len())?;
+        assert_eq!(index.reference_sequences().len(), 2);
+
+        let mut t = |region: Region, expected: &[(i32, i32)]| -> io::Result<()> {
+            let actual: Vec<_> = reader
+                .query(header.contigs(), &index, &region)?
+                .map(|result| {
+                    result.map(|record| (record.chromosome_id(), i32::from(record.position())))
+                })
+                .collect::<io::Result<_>>()?;
+
+            assert_eq!(actual, expected);
+
+            Ok(())
+        };
+
+        t(Region::mapped("sq0", 10..=14), &[(0, 13)])?;
+        t(Region::mapped("sq0", 16..=16), &[(0, 13)])?;
+        t(Region::mapped("sq0", 17..=1 << 21), &[(0, 1 << 20)])?;
+        t(Region::mapped("sq1", 1..=21), &[(1, 21)])?;
+
+        Ok(())
+    }
+}
@@ -5,12 +5,13 @@
 #[cfg(feature = "async")]
 mod r#async;
 
+pub mod csi;
 pub mod header;
 pub mod reader;
 pub mod record;
 mod writer;
 
-pub use self::{reader::Reader, record::Record, writer::Writer};
+pub use self::{csi::index, reader::Reader, record::Record, writer::Writer};
 
 #[cfg(feature = "async")]
 pub use self::r#async::Reader as AsyncReader;
@@ -298,7 +298,10 @@ mod tests {
 
         let expected = VcfInfo::try_from(vec![
             InfoField::new("HM3".parse()?, Some(InfoFieldValue::Flag)),
-            InfoField::new(InfoFieldKey::AlleleCount, Some(InfoFieldValue::Integer(3))),
+            InfoField::new(
+                InfoFieldKey::AlleleCount,
+                Some(InfoFieldValue::IntegerArray(vec![3])),
+            ),
             InfoField::new(
                 InfoFieldKey::TotalAlleleCount,
                 Some(InfoFieldValue::Integer(6)),
@@ -1,4 +1,5 @@
 use std::{
+    fmt,
     io::{self, Read},
     str,
 };
@@ -16,6 +17,7 @@ use noodles_vcf::{
 };
 
 const NUL: u8 = 0x00;
+const MISSING_VALUE: &str = ".";
 
 use crate::{
     header::StringMap,
@@ -23,16 +25,13 @@ use crate::{
     record::value::{Float, Int16, Int32, Int8, Type},
 };
 
-pub fn read_genotypes<R>(
-    reader: &mut R,
+pub fn read_genotypes(
+    reader: &mut &[u8],
     formats: &vcf::header::Formats,
     string_map: &StringMap,
     sample_count: usize,
     format_count: usize,
-) -> io::Result<Genotypes>
-where
-    R: Read,
-{
+) -> io::Result<Genotypes> {
     use vcf::record::genotypes::genotype::Field;
 
     let mut keys = Vec::with_capacity(format_count);
@@ -46,6 +45,9 @@ where
             read_genotype_genotype_field_values(reader, sample_count)?
         } else {
             read_genotype_field_values(reader, sample_count)?
+                .into_iter()
+                .map(|value| resolve_genotype_field_value(&key, value))
+                .collect::<io::Result<_>>()?
         };
 
         for (fields, value) in genotypes.iter_mut().zip(values) {
@@ -96,61 +98,80 @@ where
         })
 }
 
-fn read_genotype_field_values<R>(
-    reader: &mut R,
+fn read_genotype_field_values(
+    reader: &mut &[u8],
     sample_count: usize,
-) -> io::Result<Vec<Option<Value>>>
-where
-    R: Read,
-{
-    match read_type(reader)? {
-        Some(Type::Int8(len)) => match len {
-            0 => Err(io::Error::new(
-                io::ErrorKind::InvalidData,
-                format!("invalid number: {}", len),
-            )),
-            1 => read_genotype_field_int8_values(reader, sample_count),
-            _ => read_genotype_field_int8_array_values(reader, sample_count, len),
-        },
-        Some(Type::Int16(len)) => match len {
-            0 => Err(io::Error::new(
-                io::ErrorKind::InvalidData,
-                format!("invalid number: {}", len),
-            )),
-            1 => read_genotype_field_int16_values(reader, sample_count),
-            _ => read_genotype_field_int16_array_values(reader, sample_count, len),
-        },
-        Some(Type::Int32(len)) => match len {
-            0 => Err(io::Error::new(
-                io::ErrorKind::InvalidData,
-                format!("invalid number: {}", len),
-            )),
-            1 => read_genotype_field_int32_values(reader, sample_count),
-            _ => read_genotype_field_int32_array_values(reader, sample_count, len),
-        },
-        Some(Type::Float(len)) => match len {
-            0 => Err(io::Error::new(
-                io::ErrorKind::InvalidData,
-                format!("invalid number: {}", len),
-            )),
-            1 => read_genotype_field_float_values(reader, sample_count),
-            _ => read_genotype_field_float_array_values(reader, sample_count, len),
-        },
+) -> io::Result<Vec<Option<Value>>> {
+    let ty = read_type(reader)?;
+    check_values_len(reader, ty, sample_count)?;
+
+    match ty {
+        None
+        | Some(
+            Type::Int8(0) | Type::Int16(0) | Type::Int32(0) | Type::Float(0) | Type::String(0),
+        ) => Ok(vec![None; sample_count]),
+        Some(Type::Int8(1)) => read_genotype_field_int8_values(reader, sample_count),
+        Some(Type::Int8(len)) => read_genotype_field_int8_array_values(reader, sample_count, len),
+        Some(Type::Int16(1)) => read_genotype_field_int16_values(reader, sample_count),
+        Some(Type::Int16(len)) => read_genotype_field_int16_array_values(reader, sample_count, len),
+        Some(Type::Int32(1)) => read_genotype_field_int32_values(reader, sample_count),
+        Some(Type::Int32(len)) => read_genotype_field_int32_array_values(reader, sample_count, len),
+        Some(Type::Float(1)) => read_genotype_field_float_values(reader, sample_count),
+        Some(Type::Float(len)) => read_genotype_field_float_array_values(reader, sample_count, len),
         Some(Type::String(len)) => read_genotype_field_string_values(reader, sample_count, len),
-        ty => Err(io::Error::new(
-            io::ErrorKind::InvalidData,
-            format!("unhandled type: {:?}", ty),
-        )),
     }
 }
 
-fn read_genotype_field_int8_values<R>(
-    reader: &mut R,
+// Checks that the reader holds the values of all samples before they are allocated.
+fn check_values_len(reader: &[u8], ty: Option<Type>, sample_count: usize) -> io::Result<()> {
+    use std::mem;
+
+    let (len, value_size) = match ty {
+        None => return Ok(()),
+        Some(Type::Int8(len)) => (len, mem::size_of::<i8>()),
+        Some(Type::Int16(len)) => (len, mem::size_of::<i16>()),
+        Some(Type::Int32(len)) => (len, mem::size_of::<i32>()),
+        Some(Type::Float(len)) => (len, mem::size_of::<f32>()),
+        Some(Type::String(len)) => (len, mem::size_of::<u8>()),
+    };
+
+    match len
+        .checked_mul(sample_count)
+        .and_then(|n| n.checked_mul(value_size))
+    {
+        Some(size) if size <= reader.len() => Ok(()),
+        _ => Err(io::Error::from(io::ErrorKind::UnexpectedEof)),
+    }
+}
+
+// Converts a raw value to the shape given by the key, i.e., single values of fields with a number
+// other than 1 are arrays of length 1, and strings of character and string fields are parsed as
+// VCF values.
+fn resolve_genotype_field_value(key: &Key, value: Option<Value>) -> io::Result<Option<Value>> {
+    use vcf::header::Number;
+
+    let is_array = key.number() != Number::Count(1);
+
+    match value {
+        Some(Value::Integer(n)) if is_array => Ok(Some(Value::IntegerArray(vec![Some(n)]))),
+        Some(Value::Float(n)) if is_array => Ok(Some(Value::FloatArray(vec![Some(n)]))),
+        Some(Value::String(s)) => {
+            if s == MISSING_VALUE {
+                Ok(None)
+            } else {
+                Value::from_str_key(&s, key)
+                    .map(Some)
+                    .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+            }
+        }
+        value => Ok(value),
+    }
+}
+
+fn read_genotype_field_int8_values(
+    reader: &mut &[u8],
     sample_count: usize,
-) -> io::Result<Vec<Option<Value>>>
-where
-    R: Read,
-{
+) -> io::Result<Vec<Option<Value>>> {
     let mut values = Vec::with_capacity(sample_count);
 
     for _ in 0..sample_count {
@@ -158,56 +179,47 @@ where
 
         match value {
             Int8::Value(n) => values.push(Some(Value::Integer(i32::from(n)))),
-            Int8::Missing => values.push(None),
-            _ => todo!("unhandled i8 value: {:?}", value),
+            // An empty vector is a missing value.
+            Int8::Missing | Int8::EndOfVector => values.push(None),
+            Int8::Reserved(_) => return Err(invalid_value_error(value)),
         }
     }
 
     Ok(values)
 }
 
-fn read_genotype_field_int8_array_values<R>(
-    reader: &mut R,
+fn read_genotype_field_int8_array_values(
+    reader: &mut &[u8],
     sample_count: usize,
     len: usize,
-) -> io::Result<Vec<Option<Value>>>
-where
-    R: Read,
-{
+) -> io::Result<Vec<Option<Value>>> {
     let mut values = Vec::with_capacity(sample_count);
+    let mut buf = vec![0; len];
 
     for _ in 0..sample_count {
-        let mut buf = vec![0; len];
         reader.read_i8_into(&mut buf)?;
 
-        let vs: Vec<_> = buf
-            .into_iter()
-            .map(Int8::from)
-            .filter_map(|value| match value {
-                Int8::Value(n) => Some(Some(i32::from(n))),
-                Int8::Missing => Some(None),
-                Int8::EndOfVector => None,
-                _ => todo!("unhandled i8 array value: {:?}", value),
-            })
-            .collect();
+        let mut vs = Vec::with_capacity(len);
 
-        if vs.len() == 1 && vs[0].is_none() {
-            values.push(None);
-        } else {
-            values.push(Some(Value::IntegerArray(vs)));
+        for value in buf.iter().copied().map(Int8::from) {
+            match value {
+                Int8::Value(n) => vs.push(Some(i32::from(n))),
+                Int8::Missing => vs.push(None),
+                Int8::EndOfVector => break,
+                Int8::Reserved(_) => return Err(invalid_value_error(value)),
+            }
         }
+
+        values.push(array_value(vs, Value::IntegerArray));
     }
 
     Ok(values)
 }
 
-fn read_genotype_field_int16_values<R>(
-    reader: &mut R,
+fn read_genotype_field_int16_values(
+    reader: &mut &[u8],
     sample_count: usize,
-) -> io::Result<Vec<Option<Value>>>
-where
-    R: Read,
-{
+) -> io::Result<Vec<Option<Value>>> {
     let mut values = Vec::with_capacity(sample_count);
 
     for _ in 0..sample_count {
@@ -215,56 +227,47 @@ where
 
         match value {
             Int16::Value(n) => values.push(Some(Value::Integer(i32::from(n)))),
-            Int16::Missing => values.push(None),
-            _ => todo!("unhandled i16 value: {:?}", value),
+            // An empty vector is a missing value.
+            Int16::Missing | Int16::EndOfVector => values.push(None),
+            Int16::Reserved(_) => return Err(invalid_value_error(value)),
         }
     }
 
     Ok(values)
 }
 
-fn read_genotype_field_int16_array_values<R>(
-    reader: &mut R,
+fn read_genotype_field_int16_array_values(
+    reader: &mut &[u8],
     sample_count: usize,
     len: usize,
-) -> io::Result<Vec<Option<Value>>>
-where
-    R: Read,
-{
+) -> io::Result<Vec<Option<Value>>> {
     let mut values = Vec::with_capacity(sample_count);
+    let mut buf = vec![0; len];
 
     for _ in 0..sample_count {
-        let mut buf = vec![0; len];
         reader.read_i16_into::<LittleEndian>(&mut buf)?;
 
-        let vs: Vec<_> = buf
-            .into_iter()
-            .map(Int16::from)
-            .filter_map(|value| match value {
-                Int16::Value(n) => Some(Some(i32::from(n))),
-                Int16::Missing => Some(None),
-                Int16::EndOfVector => None,
-                _ => todo!("unhandled i16 array value: {:?}", value),
-            })
-            .collect();
+        let mut vs = Vec::with_capacity(len);
 
-        if vs.len() == 1 && vs[0].is_none() {
-            values.push(None);
-        } else {
-            values.push(Some(Value::IntegerArray(vs)));
+        for value in buf.iter().copied().map(Int16::from) {
+            match value {
+                Int16::Value(n) => vs.push(Some(i32::from(n))),
+                Int16::Missing => vs.push(None),
+                Int16::EndOfVector => break,
+                Int16::Reserved(_) => return Err(invalid_value_error(value)),
+            }
         }
+
+        values.push(array_value(vs, Value::IntegerArray));
     }
 
     Ok(values)
 }
 
-fn read_genotype_field_int32_values<R>(
-    reader: &mut R,
+fn read_genotype_field_int32_values(
+    reader: &mut &[u8],
     sample_count: usize,
-) -> io::Result<Vec<Option<Value>>>
-where
-    R: Read,
-{
+) -> io::Result<Vec<Option<Value>>> {
     let mut values = Vec::with_capacity(sample_count);
 
     for _ in 0..sample_count {
@@ -272,56 +275,47 @@ where
 
         match value {
             Int32::Value(n) => values.push(Some(Value::Integer(n))),
-            Int32::Missing => values.push(None),
-            _ => todo!("unhandled i32 value: {:?}", value),
+            // An empty vector is a missing value.
+            Int32::Missing | Int32::EndOfVector => values.push(None),
+            Int32::Reserved(_) => return Err(invalid_value_error(value)),
         }
     }
 
     Ok(values)
 }
 
-fn read_genotype_field_int32_array_values<R>(
-    reader: &mut R,
+fn read_genotype_field_int32_array_values(
+    reader: &mut &[u8],
     sample_count: usize,
     len: usize,
-) -> io::Result<Vec<Option<Value>>>
-where
-    R: Read,
-{
+) -> io::Result<Vec<Option<Value>>> {
     let mut values = Vec::with_capacity(sample_count);
+    let mut buf = vec![0; len];
 
     for _ in 0..sample_count {
-        let mut buf = vec![0; len];
         reader.read_i32_into::<LittleEndian>(&mut buf)?;
 
-        let vs: Vec<_> = buf
-            .into_iter()
-            .map(Int32::from)
-            .filter_map(|value| match value {
-                Int32::Value(n) => Some(Some(n)),
-                Int32::Missing => Some(None),
-                Int32::EndOfVector => None,
-                _ => todo!("unhandled i32 array value: {:?}", value),
-            })
-            .collect();
+        let mut vs = Vec::with_capacity(len);
 
-        if vs.len() == 1 && vs[0].is_none() {
-            values.push(None);
-        } else {
-            values.push(Some(Value::IntegerArray(vs)));
+        for value in buf.iter().copied().map(Int32::from) {
+            match value {
+                Int32::Value(n) => vs.push(Some(n)),
+                Int32::Missing => vs.push(None),
+                Int32::EndOfVector => break,
+                Int32::Reserved(_) => return Err(invalid_value_error(value)),
+            }
         }
+
+        values.push(array_value(vs, Value::IntegerArray));
     }
 
     Ok(values)
 }
 
-fn read_genotype_field_float_values<R>(
-    reader: &mut R,
+fn read_genotype_field_float_values(
+    reader: &mut &[u8],
     sample_count: usize,
-) -> io::Result<Vec<Option<Value>>>
-where
-    R: Read,
-{
+) -> io::Result<Vec<Option<Value>>> {
     let mut values = Vec::with_capacity(sample_count);
 
     for _ in 0..sample_count {
@@ -329,57 +323,69 @@ where
 
         match value {
             Float::Value(n) => values.push(Some(Value::Float(n))),
-            Float::Missing => values.push(None),
-            _ => todo!("unhandled f32 value: {:?}", value),
+            // An empty vector is a missing value.
+            Float::Missing | Float::EndOfVector => values.push(None),
+            Float::Reserved(_) => return Err(invalid_value_error(value)),
         }
     }
 
     Ok(values)
 }
 
-fn read_genotype_field_float_array_values<R>(
-    reader: &mut R,
+fn read_genotype_field_float_array_values(
+    reader: &mut &[u8],
     sample_count: usize,
     len: usize,
-) -> io::Result<Vec<Option<Value>>>
-where
-    R: Read,
-{
+) -> io::Result<Vec<Option<Value>>> {
     let mut values = Vec::with_capacity(sample_count);
+    let mut buf = vec![0.0; len];
 
     for _ in 0..sample_count {
-        let mut buf = vec![0.0; len];
         reader.read_f32_into::<LittleEndian>(&mut buf)?;
 
-        let vs: Vec<_> = buf
-            .into_iter()
-            .map(Float::from)
-            .filter_map(|value| match value {
-                Float::Value(n) => Some(Some(n)),
-                Float::Missing => Some(None),
-                Float::EndOfVector => None,
-                _ => todo!("unhandled f32 array value: {:?}", value),
-            })
-            .collect();
+        let mut vs = Vec::with_capacity(len);
 
-        if vs.len() == 1 && vs[0].is_none() {
-            values.push(None);
-        } else {
-            values.push(Some(Value::FloatArray(vs)));
+        for value in buf.iter().copied().map(Float::from) {
+            match value {
+                Float::Value(n) => vs.push(Some(n)),
+                Float::Missing => vs.push(None),
+                Float::EndOfVector => break,
+                Float::Reserved(_) => return Err(invalid_value_error(value)),
+            }
         }
+
+        values.push(array_value(vs, Value::FloatArray));
     }
 
     Ok(values)
 }
 
-fn read_genotype_field_string_values<R>(
-    reader: &mut R,
-    sample_count: usize,
-    len: usize,
-) -> io::Result<Vec<Option<Value>>>
+// A vector with no values or a single missing value is a missing value.
+fn array_value<T, F>(values: Vec<Option<T>>, f: F) -> Option<Value>
 where
-    R: Read,
+    F: FnOnce(Vec<Option<T>>) -> Value,
+{
+    match values[..] {
+        [] | [None] => None,
+        _ => Some(f(values)),
+    }
+}
+
+fn invalid_value_error<T>(value: T) -> io::Error
+where
+    T: fmt::Debug,
 {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("invalid value: {:?}", value),
+    )
+}
+
+fn read_genotype_field_string_values(
+    reader: &mut &[u8],
+    sample_count: usize,
+    len: usize,
+) -> io::Result<Vec<Option<Value>>> {
     let mut values = Vec::with_capacity(sample_count);
     let mut buf = vec![0; len];
 
@@ -392,59 +398,123 @@ where
         };
 
         let s = str::from_utf8(data).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
-        let value = Value::String(s.into());
 
-        values.push(Some(value));
+        // An empty string is a missing value.
+        let value = if s.is_empty() {
+            None
+        } else {
+            Some(Value::String(s.into()))
+        };
+
+        values.push(value);
     }
 
     Ok(values)
 }
 
-fn read_genotype_genotype_field_values<R>(
-    reader: &mut R,
+fn read_genotype_genotype_field_values(
+    reader: &mut &[u8],
     sample_count: usize,
-) -> io::Result<Vec<Option<Value>>>
-where
-    R: Read,
-{
+) -> io::Result<Vec<Option<Value>>> {
+    let ty = read_type(reader)?;
+    check_values_len(reader, ty, sample_count)?;
+
     let mut values = Vec::with_capacity(sample_count);
 
-    match read_type(reader)? {
-        Some(Type::Int8(len)) => match len {
-            0 => values.push(None),
-            1 => {
-                for _ in 0..sample_count {
-                    let value = reader
-                        .read_i8()
-                        .map(|v| parse_genotype_genotype_field_values(&[v]))
-                        .map(Value::String)?;
-
-                    values.push(Some(value));
+    match ty {
+        None | Some(Type::Int8(0) | Type::Int16(0) | Type::Int32(0)) => {
+            values.resize(sample_count, None);
+        }
+        Some(Type::Int8(len)) => {
+            let mut buf = vec![0; len];
+
+            for _ in 0..sample_count {
+                reader.read_i8_into(&mut buf)?;
+
+                let mut raw_values = Vec::with_capacity(len);
+
+                for value in buf.iter().copied().map(Int8::from) {
+                    match value {
+                        Int8::Value(n) => raw_values.push(i32::from(n)),
+                        Int8::Missing => raw_values.push(0),
+                        Int8::EndOfVector => break,
+                        Int8::Reserved(_) => return Err(invalid_value_error(value)),
+                    }
                 }
+
+                values.push(genotype_value(&raw_values)?);
             }
-            _ => {
-                for _ in 0..sample_count {
-                    let mut buf = vec![0; len];
-                    reader.read_i8_into(&mut buf)?;
-                    let value = Value::String(parse_genotype_genotype_field_values(&buf));
-                    values.push(Some(value));
+        }
+        Some(Type::Int16(len)) => {
+            let mut buf = vec![0; len];
+
+            for _ in 0..sample_count {
+                reader.read_i16_into::<LittleEndian>(&mut buf)?;
+
+                let mut raw_values = Vec::with_capacity(len);
+
+                for value in buf.iter().copied().map(Int16::from) {
+                    match value {
+                        Int16::Value(n) => raw_values.push(i32::from(n)),
+                        Int16::Missing => raw_values.push(0),
+                        Int16::EndOfVector => break,
+                        Int16::Reserved(_) => return Err(invalid_value_error(value)),
+                    }
                 }
+
+                values.push(genotype_value(&raw_values)?);
             }
-        },
-        ty => todo!("unhandled type: {:?}", ty),
+        }
+        Some(Type::Int32(len)) => {
+            let mut buf = vec![0; len];
+
+            for _ in 0..sample_count {
+                reader.read_i32_into::<LittleEndian>(&mut buf)?;
+
+                let mut raw_values = Vec::with_capacity(len);
+
+                for value in buf.iter().copied().map(Int32::from) {
+                    match value {
+                        Int32::Value(n) => raw_values.push(n),
+                        Int32::Missing => raw_values.push(0),
+                        Int32::EndOfVector => break,
+                        Int32::Reserved(_) => return Err(invalid_value_error(value)),
+                    }
+                }
+
+                values.push(genotype_value(&raw_values)?);
+            }
+        }
+        ty => {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("invalid genotype field type: {:?}", ty),
+            ))
+        }
     }
 
     Ok(values)
 }
 
-fn parse_genotype_genotype_field_values(values: &[i8]) -> String {
+// An empty genotype or a single missing allele is a missing value.
+fn genotype_value(values: &[i32]) -> io::Result<Option<Value>> {
+    if values.is_empty() {
+        return Ok(None);
+    }
+
+    let s = parse_genotype_genotype_field_values(values)?;
+
+    if s == MISSING_VALUE {
+        Ok(None)
+    } else {
+        Ok(Some(Value::String(s)))
+    }
+}
+
+fn parse_genotype_genotype_field_values(values: &[i32]) -> io::Result<String> {
     let mut genotype = String::new();
 
     for (i, &value) in values.iter().enumerate() {
-        if let Int8::EndOfVector = Int8::from(value) {
-            break;
-        }
-
         let j = (value >> 1) - 1;
         let is_phased = value & 0x01 == 1;
 
@@ -456,14 +526,19 @@ fn parse_genotype_genotype_field_values(values: &[i8]) -> String {
             }
         }
 
-        if j == -1 {
-            genotype.push('.');
-        } else {
-            genotype.push_str(&format!("{}", j));
+        match j {
+            -1 => genotype.push('.'),
+            0.. => genotype.push_str(&format!("{}", j)),
+            _ => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("invalid genotype allele: {}", value),
+                ))
+            }
         }
     }
 
-    genotype
+    Ok(genotype)
 }
 
 #[cfg(test)]
@@ -661,27 +736,172 @@ mod tests {
     }
 
     #[test]
-    fn test_parse_genotype_genotype_field_values() {
+    fn test_read_genotype_field_values_with_sentinel_values() -> io::Result<()> {
+        let data = [
+            0x21, // Some(Type::Int8(2))
+            0x08, 0x81, // Some([Some(8)])
+            0x80, 0x81, // None
+            0x81, 0x81, // None
+            0x80, 0x0d, // Some([None, Some(13)])
+        ];
+        let mut reader = &data[..];
+
+        let actual = read_genotype_field_values(&mut reader, 4)?;
+        let expected = vec![
+            Some(Value::IntegerArray(vec![Some(8)])),
+            None,
+            None,
+            Some(Value::IntegerArray(vec![None, Some(13)])),
+        ];
+
+        assert_eq!(actual, expected);
+
+        let data = [
+            0x15, // Some(Type::Float(1))
+            0x02, 0x00, 0x80, 0x7f, // None (end of vector)
+        ];
+        let mut reader = &data[..];
+        assert_eq!(read_genotype_field_values(&mut reader, 1)?, vec![None]);
+
+        let data = [
+            0x00, // None
+        ];
+        let mut reader = &data[..];
+        assert_eq!(
+            read_genotype_field_values(&mut reader, 2)?,
+            vec![None, None]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_genotype_field_values_with_reserved_values() {
+        fn t(data: &[u8]) {
+            let mut reader = data;
+            assert!(matches!(
+                read_genotype_field_values(&mut reader, 1),
+                Err(e) if e.kind() == io::ErrorKind::InvalidData
+            ));
+        }
+
+        t(&[0x11, 0x82]);
+        t(&[0x21, 0x00, 0x87]);
+        t(&[0x12, 0x02, 0x80]);
+        t(&[0x13, 0x07, 0x00, 0x00, 0x80]);
+        t(&[0x15, 0x03, 0x00, 0x80, 0x7f]);
+        t(&[0x25, 0x00, 0x00, 0x00, 0x00, 0x07, 0x00, 0x80, 0x7f]);
+    }
+
+    #[test]
+    fn test_read_genotype_field_values_with_length_past_end_of_buffer() {
+        let data = [
+            0xf3, // Some(Type::Int32(15)), the length overflows
+            0x13, 0xff, 0xff, 0xff, 0x7f, // 2147483647
+            0x00,
+        ];
+        let mut reader = &data[..];
+        assert!(matches!(
+            read_genotype_field_values(&mut reader, 1),
+            Err(e) if e.kind() == io::ErrorKind::UnexpectedEof
+        ));
+
+        let data = [
+            0xf1, // Some(Type::Int8(15)), the length overflows
+            0x13, 0xff, 0xff, 0xff, 0x7f, // 2147483647
+            0x00,
+        ];
+        let mut reader = &data[..];
+        assert!(matches!(
+            read_genotype_genotype_field_values(&mut reader, 1),
+            Err(e) if e.kind() == io::ErrorKind::UnexpectedEof
+        ));
+    }
+
+    #[test]
+    fn test_read_genotype_genotype_field_values() -> io::Result<()> {
+        fn t(data: &[u8], sample_count: usize, expected: &[Option<&str>]) -> io::Result<()> {
+            let mut reader = data;
+            let actual = read_genotype_genotype_field_values(&mut reader, sample_count)?;
+            let expected: Vec<_> = expected
+                .iter()
+                .map(|value| value.map(|s| Value::String(s.into())))
+                .collect();
+            assert_eq!(actual, expected);
+            Ok(())
+        }
+
+        t(
+            &[
+                0x21, // Some(Type::Int8(2))
+                0x02, 0x05, // 0|1
+                0x00, 0x81, // .
+                0x81, 0x81, // (empty)
+            ],
+            3,
+            &[Some("0|1"), None, None],
+        )?;
+
+        t(
+            &[
+                0x22, // Some(Type::Int16(2))
+                0x02, 0x00, 0x80, 0x00, // 0/63
+                0x04, 0x00, 0x01, 0x80, // 1
+            ],
+            2,
+            &[Some("0/63"), Some("1")],
+        )?;
+
+        t(
+            &[
+                0x13, // Some(Type::Int32(1))
+                0x03, 0x01, 0x00, 0x00, // 128
+            ],
+            1,
+            &[Some("128")],
+        )?;
+
+        t(&[0x01], 2, &[None, None])?;
+
+        let data = [0x17, b'0'];
+        let mut reader = &data[..];
+        assert!(matches!(
+            read_genotype_genotype_field_values(&mut reader, 1),
+            Err(e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+
+        let data = [0x11, 0x83];
+        let mut reader = &data[..];
+        assert!(matches!(
+            read_genotype_genotype_field_values(&mut reader, 1),
+            Err(e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_genotype_genotype_field_values() -> io::Result<()> {
         // Examples from § 6.3.3 Type encoding (2021-05-13)
 
-        assert_eq!(parse_genotype_genotype_field_values(&[0x02, 0x02]), "0/0");
-        assert_eq!(parse_genotype_genotype_field_values(&[0x02, 0x04]), "0/1");
-        assert_eq!(parse_genotype_genotype_field_values(&[0x04, 0x04]), "1/1");
-        assert_eq!(parse_genotype_genotype_field_values(&[0x02, 0x05]), "0|1");
-        assert_eq!(parse_genotype_genotype_field_values(&[0x00, 0x00]), "./.");
-        assert_eq!(parse_genotype_genotype_field_values(&[0x02]), "0");
-        assert_eq!(parse_genotype_genotype_field_values(&[0x04]), "1");
+        assert_eq!(parse_genotype_genotype_field_values(&[0x02, 0x02])?, "0/0");
+        assert_eq!(parse_genotype_genotype_field_values(&[0x02, 0x04])?, "0/1");
+        assert_eq!(parse_genotype_genotype_field_values(&[0x04, 0x04])?, "1/1");
+        assert_eq!(parse_genotype_genotype_field_values(&[0x02, 0x05])?, "0|1");
+        assert_eq!(parse_genotype_genotype_field_values(&[0x00, 0x00])?, "./.");
+        assert_eq!(parse_genotype_genotype_field_values(&[0x02])?, "0");
+        assert_eq!(parse_genotype_genotype_field_values(&[0x04])?, "1");
         assert_eq!(
-            parse_genotype_genotype_field_values(&[0x02, 0x04, 0x06]),
+            parse_genotype_genotype_field_values(&[0x02, 0x04, 0x06])?,
             "0/1/2"
         );
         assert_eq!(
-            parse_genotype_genotype_field_values(&[0x02, 0x04, 0x07]),
+            parse_genotype_genotype_field_values(&[0x02, 0x04, 0x07])?,
             "0/1|2"
         );
-        assert_eq!(
-            parse_genotype_genotype_field_values(&[0x02, i8::from(Int8::EndOfVector)]),
-            "0"
-        );
+
+        assert!(parse_genotype_genotype_field_values(&[-4]).is_err());
+
+        Ok(())
     }
 }
@@ -1,6 +1,9 @@
 use std::io::{self, Read};
 
-use noodles_vcf::{self as vcf, header::info::Type};
+use noodles_vcf::{
+    self as vcf,
+    header::{info::Type, Number},
+};
 
 use crate::{
     header::StringMap,
@@ -90,12 +93,33 @@ fn read_info_field_value<R>(
 where
     R: Read,
 {
-    match info.ty() {
+    let value = match info.ty() {
         Type::Integer => read_info_field_integer_value(reader),
         Type::Flag => read_info_field_flag_value(reader),
         Type::Float => read_info_field_float_value(reader),
         Type::Character => read_info_field_character_value(reader),
         Type::String => read_info_field_string_value(reader),
+    }?;
+
+    match info.number() {
+        Number::Count(0 | 1) => Ok(value),
+        _ => Ok(value.map(into_array_value)),
+    }
+}
+
+// Single values of fields with a number other than 1 are arrays of length 1. Strings of these
+// fields are comma-separated lists.
+fn into_array_value(value: vcf::record::info::field::Value) -> vcf::record::info::field::Value {
+    use vcf::record::info::field::Value;
+
+    const DELIMITER: char = ',';
+
+    match value {
+        Value::Integer(n) => Value::IntegerArray(vec![n]),
+        Value::Float(n) => Value::FloatArray(vec![n]),
+        Value::Character(c) => Value::CharacterArray(vec![c]),
+        Value::String(s) => Value::StringArray(s.split(DELIMITER).map(String::from).collect()),
+        _ => value,
     }
 }
 
@@ -182,13 +206,28 @@ where
                     io::Error::new(io::ErrorKind::InvalidData, "INFO character value missing")
                 })?,
             _ => Ok(Some(vcf::record::info::field::Value::CharacterArray(
-                s.chars().collect(),
+                parse_character_array(&s),
             ))),
         },
         v => Err(type_mismatch_error(v, Type::Character)),
     }
 }
 
+// Characters in an array are typically comma-separated, but a character can itself be a comma.
+// If every other character is a delimiter, the characters between them are the values.
+// Otherwise, each character is a value.
+fn parse_character_array(s: &str) -> Vec<char> {
+    const DELIMITER: char = ',';
+
+    let is_delimited = s.chars().skip(1).step_by(2).all(|c| c == DELIMITER);
+
+    if is_delimited {
+        s.chars().step_by(2).collect()
+    } else {
+        s.chars().collect()
+    }
+}
+
 fn read_info_field_string_value<R>(
     reader: &mut R,
 ) -> io::Result<Option<vcf::record::info::field::Value>>
@@ -439,6 +478,12 @@ mod tests {
 
         // Some(Value::String(Some(String::from("nd"))))
         t(&[0x27, 0x6e, 0x64], &info, Some(vec!['n', 'd']))?;
+        // Some(Value::String(Some(String::from("n,d"))))
+        t(&[0x37, 0x6e, 0x2c, 0x64], &info, Some(vec!['n', 'd']))?;
+        // Some(Value::String(Some(String::from(",,n"))))
+        t(&[0x37, 0x2c, 0x2c, 0x6e], &info, Some(vec![',', 'n']))?;
+        // Some(Value::String(Some(String::from("n,,"))))
+        t(&[0x37, 0x6e, 0x2c, 0x2c], &info, Some(vec!['n', ',']))?;
 
         Ok(())
     }
@@ -474,4 +519,57 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_read_info_field_value_with_string_array_value() -> io::Result<()> {
+        fn t(
+            mut reader: &[u8],
+            info: &vcf::header::Info,
+            expected_value: Option<Vec<&str>>,
+        ) -> io::Result<()> {
+            let actual = read_info_field_value(&mut reader, info)?;
+            let expected = expected_value.map(|values| {
+                vcf::record::info::field::Value::StringArray(
+                    values.into_iter().map(String::from).collect(),
+                )
+            });
+            assert_eq!(actual, expected);
+            Ok(())
+        }
+
+        let info = vcf::header::Info::from(Key::Other(
+            String::from("STRING"),
+            Number::Unknown,
+            Type::String,
+            String::default(),
+        ));
+
+        // None
+        t(&[0x00], &info, None)?;
+
+        // Some(Value::String(Some(String::from("nd"))))
+        t(&[0x27, 0x6e, 0x64], &info, Some(vec!["nd"]))?;
+        // Some(Value::String(Some(String::from("n,ls"))))
+        t(
+            &[0x47, 0x6e, 0x2c, 0x6c, 0x73],
+            &info,
+            Some(vec!["n", "ls"]),
+        )?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_info_field_value_with_single_value_array() -> io::Result<()> {
+        let info = vcf::header::Info::from(Key::AlleleCount);
+
+        // Some(Value::Int8(Some(Int8::Value(8))))
+        let mut reader = &[0x11, 0x08][..];
+        assert_eq!(
+            read_info_field_value(&mut reader, &info)?,
+            Some(vcf::record::info::field::Value::IntegerArray(vec![8]))
+        );
+
+        Ok(())
+    }
 }
@@ -4,7 +4,7 @@ pub use self::ty::read_type;
 
 use std::io::{self, Read};
 
-use byteorder::{LittleEndian, ReadBytesExt};
+use byteorder::{ByteOrder, LittleEndian, ReadBytesExt};
 
 use crate::record::{
     value::{Float, Int16, Int32, Int8, Type},
@@ -67,6 +67,41 @@ where
     }
 }
 
+pub fn skip_value<R>(reader: &mut R) -> io::Result<()>
+where
+    R: Read,
+{
+    let ty = read_type(reader)?;
+    skip_values(reader, ty, 1)
+}
+
+// Discards `n` vectors of the given type.
+pub fn skip_values<R>(reader: &mut R, ty: Option<Type>, n: usize) -> io::Result<()>
+where
+    R: Read,
+{
+    let (len, size) = match ty {
+        Some(Type::Int8(len) | Type::String(len)) => (len, 1),
+        Some(Type::Int16(len)) => (len, 2),
+        Some(Type::Int32(len) | Type::Float(len)) => (len, 4),
+        None => return Ok(()),
+    };
+
+    let byte_count = len
+        .checked_mul(size)
+        .and_then(|m| m.checked_mul(n))
+        .and_then(|m| u64::try_from(m).ok())
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "invalid value length"))?;
+
+    let copied = io::copy(&mut reader.take(byte_count), &mut io::sink())?;
+
+    if copied < byte_count {
+        Err(io::Error::from(io::ErrorKind::UnexpectedEof))
+    } else {
+        Ok(())
+    }
+}
+
 fn read_i8<R>(reader: &mut R) -> io::Result<i8>
 where
     R: Read,
@@ -78,9 +113,8 @@ fn read_i8_array<R>(reader: &mut R, len: usize) -> io::Result<Vec<i8>>
 where
     R: Read,
 {
-    let mut buf = vec![0; len];
-    reader.read_i8_into(&mut buf)?;
-    Ok(buf)
+    let buf = read_array_data(reader, len, 1)?;
+    Ok(buf.into_iter().map(|n| n as i8).collect())
 }
 
 fn read_i16<R>(reader: &mut R) -> io::Result<i16>
@@ -94,9 +128,10 @@ fn read_i16_array<R>(reader: &mut R, len: usize) -> io::Result<Vec<i16>>
 where
     R: Read,
 {
-    let mut buf = vec![0; len];
-    reader.read_i16_into::<LittleEndian>(&mut buf)?;
-    Ok(buf)
+    let buf = read_array_data(reader, len, 2)?;
+    let mut values = vec![0; len];
+    LittleEndian::read_i16_into(&buf, &mut values);
+    Ok(values)
 }
 
 fn read_i32<R>(reader: &mut R) -> io::Result<i32>
@@ -110,9 +145,10 @@ fn read_i32_array<R>(reader: &mut R, len: usize) -> io::Result<Vec<i32>>
 where
     R: Read,
 {
-    let mut buf = vec![0; len];
-    reader.read_i32_into::<LittleEndian>(&mut buf)?;
-    Ok(buf)
+    let buf = read_array_data(reader, len, 4)?;
+    let mut values = vec![0; len];
+    LittleEndian::read_i32_into(&buf, &mut values);
+    Ok(values)
 }
 
 fn read_float<R>(reader: &mut R) -> io::Result<f32>
@@ -126,24 +162,80 @@ fn read_float_array<R>(reader: &mut R, len: usize) -> io::Result<Vec<f32>>
 where
     R: Read,
 {
-    let mut buf = vec![0.0; len];
-    reader.read_f32_into::<LittleEndian>(&mut buf)?;
-    Ok(buf)
+    let buf = read_array_data(reader, len, 4)?;
+    let mut values = vec![0.0; len];
+    LittleEndian::read_f32_into(&buf, &mut values);
+    Ok(values)
 }
 
 fn read_string<R>(reader: &mut R, len: usize) -> io::Result<String>
 where
     R: Read,
 {
-    let mut buf = vec![0; len];
-    reader.read_exact(&mut buf)?;
+    let buf = read_array_data(reader, len, 1)?;
     String::from_utf8(buf).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
 }
 
+// Reads the raw data of `len` values that are each `size` bytes.
+//
+// The length is read from the input and is untrusted. Rather than allocating the buffer up front,
+// it grows as data is read, which bounds it by the remaining input. An error is returned if the
+// input ends before all values are read.
+fn read_array_data<R>(reader: &mut R, len: usize, size: usize) -> io::Result<Vec<u8>>
+where
+    R: Read,
+{
+    let byte_count = len
+        .checked_mul(size)
+        .and_then(|n| u64::try_from(n).ok())
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "invalid value length"))?;
+
+    let mut buf = Vec::new();
+    reader.take(byte_count).read_to_end(&mut buf)?;
+
+    if (buf.len() as u64) < byte_count {
+        Err(io::Error::from(io::ErrorKind::UnexpectedEof))
+    } else {
+        Ok(buf)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_skip_value() -> io::Result<()> {
+        let data = [
+            0x00, // None
+            0x11, 0x05, // Some(Value::Int8(Some(Int8::Value(5))))
+            0x32, 0x79, 0x01, 0x62, 0x02, 0xdb,
+            0x03, // Some(Value::Int16Array([377, 610, 987]))
+            0x15, 0x00, 0x00, 0x00, 0x3f, // Some(Value::Float(Some(Float::Value(0.5))))
+            0x37, b'n', b'd', b'l', // Some(Value::String(Some(String::from("ndl"))))
+            0x11, 0x08, // Some(Value::Int8(Some(Int8::Value(8))))
+        ];
+        let mut reader = &data[..];
+
+        for _ in 0..5 {
+            skip_value(&mut reader)?;
+        }
+
+        assert!(matches!(
+            read_value(&mut reader),
+            Ok(Some(Value::Int8(Some(Int8::Value(8)))))
+        ));
+
+        let data = [0x23, 0x00, 0x00, 0x00, 0x00];
+        let mut reader = &data[..];
+        assert!(matches!(
+            skip_value(&mut reader),
+            Err(e) if e.kind() == io::ErrorKind::UnexpectedEof
+        ));
+
+        Ok(())
+    }
+
     #[test]
     fn test_read_value() {
         let data = [0x00];
@@ -257,4 +349,17 @@ mod tests {
             Ok(Some(Value::String(Some(value)))) if value == "ndls"
         ));
     }
+
+    #[test]
+    fn test_read_value_with_length_past_end_of_input() {
+        // The length is the overflow length 2^31 - 1.
+        for ty in [0xf1, 0xf2, 0xf3, 0xf5, 0xf7] {
+            let data = [ty, 0x13, 0xff, 0xff, 0xff, 0x7f, 0x00, 0x00, 0x00, 0x00];
+            let mut reader = &data[..];
+            assert!(matches!(
+                read_value(&mut reader),
+                Err(e) if e.kind() == io::ErrorKind::UnexpectedEof
+            ));
+        }
+    }
 }
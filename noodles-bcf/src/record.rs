@@ -2,12 +2,11 @@
 
 mod convert;
 mod filters;
-mod genotypes;
+pub mod genotypes;
 mod info;
-pub(crate) mod value;
+pub mod value;
 
-pub(crate) use self::value::Value;
-pub use self::{filters::Filters, genotypes::Genotypes, info::Info};
+pub use self::{filters::Filters, genotypes::Genotypes, info::Info, value::Value};
 
 use std::io;
 
@@ -85,7 +84,7 @@ impl Record {
         &mut self.pos
     }
 
-    fn rlen(&self) -> i32 {
+    pub(crate) fn rlen(&self) -> i32 {
         self.rlen
     }
 
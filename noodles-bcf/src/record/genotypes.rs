@@ -1,8 +1,15 @@
-use std::io;
+//! BCF record genotypes and fields.
 
+mod values;
+
+pub use self::values::Values;
+
+use std::io::{self, Read};
+
+use byteorder::{LittleEndian, ReadBytesExt};
 use noodles_vcf as vcf;
 
-use crate::header::StringMap;
+use crate::{header::StringMap, record::value::Type};
 
 /// BCF record genotypes.
 #[derive(Clone, Debug, Default, Eq, PartialEq)]
@@ -55,6 +62,29 @@ impl Genotypes {
         Ok(genotypes)
     }
 
+    /// Creates BCF record genotypes by wrapping the given buffer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bcf::record::Genotypes;
+    ///
+    /// let data = vec![
+    ///     0x11, 0x01, 0x11, // GT, Some(Type::Int8(1))
+    ///     0x02, // sample 0: 0
+    ///     0x04, // sample 1: 1
+    /// ];
+    ///
+    /// let genotypes = Genotypes::new(data, 1, 2);
+    /// ```
+    pub fn new(buf: Vec<u8>, format_count: usize, sample_count: usize) -> Self {
+        Self {
+            buf,
+            format_count,
+            sample_count,
+        }
+    }
+
     /// Returns the number of samples.
     ///
     /// # Examples
@@ -112,6 +142,63 @@ impl Genotypes {
         self.format_count
     }
 
+    /// Returns the raw values of the field with the given string map index for all samples.
+    ///
+    /// This only decodes the values of the matching field. Other fields are skipped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_bcf::record::{genotypes::Values, Genotypes};
+    ///
+    /// let data = vec![
+    ///     0x11, 0x01, 0x21, // GT, Some(Type::Int8(2))
+    ///     0x02, 0x02, // sample 0: 0/0
+    ///     0x02, 0x05, // sample 1: 0|1
+    ///     0x11, 0x02, 0x11, // DP, Some(Type::Int8(1))
+    ///     0x08, // sample 0: 8
+    ///     0x0d, // sample 1: 13
+    /// ];
+    ///
+    /// let genotypes = Genotypes::new(data, 2, 2);
+    ///
+    /// assert_eq!(
+    ///     genotypes.get_raw_values(2).transpose()?,
+    ///     Some(Values::Int8(vec![8, 13]))
+    /// );
+    ///
+    /// assert!(genotypes.get_raw_values(3).is_none());
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn get_raw_values(&self, string_map_index: usize) -> Option<io::Result<Values>> {
+        use crate::reader::{
+            string_map::read_string_map_index,
+            value::{read_type, skip_values},
+        };
+
+        let mut reader = &self.buf[..];
+
+        for _ in 0..self.format_count() {
+            let result = read_string_map_index(&mut reader)
+                .and_then(|i| read_type(&mut reader).map(|ty| (i, ty)));
+
+            match result {
+                Ok((i, ty)) if i == string_map_index => {
+                    return Some(read_values(&mut reader, ty, self.len()));
+                }
+                Ok((_, ty)) => {
+                    if let Err(e) = skip_values(&mut reader, ty, self.len()) {
+                        return Some(Err(e));
+                    }
+                }
+                Err(e) => return Some(Err(e)),
+            }
+        }
+
+        None
+    }
+
     pub(crate) fn set_format_count(&mut self, format_count: usize) {
         self.format_count = format_count;
     }
@@ -121,6 +208,61 @@ impl Genotypes {
     }
 }
 
+fn read_values(reader: &mut &[u8], ty: Option<Type>, sample_count: usize) -> io::Result<Values> {
+    use std::mem;
+
+    let ty = match ty {
+        Some(ty) => ty,
+        None => return Ok(Values::Int8(Vec::new())),
+    };
+
+    let (len, value_size) = match ty {
+        Type::Int8(len) => (len, mem::size_of::<i8>()),
+        Type::Int16(len) => (len, mem::size_of::<i16>()),
+        Type::Int32(len) => (len, mem::size_of::<i32>()),
+        Type::Float(len) => (len, mem::size_of::<f32>()),
+        Type::String(len) => (len, mem::size_of::<u8>()),
+    };
+
+    let n = len.checked_mul(sample_count).ok_or_else(|| {
+        io::Error::new(io::ErrorKind::InvalidData, "invalid genotype field length")
+    })?;
+
+    // The buffer is checked to hold all values before allocating them.
+    match n.checked_mul(value_size) {
+        Some(size) if size <= reader.len() => {}
+        _ => return Err(io::Error::from(io::ErrorKind::UnexpectedEof)),
+    }
+
+    match ty {
+        Type::Int8(_) => {
+            let mut buf = vec![0; n];
+            reader.read_i8_into(&mut buf)?;
+            Ok(Values::Int8(buf))
+        }
+        Type::Int16(_) => {
+            let mut buf = vec![0; n];
+            reader.read_i16_into::<LittleEndian>(&mut buf)?;
+            Ok(Values::Int16(buf))
+        }
+        Type::Int32(_) => {
+            let mut buf = vec![0; n];
+            reader.read_i32_into::<LittleEndian>(&mut buf)?;
+            Ok(Values::Int32(buf))
+        }
+        Type::Float(_) => {
+            let mut buf = vec![0.0; n];
+            reader.read_f32_into::<LittleEndian>(&mut buf)?;
+            Ok(Values::Float(buf))
+        }
+        Type::String(_) => {
+            let mut buf = vec![0; n];
+            reader.read_exact(&mut buf)?;
+            Ok(Values::String(buf))
+        }
+    }
+}
+
 impl AsRef<[u8]> for Genotypes {
     fn as_ref(&self) -> &[u8] {
         &self.buf
@@ -132,3 +274,76 @@ impl AsMut<Vec<u8>> for Genotypes {
         &mut self.buf
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_get_raw_values() -> io::Result<()> {
+        let data = vec![
+            0x11, 0x01, 0x21, // GT, Some(Type::Int8(2))
+            0x02, 0x04, // sample 0: 0/1
+            0x04, 0x81, // sample 1: 1
+            0x11, 0x02, 0x12, // DP, Some(Type::Int16(1))
+            0x08, 0x00, // sample 0: 8
+            0x79, 0x01, // sample 1: 377
+            0x11, 0x03, 0x25, // GL, Some(Type::Float(2))
+            0x00, 0x00, 0x00, 0xbf, 0x00, 0x00, 0x80, 0xbf, // sample 0: [-0.5, -1.0]
+            0x00, 0x00, 0x00, 0x40, 0x02, 0x00, 0x80, 0x7f, // sample 1: [2.0]
+            0x11, 0x04, 0x27, // FT, Some(Type::String(2))
+            b'q', b'1', // sample 0: q1
+            b'.', 0x00, // sample 1: .
+            0x11, 0x05, 0x00, // XX, None
+        ];
+
+        let genotypes = Genotypes::new(data, 5, 2);
+
+        assert_eq!(
+            genotypes.get_raw_values(1).transpose()?,
+            Some(Values::Int8(vec![0x02, 0x04, 0x04, -127]))
+        );
+        assert_eq!(
+            genotypes.get_raw_values(2).transpose()?,
+            Some(Values::Int16(vec![8, 377]))
+        );
+
+        match genotypes.get_raw_values(3).transpose()? {
+            Some(Values::Float(values)) => {
+                assert_eq!(values.len(), 4);
+                assert_eq!(values[..3], [-0.5, -1.0, 2.0]);
+                assert_eq!(values[3].to_bits(), 0x7f800002);
+            }
+            values => panic!("unexpected values: {:?}", values),
+        }
+
+        assert_eq!(
+            genotypes.get_raw_values(4).transpose()?,
+            Some(Values::String(b"q1.\0".to_vec()))
+        );
+        assert_eq!(
+            genotypes.get_raw_values(5).transpose()?,
+            Some(Values::Int8(Vec::new()))
+        );
+        assert!(genotypes.get_raw_values(6).is_none());
+
+        let genotypes = Genotypes::new(vec![0x11, 0x01, 0x21, 0x02, 0x04], 1, 2);
+        assert!(matches!(
+            genotypes.get_raw_values(1),
+            Some(Err(e)) if e.kind() == io::ErrorKind::UnexpectedEof
+        ));
+
+        // The length (2^31 - 1) is greater than the buffer.
+        let genotypes = Genotypes::new(
+            vec![0x11, 0x01, 0xf1, 0x13, 0xff, 0xff, 0xff, 0x7f, 0x00],
+            1,
+            2,
+        );
+        assert!(matches!(
+            genotypes.get_raw_values(1),
+            Some(Err(e)) if e.kind() == io::ErrorKind::UnexpectedEof
+        ));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,55 @@
+/// The raw values of a BCF record genotype field for all samples.
+///
+/// Each sample has the same number of values, i.e., `n = values.len() / sample_count`, and the
+/// values are contiguous in sample order, i.e., the values of sample `i` are in the range
+/// `i * n..(i + 1) * n`. Vectors shorter than `n` are padded with end-of-vector values, or in the
+/// case of strings, NUL.
+///
+/// A field with no type and no values is an empty [`Self::Int8`].
+#[derive(Clone, Debug, PartialEq)]
+pub enum Values {
+    /// 8-bit integers.
+    Int8(Vec<i8>),
+    /// 16-bit integers.
+    Int16(Vec<i16>),
+    /// 32-bit integers.
+    Int32(Vec<i32>),
+    /// Single-precision floating-points.
+    Float(Vec<f32>),
+    /// Characters.
+    String(Vec<u8>),
+}
+
+impl Values {
+    /// Returns the number of values for all samples.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bcf::record::genotypes::Values;
+    /// let values = Values::Int8(vec![2, 4, 2, -127]);
+    /// assert_eq!(values.len(), 4);
+    /// ```
+    pub fn len(&self) -> usize {
+        match self {
+            Self::Int8(values) => values.len(),
+            Self::Int16(values) => values.len(),
+            Self::Int32(values) => values.len(),
+            Self::Float(values) => values.len(),
+            Self::String(values) => values.len(),
+        }
+    }
+
+    /// Returns whether there are any values.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bcf::record::genotypes::Values;
+    /// let values = Values::Int8(Vec::new());
+    /// assert!(values.is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
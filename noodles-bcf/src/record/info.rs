@@ -2,7 +2,7 @@ use std::io;
 
 use noodles_vcf as vcf;
 
-use crate::header::StringMap;
+use crate::{header::StringMap, record::Value};
 
 /// BCF record info.
 #[derive(Clone, Debug, Default, Eq, PartialEq)]
@@ -125,7 +125,7 @@ impl Info {
     ///
     /// assert_eq!(
     ///     info.get(&header, &string_map, &Key::AlleleCount).transpose()?,
-    ///     Some(Field::new(Key::AlleleCount, Some(Value::Integer(5))))
+    ///     Some(Field::new(Key::AlleleCount, Some(Value::IntegerArray(vec![5]))))
     /// );
     ///
     /// assert!(info.get(&header, &string_map, &Key::AncestralAllele).is_none());
@@ -177,7 +177,7 @@ impl Info {
     ///
     /// assert_eq!(
     ///     fields.next().transpose()?,
-    ///     Some(Field::new(Key::AlleleCount, Some(Value::Integer(5))))
+    ///     Some(Field::new(Key::AlleleCount, Some(Value::IntegerArray(vec![5]))))
     /// );
     ///
     /// assert_eq!(
@@ -198,6 +198,59 @@ impl Info {
         (0..self.len()).map(move |_| read_info_field(&mut reader, header.infos(), string_map))
     }
 
+    /// Returns the raw value of the field with the given string map index.
+    ///
+    /// This only decodes the value of the matching field. Other fields are skipped. The outer
+    /// `Option` is `None` if the field is not present, and the inner `Option` is `None` if the
+    /// field value has no type, e.g., a flag.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_bcf::record::{
+    ///     value::Int8,
+    ///     Info, Value,
+    /// };
+    ///
+    /// let data = vec![
+    ///     0x11, 0x01, 0x11, 0x05, // AC=5
+    ///     0x11, 0x02, 0x11, 0x08, // DP=8
+    /// ];
+    ///
+    /// let info = Info::new(data, 2);
+    ///
+    /// assert_eq!(
+    ///     info.get_raw_value(2).transpose()?,
+    ///     Some(Some(Value::Int8(Some(Int8::Value(8)))))
+    /// );
+    ///
+    /// assert!(info.get_raw_value(3).is_none());
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn get_raw_value(&self, string_map_index: usize) -> Option<io::Result<Option<Value>>> {
+        use crate::reader::{
+            string_map::read_string_map_index,
+            value::{read_value, skip_value},
+        };
+
+        let mut reader = &self.buf[..];
+
+        for _ in 0..self.len() {
+            match read_string_map_index(&mut reader) {
+                Ok(i) if i == string_map_index => return Some(read_value(&mut reader)),
+                Ok(_) => {
+                    if let Err(e) = skip_value(&mut reader) {
+                        return Some(Err(e));
+                    }
+                }
+                Err(e) => return Some(Err(e)),
+            }
+        }
+
+        None
+    }
+
     pub(crate) fn set_field_count(&mut self, field_count: usize) {
         self.field_count = field_count;
     }
@@ -214,3 +267,54 @@ impl AsMut<Vec<u8>> for Info {
         &mut self.buf
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::record::value::Int8;
+
+    #[test]
+    fn test_get_raw_value() -> io::Result<()> {
+        let data = vec![
+            0x11, 0x01, 0x11, 0x05, // AC=5
+            0x11, 0x02, 0x27, b'n', b'd', // AA=nd
+            0x11, 0x03, 0x00, // DB
+            0x11, 0x04, 0x25, 0x00, 0x00, 0x00, 0x3f, 0x00, 0x00, 0x80, 0x3e, // AF=0.5,0.25
+        ];
+
+        let info = Info::new(data, 4);
+
+        assert_eq!(
+            info.get_raw_value(1).transpose()?,
+            Some(Some(Value::Int8(Some(Int8::Value(5)))))
+        );
+        assert_eq!(
+            info.get_raw_value(2).transpose()?,
+            Some(Some(Value::String(Some(String::from("nd")))))
+        );
+        assert_eq!(info.get_raw_value(3).transpose()?, Some(None));
+        assert_eq!(
+            info.get_raw_value(4).transpose()?,
+            Some(Some(Value::FloatArray(vec![0.5, 0.25])))
+        );
+        assert!(info.get_raw_value(5).is_none());
+
+        let info = Info::new(vec![0x11, 0x01, 0x15, 0x00], 2);
+        assert!(matches!(
+            info.get_raw_value(2),
+            Some(Err(e)) if e.kind() == io::ErrorKind::UnexpectedEof
+        ));
+
+        // The value length is 2^31 - 1, which is past the end of the buffer.
+        let info = Info::new(
+            vec![0x11, 0x01, 0xf3, 0x13, 0xff, 0xff, 0xff, 0x7f, 0x00],
+            1,
+        );
+        assert!(matches!(
+            info.get_raw_value(1),
+            Some(Err(e)) if e.kind() == io::ErrorKind::UnexpectedEof
+        ));
+
+        Ok(())
+    }
+}
@@ -1,3 +1,5 @@
+//! BCF record typed values.
+
 mod float;
 mod int16;
 mod int32;
@@ -6,15 +8,27 @@ mod ty;
 
 pub use self::{float::Float, int16::Int16, int32::Int32, int8::Int8, ty::Type};
 
+/// A BCF record typed value.
+///
+/// A `None` in a single value variant is a value with a length of 0.
 #[derive(Clone, Debug, PartialEq)]
 pub enum Value {
+    /// An 8-bit integer.
     Int8(Option<Int8>),
+    /// An array of 8-bit integers.
     Int8Array(Vec<i8>),
+    /// A 16-bit integer.
     Int16(Option<Int16>),
+    /// An array of 16-bit integers.
     Int16Array(Vec<i16>),
+    /// A 32-bit integer.
     Int32(Option<Int32>),
+    /// An array of 32-bit integers.
     Int32Array(Vec<i32>),
+    /// A single-precision floating-point.
     Float(Option<Float>),
+    /// An array of single-precision floating-points.
     FloatArray(Vec<f32>),
+    /// A string.
     String(Option<String>),
 }
@@ -1,9 +1,14 @@
+/// A BCF record floating-point value.
 #[derive(Clone, Debug, PartialEq)]
 #[non_exhaustive]
 pub enum Float {
+    /// A value.
     Value(f32),
+    /// A missing value.
     Missing,
+    /// The end of a vector.
     EndOfVector,
+    /// A reserved value.
     Reserved(f32),
 }
 
@@ -1,9 +1,14 @@
+/// A BCF record 32-bit integer value.
 #[derive(Clone, Debug, Eq, PartialEq)]
 #[non_exhaustive]
 pub enum Int32 {
+    /// A value.
     Value(i32),
+    /// A missing value.
     Missing,
+    /// The end of a vector.
     EndOfVector,
+    /// A reserved value.
     Reserved(i32),
 }
 
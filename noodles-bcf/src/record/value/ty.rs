@@ -1,8 +1,16 @@
+/// A BCF record value type.
+///
+/// Each variant holds the number of values of the type.
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum Type {
+    /// An 8-bit integer.
     Int8(usize),
+    /// A 16-bit integer.
     Int16(usize),
+    /// A 32-bit integer.
     Int32(usize),
+    /// A single-precision floating-point.
     Float(usize),
+    /// A character.
     String(usize),
 }
@@ -263,4 +263,125 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_write_vcf_record_round_trip() -> Result<(), Box<dyn std::error::Error>> {
+        use crate::{header::StringMap, Reader, Record};
+
+        const RAW_HEADER: &str = r#"##fileformat=VCFv4.3
+##FILTER=<ID=PASS,Description="All filters passed">
+##FILTER=<ID=q10,Description="Quality below 10">
+##FILTER=<ID=s50,Description="Less than 50% of samples have data">
+##INFO=<ID=NS,Number=1,Type=Integer,Description="Number of samples with data">
+##INFO=<ID=AC,Number=A,Type=Integer,Description="Allele count">
+##INFO=<ID=AF,Number=A,Type=Float,Description="Allele frequency">
+##INFO=<ID=AA,Number=1,Type=String,Description="Ancestral allele">
+##INFO=<ID=DB,Number=0,Type=Flag,Description="dbSNP membership">
+##INFO=<ID=DP,Number=1,Type=Integer,Description="Combined depth">
+##FORMAT=<ID=GT,Number=1,Type=String,Description="Genotype">
+##FORMAT=<ID=GQ,Number=1,Type=Integer,Description="Genotype quality">
+##FORMAT=<ID=AD,Number=R,Type=Integer,Description="Read depth for each allele">
+##FORMAT=<ID=GL,Number=G,Type=Float,Description="Genotype likelihoods">
+##FORMAT=<ID=FT,Number=1,Type=String,Description="Sample filter">
+##contig=<ID=sq0>
+##contig=<ID=sq1>
+#CHROM	POS	ID	REF	ALT	QUAL	FILTER	INFO	FORMAT	sample0	sample1	sample2
+"#;
+
+        let header: vcf::Header = RAW_HEADER.parse()?;
+        let string_map = StringMap::from(&header);
+
+        let records: Vec<vcf::Record> = [
+            "sq0\t1\t.\tA\t.\t.\t.\t.\tGT\t0|0\t0/0\t./.",
+            "sq0\t8\trs13\tA\tC,G\t30.1\tPASS\tNS=3;AC=1,2;AF=0.5,0.25;AA=A;DB\tGT:GQ:AD:GL:FT\t0|1:13:5,8,0:-0.5,-1,-2,-3,-4,-5:PASS\t1/2/1:.:.:.:.\t./.:21:3,.,1:-1,.:q10;s50",
+            "sq1\t21\t.\tAC\tA\t.\tq10;s50\tAC=1;AF=.;DP=.\tGT:GQ\t1:.\t0/1:8\t.:.",
+        ]
+        .iter()
+        .map(|s| vcf::Record::try_from_str(s, &header))
+        .collect::<Result<_, _>>()?;
+
+        let mut writer = Writer::from(Vec::new());
+        writer.write_file_format()?;
+        writer.write_header(&header)?;
+
+        for record in &records {
+            writer.write_vcf_record(&header, &string_map, record)?;
+        }
+
+        let data = writer.into_inner();
+        let mut reader = Reader::from(&data[..]);
+        reader.read_file_format()?;
+        reader.read_header()?;
+
+        let mut record = Record::default();
+
+        for expected in &records {
+            reader.read_record(&mut record)?;
+            let actual = record.try_into_vcf_record(&header, &string_map)?;
+            assert_eq!(&actual, expected);
+        }
+
+        assert_eq!(reader.read_record(&mut record)?, 0);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_write_vcf_record_with_sample_subset() -> Result<(), Box<dyn std::error::Error>> {
+        use vcf::subset::Subsetter;
+
+        use crate::{header::StringMap, Reader, Record};
+
+        const RAW_HEADER: &str = r#"##fileformat=VCFv4.3
+##INFO=<ID=AC,Number=A,Type=Integer,Description="Allele count">
+##INFO=<ID=AF,Number=A,Type=Float,Description="Allele frequency">
+##FORMAT=<ID=GT,Number=1,Type=String,Description="Genotype">
+##FORMAT=<ID=AD,Number=R,Type=Integer,Description="Read depth for each allele">
+##contig=<ID=sq0>
+#CHROM	POS	ID	REF	ALT	QUAL	FILTER	INFO	FORMAT	sample0	sample1	sample2
+"#;
+
+        let header: vcf::Header = RAW_HEADER.parse()?;
+
+        let subsetter = Subsetter::builder()
+            .set_sample_names(["sample2", "sample0"])
+            .set_update_allele_counts(true)
+            .set_trim_alternate_bases(true)
+            .build(&header)?;
+
+        let subset_header = subsetter.header();
+        let string_map = StringMap::from(subset_header);
+
+        let mut record = vcf::Record::try_from_str(
+            "sq0\t8\t.\tA\tC,G\t.\tPASS\tAC=2,1;AF=0.333333,0.166667\tGT:AD\t0/1:5,3,0\t1/2:0,4,4\t0/0:9,0,0",
+            &header,
+        )?;
+
+        subsetter.subset_record(&mut record)?;
+
+        assert_eq!(
+            record.to_string(),
+            "sq0\t8\t.\tA\tC\t.\tPASS\tAC=1;AF=0.25;AN=4\tGT:AD\t0/0:9,0\t0/1:5,3"
+        );
+
+        let mut writer = Writer::from(Vec::new());
+        writer.write_file_format()?;
+        writer.write_header(subset_header)?;
+        writer.write_vcf_record(subset_header, &string_map, &record)?;
+
+        let data = writer.into_inner();
+        let mut reader = Reader::from(&data[..]);
+        reader.read_file_format()?;
+        let actual_header: vcf::Header = reader.read_header()?.parse()?;
+        assert_eq!(actual_header.sample_names(), subset_header.sample_names());
+
+        let mut actual = Record::default();
+        reader.read_record(&mut actual)?;
+        assert_eq!(
+            actual.try_into_vcf_record(subset_header, &string_map)?,
+            record
+        );
+
+        Ok(())
+    }
 }
@@ -67,15 +67,8 @@ fn write_filter<W>(writer: &mut W, filters: &Filters) -> io::Result<()>
 where
     W: Write,
 {
-    use crate::writer::value::write_value;
-
-    if filters.is_empty() {
-        write_value(writer, None)?;
-    } else {
-        todo!();
-    }
-
-    Ok(())
+    use crate::writer::string_map::write_string_map_indices;
+    write_string_map_indices(writer, filters.as_ref())
 }
 
 #[cfg(test)]
@@ -89,7 +82,7 @@ mod tests {
         write_record(&mut buf, &record)?;
 
         let expected = [
-            0x1d, 0x00, 0x00, 0x00, // l_shared = 29
+            0x1c, 0x00, 0x00, 0x00, // l_shared = 28
             0x00, 0x00, 0x00, 0x00, // l_indiv = 0
             0x00, 0x00, 0x00, 0x00, // chrom = 0,
             0x00, 0x00, 0x00, 0x00, // pos = 0 (0-based)
@@ -101,7 +94,6 @@ mod tests {
             0x00, // n_fmt = 0
             0x07, // id = None
             0x17, b'A', // ref = [A]
-            0x07, // alt = []
             0x00, // filter = []
         ];
 
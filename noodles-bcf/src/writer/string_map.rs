@@ -25,6 +25,32 @@ where
     }
 }
 
+pub fn write_string_map_indices<W>(writer: &mut W, indices: &[usize]) -> io::Result<()>
+where
+    W: Write,
+{
+    if indices.is_empty() {
+        return write_value(writer, None);
+    }
+
+    let max_index = indices.iter().copied().max().unwrap_or_default();
+
+    let value = if i8::try_from(max_index).is_ok() {
+        Value::Int8Array(indices.iter().map(|&i| i as i8).collect())
+    } else if i16::try_from(max_index).is_ok() {
+        Value::Int16Array(indices.iter().map(|&i| i as i16).collect())
+    } else if i32::try_from(max_index).is_ok() {
+        Value::Int32Array(indices.iter().map(|&i| i as i32).collect())
+    } else {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("invalid index: {}", max_index),
+        ));
+    };
+
+    write_value(writer, Some(value))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -60,4 +86,30 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_write_string_map_indices() -> io::Result<()> {
+        fn t(buf: &mut Vec<u8>, indices: &[usize], expected: &[u8]) -> io::Result<()> {
+            buf.clear();
+            write_string_map_indices(buf, indices)?;
+            assert_eq!(buf, expected);
+            Ok(())
+        }
+
+        let mut buf = Vec::new();
+
+        t(&mut buf, &[], &[0x00])?;
+        t(&mut buf, &[0], &[0x11, 0x00])?;
+        t(&mut buf, &[1, 127], &[0x21, 0x01, 0x7f])?;
+        t(&mut buf, &[1, 128], &[0x22, 0x01, 0x00, 0x80, 0x00])?;
+        t(&mut buf, &[32768], &[0x13, 0x00, 0x80, 0x00, 0x00])?;
+
+        buf.clear();
+        assert!(matches!(
+            write_string_map_indices(&mut buf, &[2147483648]),
+            Err(ref e) if e.kind() == io::ErrorKind::InvalidInput
+        ));
+
+        Ok(())
+    }
 }
@@ -28,23 +28,20 @@ pub fn write_genotypes<W>(
 where
     W: Write,
 {
-    for (i, key) in keys.iter().enumerate() {
+    for key in keys.iter() {
         write_genotype_field_key(writer, string_map, key)?;
 
-        let mut values = Vec::with_capacity(keys.len());
+        // Trailing fields of a sample may be dropped, in which case they are missing.
+        let values: Vec<_> = genotypes
+            .iter()
+            .map(|genotype| genotype.get(key).and_then(|field| field.value()))
+            .collect();
 
-        for genotype in genotypes {
-            let value = genotype
-                .get_index(i)
-                .map(|(_, field)| field.value())
-                .ok_or_else(|| {
-                    io::Error::new(io::ErrorKind::InvalidData, "missing genotype field")
-                })?;
-
-            values.push(value);
+        if key == &Key::Genotype {
+            write_genotype_genotype_field_values(writer, &values)?;
+        } else {
+            write_genotype_field_values(writer, key, &values)?;
         }
-
-        write_genotype_field_values(writer, key, &values)?;
     }
 
     Ok(())
@@ -225,7 +222,8 @@ fn write_genotype_field_integer_array_values<W>(
 where
     W: Write,
 {
-    let mut max_len = usize::MIN;
+    // A missing value is written as a single missing element.
+    let mut max_len = 1;
     let (mut min, mut max) = (i32::MAX, i32::MIN);
 
     for value in values {
@@ -446,12 +444,12 @@ where
 {
     let max_len = values
         .iter()
-        .flat_map(|value| match value {
-            Some(Value::FloatArray(vs)) => Some(vs.len()),
-            _ => None,
+        .map(|value| match value {
+            Some(Value::FloatArray(vs)) => vs.len(),
+            _ => 1,
         })
         .max()
-        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "missing FloatArray values"))?;
+        .unwrap_or(1);
 
     write_type(writer, Some(Type::Float(max_len)))?;
 
@@ -569,12 +567,12 @@ where
 {
     let max_len = values
         .iter()
-        .flat_map(|value| match value {
-            Some(Value::String(s)) => Some(s.len()),
-            _ => None,
+        .map(|value| match value {
+            Some(Value::String(s)) => s.len(),
+            _ => MISSING_VALUE.len_utf8(),
         })
         .max()
-        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "missing String values"))?;
+        .unwrap_or(1);
 
     let mut buf = Vec::with_capacity(values.len() * max_len);
 
@@ -673,6 +671,130 @@ where
     Ok(())
 }
 
+fn write_genotype_genotype_field_values<W>(
+    writer: &mut W,
+    values: &[Option<&Value>],
+) -> io::Result<()>
+where
+    W: Write,
+{
+    use vcf::record::genotypes::genotype::field::value::Genotype;
+
+    let mut genotypes = Vec::with_capacity(values.len());
+
+    for value in values {
+        let genotype = match value {
+            Some(Value::String(s)) => s
+                .parse::<Genotype>()
+                .map(Some)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?,
+            None => None,
+            Some(v) => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("type mismatch: expected String, got {:?}", v),
+                ))
+            }
+        };
+
+        genotypes.push(genotype);
+    }
+
+    let max_len = genotypes
+        .iter()
+        .map(|genotype| genotype.as_ref().map(|g| g.len()).unwrap_or(1))
+        .max()
+        .unwrap_or(1);
+
+    // Encoded alleles, padded with `None` as end-of-vector markers.
+    let mut raw_values = Vec::with_capacity(genotypes.len() * max_len);
+    let mut max = 0;
+
+    for genotype in &genotypes {
+        let len = match genotype {
+            Some(genotype) => {
+                for allele in genotype.iter() {
+                    let n = encode_genotype_allele(allele)?;
+                    max = cmp::max(max, n);
+                    raw_values.push(Some(n));
+                }
+
+                genotype.len()
+            }
+            None => {
+                raw_values.push(Some(0));
+                1
+            }
+        };
+
+        for _ in len..max_len {
+            raw_values.push(None);
+        }
+    }
+
+    if max <= i32::from(Int8::MAX_VALUE) {
+        write_type(writer, Some(Type::Int8(max_len)))?;
+
+        for raw_value in raw_values {
+            let n = match raw_value {
+                Some(n) => {
+                    i8::try_from(n).map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?
+                }
+                None => i8::from(Int8::EndOfVector),
+            };
+
+            writer.write_i8(n)?;
+        }
+    } else if max <= i32::from(Int16::MAX_VALUE) {
+        write_type(writer, Some(Type::Int16(max_len)))?;
+
+        for raw_value in raw_values {
+            let n = match raw_value {
+                Some(n) => {
+                    i16::try_from(n).map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?
+                }
+                None => i16::from(Int16::EndOfVector),
+            };
+
+            writer.write_i16::<LittleEndian>(n)?;
+        }
+    } else {
+        write_type(writer, Some(Type::Int32(max_len)))?;
+
+        for raw_value in raw_values {
+            let n = raw_value.unwrap_or_else(|| i32::from(Int32::EndOfVector));
+            writer.write_i32::<LittleEndian>(n)?;
+        }
+    }
+
+    Ok(())
+}
+
+// § 6.3.3 Type encoding (2021-05-13): `(allele + 1) << 1 | phased`, where 0 is a missing allele.
+fn encode_genotype_allele(
+    allele: &vcf::record::genotypes::genotype::field::value::genotype::Allele,
+) -> io::Result<i32> {
+    use vcf::record::genotypes::genotype::field::value::genotype::allele::Phasing;
+
+    let i = match allele.position() {
+        Some(position) => position + 1,
+        None => 0,
+    };
+
+    let is_phased = matches!(allele.phasing(), Some(Phasing::Phased));
+
+    i32::try_from(i)
+        .ok()
+        .and_then(|n| n.checked_mul(2))
+        .and_then(|n| n.checked_add(i32::from(is_phased)))
+        .ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("invalid genotype allele position: {}", i - 1),
+            )
+        })
+}
+
 #[cfg(test)]
 mod tests {
     use noodles_vcf::header::{format, Number};
@@ -1211,4 +1333,98 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_write_genotype_field_values_with_missing_values() -> io::Result<()> {
+        fn t(key: &Key, expected: &[u8]) -> io::Result<()> {
+            let mut buf = Vec::new();
+            write_genotype_field_values(&mut buf, key, &[None, None])?;
+            assert_eq!(buf, expected);
+            Ok(())
+        }
+
+        t(
+            &Key::ReadDepths,
+            &[
+                0x11, // Some(Type::Int8(1))
+                0x80, // None
+                0x80, // None
+            ],
+        )?;
+
+        t(
+            &Key::GenotypeLikelihoods,
+            &[
+                0x15, // Some(Type::Float(1))
+                0x01, 0x00, 0x80, 0x7f, // None
+                0x01, 0x00, 0x80, 0x7f, // None
+            ],
+        )?;
+
+        t(
+            &Key::Filter,
+            &[
+                0x17, // Some(Type::String(1))
+                b'.', // None
+                b'.', // None
+            ],
+        )?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_write_genotype_genotype_field_values() -> io::Result<()> {
+        fn t(values: &[Option<&str>], expected: &[u8]) -> io::Result<()> {
+            let values: Vec<_> = values
+                .iter()
+                .map(|value| value.map(|s| Value::String(s.into())))
+                .collect();
+            let values: Vec<_> = values.iter().map(|value| value.as_ref()).collect();
+
+            let mut buf = Vec::new();
+            write_genotype_genotype_field_values(&mut buf, &values)?;
+            assert_eq!(buf, expected);
+
+            Ok(())
+        }
+
+        t(
+            &[Some("0/0"), Some("0|1"), Some("./."), None],
+            &[
+                0x21, // Some(Type::Int8(2))
+                0x02, 0x02, // 0/0
+                0x02, 0x05, // 0|1
+                0x00, 0x00, // ./.
+                0x00, 0x81, // .
+            ],
+        )?;
+
+        t(
+            &[Some("1"), Some("1/2/0")],
+            &[
+                0x31, // Some(Type::Int8(3))
+                0x04, 0x81, 0x81, // 1
+                0x04, 0x06, 0x02, // 1/2/0
+            ],
+        )?;
+
+        t(
+            &[Some("0/63")],
+            &[
+                0x22, // Some(Type::Int16(2))
+                0x02, 0x00, // 0
+                0x80, 0x00, // 63
+            ],
+        )?;
+
+        let value = Value::Integer(0);
+        let mut buf = Vec::new();
+        assert!(matches!(
+            write_genotype_genotype_field_values(&mut buf, &[Some(&value)]),
+            Err(e) if e.kind() == io::ErrorKind::InvalidInput
+        ));
+
+        Ok(())
+    }
 }
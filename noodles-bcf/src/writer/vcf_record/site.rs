@@ -8,7 +8,7 @@ use noodles_vcf as vcf;
 use crate::{
     header::StringMap,
     record::value::{Float, Value},
-    writer::value::write_value,
+    writer::{string_map::write_string_map_indices, value::write_value},
 };
 
 use self::info::write_info;
@@ -64,21 +64,25 @@ where
 {
     use vcf::record::Chromosome;
 
-    let chrom = match chromosome {
-        Chromosome::Name(name) => contigs
-            .get_index_of(name)
-            .ok_or_else(|| {
-                io::Error::new(
-                    io::ErrorKind::InvalidInput,
-                    format!("chromosome not in string map: {}", name),
-                )
-            })
-            .and_then(|i| {
-                i32::try_from(i).map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))
-            })?,
-        Chromosome::Symbol(_) => todo!("unhandled chromosome: {:?}", chromosome),
+    let i = match chromosome {
+        Chromosome::Name(name) => contigs.get_index_of(name),
+        // A symbolic chromosome refers to a contig by its ID, with or without the angle brackets.
+        Chromosome::Symbol(id) => contigs
+            .get_index_of(&chromosome.to_string())
+            .or_else(|| contigs.get_index_of(id)),
     };
 
+    let chrom = i
+        .ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("chromosome not in string map: {}", chromosome),
+            )
+        })
+        .and_then(|i| {
+            i32::try_from(i).map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))
+        })?;
+
     writer.write_i32::<LittleEndian>(chrom)
 }
 
@@ -98,7 +102,7 @@ pub(crate) fn write_rlen<W>(
 where
     W: Write,
 {
-    let rlen = i32::from(start) - i32::from(end) + 1;
+    let rlen = i32::from(end) - i32::from(start) + 1;
     writer.write_i32::<LittleEndian>(rlen)
 }
 
@@ -181,13 +185,9 @@ where
     let ref_value = Some(Value::String(Some(r#ref)));
     write_value(writer, ref_value)?;
 
-    if alternate_bases.is_empty() {
-        write_value(writer, Some(Value::String(None)))?;
-    } else {
-        for allele in alternate_bases.iter() {
-            let alt_value = Some(Value::String(Some(allele.to_string())));
-            write_value(writer, alt_value)?;
-        }
+    for allele in alternate_bases.iter() {
+        let alt_value = Some(Value::String(Some(allele.to_string())));
+        write_value(writer, alt_value)?;
     }
 
     Ok(())
@@ -209,25 +209,110 @@ where
         Some(Filters::Fail(ids)) => ids
             .iter()
             .map(|id| {
-                string_map
-                    .get_index_of(id)
-                    .ok_or_else(|| {
-                        io::Error::new(
-                            io::ErrorKind::InvalidInput,
-                            format!("filter missing from string map: {}", id),
-                        )
-                    })
-                    .and_then(|i| {
-                        i8::try_from(i).map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))
-                    })
+                string_map.get_index_of(id).ok_or_else(|| {
+                    io::Error::new(
+                        io::ErrorKind::InvalidInput,
+                        format!("filter missing from string map: {}", id),
+                    )
+                })
             })
             .collect::<Result<_, _>>()?,
     };
 
-    if indices.is_empty() {
-        write_value(writer, None)
-    } else {
-        let value = Some(Value::Int8Array(indices));
-        write_value(writer, value)
+    write_string_map_indices(writer, &indices)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_write_chrom() -> Result<(), Box<dyn std::error::Error>> {
+        use vcf::{header::Contig, record::Chromosome};
+
+        fn t(
+            buf: &mut Vec<u8>,
+            contigs: &vcf::header::Contigs,
+            chromosome: &Chromosome,
+            expected: &[u8],
+        ) -> io::Result<()> {
+            buf.clear();
+            write_chrom(buf, contigs, chromosome)?;
+            assert_eq!(buf, expected);
+            Ok(())
+        }
+
+        let header = vcf::Header::builder()
+            .add_contig(Contig::new("sq0"))
+            .add_contig(Contig::new("sq1"))
+            .build();
+        let contigs = header.contigs();
+
+        let mut buf = Vec::new();
+
+        t(
+            &mut buf,
+            contigs,
+            &"sq0".parse()?,
+            &[0x00, 0x00, 0x00, 0x00],
+        )?;
+        t(
+            &mut buf,
+            contigs,
+            &"<sq1>".parse()?,
+            &[0x01, 0x00, 0x00, 0x00],
+        )?;
+
+        buf.clear();
+        assert!(matches!(
+            write_chrom(&mut buf, contigs, &"sq2".parse()?),
+            Err(e) if e.kind() == io::ErrorKind::InvalidInput
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_write_filter() -> Result<(), Box<dyn std::error::Error>> {
+        use vcf::record::Filters;
+
+        fn t(
+            buf: &mut Vec<u8>,
+            string_map: &StringMap,
+            filters: Option<&Filters>,
+            expected: &[u8],
+        ) -> io::Result<()> {
+            buf.clear();
+            write_filter(buf, string_map, filters)?;
+            assert_eq!(buf, expected);
+            Ok(())
+        }
+
+        let string_map: StringMap = r#"##fileformat=VCFv4.3
+##FILTER=<ID=PASS,Description="All filters passed">
+##FILTER=<ID=q10,Description="Quality below 10">
+##FILTER=<ID=s50,Description="Less than 50% of samples have data">
+#CHROM	POS	ID	REF	ALT	QUAL	FILTER	INFO
+"#
+        .parse()?;
+
+        let mut buf = Vec::new();
+
+        t(&mut buf, &string_map, None, &[0x00])?;
+        t(&mut buf, &string_map, Some(&Filters::Pass), &[0x11, 0x00])?;
+        t(
+            &mut buf,
+            &string_map,
+            Some(&"q10;s50".parse()?),
+            &[0x21, 0x01, 0x02],
+        )?;
+
+        buf.clear();
+        assert!(matches!(
+            write_filter(&mut buf, &string_map, Some(&"s10".parse()?)),
+            Err(e) if e.kind() == io::ErrorKind::InvalidInput
+        ));
+
+        Ok(())
     }
 }
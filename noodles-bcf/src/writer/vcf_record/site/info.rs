@@ -35,7 +35,12 @@ where
     W: Write,
 {
     write_info_field_key(writer, string_map, field.key())?;
-    write_info_field_value(writer, field.value())?;
+
+    match field.value() {
+        Some(value) => write_info_field_value(writer, value)?,
+        None => write_info_field_missing_value(writer, field.key())?,
+    }
+
     Ok(())
 }
 
@@ -60,7 +65,7 @@ where
 
 fn write_info_field_value<W>(
     writer: &mut W,
-    value: Option<&vcf::record::info::field::Value>,
+    value: &vcf::record::info::field::Value,
 ) -> io::Result<()>
 where
     W: Write,
@@ -68,27 +73,44 @@ where
     use vcf::record::info::field;
 
     match value {
-        Some(field::Value::Integer(n)) => write_info_field_integer_value(writer, *n),
-        Some(field::Value::Float(n)) => write_info_field_float_value(writer, *n),
-        Some(field::Value::Flag) => write_info_field_flag_value(writer),
-        Some(field::Value::Character(c)) => write_info_field_character_value(writer, *c),
-        Some(field::Value::String(s)) => write_info_field_string_value(writer, s),
-        Some(field::Value::IntegerArray(values)) => {
-            write_info_field_integer_array_value(writer, values)
-        }
-        Some(field::Value::FloatArray(values)) => {
-            write_info_field_float_array_value(writer, values)
-        }
-        Some(field::Value::CharacterArray(values)) => {
+        field::Value::Integer(n) => write_info_field_integer_value(writer, *n),
+        field::Value::Float(n) => write_info_field_float_value(writer, *n),
+        field::Value::Flag => write_info_field_flag_value(writer),
+        field::Value::Character(c) => write_info_field_character_value(writer, *c),
+        field::Value::String(s) => write_info_field_string_value(writer, s),
+        field::Value::IntegerArray(values) => write_info_field_integer_array_value(writer, values),
+        field::Value::FloatArray(values) => write_info_field_float_array_value(writer, values),
+        field::Value::CharacterArray(values) => {
             write_info_field_character_array_value(writer, values)
         }
-        Some(field::Value::StringArray(values)) => {
-            write_info_field_string_array_value(writer, values)
-        }
-        _ => todo!("unhandled INFO field value: {:?}", value),
+        field::Value::StringArray(values) => write_info_field_string_array_value(writer, values),
     }
 }
 
+fn write_info_field_missing_value<W>(
+    writer: &mut W,
+    key: &vcf::record::info::field::Key,
+) -> io::Result<()>
+where
+    W: Write,
+{
+    use vcf::header::info::Type;
+
+    let value = match key.ty() {
+        Type::Integer => Value::Int8(None),
+        Type::Float => Value::Float(None),
+        Type::Flag => {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("missing info field flag value: {}", key),
+            ))
+        }
+        Type::Character | Type::String => Value::String(None),
+    };
+
+    write_value(writer, Some(value))
+}
+
 fn write_info_field_integer_value<W>(writer: &mut W, n: i32) -> io::Result<()>
 where
     W: Write,
@@ -258,7 +280,7 @@ mod test {
 
         fn t(buf: &mut Vec<u8>, value: &field::Value, expected: &[u8]) -> io::Result<()> {
             buf.clear();
-            write_info_field_value(buf, Some(value))?;
+            write_info_field_value(buf, value)?;
             assert_eq!(buf, expected);
             Ok(())
         }
@@ -268,7 +290,7 @@ mod test {
         let value = field::Value::Integer(-2147483641);
         buf.clear();
         assert!(matches!(
-            write_info_field_value(&mut buf, Some(&value)),
+            write_info_field_value(&mut buf, &value),
             Err(ref e) if e.kind() == io::ErrorKind::InvalidInput
         ));
 
@@ -314,7 +336,7 @@ mod test {
 
         let mut buf = Vec::new();
         let value = field::Value::Float(0.0);
-        write_info_field_value(&mut buf, Some(&value))?;
+        write_info_field_value(&mut buf, &value)?;
 
         let expected = [0x15, 0x00, 0x00, 0x00, 0x00];
 
@@ -329,7 +351,7 @@ mod test {
 
         let mut buf = Vec::new();
         let value = field::Value::Flag;
-        write_info_field_value(&mut buf, Some(&value))?;
+        write_info_field_value(&mut buf, &value)?;
 
         let expected = [0x00];
 
@@ -344,7 +366,7 @@ mod test {
 
         let mut buf = Vec::new();
         let value = field::Value::Character('n');
-        write_info_field_value(&mut buf, Some(&value))?;
+        write_info_field_value(&mut buf, &value)?;
 
         let expected = [0x17, 0x6e];
 
@@ -359,7 +381,7 @@ mod test {
 
         let mut buf = Vec::new();
         let value = field::Value::String(String::from("ndls"));
-        write_info_field_value(&mut buf, Some(&value))?;
+        write_info_field_value(&mut buf, &value)?;
 
         let expected = [0x47, 0x6e, 0x64, 0x6c, 0x73];
 
@@ -374,7 +396,7 @@ mod test {
 
         fn t(buf: &mut Vec<u8>, value: &field::Value, expected: &[u8]) -> io::Result<()> {
             buf.clear();
-            write_info_field_value(buf, Some(value))?;
+            write_info_field_value(buf, value)?;
             assert_eq!(buf, expected);
             Ok(())
         }
@@ -384,7 +406,7 @@ mod test {
         let value = field::Value::IntegerArray(vec![-2147483641, -2147483640]);
         buf.clear();
         assert!(matches!(
-            write_info_field_value(&mut buf, Some(&value)),
+            write_info_field_value(&mut buf, &value),
             Err(ref e) if e.kind() == io::ErrorKind::InvalidInput
         ));
 
@@ -446,7 +468,7 @@ mod test {
 
         let mut buf = Vec::new();
         let value = field::Value::FloatArray(vec![0.0, 1.0]);
-        write_info_field_value(&mut buf, Some(&value))?;
+        write_info_field_value(&mut buf, &value)?;
 
         let expected = [0x25, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x80, 0x3f];
 
@@ -461,7 +483,7 @@ mod test {
 
         let mut buf = Vec::new();
         let value = field::Value::CharacterArray(vec!['n', 'd', 'l', 's']);
-        write_info_field_value(&mut buf, Some(&value))?;
+        write_info_field_value(&mut buf, &value)?;
 
         let expected = [0x77, 0x6e, 0x2c, 0x64, 0x2c, 0x6c, 0x2c, 0x73];
 
@@ -476,7 +498,7 @@ mod test {
 
         let mut buf = Vec::new();
         let value = field::Value::StringArray(vec![String::from("nd"), String::from("ls")]);
-        write_info_field_value(&mut buf, Some(&value))?;
+        write_info_field_value(&mut buf, &value)?;
 
         let expected = [0x57, 0x6e, 0x64, 0x2c, 0x6c, 0x73];
 
@@ -484,4 +506,30 @@ mod test {
 
         Ok(())
     }
+
+    #[test]
+    fn test_write_info_field_missing_value() -> io::Result<()> {
+        use vcf::record::info::field::Key;
+
+        fn t(buf: &mut Vec<u8>, key: &Key, expected: &[u8]) -> io::Result<()> {
+            buf.clear();
+            write_info_field_missing_value(buf, key)?;
+            assert_eq!(buf, expected);
+            Ok(())
+        }
+
+        let mut buf = Vec::new();
+
+        t(&mut buf, &Key::TotalDepth, &[0x01])?;
+        t(&mut buf, &Key::AlleleFrequencies, &[0x05])?;
+        t(&mut buf, &Key::AncestralAllele, &[0x07])?;
+
+        buf.clear();
+        assert!(matches!(
+            write_info_field_missing_value(&mut buf, &Key::IsInDbSnp),
+            Err(e) if e.kind() == io::ErrorKind::InvalidInput
+        ));
+
+        Ok(())
+    }
 }
@@ -0,0 +1,70 @@
+//! gzip index (GZI).
+//!
+//! A gzip index maps uncompressed positions to the start of blocks in a BGZF stream. It is
+//! typically used alongside a FASTA index to randomly access a bgzip-compressed FASTA file.
+
+mod index;
+mod indexer;
+mod reader;
+mod writer;
+
+pub use self::{index::Index, reader::Reader, writer::Writer};
+
+use std::{
+    fs::File,
+    io::{self, BufReader},
+    path::Path,
+};
+
+use self::indexer::Indexer;
+
+/// Reads the entire contents of a gzip index.
+///
+/// This is a convenience function and is equivalent to opening the file at the given path and
+/// reading the index.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use std::io;
+/// use noodles_bgzf::gzi;
+/// let index = gzi::read("reference.fa.gz.gzi")?;
+/// # Ok::<(), io::Error>(())
+/// ```
+pub fn read<P>(src: P) -> io::Result<Index>
+where
+    P: AsRef<Path>,
+{
+    let mut reader = File::open(src).map(BufReader::new).map(Reader::new)?;
+    reader.read_index()
+}
+
+/// Indexes a BGZF file.
+///
+/// This scans each block in the file. An entry is added for the start of every block except the
+/// first.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use std::io;
+/// use noodles_bgzf::gzi;
+/// let index = gzi::index("reference.fa.gz")?;
+/// # Ok::<(), io::Error>(())
+/// ```
+pub fn index<P>(src: P) -> io::Result<Index>
+where
+    P: AsRef<Path>,
+{
+    let mut indexer = File::open(src).map(BufReader::new).map(Indexer::new)?;
+    let mut entries = Vec::new();
+
+    while let Some(entry) = indexer.index_block()? {
+        entries.push(entry);
+    }
+
+    // The last entry is the end of the stream and not the start of a block.
+    entries.pop();
+
+    Ok(Index::from(entries))
+}
@@ -0,0 +1,166 @@
+use std::io;
+
+use crate::VirtualPosition;
+
+/// A gzip index (GZI).
+///
+/// A gzip index holds a list of compressed and uncompressed position pairs. Each pair is the
+/// start of a block in the compressed stream and its corresponding position in the uncompressed
+/// stream. The first block, which is always at (0, 0), is implicit and not stored.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Index(Vec<(u64, u64)>);
+
+impl Index {
+    /// Returns the number of entries in the index.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf::gzi;
+    /// let index = gzi::Index::from(vec![(4668, 21294)]);
+    /// assert_eq!(index.len(), 1);
+    /// ```
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns whether the index has any entries.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf::gzi;
+    /// let index = gzi::Index::default();
+    /// assert!(index.is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns the virtual position of the given uncompressed position.
+    ///
+    /// The compressed position of the result is the start of the block that contains the given
+    /// uncompressed position.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_bgzf::{self as bgzf, gzi};
+    ///
+    /// let index = gzi::Index::from(vec![(4668, 21294), (9134, 42588)]);
+    ///
+    /// assert_eq!(index.query(0)?, bgzf::VirtualPosition::from(0));
+    /// assert_eq!(index.query(21295)?, bgzf::VirtualPosition::from(4668 << 16 | 1));
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn query(&self, pos: u64) -> io::Result<VirtualPosition> {
+        let i = self.0.partition_point(|&(_, upos)| upos <= pos);
+
+        let (cpos, block_upos) = if i == 0 { (0, 0) } else { self.0[i - 1] };
+
+        let upos = u16::try_from(pos - block_upos)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+
+        VirtualPosition::try_from((cpos, upos))
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))
+    }
+
+    /// Returns the uncompressed position of the given virtual position.
+    ///
+    /// This is the inverse of [`Self::query`]. The compressed position of the virtual position
+    /// must be the start of a block.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_bgzf::{self as bgzf, gzi};
+    ///
+    /// let index = gzi::Index::from(vec![(4668, 21294), (9134, 42588)]);
+    ///
+    /// let virtual_position = bgzf::VirtualPosition::from(4668 << 16 | 1);
+    /// assert_eq!(index.uncompressed_position(virtual_position)?, 21295);
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn uncompressed_position(&self, virtual_position: VirtualPosition) -> io::Result<u64> {
+        let (cpos, upos) = virtual_position.into();
+
+        let block_upos = if cpos == 0 {
+            0
+        } else {
+            self.0
+                .binary_search_by_key(&cpos, |&(c, _)| c)
+                .map(|i| self.0[i].1)
+                .map_err(|_| {
+                    io::Error::new(
+                        io::ErrorKind::InvalidInput,
+                        format!("compressed position {} is not the start of a block", cpos),
+                    )
+                })?
+        };
+
+        Ok(block_upos + u64::from(upos))
+    }
+}
+
+impl AsRef<[(u64, u64)]> for Index {
+    fn as_ref(&self) -> &[(u64, u64)] {
+        &self.0
+    }
+}
+
+impl From<Vec<(u64, u64)>> for Index {
+    fn from(entries: Vec<(u64, u64)>) -> Self {
+        Self(entries)
+    }
+}
+
+impl From<Index> for Vec<(u64, u64)> {
+    fn from(index: Index) -> Self {
+        index.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_query() -> Result<(), Box<dyn std::error::Error>> {
+        let index = Index::from(vec![(4668, 21294), (9134, 42588)]);
+
+        assert_eq!(index.query(0)?, VirtualPosition::try_from((0, 0))?);
+        assert_eq!(index.query(21293)?, VirtualPosition::try_from((0, 21293))?);
+        assert_eq!(index.query(21294)?, VirtualPosition::try_from((4668, 0))?);
+        assert_eq!(
+            index.query(42587)?,
+            VirtualPosition::try_from((4668, 21293))?
+        );
+        assert_eq!(index.query(42588)?, VirtualPosition::try_from((9134, 0))?);
+        assert_eq!(index.query(42600)?, VirtualPosition::try_from((9134, 12))?);
+
+        assert!(index.query(1 << 20).is_err());
+
+        let index = Index::default();
+        assert_eq!(index.query(8)?, VirtualPosition::try_from((0, 8))?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_uncompressed_position() -> Result<(), Box<dyn std::error::Error>> {
+        let index = Index::from(vec![(4668, 21294), (9134, 42588)]);
+
+        let virtual_position = VirtualPosition::try_from((0, 8))?;
+        assert_eq!(index.uncompressed_position(virtual_position)?, 8);
+
+        let virtual_position = VirtualPosition::try_from((9134, 12))?;
+        assert_eq!(index.uncompressed_position(virtual_position)?, 42600);
+
+        let virtual_position = VirtualPosition::try_from((13, 0))?;
+        assert!(index.uncompressed_position(virtual_position).is_err());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,71 @@
+use std::io::{self, Read};
+
+use crate::reader::read_compressed_block;
+
+/// A gzip index (GZI) indexer.
+///
+/// This scans the blocks of a BGZF stream without decompressing them.
+pub(crate) struct Indexer<R> {
+    inner: R,
+    buf: Vec<u8>,
+    compressed_position: u64,
+    uncompressed_position: u64,
+}
+
+impl<R> Indexer<R>
+where
+    R: Read,
+{
+    pub fn new(inner: R) -> Self {
+        Self {
+            inner,
+            buf: Vec::new(),
+            compressed_position: 0,
+            uncompressed_position: 0,
+        }
+    }
+
+    /// Reads the next block and returns the position pair at the end of it.
+    ///
+    /// This returns `None` when the stream reaches EOF.
+    pub fn index_block(&mut self) -> io::Result<Option<(u64, u64)>> {
+        match read_compressed_block(&mut self.inner, &mut self.buf)? {
+            (0, 0) => Ok(None),
+            (clen, ulen) => {
+                self.compressed_position += clen as u64;
+                self.uncompressed_position += ulen as u64;
+                Ok(Some((self.compressed_position, self.uncompressed_position)))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use super::*;
+    use crate::{writer::BGZF_EOF, Writer};
+
+    #[test]
+    fn test_index_block() -> io::Result<()> {
+        let mut writer = Writer::new(Vec::new());
+        writer.write_all(b"noodles")?;
+        writer.flush()?;
+        let block_0_len = writer.get_ref().len() as u64;
+        writer.write_all(b"bgzf")?;
+        let data = writer.finish()?;
+
+        let mut indexer = Indexer::new(&data[..]);
+
+        assert_eq!(indexer.index_block()?, Some((block_0_len, 7)));
+
+        let eof_start = (data.len() - BGZF_EOF.len()) as u64;
+        assert_eq!(indexer.index_block()?, Some((eof_start, 11)));
+
+        assert_eq!(indexer.index_block()?, Some((data.len() as u64, 11)));
+        assert!(indexer.index_block()?.is_none());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,232 @@
+use std::io::{self, BufRead, Read, Seek, SeekFrom};
+
+use super::{gzi, Reader};
+
+/// An indexed BGZF reader.
+///
+/// This pairs a BGZF reader with a gzip index (GZI), which allows seeking by uncompressed
+/// positions using [`std::io::Seek`]. This is useful with readers that only know about offsets in
+/// the uncompressed stream, e.g., a FASTA reader querying with a FASTA index.
+///
+/// Seeking from the end of the stream ([`std::io::SeekFrom::End`]) is not supported.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use std::{fs::File, io::{self, Read, Seek, SeekFrom}};
+/// use noodles_bgzf::{self as bgzf, gzi};
+///
+/// let index = gzi::read("data.gz.gzi")?;
+/// let mut reader = File::open("data.gz").map(|f| bgzf::IndexedReader::new(f, index))?;
+///
+/// reader.seek(SeekFrom::Start(21295))?;
+///
+/// let mut buf = [0; 8];
+/// reader.read_exact(&mut buf)?;
+/// # Ok::<(), io::Error>(())
+/// ```
+pub struct IndexedReader<R> {
+    inner: Reader<R>,
+    index: gzi::Index,
+}
+
+impl<R> IndexedReader<R>
+where
+    R: Read,
+{
+    /// Creates an indexed BGZF reader.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf::{self as bgzf, gzi};
+    /// let data = [];
+    /// let reader = bgzf::IndexedReader::new(&data[..], gzi::Index::default());
+    /// ```
+    pub fn new(inner: R, index: gzi::Index) -> Self {
+        Self::from_reader(Reader::new(inner), index)
+    }
+
+    /// Creates an indexed BGZF reader from an existing BGZF reader.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf::{self as bgzf, gzi};
+    /// let data = [];
+    /// let reader = bgzf::Reader::builder(&data[..]).set_worker_count(2).build();
+    /// let reader = bgzf::IndexedReader::from_reader(reader, gzi::Index::default());
+    /// ```
+    pub fn from_reader(inner: Reader<R>, index: gzi::Index) -> Self {
+        Self { inner, index }
+    }
+
+    /// Returns a reference to the underlying BGZF reader.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf::{self as bgzf, gzi};
+    /// let data = [];
+    /// let reader = bgzf::IndexedReader::new(&data[..], gzi::Index::default());
+    /// assert_eq!(reader.get_ref().virtual_position(), bgzf::VirtualPosition::from(0));
+    /// ```
+    pub fn get_ref(&self) -> &Reader<R> {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the underlying BGZF reader.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf::{self as bgzf, gzi};
+    /// let data = [];
+    /// let mut reader = bgzf::IndexedReader::new(&data[..], gzi::Index::default());
+    /// assert_eq!(reader.get_mut().virtual_position(), bgzf::VirtualPosition::from(0));
+    /// ```
+    pub fn get_mut(&mut self) -> &mut Reader<R> {
+        &mut self.inner
+    }
+
+    /// Returns the gzip index.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf::{self as bgzf, gzi};
+    /// let data = [];
+    /// let reader = bgzf::IndexedReader::new(&data[..], gzi::Index::default());
+    /// assert!(reader.index().is_empty());
+    /// ```
+    pub fn index(&self) -> &gzi::Index {
+        &self.index
+    }
+
+    /// Returns the underlying BGZF reader.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf::{self as bgzf, gzi};
+    /// let data = [];
+    /// let reader = bgzf::IndexedReader::new(&data[..], gzi::Index::default());
+    /// let reader = reader.into_inner();
+    /// ```
+    pub fn into_inner(self) -> Reader<R> {
+        self.inner
+    }
+}
+
+impl<R> Read for IndexedReader<R>
+where
+    R: Read,
+{
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.inner.read(buf)
+    }
+
+    fn read_exact(&mut self, buf: &mut [u8]) -> io::Result<()> {
+        self.inner.read_exact(buf)
+    }
+}
+
+impl<R> BufRead for IndexedReader<R>
+where
+    R: Read,
+{
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        self.inner.fill_buf()
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.inner.consume(amt);
+    }
+}
+
+impl<R> Seek for IndexedReader<R>
+where
+    R: Read + Seek,
+{
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        let pos = match pos {
+            SeekFrom::Start(pos) => pos,
+            SeekFrom::Current(delta) => {
+                let pos = self.stream_position()?;
+
+                let new_pos = if delta >= 0 {
+                    pos.checked_add(delta as u64)
+                } else {
+                    pos.checked_sub(delta.unsigned_abs())
+                };
+
+                new_pos.ok_or_else(|| {
+                    io::Error::new(io::ErrorKind::InvalidInput, "invalid seek position")
+                })?
+            }
+            SeekFrom::End(_) => {
+                return Err(io::Error::new(
+                    io::ErrorKind::Unsupported,
+                    "seeking from the end of a BGZF stream is not supported",
+                ))
+            }
+        };
+
+        self.inner.seek_by_uncompressed_position(&self.index, pos)
+    }
+
+    fn stream_position(&mut self) -> io::Result<u64> {
+        self.index
+            .uncompressed_position(self.inner.virtual_position())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{Cursor, Write};
+
+    use super::*;
+    use crate::Writer;
+
+    fn build_data() -> io::Result<(Vec<u8>, gzi::Index)> {
+        let mut writer = Writer::new(Vec::new());
+        let mut entries = Vec::new();
+        let mut uncompressed_position = 0;
+
+        for chunk in [&b"noodles"[..], b"-", b"bgzf"] {
+            writer.write_all(chunk)?;
+            writer.flush()?;
+
+            uncompressed_position += chunk.len() as u64;
+            entries.push((writer.position(), uncompressed_position));
+        }
+
+        Ok((writer.finish()?, gzi::Index::from(entries)))
+    }
+
+    #[test]
+    fn test_seek() -> io::Result<()> {
+        let (data, index) = build_data()?;
+        let mut reader = IndexedReader::new(Cursor::new(data), index);
+
+        assert_eq!(reader.seek(SeekFrom::Start(3))?, 3);
+        assert_eq!(reader.stream_position()?, 3);
+
+        let mut buf = [0; 6];
+        reader.read_exact(&mut buf)?;
+        assert_eq!(&buf, b"dles-b");
+        assert_eq!(reader.stream_position()?, 9);
+
+        assert_eq!(reader.seek(SeekFrom::Current(-2))?, 7);
+
+        let mut buf = Vec::new();
+        reader.read_to_end(&mut buf)?;
+        assert_eq!(buf, b"-bgzf");
+
+        reader.seek(SeekFrom::Start(1))?;
+        assert!(reader.seek(SeekFrom::Current(-2)).is_err());
+        assert!(reader.seek(SeekFrom::End(0)).is_err());
+
+        Ok(())
+    }
+}
@@ -1,3 +1,10 @@
+//! BGZF reader.
+
+mod builder;
+mod inflater;
+
+pub use self::builder::Builder;
+
 use std::{
     cmp,
     io::{self, BufRead, Read, Seek, SeekFrom},
@@ -5,7 +12,9 @@ use std::{
 
 use byteorder::{ByteOrder, LittleEndian};
 
-use super::{gz, Block, VirtualPosition, BGZF_HEADER_SIZE};
+use super::{gz, gzi, Block, VirtualPosition, BGZF_HEADER_SIZE};
+
+use self::inflater::Inflater;
 
 /// A BGZF reader.
 ///
@@ -17,6 +26,9 @@ use super::{gz, Block, VirtualPosition, BGZF_HEADER_SIZE};
 /// correctly track (virtual) positions, the reader _cannot_ be double buffered (e.g., using
 /// [`std::io::BufReader`]).
 ///
+/// By default, blocks are decompressed on the calling thread. Use [`Reader::builder`] to set a
+/// worker count to decompress blocks in parallel.
+///
 /// # Examples
 ///
 /// ```no_run
@@ -32,12 +44,27 @@ pub struct Reader<R> {
     position: u64,
     cdata: Vec<u8>,
     block: Block,
+    inflater: Option<Inflater>,
 }
 
 impl<R> Reader<R>
 where
     R: Read,
 {
+    /// Creates a BGZF reader builder.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf as bgzf;
+    /// let data = [];
+    /// let builder = bgzf::Reader::builder(&data[..]);
+    /// let reader = builder.build();
+    /// ```
+    pub fn builder(inner: R) -> Builder<R> {
+        Builder::new(inner)
+    }
+
     /// Creates a BGZF reader.
     ///
     /// # Examples
@@ -48,12 +75,7 @@ where
     /// let reader = bgzf::Reader::new(&data[..]);
     /// ```
     pub fn new(inner: R) -> Self {
-        Self {
-            inner,
-            position: 0,
-            cdata: Vec::new(),
-            block: Block::default(),
-        }
+        Self::builder(inner).build()
     }
 
     /// Returns a reference to the underlying reader.
@@ -125,6 +147,41 @@ where
     pub fn virtual_position(&self) -> VirtualPosition {
         self.block.virtual_position()
     }
+
+    /// Reads the next block.
+    ///
+    /// If a pool of workers is available, compressed blocks are read ahead and queued for
+    /// decompression before the next decompressed block is received.
+    ///
+    /// If successful, the block size is returned. If a block size of 0 is returned, the stream
+    /// reached EOF.
+    fn read_block(&mut self) -> io::Result<usize> {
+        let inflater = match self.inflater.as_mut() {
+            Some(inflater) => inflater,
+            None => return read_block(&mut self.inner, &mut self.cdata, &mut self.block),
+        };
+
+        while !inflater.is_full() {
+            let mut cdata = Vec::new();
+
+            match read_compressed_block(&mut self.inner, &mut cdata) {
+                Ok((0, 0)) => break,
+                Ok((clen, ulen)) => inflater.send(cdata, clen, ulen),
+                Err(e) => {
+                    inflater.send_error(e);
+                    break;
+                }
+            }
+        }
+
+        match inflater.recv() {
+            Some(result) => {
+                self.block = result?;
+                Ok(self.block.clen() as usize)
+            }
+            None => Ok(0),
+        }
+    }
 }
 
 impl<R> Reader<R>
@@ -136,6 +193,8 @@ where
     /// The underlying stream's cursor is first moved the the compressed position. A block is read,
     /// decompressed, and has its own cursor moved to the uncompressed position.
     ///
+    /// Any blocks that were read ahead are discarded.
+    ///
     /// # Examples
     ///
     /// ```no_run
@@ -151,14 +210,73 @@ where
 
         self.inner.seek(SeekFrom::Start(cpos))?;
 
-        let block_size = read_block(&mut self.inner, &mut self.cdata, &mut self.block)?;
+        if let Some(inflater) = self.inflater.as_mut() {
+            inflater.clear();
+        }
+
+        let block_size = self.read_block()?;
         self.position = cpos + (block_size as u64);
 
+        if block_size == 0 {
+            self.block = Block::default();
+        }
+
+        let upos = usize::from(upos);
+
+        if upos > self.block.ulen() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!(
+                    "invalid uncompressed position: expected <= {}, got {}",
+                    self.block.ulen(),
+                    upos
+                ),
+            ));
+        }
+
         self.block.set_cpos(cpos);
-        self.block.set_upos(usize::from(upos));
+        self.block.set_upos(upos);
 
         Ok(pos)
     }
+
+    /// Seeks the stream to the given uncompressed position.
+    ///
+    /// The given gzip index is used to find the block that contains the uncompressed position.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::{self, Cursor, Read, Write};
+    /// use noodles_bgzf::{self as bgzf, gzi};
+    ///
+    /// let mut writer = bgzf::Writer::new(Vec::new());
+    /// writer.write_all(b"noodles")?;
+    /// writer.flush()?;
+    /// let compressed_position = writer.position();
+    /// writer.write_all(b"-bgzf")?;
+    /// let data = writer.finish()?;
+    ///
+    /// let index = gzi::Index::from(vec![(compressed_position, 7)]);
+    ///
+    /// let mut reader = bgzf::Reader::new(Cursor::new(data));
+    /// reader.seek_by_uncompressed_position(&index, 8)?;
+    ///
+    /// let mut buf = Vec::new();
+    /// reader.read_to_end(&mut buf)?;
+    ///
+    /// assert_eq!(buf, b"bgzf");
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn seek_by_uncompressed_position(
+        &mut self,
+        index: &gzi::Index,
+        pos: u64,
+    ) -> io::Result<u64> {
+        let virtual_position = index.query(pos)?;
+        self.seek(virtual_position)?;
+        Ok(pos)
+    }
 }
 
 impl<R> Read for Reader<R>
@@ -171,7 +289,10 @@ where
         // If a new block is about to be read and the given buffer is guaranteed to be larger than
         // next block, reading to the block buffer can be skipped. The uncompressed data is read
         // directly to the given buffer to avoid double copying.
-        if self.block.is_eof() && buf.len() >= block::MAX_UNCOMPRESSED_DATA_LENGTH {
+        if self.inflater.is_none()
+            && self.block.is_eof()
+            && buf.len() >= block::MAX_UNCOMPRESSED_DATA_LENGTH
+        {
             let block_size =
                 read_block_into(&mut self.inner, &mut self.cdata, &mut self.block, buf)?;
             self.block.set_cpos(self.position);
@@ -214,7 +335,7 @@ where
 
     fn fill_buf(&mut self) -> io::Result<&[u8]> {
         if self.block.is_eof() {
-            let block_size = read_block(&mut self.inner, &mut self.cdata, &mut self.block)?;
+            let block_size = self.read_block()?;
             self.block.set_cpos(self.position);
             self.position += block_size as u64;
         }
@@ -283,7 +404,10 @@ pub(crate) fn inflate_data(reader: &[u8], writer: &mut [u8]) -> io::Result<()> {
     decoder.read_exact(writer)
 }
 
-fn read_compressed_block<R>(reader: &mut R, buf: &mut Vec<u8>) -> io::Result<(usize, usize)>
+pub(crate) fn read_compressed_block<R>(
+    reader: &mut R,
+    buf: &mut Vec<u8>,
+) -> io::Result<(usize, usize)>
 where
     R: Read,
 {
@@ -423,6 +547,88 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_read_with_workers() -> io::Result<()> {
+        use std::io::Write;
+
+        use crate::Writer;
+
+        let data: Vec<u8> = (0..=u8::MAX).cycle().take(1 << 20).collect();
+
+        let mut writer = Writer::new(Vec::new());
+        writer.write_all(&data)?;
+        let compressed_data = writer.finish()?;
+
+        let mut reader = Reader::builder(&compressed_data[..])
+            .set_worker_count(4)
+            .build();
+
+        let mut buf = Vec::new();
+        reader.read_to_end(&mut buf)?;
+
+        assert_eq!(buf, data);
+
+        let eof = VirtualPosition::try_from((compressed_data.len() as u64, 0))
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        assert_eq!(reader.virtual_position(), eof);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_seek_with_workers() -> Result<(), Box<dyn std::error::Error>> {
+        use std::io::Write;
+
+        use crate::Writer;
+
+        let mut writer = Writer::new(Vec::new());
+
+        for chunk in [&b"noodles"[..], b"-", b"bgzf"] {
+            writer.write_all(chunk)?;
+            writer.flush()?;
+        }
+
+        let data = writer.finish()?;
+
+        let mut reader = Reader::builder(Cursor::new(&data))
+            .set_worker_count(2)
+            .build();
+
+        let mut buf = [0; 7];
+        reader.read_exact(&mut buf)?;
+        assert_eq!(&buf, b"noodles");
+
+        let position = reader.virtual_position();
+
+        let mut buf = Vec::new();
+        reader.read_to_end(&mut buf)?;
+        assert_eq!(buf, b"-bgzf");
+
+        reader.seek(VirtualPosition::try_from((0, 4))?)?;
+        assert_eq!(
+            reader.virtual_position(),
+            VirtualPosition::try_from((0, 4))?
+        );
+
+        let mut buf = [0; 3];
+        reader.read_exact(&mut buf)?;
+        assert_eq!(&buf, b"les");
+        assert_eq!(reader.virtual_position(), position);
+
+        let mut buf = Vec::new();
+        reader.read_to_end(&mut buf)?;
+        assert_eq!(buf, b"-bgzf");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_seek_with_invalid_uncompressed_position() -> Result<(), Box<dyn std::error::Error>> {
+        let mut reader = Reader::new(Cursor::new(BGZF_EOF));
+        assert!(reader.seek(VirtualPosition::try_from((0, 1))?).is_err());
+        Ok(())
+    }
+
     #[test]
     fn test_read_header() -> io::Result<()> {
         let mut reader = BGZF_EOF;
@@ -0,0 +1,91 @@
+use std::io::Read;
+
+use super::{Inflater, Reader};
+use crate::Block;
+
+/// A BGZF reader builder.
+#[derive(Debug)]
+pub struct Builder<R> {
+    inner: R,
+    worker_count: Option<usize>,
+}
+
+impl<R> Builder<R>
+where
+    R: Read,
+{
+    pub(crate) fn new(inner: R) -> Self {
+        Self {
+            inner,
+            worker_count: None,
+        }
+    }
+
+    /// Sets a worker count.
+    ///
+    /// When the worker count is greater than 1, the reader reads ahead and decompresses up to
+    /// `worker_count` blocks in parallel on a pool of threads. Blocks are still emitted in stream
+    /// order.
+    ///
+    /// By default, the worker count is set to 1, i.e., blocks are decompressed on the calling
+    /// thread.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf as bgzf;
+    /// let data = [];
+    /// let builder = bgzf::Reader::builder(&data[..]).set_worker_count(8);
+    /// ```
+    pub fn set_worker_count(mut self, worker_count: usize) -> Self {
+        self.worker_count = Some(worker_count);
+        self
+    }
+
+    /// Builds a BGZF reader.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf as bgzf;
+    /// let data = [];
+    /// let reader = bgzf::Reader::builder(&data[..]).build();
+    /// ```
+    pub fn build(self) -> Reader<R> {
+        let inflater = match self.worker_count {
+            Some(n) if n > 1 => Some(Inflater::new(n)),
+            _ => None,
+        };
+
+        Reader {
+            inner: self.inner,
+            position: 0,
+            cdata: Vec::new(),
+            block: Block::default(),
+            inflater,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new() {
+        let builder = Builder::new(&[][..]);
+        assert!(builder.worker_count.is_none());
+    }
+
+    #[test]
+    fn test_build() {
+        let reader = Builder::new(&[][..]).build();
+        assert!(reader.inflater.is_none());
+
+        let reader = Builder::new(&[][..]).set_worker_count(1).build();
+        assert!(reader.inflater.is_none());
+
+        let reader = Builder::new(&[][..]).set_worker_count(2).build();
+        assert!(reader.inflater.is_some());
+    }
+}
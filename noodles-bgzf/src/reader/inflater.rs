@@ -0,0 +1,163 @@
+use std::{
+    collections::VecDeque,
+    io,
+    thread::{self, JoinHandle},
+};
+
+use crossbeam_channel::{Receiver, Sender};
+
+use crate::{block, Block};
+
+use super::inflate_data;
+
+// (CDATA, BSIZE + 1, ISIZE, result sender)
+type Request = (Vec<u8>, usize, usize, Sender<io::Result<Block>>);
+
+/// A pool of workers that decompress blocks in parallel.
+///
+/// Blocks are sent in stream order, and results are received in the same order.
+pub(crate) struct Inflater {
+    tx: Option<Sender<Request>>,
+    handles: Vec<JoinHandle<()>>,
+    queue: VecDeque<Receiver<io::Result<Block>>>,
+    capacity: usize,
+}
+
+impl Inflater {
+    pub fn new(worker_count: usize) -> Self {
+        let (tx, rx) = crossbeam_channel::unbounded::<Request>();
+
+        let handles = (0..worker_count)
+            .map(|_| {
+                let rx = rx.clone();
+
+                thread::spawn(move || {
+                    while let Ok((cdata, clen, ulen, result_tx)) = rx.recv() {
+                        let result = inflate(&cdata, clen, ulen);
+                        // The receiver is dropped when pending blocks are discarded, e.g., after
+                        // a seek.
+                        let _ = result_tx.send(result);
+                    }
+                })
+            })
+            .collect();
+
+        Self {
+            tx: Some(tx),
+            handles,
+            queue: VecDeque::with_capacity(worker_count),
+            capacity: worker_count,
+        }
+    }
+
+    /// Returns whether the number of pending blocks is at capacity.
+    pub fn is_full(&self) -> bool {
+        self.queue.len() >= self.capacity
+    }
+
+    /// Queues a compressed block to be decompressed.
+    pub fn send(&mut self, cdata: Vec<u8>, clen: usize, ulen: usize) {
+        let (result_tx, result_rx) = crossbeam_channel::bounded(1);
+
+        if let Some(tx) = self.tx.as_ref() {
+            // Workers only stop when the request sender is dropped.
+            tx.send((cdata, clen, ulen, result_tx)).ok();
+        }
+
+        self.queue.push_back(result_rx);
+    }
+
+    /// Queues an error to be returned in place of a block.
+    ///
+    /// This preserves the order of results when reading a compressed block fails after previous
+    /// blocks have been queued.
+    pub fn send_error(&mut self, e: io::Error) {
+        let (result_tx, result_rx) = crossbeam_channel::bounded(1);
+        result_tx.send(Err(e)).ok();
+        self.queue.push_back(result_rx);
+    }
+
+    /// Receives the next decompressed block in stream order.
+    ///
+    /// This returns `None` if there are no pending blocks.
+    pub fn recv(&mut self) -> Option<io::Result<Block>> {
+        self.queue.pop_front().map(|rx| {
+            rx.recv().unwrap_or_else(|_| {
+                Err(io::Error::new(
+                    io::ErrorKind::BrokenPipe,
+                    "inflater worker disconnected",
+                ))
+            })
+        })
+    }
+
+    /// Discards all pending blocks.
+    pub fn clear(&mut self) {
+        self.queue.clear();
+    }
+}
+
+impl Drop for Inflater {
+    fn drop(&mut self) {
+        self.queue.clear();
+
+        // Dropping the sender disconnects the channel, which stops the workers.
+        self.tx.take();
+
+        for handle in self.handles.drain(..) {
+            handle.join().ok();
+        }
+    }
+}
+
+fn inflate(cdata: &[u8], clen: usize, ulen: usize) -> io::Result<Block> {
+    if ulen > block::MAX_UNCOMPRESSED_DATA_LENGTH {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!(
+                "expected ulen <= {}, got {}",
+                block::MAX_UNCOMPRESSED_DATA_LENGTH,
+                ulen
+            ),
+        ));
+    }
+
+    let mut block = Block::default();
+
+    block.set_clen(clen as u64);
+    block.set_upos(0);
+    block.set_ulen(ulen);
+
+    inflate_data(cdata, block.buffer_mut())?;
+
+    Ok(block)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_recv() -> io::Result<()> {
+        // deflate(b"noodles")
+        let cdata = vec![0xcb, 0xcb, 0xcf, 0x4f, 0xc9, 0x49, 0x2d, 0x06, 0x00];
+
+        let mut inflater = Inflater::new(2);
+        assert!(!inflater.is_full());
+
+        inflater.send(cdata, 35, 7);
+        inflater.send_error(io::Error::from(io::ErrorKind::InvalidData));
+        assert!(inflater.is_full());
+
+        let block = inflater.recv().transpose()?;
+        assert_eq!(
+            block.map(|b| b.buffer().to_vec()),
+            Some(b"noodles".to_vec())
+        );
+
+        assert!(inflater.recv().transpose().is_err());
+        assert!(inflater.recv().is_none());
+
+        Ok(())
+    }
+}
@@ -1,18 +1,26 @@
 //! BGZF writer.
 
+mod block_position;
+mod builder;
 mod compression_level;
+mod deflater;
 
-pub use self::compression_level::CompressionLevel;
+pub use self::{
+    block_position::BlockPosition, builder::Builder, compression_level::CompressionLevel,
+};
 
 use std::{
     cmp,
+    collections::VecDeque,
     io::{self, Write},
 };
 
 use byteorder::{LittleEndian, WriteBytesExt};
 use flate2::Crc;
 
-use super::{block, gz, BGZF_HEADER_SIZE};
+use super::{block, gz, VirtualPosition, BGZF_HEADER_SIZE};
+
+use self::deflater::Deflater;
 
 const BGZF_FLG: u8 = 0x04; // FEXTRA
 const BGZF_XFL: u8 = 0x00; // none
@@ -43,6 +51,9 @@ pub(crate) static BGZF_EOF: &[u8] = &[
 ///
 /// This implements [`std::io::Write`], consuming uncompressed data and emitting compressed data.
 ///
+/// By default, blocks are compressed on the calling thread. Use [`Writer::builder`] to set a
+/// worker count to compress blocks in parallel. Blocks are always written in order.
+///
 /// # Examples
 ///
 /// ```
@@ -61,13 +72,42 @@ where
     W: Write,
 {
     inner: Option<W>,
+    position: u64,
     buf: Vec<u8>,
+    compression_level: CompressionLevel,
+    deflater: Option<Deflater>,
+    block_index: u64,
+    written_block_count: u64,
+    block_offsets: Option<BlockOffsets>,
+}
+
+// The compressed offsets of written blocks, starting at the block with the given index.
+//
+// The last offset is the start of the next block to be written. Offsets are only retained once a
+// block position is resolved, and offsets of blocks before a resolved position are discarded.
+#[derive(Debug)]
+struct BlockOffsets {
+    start: u64,
+    offsets: VecDeque<u64>,
 }
 
 impl<W> Writer<W>
 where
     W: Write,
 {
+    /// Creates a BGZF writer builder.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf as bgzf;
+    /// let builder = bgzf::Writer::builder(Vec::new());
+    /// let writer = builder.build();
+    /// ```
+    pub fn builder(inner: W) -> Builder<W> {
+        Builder::new(inner)
+    }
+
     /// Creates a writer with a default compression level.
     ///
     /// # Examples
@@ -77,10 +117,7 @@ where
     /// let writer = bgzf::Writer::new(Vec::new());
     /// ```
     pub fn new(inner: W) -> Self {
-        Self {
-            inner: Some(inner),
-            buf: Vec::with_capacity(block::MAX_UNCOMPRESSED_DATA_LENGTH),
-        }
+        Self::builder(inner).build()
     }
 
     /// Returns a reference to the underlying writer.
@@ -96,16 +133,178 @@ where
         self.inner.as_ref().unwrap()
     }
 
+    /// Returns the current position of the stream.
+    ///
+    /// This is the number of compressed bytes written to the underlying writer. It does not
+    /// include blocks that are pending compression.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf as bgzf;
+    /// let writer = bgzf::Writer::new(Vec::new());
+    /// assert_eq!(writer.position(), 0);
+    /// ```
+    pub fn position(&self) -> u64 {
+        self.position
+    }
+
+    /// Returns the current virtual position of the stream, if it is known.
+    ///
+    /// The compressed position is the start of the block that the next written byte will be
+    /// part of. When the writer uses workers, this depends on the sizes of pending blocks. This
+    /// never waits for blocks that are still being compressed and instead returns `None` until
+    /// they finish. Use [`Self::block_position`] to get a position that is always known. An error
+    /// is returned if a pending block failed to compress.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::{self, Write};
+    /// use noodles_bgzf as bgzf;
+    ///
+    /// let mut writer = bgzf::Writer::new(Vec::new());
+    /// assert_eq!(writer.virtual_position()?, Some(bgzf::VirtualPosition::from(0)));
+    ///
+    /// writer.write_all(b"noodles")?;
+    /// assert_eq!(writer.virtual_position()?, Some(bgzf::VirtualPosition::from(7)));
+    /// # Ok::<(), io::Error>(())
+    /// ```
+    pub fn virtual_position(&self) -> io::Result<Option<VirtualPosition>> {
+        let cpos = match self.deflater.as_ref() {
+            Some(deflater) => match deflater.pending_block_size()? {
+                Some(size) => self.position + size,
+                None => return Ok(None),
+            },
+            None => self.position,
+        };
+
+        // The buffer is flushed when it is full, so its length is always < 2^16.
+        let upos = self.buf.len() as u16;
+
+        VirtualPosition::try_from((cpos, upos))
+            .map(Some)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))
+    }
+
+    /// Returns the current block position of the stream.
+    ///
+    /// This is the index of the block that the next written byte will be part of and the offset
+    /// in its uncompressed data. Unlike [`Self::virtual_position`], this is always known, even
+    /// when blocks are still being compressed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::{self, Write};
+    /// use noodles_bgzf as bgzf;
+    ///
+    /// let mut writer = bgzf::Writer::new(Vec::new());
+    /// writer.write_all(b"noodles")?;
+    ///
+    /// let position = writer.block_position();
+    /// assert_eq!(position.block_index(), 0);
+    /// assert_eq!(position.offset(), 7);
+    /// # Ok::<(), io::Error>(())
+    /// ```
+    pub fn block_position(&self) -> BlockPosition {
+        // The buffer is flushed when it is full, so its length is always < 2^16.
+        BlockPosition::new(self.block_index, self.buf.len() as u16)
+    }
+
+    /// Resolves a block position to a virtual position.
+    ///
+    /// This returns `None` if the block of the given position is not yet written, i.e., blocks
+    /// before it are still being compressed. Once resolved, the offsets of blocks before the given
+    /// position are discarded, so block positions must be resolved in stream order. Offsets are
+    /// only retained from the first call, so positions in blocks written before it cannot be
+    /// resolved.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::{self, Write};
+    /// use noodles_bgzf as bgzf;
+    ///
+    /// let mut writer = bgzf::Writer::new(Vec::new());
+    /// writer.write_all(b"noodles")?;
+    ///
+    /// let position = writer.block_position();
+    /// assert_eq!(
+    ///     writer.resolve_block_position(position),
+    ///     Some(bgzf::VirtualPosition::from(7))
+    /// );
+    /// # Ok::<(), io::Error>(())
+    /// ```
+    pub fn resolve_block_position(&mut self, position: BlockPosition) -> Option<VirtualPosition> {
+        let written_block_count = self.written_block_count;
+        let cpos = self.position;
+
+        let block_offsets = self.block_offsets.get_or_insert_with(|| BlockOffsets {
+            start: written_block_count,
+            offsets: VecDeque::from([cpos]),
+        });
+
+        let i = position.block_index().checked_sub(block_offsets.start)?;
+        let i = usize::try_from(i).ok()?;
+        let cpos = block_offsets.offsets.get(i).copied()?;
+
+        block_offsets.offsets.drain(..i);
+        block_offsets.start = position.block_index();
+
+        VirtualPosition::try_from((cpos, position.offset())).ok()
+    }
+
     fn flush_block(&mut self) -> io::Result<()> {
-        let (cdata, crc32, r#isize) = deflate_data(&self.buf, Default::default())?;
+        let deflater = match self.deflater.as_mut() {
+            Some(deflater) => deflater,
+            None => {
+                let data = deflate_data(&self.buf, self.compression_level.into())?;
+                self.buf.clear();
+                self.block_index += 1;
+                return self.write_block(data);
+            }
+        };
+
+        let buf = std::mem::replace(
+            &mut self.buf,
+            Vec::with_capacity(block::MAX_UNCOMPRESSED_DATA_LENGTH),
+        );
+
+        deflater.send(buf);
+        self.block_index += 1;
+
+        if deflater.is_full() {
+            self.write_pending_block()?;
+        }
 
+        Ok(())
+    }
+
+    fn write_pending_block(&mut self) -> io::Result<bool> {
+        let result = match self.deflater.as_mut().and_then(|deflater| deflater.recv()) {
+            Some(result) => result,
+            None => return Ok(false),
+        };
+
+        self.write_block(result?)?;
+
+        Ok(true)
+    }
+
+    fn write_block(&mut self, (cdata, crc32, r#isize): (Vec<u8>, u32, u32)) -> io::Result<()> {
         let inner = self.inner.as_mut().unwrap();
 
         write_header(inner, cdata.len())?;
         inner.write_all(&cdata[..])?;
         write_trailer(inner, crc32, r#isize)?;
 
-        self.buf.clear();
+        self.position += (BGZF_HEADER_SIZE + cdata.len() + gz::TRAILER_SIZE) as u64;
+        self.written_block_count += 1;
+
+        if let Some(block_offsets) = self.block_offsets.as_mut() {
+            block_offsets.offsets.push_back(self.position);
+        }
 
         Ok(())
     }
@@ -129,7 +328,9 @@ where
     pub fn try_finish(&mut self) -> io::Result<()> {
         self.flush()?;
         let inner = self.inner.as_mut().unwrap();
-        inner.write_all(BGZF_EOF)
+        inner.write_all(BGZF_EOF)?;
+        self.position += BGZF_EOF.len() as u64;
+        Ok(())
     }
 
     /// Returns the underlying writer after finishing the output stream.
@@ -171,11 +372,6 @@ where
     W: Write,
 {
     fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
-        if self.buf.len() >= block::MAX_UNCOMPRESSED_DATA_LENGTH {
-            self.flush()?;
-            return Err(io::Error::from(io::ErrorKind::Interrupted));
-        }
-
         let max_write_len = cmp::min(
             block::MAX_UNCOMPRESSED_DATA_LENGTH - self.buf.len(),
             buf.len(),
@@ -183,15 +379,21 @@ where
 
         self.buf.extend_from_slice(&buf[..max_write_len]);
 
+        if self.buf.len() >= block::MAX_UNCOMPRESSED_DATA_LENGTH {
+            self.flush_block()?;
+        }
+
         Ok(max_write_len)
     }
 
     fn flush(&mut self) -> io::Result<()> {
-        if self.buf.is_empty() {
-            Ok(())
-        } else {
-            self.flush_block()
+        if !self.buf.is_empty() {
+            self.flush_block()?;
         }
+
+        while self.write_pending_block()? {}
+
+        Ok(())
     }
 }
 
@@ -285,4 +487,105 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_virtual_position() -> io::Result<()> {
+        let mut writer = Writer::new(Vec::new());
+
+        writer.write_all(b"noodles")?;
+        assert_eq!(writer.virtual_position()?, Some(VirtualPosition::from(7)));
+
+        writer.flush()?;
+        let cpos = writer.get_ref().len() as u64;
+        assert_eq!(writer.position(), cpos);
+        assert_eq!(
+            writer.virtual_position()?,
+            Some(VirtualPosition::from(cpos << 16))
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_resolve_block_position() -> io::Result<()> {
+        let data = vec![0; block::MAX_UNCOMPRESSED_DATA_LENGTH + 8];
+
+        let mut writer = Writer::new(Vec::new());
+
+        let start_position = writer.block_position();
+        assert_eq!(
+            writer.resolve_block_position(start_position),
+            Some(VirtualPosition::default())
+        );
+
+        writer.write_all(&data)?;
+        let end_position = writer.block_position();
+        assert_eq!(end_position, BlockPosition::new(1, 8));
+
+        let cpos = writer.position();
+        assert_eq!(
+            writer.resolve_block_position(end_position),
+            Some(VirtualPosition::from(cpos << 16 | 8))
+        );
+
+        // The offset of the first block was discarded.
+        assert!(writer.resolve_block_position(start_position).is_none());
+
+        let position = BlockPosition::new(2, 0);
+        assert!(writer.resolve_block_position(position).is_none());
+
+        writer.flush()?;
+        let cpos = writer.position();
+        assert_eq!(
+            writer.resolve_block_position(position),
+            Some(VirtualPosition::from(cpos << 16))
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_write_with_workers() -> io::Result<()> {
+        let data: Vec<u8> = (0..=u8::MAX).cycle().take(1 << 20).collect();
+
+        let mut expected_writer = Writer::new(Vec::new());
+        let mut writer = Writer::builder(Vec::new()).set_worker_count(4).build();
+
+        let mut expected_positions = Vec::new();
+        let mut pending_positions = VecDeque::new();
+        let mut actual_positions = Vec::new();
+
+        for chunk in data.chunks(1000) {
+            expected_writer.write_all(chunk)?;
+            expected_positions.extend(expected_writer.virtual_position()?);
+
+            writer.write_all(chunk)?;
+            pending_positions.push_back(writer.block_position());
+
+            while let Some(position) = pending_positions.front() {
+                match writer.resolve_block_position(*position) {
+                    Some(virtual_position) => {
+                        actual_positions.push(virtual_position);
+                        pending_positions.pop_front();
+                    }
+                    None => break,
+                }
+            }
+        }
+
+        writer.flush()?;
+
+        for position in pending_positions {
+            actual_positions.extend(writer.resolve_block_position(position));
+        }
+
+        assert_eq!(actual_positions, expected_positions);
+
+        let expected = expected_writer.finish()?;
+        let actual = writer.finish()?;
+
+        assert_eq!(actual, expected);
+
+        Ok(())
+    }
 }
@@ -0,0 +1,50 @@
+/// A position in the uncompressed stream of a BGZF writer.
+///
+/// This is the index of the block that a byte is written to and its offset in the block's
+/// uncompressed data. Unlike a virtual position, it is known as soon as the byte is written, even
+/// when the block is still being compressed. Use [`super::Writer::resolve_block_position`] to
+/// convert it to a virtual position once the block is written.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct BlockPosition {
+    block_index: u64,
+    offset: u16,
+}
+
+impl BlockPosition {
+    pub(crate) fn new(block_index: u64, offset: u16) -> Self {
+        Self {
+            block_index,
+            offset,
+        }
+    }
+
+    /// Returns the index of the block in the stream.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf as bgzf;
+    /// let writer = bgzf::Writer::new(Vec::new());
+    /// assert_eq!(writer.block_position().block_index(), 0);
+    /// ```
+    pub fn block_index(&self) -> u64 {
+        self.block_index
+    }
+
+    /// Returns the offset in the uncompressed data of the block.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::{self, Write};
+    /// use noodles_bgzf as bgzf;
+    ///
+    /// let mut writer = bgzf::Writer::new(Vec::new());
+    /// writer.write_all(b"noodles")?;
+    /// assert_eq!(writer.block_position().offset(), 7);
+    /// # Ok::<(), io::Error>(())
+    /// ```
+    pub fn offset(&self) -> u16 {
+        self.offset
+    }
+}
@@ -0,0 +1,115 @@
+use std::io::Write;
+
+use super::{CompressionLevel, Deflater, Writer};
+use crate::block;
+
+/// A BGZF writer builder.
+#[derive(Debug)]
+pub struct Builder<W> {
+    inner: W,
+    compression_level: Option<CompressionLevel>,
+    worker_count: Option<usize>,
+}
+
+impl<W> Builder<W>
+where
+    W: Write,
+{
+    pub(crate) fn new(inner: W) -> Self {
+        Self {
+            inner,
+            compression_level: None,
+            worker_count: None,
+        }
+    }
+
+    /// Sets a compression level.
+    ///
+    /// By default, the compression level is set to level 6.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf::{self as bgzf, writer::CompressionLevel};
+    ///
+    /// let builder = bgzf::Writer::builder(Vec::new())
+    ///     .set_compression_level(CompressionLevel::best());
+    /// ```
+    pub fn set_compression_level(mut self, compression_level: CompressionLevel) -> Self {
+        self.compression_level = Some(compression_level);
+        self
+    }
+
+    /// Sets a worker count.
+    ///
+    /// When the worker count is greater than 1, up to `worker_count` blocks are compressed in
+    /// parallel on a pool of threads. Blocks are still written in stream order.
+    ///
+    /// By default, the worker count is set to 1, i.e., blocks are compressed on the calling
+    /// thread.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf as bgzf;
+    /// let builder = bgzf::Writer::builder(Vec::new()).set_worker_count(8);
+    /// ```
+    pub fn set_worker_count(mut self, worker_count: usize) -> Self {
+        self.worker_count = Some(worker_count);
+        self
+    }
+
+    /// Builds a BGZF writer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf as bgzf;
+    /// let writer = bgzf::Writer::builder(Vec::new()).build();
+    /// ```
+    pub fn build(self) -> Writer<W> {
+        let compression_level = self.compression_level.unwrap_or_default();
+
+        let deflater = match self.worker_count {
+            Some(n) if n > 1 => Some(Deflater::new(n, compression_level)),
+            _ => None,
+        };
+
+        Writer {
+            inner: Some(self.inner),
+            position: 0,
+            buf: Vec::with_capacity(block::MAX_UNCOMPRESSED_DATA_LENGTH),
+            compression_level,
+            deflater,
+            block_index: 0,
+            written_block_count: 0,
+            block_offsets: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new() {
+        let builder = Builder::new(Vec::new());
+        assert!(builder.compression_level.is_none());
+        assert!(builder.worker_count.is_none());
+    }
+
+    #[test]
+    fn test_build() {
+        let writer = Builder::new(Vec::new()).build();
+        assert_eq!(writer.compression_level, CompressionLevel::default());
+        assert!(writer.deflater.is_none());
+
+        let writer = Builder::new(Vec::new())
+            .set_compression_level(CompressionLevel::fast())
+            .set_worker_count(2)
+            .build();
+        assert_eq!(writer.compression_level, CompressionLevel::fast());
+        assert!(writer.deflater.is_some());
+    }
+}
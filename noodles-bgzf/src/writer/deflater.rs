@@ -0,0 +1,249 @@
+use std::{
+    collections::VecDeque,
+    io,
+    sync::{Arc, Condvar, Mutex, MutexGuard},
+    thread::{self, JoinHandle},
+};
+
+use crossbeam_channel::Sender;
+
+use super::{deflate_data, CompressionLevel};
+use crate::{gz, BGZF_HEADER_SIZE};
+
+// (CDATA, CRC32, ISIZE)
+type GzData = (Vec<u8>, u32, u32);
+
+type Request = (Vec<u8>, Arc<Slot>);
+
+/// The total size of compressed blocks that have finished but are not yet received.
+///
+/// The error is the first error of the failed blocks that are not yet received.
+#[derive(Debug, Default)]
+struct Progress {
+    finished_count: usize,
+    finished_size: u64,
+    failed_count: usize,
+    error: Option<(io::ErrorKind, String)>,
+}
+
+#[derive(Debug, Default)]
+struct Shared {
+    progress: Mutex<Progress>,
+}
+
+impl Shared {
+    fn finish(&self, result: &io::Result<GzData>) {
+        let mut progress = self.progress.lock().unwrap();
+
+        progress.finished_count += 1;
+
+        match result {
+            Ok((cdata, _, _)) => progress.finished_size += block_size(cdata),
+            Err(e) => {
+                progress.failed_count += 1;
+
+                if progress.error.is_none() {
+                    progress.error = Some((e.kind(), e.to_string()));
+                }
+            }
+        }
+    }
+
+    fn remove(&self, result: &io::Result<GzData>) {
+        let mut progress = self.progress.lock().unwrap();
+
+        progress.finished_count -= 1;
+
+        match result {
+            Ok((cdata, _, _)) => progress.finished_size -= block_size(cdata),
+            Err(_) => {
+                progress.failed_count -= 1;
+
+                if progress.failed_count == 0 {
+                    progress.error = None;
+                }
+            }
+        }
+    }
+}
+
+/// A placeholder for the result of compressing a block.
+#[derive(Debug, Default)]
+struct Slot {
+    result: Mutex<Option<io::Result<GzData>>>,
+    cvar: Condvar,
+}
+
+impl Slot {
+    fn set(&self, result: io::Result<GzData>) {
+        let mut guard = self.result.lock().unwrap();
+        *guard = Some(result);
+        self.cvar.notify_all();
+    }
+
+    fn wait(&self) -> MutexGuard<'_, Option<io::Result<GzData>>> {
+        let guard = self.result.lock().unwrap();
+        self.cvar
+            .wait_while(guard, |result| result.is_none())
+            .unwrap()
+    }
+}
+
+/// A pool of workers that compress blocks in parallel.
+///
+/// Blocks are sent in stream order, and results are received in the same order.
+#[derive(Debug)]
+pub(crate) struct Deflater {
+    tx: Option<Sender<Request>>,
+    shared: Arc<Shared>,
+    handles: Vec<JoinHandle<()>>,
+    queue: VecDeque<Arc<Slot>>,
+    capacity: usize,
+}
+
+impl Deflater {
+    pub fn new(worker_count: usize, compression_level: CompressionLevel) -> Self {
+        let (tx, rx) = crossbeam_channel::unbounded::<Request>();
+        let shared = Arc::new(Shared::default());
+
+        let handles = (0..worker_count)
+            .map(|_| {
+                let rx = rx.clone();
+                let shared = shared.clone();
+
+                thread::spawn(move || {
+                    while let Ok((data, slot)) = rx.recv() {
+                        let result = deflate_data(&data, compression_level.into());
+                        // The progress is updated before the slot is filled so that a received
+                        // block is always accounted for.
+                        shared.finish(&result);
+                        slot.set(result);
+                    }
+                })
+            })
+            .collect();
+
+        Self {
+            tx: Some(tx),
+            shared,
+            handles,
+            queue: VecDeque::with_capacity(worker_count),
+            capacity: worker_count,
+        }
+    }
+
+    /// Returns whether the number of pending blocks is at capacity.
+    pub fn is_full(&self) -> bool {
+        self.queue.len() >= self.capacity
+    }
+
+    /// Queues uncompressed data to be compressed as a block.
+    pub fn send(&mut self, data: Vec<u8>) {
+        let slot = Arc::new(Slot::default());
+
+        if let Some(tx) = self.tx.as_ref() {
+            // Workers only stop when the request sender is dropped.
+            tx.send((data, slot.clone())).ok();
+        }
+
+        self.queue.push_back(slot);
+    }
+
+    /// Receives the next compressed block in stream order.
+    ///
+    /// This returns `None` if there are no pending blocks.
+    pub fn recv(&mut self) -> Option<io::Result<GzData>> {
+        let slot = self.queue.pop_front()?;
+        let result = slot.wait().take().expect("missing deflate result");
+        self.shared.remove(&result);
+        Some(result)
+    }
+
+    /// Returns the total size of all pending blocks once compressed.
+    ///
+    /// Sizes are accumulated as workers finish blocks. This does not wait and returns `None` if
+    /// any pending block is still being compressed. If any pending block failed to compress, an
+    /// error is returned.
+    pub fn pending_block_size(&self) -> io::Result<Option<u64>> {
+        let progress = self.shared.progress.lock().unwrap();
+
+        if let Some((kind, message)) = &progress.error {
+            return Err(io::Error::new(*kind, message.clone()));
+        }
+
+        if progress.finished_count < self.queue.len() {
+            Ok(None)
+        } else {
+            Ok(Some(progress.finished_size))
+        }
+    }
+}
+
+fn block_size(cdata: &[u8]) -> u64 {
+    (BGZF_HEADER_SIZE + cdata.len() + gz::TRAILER_SIZE) as u64
+}
+
+impl Drop for Deflater {
+    fn drop(&mut self) {
+        // Dropping the sender disconnects the channel, which stops the workers.
+        self.tx.take();
+
+        for handle in self.handles.drain(..) {
+            handle.join().ok();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_recv() -> io::Result<()> {
+        let mut deflater = Deflater::new(2, CompressionLevel::default());
+        assert!(!deflater.is_full());
+
+        deflater.send(b"noodles".to_vec());
+        deflater.send(b"bgzf".to_vec());
+        assert!(deflater.is_full());
+
+        let expected = deflate_data(b"noodles", CompressionLevel::default().into())?;
+        assert_eq!(deflater.recv().transpose()?, Some(expected));
+
+        let expected = deflate_data(b"bgzf", CompressionLevel::default().into())?;
+        assert_eq!(deflater.recv().transpose()?, Some(expected));
+
+        assert!(deflater.recv().is_none());
+        assert_eq!(deflater.pending_block_size()?, Some(0));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_pending_block_size_with_failed_block() -> io::Result<()> {
+        let deflater = Deflater::new(0, CompressionLevel::default());
+        assert_eq!(deflater.pending_block_size()?, Some(0));
+
+        let failed_result = || Err(io::Error::from(io::ErrorKind::InvalidInput));
+
+        deflater.shared.finish(&failed_result());
+        deflater.shared.finish(&failed_result());
+
+        assert!(matches!(
+            deflater.pending_block_size(),
+            Err(e) if e.kind() == io::ErrorKind::InvalidInput
+        ));
+
+        deflater.shared.remove(&failed_result());
+
+        assert!(matches!(
+            deflater.pending_block_size(),
+            Err(e) if e.kind() == io::ErrorKind::InvalidInput
+        ));
+
+        deflater.shared.remove(&failed_result());
+        assert_eq!(deflater.pending_block_size()?, Some(0));
+
+        Ok(())
+    }
+}
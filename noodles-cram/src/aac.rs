@@ -0,0 +1,67 @@
+//! Adaptive arithmetic coding.
+
+mod decode;
+mod encode;
+mod flags;
+mod model;
+mod range_coder;
+
+pub use self::{decode::decode, encode::encode, flags::Flags};
+
+pub(crate) use self::{model::Model, range_coder::RangeCoder};
+
+#[cfg(test)]
+mod tests {
+    use std::io;
+
+    use super::*;
+
+    #[test]
+    fn test_self() -> io::Result<()> {
+        let data = b"noodles-cram: aaaaaaaabbbbbbbbbbccccdddddddeeeeeeeeeeeeeeeeeeee";
+
+        for flags in [
+            Flags::empty(),
+            Flags::ORDER,
+            Flags::RLE,
+            Flags::ORDER | Flags::RLE,
+            Flags::EXT,
+            Flags::CAT,
+            Flags::STRIPE,
+            Flags::STRIPE | Flags::ORDER | Flags::RLE,
+            Flags::PACK,
+            Flags::PACK | Flags::ORDER,
+        ] {
+            let compressed_data = encode(flags, data)?;
+            let decompressed_data = decode(&mut &compressed_data[..], 0)?;
+            assert_eq!(decompressed_data, data, "flags = {:?}", flags);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_self_with_all_symbols() -> io::Result<()> {
+        let data: Vec<_> = (0..=255).cycle().take(4096).collect();
+
+        for flags in [Flags::empty(), Flags::ORDER, Flags::ORDER | Flags::RLE] {
+            let compressed_data = encode(flags, &data)?;
+            let decompressed_data = decode(&mut &compressed_data[..], 0)?;
+            assert_eq!(decompressed_data, data, "flags = {:?}", flags);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_encode_compresses() -> io::Result<()> {
+        let data = vec![b'A'; 1024];
+
+        for flags in [Flags::empty(), Flags::ORDER, Flags::RLE] {
+            let compressed_data = encode(flags, &data)?;
+            assert!(compressed_data.len() < data.len() / 4);
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,310 @@
+use std::io::{self, Read};
+
+use byteorder::ReadBytesExt;
+use bzip2::read::BzDecoder;
+
+use super::{Flags, Model, RangeCoder};
+use crate::rans_nx16::decode::{pack, read_uint7_as_usize, split_off};
+
+pub fn decode(src: &mut &[u8], mut len: usize) -> io::Result<Vec<u8>> {
+    let flags = src.read_u8().map(Flags::from_bits_truncate)?;
+
+    if !flags.contains(Flags::NO_SIZE) {
+        len = read_uint7_as_usize(src)?;
+    }
+
+    if flags.contains(Flags::STRIPE) {
+        return decode_stripe(src, len);
+    }
+
+    let mut pack_context = None;
+
+    if flags.contains(Flags::PACK) {
+        let (mapping, packed_len) = pack::read_context(src)?;
+        pack_context = Some((mapping, len));
+        len = packed_len;
+    }
+
+    let mut dst = if flags.contains(Flags::CAT) {
+        split_off(src, len).map(|buf| buf.to_vec())?
+    } else if flags.contains(Flags::EXT) {
+        decode_ext(src, len)?
+    } else if len == 0 {
+        Vec::new()
+    } else {
+        match (flags.contains(Flags::ORDER), flags.contains(Flags::RLE)) {
+            (false, false) => decode_order_0(src, len)?,
+            (true, false) => decode_order_1(src, len)?,
+            (false, true) => decode_rle_order_0(src, len)?,
+            (true, true) => decode_rle_order_1(src, len)?,
+        }
+    };
+
+    if let Some((mapping, len)) = pack_context {
+        dst = pack::decode(&dst, &mapping, len)?;
+    }
+
+    Ok(dst)
+}
+
+fn decode_stripe(src: &mut &[u8], len: usize) -> io::Result<Vec<u8>> {
+    let n = src.read_u8().map(usize::from)?;
+
+    if n == 0 {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "invalid stripe count",
+        ));
+    }
+
+    let compressed_lens = (0..n)
+        .map(|_| read_uint7_as_usize(src))
+        .collect::<io::Result<Vec<_>>>()?;
+
+    let mut dst = vec![0; len];
+
+    for (j, compressed_len) in compressed_lens.into_iter().enumerate() {
+        let ulen = len / n + usize::from(len % n > j);
+
+        let mut buf = split_off(src, compressed_len)?;
+        let chunk = decode(&mut buf, ulen)?;
+
+        if chunk.len() != ulen {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!(
+                    "stripe length mismatch: expected {}, got {}",
+                    ulen,
+                    chunk.len()
+                ),
+            ));
+        }
+
+        for (i, b) in chunk.into_iter().enumerate() {
+            dst[i * n + j] = b;
+        }
+    }
+
+    Ok(dst)
+}
+
+fn decode_ext(src: &mut &[u8], len: usize) -> io::Result<Vec<u8>> {
+    let mut decoder = BzDecoder::new(*src);
+    let mut dst = Vec::with_capacity(len);
+    decoder.read_to_end(&mut dst)?;
+
+    *src = &[];
+
+    if dst.len() != len {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("length mismatch: expected {}, got {}", len, dst.len()),
+        ));
+    }
+
+    Ok(dst)
+}
+
+fn read_symbol_count(src: &mut &[u8]) -> io::Result<usize> {
+    // A max symbol of 0 is used to represent all 256 symbols.
+    src.read_u8().map(|n| match n {
+        0 => 256,
+        _ => usize::from(n),
+    })
+}
+
+fn decode_order_0(src: &mut &[u8], len: usize) -> io::Result<Vec<u8>> {
+    let symbol_count = read_symbol_count(src)?;
+    let mut model = Model::new(symbol_count);
+
+    let mut range_coder = RangeCoder::default();
+    range_coder.range_decode_create(src);
+
+    (0..len)
+        .map(|_| model.decode(src, &mut range_coder))
+        .collect()
+}
+
+fn decode_order_1(src: &mut &[u8], len: usize) -> io::Result<Vec<u8>> {
+    let symbol_count = read_symbol_count(src)?;
+    let mut models = vec![Model::new(symbol_count); symbol_count];
+
+    let mut range_coder = RangeCoder::default();
+    range_coder.range_decode_create(src);
+
+    let mut dst = Vec::with_capacity(len);
+    let mut last = 0;
+
+    for _ in 0..len {
+        let model = models
+            .get_mut(usize::from(last))
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "invalid context"))?;
+
+        last = model.decode(src, &mut range_coder)?;
+        dst.push(last);
+    }
+
+    Ok(dst)
+}
+
+fn decode_rle_order_0(src: &mut &[u8], len: usize) -> io::Result<Vec<u8>> {
+    let symbol_count = read_symbol_count(src)?;
+    let mut model_lit = Model::new(symbol_count);
+    let mut model_run = vec![Model::new(4); 258];
+
+    let mut range_coder = RangeCoder::default();
+    range_coder.range_decode_create(src);
+
+    let mut dst = Vec::with_capacity(len);
+
+    while dst.len() < len {
+        let sym = model_lit.decode(src, &mut range_coder)?;
+        let run = decode_run(src, &mut range_coder, &mut model_run, sym)?;
+        push_run(&mut dst, sym, run, len)?;
+    }
+
+    Ok(dst)
+}
+
+fn decode_rle_order_1(src: &mut &[u8], len: usize) -> io::Result<Vec<u8>> {
+    let symbol_count = read_symbol_count(src)?;
+    let mut model_lit = vec![Model::new(symbol_count); symbol_count];
+    let mut model_run = vec![Model::new(4); 258];
+
+    let mut range_coder = RangeCoder::default();
+    range_coder.range_decode_create(src);
+
+    let mut dst = Vec::with_capacity(len);
+    let mut last = 0;
+
+    while dst.len() < len {
+        let model = model_lit
+            .get_mut(usize::from(last))
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "invalid context"))?;
+
+        let sym = model.decode(src, &mut range_coder)?;
+        let run = decode_run(src, &mut range_coder, &mut model_run, sym)?;
+        push_run(&mut dst, sym, run, len)?;
+
+        last = sym;
+    }
+
+    Ok(dst)
+}
+
+fn decode_run(
+    src: &mut &[u8],
+    range_coder: &mut RangeCoder,
+    model_run: &mut [Model],
+    sym: u8,
+) -> io::Result<usize> {
+    let mut run = 0;
+    let mut rctx = usize::from(sym);
+
+    loop {
+        let part = model_run[rctx].decode(src, range_coder)?;
+        run += usize::from(part);
+
+        if part < 3 {
+            break;
+        }
+
+        rctx = next_run_context(rctx, usize::from(sym));
+    }
+
+    Ok(run)
+}
+
+pub(super) fn next_run_context(rctx: usize, sym: usize) -> usize {
+    if rctx == sym {
+        256
+    } else {
+        (rctx + 1).min(257)
+    }
+}
+
+fn push_run(dst: &mut Vec<u8>, sym: u8, run: usize, len: usize) -> io::Result<()> {
+    if dst.len() + run + 1 > len {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "run exceeds uncompressed length",
+        ));
+    }
+
+    dst.resize(dst.len() + run + 1, sym);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_with_cat() -> io::Result<()> {
+        let data = [
+            0x20, // flags = CAT
+            0x07, // uncompressed size = 7
+            b'n', b'o', b'o', b'd', b'l', b'e', b's',
+        ];
+
+        let mut src = &data[..];
+        assert_eq!(decode(&mut src, 0)?, b"noodles");
+        assert!(src.is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_decode_with_order_0() -> io::Result<()> {
+        let data = [
+            0x00, // flags = {empty}
+            0x07, // uncompressed size = 7
+            0x74, // symbol count = 116
+            0x00, 0xf4, 0xe5, 0xb7, 0x4e, 0x50, 0x0f, 0x2e, 0x97, 0x00, // range coded data
+        ];
+
+        let mut src = &data[..];
+        assert_eq!(decode(&mut src, 0)?, b"noodles");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_decode_with_order_1() -> io::Result<()> {
+        let data = [
+            0x01, // flags = ORDER
+            0x0b, // uncompressed size = 11
+            0x73, // symbol count = 115
+            0x00, 0xd9, 0xd8, 0xc6, 0x3b, 0x98, 0x81, 0xbe, 0xd5, 0xb8, 0x53, 0xe8,
+            0x00, // range coded data
+        ];
+
+        let mut src = &data[..];
+        assert_eq!(decode(&mut src, 0)?, b"abracadabra");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_decode_with_rle() -> io::Result<()> {
+        let data = [
+            0x40, // flags = RLE
+            0x10, // uncompressed size = 16
+            0x65, // symbol count = 101
+            0x00, 0xf8, 0x50, 0xe9, 0xa2, 0xe5, 0xcd, 0x49, 0x00, 0x00, // range coded data
+        ];
+
+        let mut src = &data[..];
+        assert_eq!(decode(&mut src, 0)?, b"aaaaaaaabbbcdddd");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_next_run_context() {
+        assert_eq!(next_run_context(97, 97), 256);
+        assert_eq!(next_run_context(256, 97), 257);
+        assert_eq!(next_run_context(257, 97), 257);
+    }
+}
@@ -0,0 +1,245 @@
+use std::{
+    borrow::Cow,
+    io::{self, Write},
+};
+
+use byteorder::WriteBytesExt;
+use bzip2::write::BzEncoder;
+
+use super::{decode::next_run_context, Flags, Model, RangeCoder};
+use crate::rans_nx16::encode::{pack, write_uint7_as_usize};
+
+const STRIPE_COUNT: usize = 4;
+
+pub fn encode(mut flags: Flags, src: &[u8]) -> io::Result<Vec<u8>> {
+    if src.is_empty() {
+        flags = (flags & Flags::NO_SIZE) | Flags::CAT;
+    }
+
+    let mut dst = Vec::new();
+
+    if flags.contains(Flags::STRIPE) {
+        write_header(&mut dst, flags, src.len())?;
+        encode_stripe(&mut dst, flags, src)?;
+        return Ok(dst);
+    }
+
+    let mut context = Vec::new();
+    let mut data = Cow::from(src);
+
+    if flags.contains(Flags::PACK) {
+        match pack::build_mapping(&data) {
+            Some(mapping) => {
+                let packed_data = pack::encode(&data, &mapping);
+                pack::write_context(&mut context, &mapping, packed_data.len())?;
+                data = Cow::from(packed_data);
+            }
+            None => flags.remove(Flags::PACK),
+        }
+    }
+
+    write_header(&mut dst, flags, src.len())?;
+    dst.extend(context);
+
+    if flags.contains(Flags::CAT) {
+        dst.extend(data.iter());
+    } else if flags.contains(Flags::EXT) {
+        encode_ext(&mut dst, &data)?;
+    } else if data.is_empty() {
+        // An empty stream has no symbol count or coded data.
+    } else {
+        match (flags.contains(Flags::ORDER), flags.contains(Flags::RLE)) {
+            (false, false) => encode_order_0(&mut dst, &data)?,
+            (true, false) => encode_order_1(&mut dst, &data)?,
+            (false, true) => encode_rle_order_0(&mut dst, &data)?,
+            (true, true) => encode_rle_order_1(&mut dst, &data)?,
+        }
+    }
+
+    Ok(dst)
+}
+
+fn write_header<W>(writer: &mut W, flags: Flags, len: usize) -> io::Result<()>
+where
+    W: Write,
+{
+    writer.write_u8(flags.bits())?;
+
+    if !flags.contains(Flags::NO_SIZE) {
+        write_uint7_as_usize(writer, len)?;
+    }
+
+    Ok(())
+}
+
+fn encode_stripe(dst: &mut Vec<u8>, flags: Flags, src: &[u8]) -> io::Result<()> {
+    let chunk_flags = (flags - Flags::STRIPE) | Flags::NO_SIZE;
+
+    let chunks = (0..STRIPE_COUNT)
+        .map(|j| {
+            let chunk: Vec<_> = src.iter().skip(j).step_by(STRIPE_COUNT).copied().collect();
+            encode(chunk_flags, &chunk)
+        })
+        .collect::<io::Result<Vec<_>>>()?;
+
+    dst.write_u8(STRIPE_COUNT as u8)?;
+
+    for chunk in &chunks {
+        write_uint7_as_usize(dst, chunk.len())?;
+    }
+
+    for chunk in chunks {
+        dst.extend(chunk);
+    }
+
+    Ok(())
+}
+
+fn encode_ext(dst: &mut Vec<u8>, src: &[u8]) -> io::Result<()> {
+    let mut encoder = BzEncoder::new(dst, Default::default());
+    encoder.write_all(src)?;
+    encoder.finish()?;
+    Ok(())
+}
+
+// Returns the number of symbols needed to code the given data, i.e., the max symbol + 1.
+fn symbol_count(src: &[u8]) -> usize {
+    src.iter().max().map(|&n| usize::from(n) + 1).unwrap_or(0)
+}
+
+fn write_symbol_count(dst: &mut Vec<u8>, symbol_count: usize) {
+    // 256 symbols overflows a u8 and is written as 0.
+    dst.push(symbol_count as u8);
+}
+
+fn encode_order_0(dst: &mut Vec<u8>, src: &[u8]) -> io::Result<()> {
+    let symbol_count = symbol_count(src);
+    write_symbol_count(dst, symbol_count);
+
+    let mut model = Model::new(symbol_count);
+    let mut range_coder = RangeCoder::default();
+
+    for &sym in src {
+        model.encode(dst, &mut range_coder, sym)?;
+    }
+
+    range_coder.range_encode_end(dst);
+
+    Ok(())
+}
+
+fn encode_order_1(dst: &mut Vec<u8>, src: &[u8]) -> io::Result<()> {
+    let symbol_count = symbol_count(src);
+    write_symbol_count(dst, symbol_count);
+
+    let mut models = vec![Model::new(symbol_count); symbol_count];
+    let mut range_coder = RangeCoder::default();
+    let mut last = 0;
+
+    for &sym in src {
+        models[usize::from(last)].encode(dst, &mut range_coder, sym)?;
+        last = sym;
+    }
+
+    range_coder.range_encode_end(dst);
+
+    Ok(())
+}
+
+fn encode_rle_order_0(dst: &mut Vec<u8>, src: &[u8]) -> io::Result<()> {
+    let symbol_count = symbol_count(src);
+    write_symbol_count(dst, symbol_count);
+
+    let mut model_lit = Model::new(symbol_count);
+    let mut model_run = vec![Model::new(4); 258];
+    let mut range_coder = RangeCoder::default();
+
+    for (sym, run) in runs(src) {
+        model_lit.encode(dst, &mut range_coder, sym)?;
+        encode_run(dst, &mut range_coder, &mut model_run, sym, run)?;
+    }
+
+    range_coder.range_encode_end(dst);
+
+    Ok(())
+}
+
+fn encode_rle_order_1(dst: &mut Vec<u8>, src: &[u8]) -> io::Result<()> {
+    let symbol_count = symbol_count(src);
+    write_symbol_count(dst, symbol_count);
+
+    let mut model_lit = vec![Model::new(symbol_count); symbol_count];
+    let mut model_run = vec![Model::new(4); 258];
+    let mut range_coder = RangeCoder::default();
+    let mut last = 0;
+
+    for (sym, run) in runs(src) {
+        model_lit[usize::from(last)].encode(dst, &mut range_coder, sym)?;
+        encode_run(dst, &mut range_coder, &mut model_run, sym, run)?;
+        last = sym;
+    }
+
+    range_coder.range_encode_end(dst);
+
+    Ok(())
+}
+
+// Returns an iterator over (symbol, run length) pairs, where the run length is the number of
+// additional copies of the symbol.
+fn runs(src: &[u8]) -> impl Iterator<Item = (u8, usize)> + '_ {
+    let mut i = 0;
+
+    std::iter::from_fn(move || {
+        let sym = *src.get(i)?;
+        let run = src[i + 1..].iter().take_while(|&&b| b == sym).count();
+        i += run + 1;
+        Some((sym, run))
+    })
+}
+
+fn encode_run(
+    dst: &mut Vec<u8>,
+    range_coder: &mut RangeCoder,
+    model_run: &mut [Model],
+    sym: u8,
+    mut run: usize,
+) -> io::Result<()> {
+    let mut rctx = usize::from(sym);
+
+    loop {
+        let part = run.min(3);
+        model_run[rctx].encode(dst, range_coder, part as u8)?;
+        run -= part;
+
+        if part < 3 {
+            break;
+        }
+
+        rctx = next_run_context(rctx, usize::from(sym));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode_with_cat() -> io::Result<()> {
+        let actual = encode(Flags::CAT, b"noodles")?;
+        let expected = [0x20, 0x07, b'n', b'o', b'o', b'd', b'l', b'e', b's'];
+        assert_eq!(actual, expected);
+
+        let actual = encode(Flags::ORDER, b"")?;
+        assert_eq!(actual, [0x20, 0x00]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_runs() {
+        let actual: Vec<_> = runs(b"aaabcc").collect();
+        assert_eq!(actual, [(b'a', 2), (b'b', 0), (b'c', 1)]);
+    }
+}
@@ -0,0 +1,170 @@
+use std::io;
+
+use super::RangeCoder;
+
+const MAX_FREQ: u32 = (1 << 16) - 17;
+const STEP: u32 = 16;
+
+/// An adaptive frequency model.
+///
+/// Symbols are kept approximately sorted by descending frequency.
+#[derive(Clone, Debug)]
+pub struct Model {
+    symbols: Vec<u8>,
+    freqs: Vec<u32>,
+    total_freq: u32,
+}
+
+impl Model {
+    /// Creates a model with symbols in `0..symbol_count`, each with a frequency of 1.
+    ///
+    /// The symbol count must be <= 256.
+    pub fn new(symbol_count: usize) -> Self {
+        Self {
+            symbols: (0..symbol_count).map(|i| i as u8).collect(),
+            freqs: vec![1; symbol_count],
+            total_freq: symbol_count as u32,
+        }
+    }
+
+    pub fn decode(&mut self, src: &mut &[u8], range_coder: &mut RangeCoder) -> io::Result<u8> {
+        let freq = range_coder.range_get_freq(self.total_freq);
+
+        if freq >= self.total_freq {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "invalid symbol frequency",
+            ));
+        }
+
+        let mut acc = 0;
+        let mut i = 0;
+
+        while acc + self.freqs[i] <= freq {
+            acc += self.freqs[i];
+            i += 1;
+        }
+
+        range_coder.range_decode(src, acc, self.freqs[i]);
+
+        let sym = self.symbols[i];
+        self.update(i);
+
+        Ok(sym)
+    }
+
+    pub fn encode(
+        &mut self,
+        dst: &mut Vec<u8>,
+        range_coder: &mut RangeCoder,
+        sym: u8,
+    ) -> io::Result<()> {
+        let mut acc = 0;
+        let mut i = 0;
+
+        loop {
+            match self.symbols.get(i) {
+                Some(&s) if s == sym => break,
+                Some(_) => {
+                    acc += self.freqs[i];
+                    i += 1;
+                }
+                None => {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidInput,
+                        format!("symbol out of range: {}", sym),
+                    ))
+                }
+            }
+        }
+
+        range_coder.range_encode(dst, acc, self.freqs[i], self.total_freq);
+        self.update(i);
+
+        Ok(())
+    }
+
+    fn update(&mut self, i: usize) {
+        self.freqs[i] += STEP;
+        self.total_freq += STEP;
+
+        if self.total_freq > MAX_FREQ {
+            self.renormalize();
+        }
+
+        if i > 0 && self.freqs[i] > self.freqs[i - 1] {
+            self.freqs.swap(i - 1, i);
+            self.symbols.swap(i - 1, i);
+        }
+    }
+
+    fn renormalize(&mut self) {
+        self.total_freq = 0;
+
+        for f in &mut self.freqs {
+            *f -= *f >> 1;
+            self.total_freq += *f;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_self() -> io::Result<()> {
+        let data = b"abracadabra";
+
+        let mut model = Model::new(128);
+        let mut range_coder = RangeCoder::default();
+        let mut buf = Vec::new();
+
+        for &sym in data {
+            model.encode(&mut buf, &mut range_coder, sym)?;
+        }
+
+        range_coder.range_encode_end(&mut buf);
+
+        let mut model = Model::new(128);
+        let mut range_coder = RangeCoder::default();
+        let mut src = &buf[..];
+        range_coder.range_decode_create(&mut src);
+
+        let actual = data
+            .iter()
+            .map(|_| model.decode(&mut src, &mut range_coder))
+            .collect::<io::Result<Vec<_>>>()?;
+
+        assert_eq!(actual, data);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_encode_with_symbol_out_of_range() {
+        let mut model = Model::new(2);
+        let mut range_coder = RangeCoder::default();
+        let mut buf = Vec::new();
+
+        assert!(matches!(
+            model.encode(&mut buf, &mut range_coder, 2),
+            Err(ref e) if e.kind() == io::ErrorKind::InvalidInput
+        ));
+    }
+
+    #[test]
+    fn test_update() {
+        let mut model = Model::new(3);
+
+        model.update(2);
+        assert_eq!(model.symbols, [0, 2, 1]);
+        assert_eq!(model.freqs, [1, 17, 1]);
+        assert_eq!(model.total_freq, 19);
+
+        model.update(1);
+        assert_eq!(model.symbols, [2, 0, 1]);
+        assert_eq!(model.freqs, [33, 1, 1]);
+        assert_eq!(model.total_freq, 35);
+    }
+}
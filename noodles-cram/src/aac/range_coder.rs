@@ -0,0 +1,113 @@
+// The lower bound of the range before renormalization.
+const TOP: u32 = 1 << 24;
+
+/// A range coder with carry propagation.
+///
+/// The same state is used for both decoding and encoding, but a single instance only does one or
+/// the other.
+#[derive(Debug)]
+pub struct RangeCoder {
+    low: u32,
+    range: u32,
+    code: u32,
+    ff_num: u32,
+    cache: u8,
+    carry: bool,
+}
+
+impl RangeCoder {
+    pub fn range_decode_create(&mut self, src: &mut &[u8]) {
+        for _ in 0..5 {
+            self.code = (self.code << 8) + u32::from(read_u8_or_zero(src));
+        }
+    }
+
+    pub fn range_get_freq(&mut self, total_freq: u32) -> u32 {
+        self.range /= total_freq;
+        self.code / self.range
+    }
+
+    pub fn range_decode(&mut self, src: &mut &[u8], sym_low: u32, sym_freq: u32) {
+        self.code = self.code.wrapping_sub(sym_low * self.range);
+        self.range *= sym_freq;
+
+        while self.range < TOP {
+            self.range <<= 8;
+            self.code = (self.code << 8) + u32::from(read_u8_or_zero(src));
+        }
+    }
+
+    pub fn range_encode(
+        &mut self,
+        dst: &mut Vec<u8>,
+        sym_low: u32,
+        sym_freq: u32,
+        total_freq: u32,
+    ) {
+        let old_low = self.low;
+
+        self.range /= total_freq;
+        self.low = self.low.wrapping_add(sym_low * self.range);
+        self.range *= sym_freq;
+
+        if self.low < old_low {
+            self.carry = true;
+        }
+
+        while self.range < TOP {
+            self.range <<= 8;
+            self.range_shift_low(dst);
+        }
+    }
+
+    pub fn range_encode_end(&mut self, dst: &mut Vec<u8>) {
+        for _ in 0..5 {
+            self.range_shift_low(dst);
+        }
+    }
+
+    fn range_shift_low(&mut self, dst: &mut Vec<u8>) {
+        if self.low < 0xff000000 || self.carry {
+            if self.carry {
+                dst.push(self.cache.wrapping_add(1));
+                dst.extend((0..self.ff_num).map(|_| 0x00));
+            } else {
+                dst.push(self.cache);
+                dst.extend((0..self.ff_num).map(|_| 0xff));
+            }
+
+            self.ff_num = 0;
+            self.cache = (self.low >> 24) as u8;
+            self.carry = false;
+        } else {
+            self.ff_num += 1;
+        }
+
+        self.low <<= 8;
+    }
+}
+
+impl Default for RangeCoder {
+    fn default() -> Self {
+        Self {
+            low: 0,
+            range: 0xffffffff,
+            code: 0,
+            ff_num: 0,
+            cache: 0,
+            carry: false,
+        }
+    }
+}
+
+// The decoder reads one byte past the end of the encoded data, which is never used to decode a
+// symbol. Missing bytes are read as 0.
+fn read_u8_or_zero(src: &mut &[u8]) -> u8 {
+    match src.split_first() {
+        Some((&b, rest)) => {
+            *src = rest;
+            b
+        }
+        None => 0,
+    }
+}
@@ -1,3 +1,52 @@
 mod reader;
+mod writer;
 
-pub use self::reader::Reader;
+pub use self::{reader::Reader, writer::Writer};
+
+use tokio::io::{self, AsyncRead, AsyncSeek};
+
+use crate::crai;
+
+// Builds an index from the data containers of a reader. The stream is expected to be directly
+// after the file header container.
+async fn index_data_containers<R>(reader: &mut Reader<R>) -> io::Result<crai::Index>
+where
+    R: AsyncRead + AsyncSeek + Unpin,
+{
+    use crate::push_index_records;
+
+    let mut index = Vec::new();
+    let mut container_position = reader.position().await?;
+
+    while let Some((container_header, data_container)) =
+        reader.read_data_container_with_container_header().await?
+    {
+        let container_len = container_header.len();
+
+        let landmarks = container_header.landmarks();
+        let slice_count = landmarks.len();
+
+        for (i, slice) in data_container.slices().iter().enumerate() {
+            let landmark = landmarks[i];
+
+            let slice_length = if i < slice_count - 1 {
+                landmarks[i + 1] - landmark
+            } else {
+                container_len - landmark
+            };
+
+            push_index_records(
+                &mut index,
+                data_container.compression_header(),
+                slice,
+                container_position,
+                landmark as u64,
+                slice_length as u64,
+            )?;
+        }
+
+        container_position = reader.position().await?;
+    }
+
+    Ok(index)
+}
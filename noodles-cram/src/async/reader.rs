@@ -1,15 +1,33 @@
+mod builder;
 mod container;
 mod data_container;
 mod num;
+mod query;
 mod record;
 
+pub use self::builder::Builder;
+
+use futures::Stream;
+use noodles_core::Region;
+use noodles_sam as sam;
 use tokio::io::{self, AsyncRead, AsyncReadExt, AsyncSeek, AsyncSeekExt, SeekFrom};
 
-use crate::{file_definition::Version, DataContainer, FileDefinition};
+use self::query::query;
+use crate::{
+    crai,
+    data_container::{CompressionHeader, Slice},
+    file_definition::Version,
+    reader::{resolve_region, FILE_DEFINITION_LEN},
+    reference_sequence_repository::ReferenceSequenceRepository,
+    Container, DataContainer, FileDefinition, Record,
+};
 
 /// An async CRAM reader.
 pub struct Reader<R> {
     inner: R,
+    reference_sequence_repository: ReferenceSequenceRepository,
+    validate_checksums: bool,
+    position: u64,
 }
 
 impl<R> Reader<R>
@@ -26,7 +44,35 @@ where
     /// let reader = cram::AsyncReader::new(&data[..]);
     /// ```
     pub fn new(inner: R) -> Self {
-        Self { inner }
+        Self::builder(inner).build()
+    }
+
+    /// Creates an async CRAM reader builder.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_cram as cram;
+    /// let data = [];
+    /// let builder = cram::AsyncReader::builder(&data[..]);
+    /// let reader = builder.build();
+    /// ```
+    pub fn builder(inner: R) -> Builder<R> {
+        Builder::new(inner)
+    }
+
+    /// Returns a mutable reference to the reference sequence repository.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_cram as cram;
+    /// let data = [];
+    /// let mut reader = cram::AsyncReader::new(&data[..]);
+    /// reader.reference_sequence_repository_mut().clear();
+    /// ```
+    pub fn reference_sequence_repository_mut(&mut self) -> &mut ReferenceSequenceRepository {
+        &mut self.reference_sequence_repository
     }
 
     /// Reads the CRAM file definition.
@@ -55,6 +101,8 @@ where
         let format = read_format(&mut self.inner).await?;
         let file_id = read_file_id(&mut self.inner).await?;
 
+        self.position += FILE_DEFINITION_LEN;
+
         Ok(FileDefinition::new(format, file_id))
     }
 
@@ -84,10 +132,9 @@ where
     /// # }
     /// ```
     pub async fn read_file_header(&mut self) -> io::Result<String> {
-        use self::container::read_container;
         use crate::reader::read_file_header_block;
 
-        let container = read_container(&mut self.inner).await?;
+        let container = self.read_container().await?;
 
         if let Some(block) = container.blocks().first() {
             read_file_header_block(block)
@@ -125,9 +172,57 @@ where
     /// # }
     /// ```
     pub async fn read_data_container(&mut self) -> io::Result<Option<DataContainer>> {
+        self.read_data_container_with_container_header()
+            .await
+            .map(|result| result.map(|(_, data_container)| data_container))
+    }
+
+    pub(crate) async fn read_data_container_with_container_header(
+        &mut self,
+    ) -> io::Result<Option<(crate::container::Header, DataContainer)>> {
         use self::data_container::read_data_container;
 
-        read_data_container(&mut self.inner).await
+        let container = self.read_container().await?;
+
+        if container.header().is_eof() {
+            return Ok(None);
+        }
+
+        read_data_container(container).await.map(Some)
+    }
+
+    // Reads a container and, if enabled, validates its checksums.
+    async fn read_container(&mut self) -> io::Result<Container> {
+        use self::container::read_container;
+        use crate::reader::checksum::validate_checksums;
+
+        let container_offset = self.position;
+        let (container, checksums) = read_container(&mut self.inner).await?;
+        self.position += checksums.len();
+
+        if self.validate_checksums {
+            validate_checksums(container_offset, &container, &checksums)?;
+        }
+
+        Ok(container)
+    }
+
+    fn read_slice_records(
+        &mut self,
+        header: &sam::Header,
+        compression_header: &CompressionHeader,
+        slice: &Slice,
+    ) -> io::Result<Vec<Record>> {
+        let mut records = slice.records(compression_header)?;
+
+        slice.resolve_records(
+            &mut self.reference_sequence_repository,
+            header,
+            compression_header,
+            &mut records,
+        )?;
+
+        Ok(slice.resolve_mates(records))
     }
 }
 
@@ -154,7 +249,9 @@ where
     /// # }
     /// ```
     pub async fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
-        self.inner.seek(pos).await
+        let position = self.inner.seek(pos).await?;
+        self.position = position;
+        Ok(position)
     }
 
     /// Returns the current position of the underlying reader.
@@ -176,6 +273,49 @@ where
     pub async fn position(&mut self) -> io::Result<u64> {
         self.inner.seek(SeekFrom::Current(0)).await
     }
+
+    /// Returns a stream over records that intersect the given region.
+    ///
+    /// Only the slices listed in the index that overlap the region are read. The unmapped region
+    /// (`*`) returns all records without a reference sequence.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use futures::TryStreamExt;
+    /// use noodles_core::Region;
+    /// use noodles_cram::{self as cram, crai};
+    /// use noodles_sam as sam;
+    /// use tokio::fs::File;
+    ///
+    /// let mut reader = File::open("sample.cram").await.map(cram::AsyncReader::new)?;
+    /// reader.read_file_definition().await?;
+    ///
+    /// let header: sam::Header = reader.read_file_header().await?.parse()?;
+    ///
+    /// let index = crai::r#async::read("sample.cram.crai").await?;
+    /// let region = Region::mapped("sq0", 8..=13);
+    /// let mut query = reader.query(&header, &index, &region)?;
+    ///
+    /// while let Some(record) = query.try_next().await? {
+    ///     // ...
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn query<'a>(
+        &'a mut self,
+        header: &'a sam::Header,
+        index: &crai::Index,
+        region: &Region,
+    ) -> io::Result<impl Stream<Item = io::Result<Record>> + 'a> {
+        let (reference_sequence_id, interval) =
+            resolve_region(header.reference_sequences(), region)?;
+
+        Ok(query(self, header, index, reference_sequence_id, interval))
+    }
 }
 
 async fn read_magic_number<R>(reader: &mut R) -> io::Result<()>
@@ -260,4 +400,155 @@ mod tests {
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_read_data_container_with_checksum_mismatch(
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        use crate::{reader::ChecksumError, Writer};
+
+        let mut writer = Writer::new(Vec::new(), Vec::new());
+        writer.write_file_definition()?;
+        writer.write_file_header(&sam::Header::default())?;
+        writer.write_record(Record::default())?;
+        writer.try_finish()?;
+
+        let mut data = writer.get_ref().clone();
+
+        let mut reader = Reader::new(&data[..]);
+        reader.read_file_definition().await?;
+        reader.read_file_header().await?;
+        let container_offset = reader.position;
+
+        // Corrupt the length of the data container, i.e., the first field of the header.
+        data[container_offset as usize + 2] ^= 0x01;
+
+        let mut reader = Reader::new(&data[..]);
+        reader.read_file_definition().await?;
+        reader.read_file_header().await?;
+
+        let e = match reader.read_data_container().await {
+            Ok(_) => panic!("expected checksum error"),
+            Err(e) => e,
+        };
+        let checksum_error = e
+            .get_ref()
+            .and_then(|e| e.downcast_ref::<ChecksumError>())
+            .expect("missing checksum error");
+        assert_eq!(checksum_error.container_offset(), container_offset);
+        assert_eq!(checksum_error.block_index(), None);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_query() -> Result<(), Box<dyn std::error::Error>> {
+        use std::io::Cursor;
+
+        use futures::TryStreamExt;
+        use md5::{Digest, Md5};
+        use noodles_bam as bam;
+        use noodles_fasta::{
+            self as fasta,
+            record::{Definition, Sequence},
+        };
+        use sam::header::{reference_sequence::Md5Checksum, ReferenceSequence};
+
+        use crate::{reference_sequence_repository::adapters, AsyncWriter};
+
+        let reference_sequence_records = vec![
+            fasta::Record::new(
+                Definition::new("sq0", None),
+                Sequence::from(b"TTCACCCA".to_vec()),
+            ),
+            fasta::Record::new(
+                Definition::new("sq1", None),
+                Sequence::from(b"GGATTACA".to_vec()),
+            ),
+        ];
+
+        let mut header_builder = sam::Header::builder();
+
+        for record in &reference_sequence_records {
+            let mut hasher = Md5::new();
+            hasher.update(record.sequence());
+            let md5_checksum = Md5Checksum::from(<[u8; 16]>::from(hasher.finalize()));
+
+            header_builder = header_builder.add_reference_sequence(
+                ReferenceSequence::builder()
+                    .set_name(record.name().parse()?)
+                    .set_length(8)
+                    .set_md5_checksum(md5_checksum)
+                    .build()?,
+            );
+        }
+
+        let header = header_builder.build();
+
+        let mut writer = AsyncWriter::builder(Vec::new())
+            .set_reference_sequences(reference_sequence_records.clone())
+            .set_records_per_slice(1)
+            .build();
+
+        writer.write_file_definition().await?;
+        writer.write_file_header(&header).await?;
+
+        for (reference_sequence_id, alignment_start) in [(0, 2), (1, 1), (1, 5)] {
+            let record = Record::builder()
+                .set_bam_flags(sam::record::Flags::empty())
+                .set_reference_sequence_id(bam::record::ReferenceSequenceId::try_from(
+                    reference_sequence_id,
+                )?)
+                .set_alignment_start(sam::record::Position::try_from(alignment_start)?)
+                .set_read_length(4)
+                .build();
+
+            writer.write_record(record).await?;
+        }
+
+        writer.shutdown().await?;
+
+        let data = writer.get_ref().clone();
+
+        let mut reader = Reader::new(Cursor::new(&data[..]));
+        reader.read_file_definition().await?;
+        reader.read_file_header().await?;
+        let index = crate::r#async::index_data_containers(&mut reader).await?;
+
+        let mut index_writer = crai::r#async::Writer::new(Vec::new());
+        index_writer.write_index(&index).await?;
+        index_writer.shutdown().await?;
+
+        let index_data = index_writer.into_inner();
+        let mut index_reader = crai::r#async::Reader::new(&index_data[..]);
+        let index = index_reader.read_index().await?;
+
+        let repository =
+            ReferenceSequenceRepository::new(adapters::Records::new(reference_sequence_records));
+
+        let mut reader = Reader::builder(Cursor::new(&data[..]))
+            .set_reference_sequence_repository(repository)
+            .build();
+
+        reader.read_file_definition().await?;
+        reader.read_file_header().await?;
+
+        let region = "sq1:2-3".parse()?;
+        let records: Vec<_> = reader
+            .query(&header, &index, &region)?
+            .try_collect()
+            .await?;
+        let actual: Vec<_> = records.iter().map(|record| record.bases()).collect();
+        let expected: [&[u8]; 1] = [b"GGAT"];
+        assert_eq!(actual, expected);
+
+        let region = "sq0".parse()?;
+        let records: Vec<Record> = reader
+            .query(&header, &index, &region)?
+            .try_collect()
+            .await?;
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].bases(), b"TCAC");
+
+        Ok(())
+    }
 }
@@ -1,25 +1,32 @@
 mod block;
+mod crc_reader;
 mod header;
 
 pub use self::{block::read_block, header::read_header};
 
 use tokio::io::{self, AsyncRead};
 
-use crate::Container;
+use self::crc_reader::CrcReader;
+use crate::{reader::checksum::Checksums, Container};
 
-pub async fn read_container<R>(reader: &mut R) -> io::Result<Container>
+pub async fn read_container<R>(reader: &mut R) -> io::Result<(Container, Checksums)>
 where
     R: AsyncRead + Unpin,
 {
-    let header = read_header(reader).await?;
+    let (header, header_checksum) = read_header(reader).await?;
 
     let blocks_len = header.block_count();
     let mut blocks = Vec::with_capacity(blocks_len);
+    let mut block_checksums = Vec::with_capacity(blocks_len);
 
     for _ in 0..blocks_len {
-        let block = read_block(reader).await?;
+        let (block, checksum) = read_block(reader).await?;
         blocks.push(block);
+        block_checksums.push(checksum);
     }
 
-    Ok(Container::new(header, blocks))
+    let container = Container::new(header, blocks);
+    let checksums = Checksums::new(header_checksum, block_checksums);
+
+    Ok((container, checksums))
 }
@@ -1,17 +1,21 @@
 use tokio::io::{self, AsyncRead, AsyncReadExt};
 
+use super::CrcReader;
 use crate::{
     container::{
         block::{CompressionMethod, ContentType},
         Block,
     },
     r#async::reader::num::read_itf8,
+    reader::checksum::Checksum,
 };
 
-pub async fn read_block<R>(reader: &mut R) -> io::Result<Block>
+pub async fn read_block<R>(reader: &mut R) -> io::Result<(Block, Checksum)>
 where
     R: AsyncRead + Unpin,
 {
+    let reader = &mut CrcReader::new(reader);
+
     let method = reader.read_u8().await.and_then(|n| {
         CompressionMethod::try_from(n).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
     })?;
@@ -33,16 +37,19 @@ where
     let mut block_data = vec![0; size_in_bytes];
     reader.read_exact(&mut block_data).await?;
 
-    let crc32 = reader.read_u32_le().await?;
+    let checksum = Checksum::new(reader.crc());
+    let crc32 = reader.get_mut().read_u32_le().await?;
 
-    Ok(Block::builder()
+    let block = Block::builder()
         .set_compression_method(method)
         .set_content_type(block_content_type_id)
         .set_content_id(block_content_id)
         .set_uncompressed_len(raw_size_in_bytes)
         .set_data(block_data)
         .set_crc32(crc32)
-        .build())
+        .build();
+
+    Ok((block, checksum))
 }
 
 #[cfg(test)]
@@ -62,7 +69,7 @@ mod tests {
         ];
 
         let mut reader = &data[..];
-        let actual = read_block(&mut reader).await?;
+        let (actual, checksum) = read_block(&mut reader).await?;
 
         let expected = Block::builder()
             .set_compression_method(CompressionMethod::None)
@@ -74,6 +81,7 @@ mod tests {
             .build();
 
         assert_eq!(actual, expected);
+        assert_eq!(checksum.len(), data.len() as u64);
 
         Ok(())
     }
@@ -0,0 +1,71 @@
+use std::{
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use flate2::Crc;
+use futures::ready;
+use tokio::io::{self, AsyncRead, ReadBuf};
+
+/// An async reader that calculates the CRC32 of the bytes read.
+pub struct CrcReader<R> {
+    inner: R,
+    crc: Crc,
+}
+
+impl<R> CrcReader<R> {
+    pub fn new(inner: R) -> Self {
+        Self {
+            inner,
+            crc: Crc::new(),
+        }
+    }
+
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    pub fn crc(&self) -> &Crc {
+        &self.crc
+    }
+}
+
+impl<R> AsyncRead for CrcReader<R>
+where
+    R: AsyncRead + Unpin,
+{
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let filled_len = buf.filled().len();
+        ready!(Pin::new(&mut self.inner).poll_read(cx, buf))?;
+        self.crc.update(&buf.filled()[filled_len..]);
+        Poll::Ready(Ok(()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::io::AsyncReadExt;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_poll_read() -> io::Result<()> {
+        let data = b"ndls";
+        let mut reader = CrcReader::new(&data[..]);
+
+        let mut buf = Vec::new();
+        reader.read_to_end(&mut buf).await?;
+
+        let mut crc = Crc::new();
+        crc.update(data);
+
+        assert_eq!(reader.crc().sum(), crc.sum());
+        assert_eq!(reader.crc().amount(), 4);
+
+        Ok(())
+    }
+}
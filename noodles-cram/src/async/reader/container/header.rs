@@ -1,16 +1,20 @@
 use noodles_sam as sam;
 use tokio::io::{self, AsyncRead, AsyncReadExt};
 
+use super::CrcReader;
 use crate::{
     container::{self, ReferenceSequenceId},
     num::Itf8,
     r#async::reader::num::{read_itf8, read_ltf8},
+    reader::checksum::Checksum,
 };
 
-pub async fn read_header<R>(reader: &mut R) -> io::Result<container::Header>
+pub async fn read_header<R>(reader: &mut R) -> io::Result<(container::Header, Checksum)>
 where
     R: AsyncRead + Unpin,
 {
+    let reader = &mut CrcReader::new(reader);
+
     let length = reader.read_i32_le().await?;
 
     let reference_sequence_id = read_itf8(reader).await.and_then(|n| {
@@ -37,7 +41,9 @@ where
     })?;
 
     let landmarks = read_landmarks(reader).await?;
-    let crc32 = reader.read_u32_le().await?;
+
+    let checksum = Checksum::new(reader.crc());
+    let crc32 = reader.get_mut().read_u32_le().await?;
 
     let mut builder = container::Header::builder()
         .set_length(length)
@@ -54,7 +60,7 @@ where
         builder = builder.set_start_position(position);
     }
 
-    Ok(builder.build())
+    Ok((builder.build(), checksum))
 }
 
 async fn read_landmarks<R>(reader: &mut R) -> io::Result<Vec<Itf8>>
@@ -96,7 +102,7 @@ mod tests {
         ];
 
         let mut reader = &data[..];
-        let actual = read_header(&mut reader).await?;
+        let (actual, checksum) = read_header(&mut reader).await?;
 
         let expected = container::Header::builder()
             .set_length(144)
@@ -112,6 +118,7 @@ mod tests {
             .build();
 
         assert_eq!(actual, expected);
+        assert_eq!(checksum.len(), data.len() as u64);
 
         Ok(())
     }
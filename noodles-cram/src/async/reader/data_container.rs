@@ -3,40 +3,48 @@ pub mod slice;
 
 pub use self::{compression_header::read_compression_header, slice::read_slice};
 
-use tokio::io::{self, AsyncRead};
+use std::vec;
 
-use crate::data_container::{CompressionHeader, DataContainer};
+use tokio::io;
 
-pub async fn read_data_container<R>(reader: &mut R) -> io::Result<Option<DataContainer>>
-where
-    R: AsyncRead + Unpin,
-{
-    let header = super::container::read_header(reader).await?;
+use crate::{
+    container::{self, Block},
+    data_container::{CompressionHeader, DataContainer},
+    Container,
+};
 
-    if header.is_eof() {
-        return Ok(None);
-    }
+// Builds a data container from the blocks of a container.
+pub async fn read_data_container(
+    container: Container,
+) -> io::Result<(container::Header, DataContainer)> {
+    let (header, blocks) = container.into_parts();
+    let mut blocks = blocks.into_iter();
 
-    let compression_header = read_compression_header_from_block(reader).await?;
+    let compression_header = read_compression_header_from_block(&mut blocks).await?;
 
     let slice_count = header.landmarks().len();
     let mut slices = Vec::with_capacity(slice_count);
 
     for _ in 0..slice_count {
-        let slice = read_slice(reader).await?;
+        let slice = read_slice(&mut blocks).await?;
         slices.push(slice);
     }
 
-    Ok(Some(DataContainer::new(compression_header, slices)))
+    let data_container = DataContainer::new(compression_header, slices);
+
+    Ok((header, data_container))
 }
 
-async fn read_compression_header_from_block<R>(reader: &mut R) -> io::Result<CompressionHeader>
-where
-    R: AsyncRead + Unpin,
-{
-    use super::container::read_block;
+async fn read_compression_header_from_block(
+    blocks: &mut vec::IntoIter<Block>,
+) -> io::Result<CompressionHeader> {
+    let block = blocks.next().ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            "invalid data container: missing compression header block",
+        )
+    })?;
 
-    let block = read_block(reader).await?;
     let data = block.decompressed_data()?;
     let mut data_reader = &data[..];
     read_compression_header(&mut data_reader).await
@@ -9,13 +9,13 @@ where
     match read_itf8(reader).await? {
         0 => Ok(Encoding::Null),
         1 => read_external_encoding(reader).await,
-        2 => unimplemented!("GOLOMB"),
+        2 => read_golomb_encoding(reader).await,
         3 => read_huffman_encoding(reader).await,
         4 => read_byte_array_len_encoding(reader).await,
         5 => read_byte_array_stop_encoding(reader).await,
         6 => read_beta_encoding(reader).await,
         7 => read_subexp_encoding(reader).await,
-        8 => unimplemented!("GOLOMB_RICE"),
+        8 => read_golomb_rice_encoding(reader).await,
         9 => read_gamma_encoding(reader).await,
         _ => Err(io::Error::new(
             io::ErrorKind::InvalidData,
@@ -36,6 +36,19 @@ where
     Ok(Encoding::External(block_content_id))
 }
 
+async fn read_golomb_encoding<R>(reader: &mut R) -> io::Result<Encoding>
+where
+    R: AsyncRead + Unpin,
+{
+    let args = read_args(reader).await?;
+    let mut args_reader = &args[..];
+
+    let offset = read_itf8(&mut args_reader).await?;
+    let m = read_itf8(&mut args_reader).await?;
+
+    Ok(Encoding::Golomb(offset, m))
+}
+
 async fn read_huffman_encoding<R>(reader: &mut R) -> io::Result<Encoding>
 where
     R: AsyncRead + Unpin,
@@ -81,12 +94,12 @@ where
         match read_itf8(reader).await? {
             0 => Ok(Encoding::Null),
             1 => read_external_encoding(reader).await,
-            2 => unimplemented!("GOLOMB"),
+            2 => read_golomb_encoding(reader).await,
             3 => read_huffman_encoding(reader).await,
             5 => read_byte_array_stop_encoding(reader).await,
             6 => read_beta_encoding(reader).await,
             7 => read_subexp_encoding(reader).await,
-            8 => unimplemented!("GOLOMB_RICE"),
+            8 => read_golomb_rice_encoding(reader).await,
             9 => read_gamma_encoding(reader).await,
             _ => Err(io::Error::new(
                 io::ErrorKind::InvalidData,
@@ -148,6 +161,19 @@ where
     Ok(Encoding::Subexp(offset, k))
 }
 
+async fn read_golomb_rice_encoding<R>(reader: &mut R) -> io::Result<Encoding>
+where
+    R: AsyncRead + Unpin,
+{
+    let args = read_args(reader).await?;
+    let mut args_reader = &args[..];
+
+    let offset = read_itf8(&mut args_reader).await?;
+    let log2_m = read_itf8(&mut args_reader).await?;
+
+    Ok(Encoding::GolombRice(offset, log2_m))
+}
+
 async fn read_gamma_encoding<R>(reader: &mut R) -> io::Result<Encoding>
 where
     R: AsyncRead + Unpin,
@@ -204,6 +230,22 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_read_golomb_encoding() -> io::Result<()> {
+        let data = [
+            0x02, // codec ID = GOLOMB
+            0x02, // args.len = 2
+            0x01, // offset = 1
+            0x05, // m = 5
+        ];
+        let mut reader = &data[..];
+
+        let encoding = read_encoding(&mut reader).await?;
+        assert_eq!(encoding, Encoding::Golomb(1, 5));
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_read_huffman_encoding() -> io::Result<()> {
         let data = [
@@ -297,6 +339,22 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_read_golomb_rice_encoding() -> io::Result<()> {
+        let data = [
+            0x08, // codec ID = GOLOMB_RICE
+            0x02, // args.len = 2
+            0x01, // offset = 1
+            0x03, // log2_m = 3
+        ];
+        let mut reader = &data[..];
+
+        let encoding = read_encoding(&mut reader).await?;
+        assert_eq!(encoding, Encoding::GolombRice(1, 3));
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_read_gamma_encoding() -> io::Result<()> {
         let data = [
@@ -2,47 +2,42 @@ mod header;
 
 pub use self::header::read_header;
 
-use tokio::io::{self, AsyncRead};
+use std::vec;
+
+use tokio::io;
 
 use crate::{
     container::Block,
     data_container::{slice, Slice},
-    r#async::reader::container::read_block,
 };
 
-pub async fn read_slice<R>(reader: &mut R) -> io::Result<Slice>
-where
-    R: AsyncRead + Unpin,
-{
-    let header = read_header_from_block(reader).await?;
-    let core_data_block = read_block(reader).await?;
+pub async fn read_slice(blocks: &mut vec::IntoIter<Block>) -> io::Result<Slice> {
+    let header = read_header_from_block(blocks).await?;
+    let core_data_block = next_block(blocks)?;
+
+    let external_block_count = header.block_count().checked_sub(1).ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            "invalid slice: missing core data block",
+        )
+    })?;
 
-    let external_block_count = header.block_count() - 1;
-    let external_blocks = read_external_blocks(reader, external_block_count).await?;
+    let external_blocks = (0..external_block_count)
+        .map(|_| next_block(blocks))
+        .collect::<io::Result<_>>()?;
 
     Ok(Slice::new(header, core_data_block, external_blocks))
 }
 
-async fn read_header_from_block<R>(reader: &mut R) -> io::Result<slice::Header>
-where
-    R: AsyncRead + Unpin,
-{
-    let header_block = read_block(reader).await?;
+async fn read_header_from_block(blocks: &mut vec::IntoIter<Block>) -> io::Result<slice::Header> {
+    let header_block = next_block(blocks)?;
     let data = header_block.decompressed_data()?;
     let mut data_reader = &data[..];
     read_header(&mut data_reader).await
 }
 
-async fn read_external_blocks<R>(reader: &mut R, len: usize) -> io::Result<Vec<Block>>
-where
-    R: AsyncRead + Unpin,
-{
-    let mut external_blocks = Vec::with_capacity(len);
-
-    for _ in 0..len {
-        let block = read_block(reader).await?;
-        external_blocks.push(block);
-    }
-
-    Ok(external_blocks)
+fn next_block(blocks: &mut vec::IntoIter<Block>) -> io::Result<Block> {
+    blocks
+        .next()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "invalid slice: missing block"))
 }
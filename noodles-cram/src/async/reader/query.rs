@@ -0,0 +1,124 @@
+use std::vec;
+
+use futures::{stream, Stream};
+use noodles_bam as bam;
+use noodles_core::region::Interval;
+use noodles_sam as sam;
+use tokio::io::{self, AsyncRead, AsyncSeek, SeekFrom};
+
+use super::Reader;
+use crate::{
+    crai,
+    reader::query::{filter_index_records, intersects, resolve_interval},
+    Record,
+};
+
+struct Context<'a, R>
+where
+    R: AsyncRead + AsyncSeek + Unpin,
+{
+    reader: &'a mut Reader<R>,
+    header: &'a sam::Header,
+
+    containers: vec::IntoIter<(u64, Vec<u64>)>,
+
+    reference_sequence_id: Option<bam::record::ReferenceSequenceId>,
+    start: i32,
+    end: i32,
+
+    records: vec::IntoIter<Record>,
+}
+
+pub fn query<'a, R>(
+    reader: &'a mut Reader<R>,
+    header: &'a sam::Header,
+    index: &crai::Index,
+    reference_sequence_id: Option<bam::record::ReferenceSequenceId>,
+    interval: Interval,
+) -> impl Stream<Item = io::Result<Record>> + 'a
+where
+    R: AsyncRead + AsyncSeek + Unpin,
+{
+    let (start, end) = resolve_interval(interval);
+    let containers = filter_index_records(index, reference_sequence_id, start, end);
+
+    let ctx = Context {
+        reader,
+        header,
+
+        containers: containers.into_iter(),
+
+        reference_sequence_id,
+        start,
+        end,
+
+        records: Vec::new().into_iter(),
+    };
+
+    Box::pin(stream::try_unfold(ctx, |mut ctx| async {
+        loop {
+            if let Some(record) = ctx.records.next() {
+                return Ok(Some((record, ctx)));
+            }
+
+            let (offset, landmarks) = match ctx.containers.next() {
+                Some(c) => c,
+                None => return Ok(None),
+            };
+
+            ctx.records = read_container_records(&mut ctx, offset, &landmarks)
+                .await?
+                .into_iter();
+        }
+    }))
+}
+
+async fn read_container_records<R>(
+    ctx: &mut Context<'_, R>,
+    offset: u64,
+    landmarks: &[u64],
+) -> io::Result<Vec<Record>>
+where
+    R: AsyncRead + AsyncSeek + Unpin,
+{
+    ctx.reader.seek(SeekFrom::Start(offset)).await?;
+
+    let (container_header, data_container) = match ctx
+        .reader
+        .read_data_container_with_container_header()
+        .await?
+    {
+        Some(c) => c,
+        None => {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("unexpected EOF container at offset {}", offset),
+            ))
+        }
+    };
+
+    let compression_header = data_container.compression_header();
+    let mut records = Vec::new();
+
+    for (slice, &landmark) in data_container
+        .slices()
+        .iter()
+        .zip(container_header.landmarks())
+    {
+        if !landmarks.contains(&(landmark as u64)) {
+            continue;
+        }
+
+        let slice_records = ctx
+            .reader
+            .read_slice_records(ctx.header, compression_header, slice)?;
+
+        records.extend(
+            slice_records
+                .into_iter()
+                .filter(|record| intersects(record, ctx.reference_sequence_id, ctx.start, ctx.end)),
+        );
+    }
+
+    Ok(records)
+}
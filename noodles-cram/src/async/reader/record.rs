@@ -15,8 +15,11 @@ use crate::{
     },
     num::Itf8,
     r#async::reader::num::read_itf8,
-    reader::record::ReadRecordError,
-    record::{feature, Builder, Feature, Flags, NextMateFlags, ReadGroupId, Tag},
+    reader::record::{decode_itf8_with_core_data, ReadRecordError},
+    record::{
+        feature, resolve::resolve_quality_scores, Builder, Feature, Flags, NextMateFlags,
+        ReadGroupId, Tag,
+    },
     BitReader, Record,
 };
 
@@ -496,11 +499,12 @@ where
         let feature_count = self.read_number_of_read_features().await?;
 
         let mut prev_position = 0;
+        let mut features = Vec::new();
 
         for _ in 0..feature_count {
             let feature = self.read_feature(prev_position).await?;
             prev_position = feature.position();
-            builder = builder.add_feature(feature);
+            features.push(feature);
         }
 
         if let Some(mapping_quality) = self.read_mapping_quality().await? {
@@ -512,8 +516,13 @@ where
                 let quality_score = self.read_quality_score().await?;
                 builder = builder.add_quality_score(quality_score);
             }
+        } else {
+            let quality_scores = resolve_quality_scores(&features, read_length)?;
+            builder = builder.set_quality_scores(quality_scores);
         }
 
+        builder = builder.set_features(features);
+
         Ok(builder)
     }
 
@@ -918,7 +927,7 @@ where
 
 async fn decode_byte<CDR, EDR>(
     encoding: &Encoding,
-    _core_data_reader: &mut BitReader<CDR>,
+    core_data_reader: &mut BitReader<CDR>,
     external_data_readers: &mut HashMap<Itf8, EDR>,
 ) -> io::Result<u8>
 where
@@ -938,13 +947,13 @@ where
 
             reader.read_u8().await
         }
-        _ => unimplemented!("unsupported u8 encoding: {:?}", encoding),
+        _ => decode_itf8_with_core_data(encoding, core_data_reader).map(|n| n as u8),
     }
 }
 
 async fn decode_itf8<CDR, EDR>(
     encoding: &Encoding,
-    _core_data_reader: &mut BitReader<CDR>,
+    core_data_reader: &mut BitReader<CDR>,
     external_data_readers: &mut HashMap<Itf8, EDR>,
 ) -> io::Result<Itf8>
 where
@@ -964,7 +973,7 @@ where
 
             read_itf8(reader).await
         }
-        _ => unimplemented!("unsupported ITF8 encoding: {:?}", encoding),
+        _ => decode_itf8_with_core_data(encoding, core_data_reader),
     }
 }
 
@@ -998,11 +1007,15 @@ where
                     })?;
 
                 reader.read_exact(&mut buf).await?;
-
-                Ok(buf)
             } else {
-                unimplemented!("unsupported [u8] encoding: {:?}", encoding);
+                // The values of a byte array can be decoded using any byte encoding.
+                for b in &mut buf {
+                    *b = decode_byte(value_encoding, core_data_reader, external_data_readers)
+                        .await?;
+                }
             }
+
+            Ok(buf)
         }
         Encoding::ByteArrayStop(stop_byte, block_content_id) => {
             let reader = external_data_readers
@@ -1022,7 +1035,10 @@ where
 
             Ok(buf)
         }
-        _ => unimplemented!("unsupported [u8] encoding: {:?}", encoding),
+        _ => Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            ReadRecordError::UnsupportedEncoding(encoding.clone()),
+        )),
     }
 }
 
@@ -1048,6 +1064,8 @@ mod tests {
         }
 
         t(&Encoding::External(1), 0x0d).await?;
+        t(&Encoding::Huffman(vec![0x4e], vec![0]), 0x4e).await?;
+        t(&Encoding::Beta(1, 3), 3).await?;
 
         Ok(())
     }
@@ -1070,6 +1088,14 @@ mod tests {
         }
 
         t(&Encoding::External(1), 13).await?;
+        t(&Encoding::Huffman(vec![0x4e], vec![0]), 0x4e).await?;
+        t(&Encoding::Golomb(0, 3), 3).await?;
+        t(&Encoding::GolombRice(0, 1), 2).await?;
+
+        assert!(matches!(
+            t(&Encoding::Null, 0).await,
+            Err(ref e) if e.kind() == io::ErrorKind::InvalidData
+        ));
 
         Ok(())
     }
@@ -1107,6 +1133,15 @@ mod tests {
         )
         .await?;
 
+        let len_encoding = Encoding::External(1);
+        let value_encoding = Encoding::Huffman(vec![0x6e], vec![0]);
+        t(
+            &[0x02],
+            &Encoding::ByteArrayLen(Box::new(len_encoding), Box::new(value_encoding)),
+            b"nn",
+        )
+        .await?;
+
         Ok(())
     }
 }
@@ -0,0 +1,317 @@
+mod builder;
+
+pub use self::builder::Builder;
+
+use noodles_fasta as fasta;
+use noodles_sam as sam;
+use tokio::io::{self, AsyncWrite, AsyncWriteExt};
+
+use crate::{
+    container::Container,
+    file_definition::Version,
+    writer::{container::write_container, ContainerBuilder},
+    FileDefinition, Record, MAGIC_NUMBER,
+};
+
+/// An async CRAM writer.
+///
+/// Unlike [`crate::Writer`], pending containers are not written when the writer is dropped.
+/// [`Self::shutdown`] must be called to finish the output stream.
+///
+/// # Examples
+///
+/// ```
+/// # use std::io;
+/// #
+/// # #[tokio::main]
+/// # async fn main() -> io::Result<()> {
+/// use noodles_cram as cram;
+/// use noodles_sam as sam;
+///
+/// let mut writer = cram::AsyncWriter::new(Vec::new(), Vec::new());
+/// writer.write_file_definition().await?;
+///
+/// let header = sam::Header::builder().add_comment("noodles-cram").build();
+/// writer.write_file_header(&header).await?;
+///
+/// let record = cram::Record::default();
+/// writer.write_record(record).await?;
+///
+/// writer.shutdown().await?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct Writer<W> {
+    inner: W,
+    container_builder: ContainerBuilder,
+}
+
+impl<W> Writer<W>
+where
+    W: AsyncWrite + Unpin,
+{
+    /// Creates an async CRAM writer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_cram as cram;
+    /// let writer = cram::AsyncWriter::new(Vec::new(), Vec::new());
+    /// ```
+    pub fn new(inner: W, reference_sequences: Vec<fasta::Record>) -> Self {
+        Self::builder(inner)
+            .set_reference_sequences(reference_sequences)
+            .build()
+    }
+
+    /// Creates an async CRAM writer builder.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_cram as cram;
+    /// let builder = cram::AsyncWriter::builder(Vec::new());
+    /// let writer = builder.build();
+    /// ```
+    pub fn builder(inner: W) -> Builder<W> {
+        Builder::new(inner)
+    }
+
+    /// Returns a reference to the underlying writer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_cram as cram;
+    /// let writer = cram::AsyncWriter::new(Vec::new(), Vec::new());
+    /// assert!(writer.get_ref().is_empty());
+    /// ```
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Shuts down the output stream.
+    ///
+    /// This writes any pending containers and a final EOF container before shutting down the
+    /// underlying writer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// #
+    /// # #[tokio::main]
+    /// # async fn main() -> io::Result<()> {
+    /// use noodles_cram as cram;
+    /// let mut writer = cram::AsyncWriter::new(Vec::new(), Vec::new());
+    /// writer.shutdown().await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn shutdown(&mut self) -> io::Result<()> {
+        self.flush().await?;
+
+        let eof_container = Container::eof();
+        write_container_async(&mut self.inner, &eof_container).await?;
+
+        self.inner.shutdown().await
+    }
+
+    /// Writes a CRAM file definition.
+    ///
+    /// The file ID is set as a blank value (`[0x00; 20]`). The format version is 3.1 if any CRAM 3.1
+    /// block compression method is used; otherwise, it is 3.0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// #
+    /// # #[tokio::main]
+    /// # async fn main() -> io::Result<()> {
+    /// use noodles_cram as cram;
+    ///
+    /// let mut writer = cram::AsyncWriter::new(Vec::new(), Vec::new());
+    /// writer.write_file_definition().await?;
+    ///
+    /// assert_eq!(writer.get_ref(), &[
+    ///     // magic number (CRAM)
+    ///     0x43, 0x52, 0x41, 0x4d,
+    ///     // format (major, minor)
+    ///     0x03, 0x00,
+    ///     // file ID
+    ///     0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    ///     0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    /// ]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn write_file_definition(&mut self) -> io::Result<()> {
+        let version = self.container_builder.options().version();
+        let file_definition = FileDefinition::new(version, Default::default());
+
+        // magic number
+        self.inner.write_all(MAGIC_NUMBER).await?;
+
+        write_format(&mut self.inner, file_definition.version()).await?;
+
+        self.inner.write_all(file_definition.file_id()).await?;
+
+        Ok(())
+    }
+
+    /// Writes a CRAM file header container.
+    ///
+    /// The position of the stream is expected to be directly after the file definition.
+    ///
+    /// Reference sequence dictionary entries must have MD5 checksums (`M5`) set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// #
+    /// # #[tokio::main]
+    /// # async fn main() -> io::Result<()> {
+    /// use noodles_cram as cram;
+    /// use noodles_sam as sam;
+    ///
+    /// let mut writer = cram::AsyncWriter::new(Vec::new(), Vec::new());
+    /// writer.write_file_definition().await?;
+    ///
+    /// let header = sam::Header::default();
+    /// writer.write_file_header(&header).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn write_file_header(&mut self, header: &sam::Header) -> io::Result<()> {
+        let container = Container::try_from(header)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+
+        write_container_async(&mut self.inner, &container).await
+    }
+
+    /// Writes a CRAM record.
+    ///
+    /// Records are buffered and written as data containers when a container is full or when the
+    /// writer is shut down.
+    ///
+    /// If the reference mode is [`ReferenceMode::None`], the bases of mapped records are required
+    /// and are stored verbatim.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// #
+    /// # #[tokio::main]
+    /// # async fn main() -> io::Result<()> {
+    /// use noodles_cram as cram;
+    /// let mut writer = cram::AsyncWriter::new(Vec::new(), Vec::new());
+    /// let record = cram::Record::default();
+    /// writer.write_record(record).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn write_record(&mut self, record: Record) -> io::Result<()> {
+        match self.container_builder.add_record(record)? {
+            Some(container) => write_container_async(&mut self.inner, &container).await,
+            None => Ok(()),
+        }
+    }
+
+    async fn flush(&mut self) -> io::Result<()> {
+        match self.container_builder.flush()? {
+            Some(container) => write_container_async(&mut self.inner, &container).await,
+            None => Ok(()),
+        }
+    }
+}
+
+// Containers are encoded into a buffer first. A container is written as a whole, and its header
+// requires the lengths of the encoded blocks.
+async fn write_container_async<W>(writer: &mut W, container: &Container) -> io::Result<()>
+where
+    W: AsyncWrite + Unpin,
+{
+    let mut buf = Vec::new();
+    write_container(&mut buf, container)?;
+    writer.write_all(&buf).await
+}
+
+async fn write_format<W>(writer: &mut W, version: Version) -> io::Result<()>
+where
+    W: AsyncWrite + Unpin,
+{
+    let format = [version.major(), version.minor()];
+    writer.write_all(&format).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_shutdown() -> io::Result<()> {
+        let mut writer = Writer::new(Vec::new(), Vec::new());
+        writer.shutdown().await?;
+
+        let mut expected = Vec::new();
+        write_container(&mut expected, &Container::eof())?;
+
+        assert_eq!(writer.get_ref(), &expected);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_write_record() -> Result<(), Box<dyn std::error::Error>> {
+        use crate::AsyncReader;
+
+        let header = sam::Header::builder().add_comment("noodles-cram").build();
+
+        let mut writer = Writer::builder(Vec::new())
+            .set_records_per_slice(2)
+            .set_slices_per_container(1)
+            .build();
+
+        writer.write_file_definition().await?;
+        writer.write_file_header(&header).await?;
+
+        for read_name in [&b"r0"[..], b"r1", b"r2"] {
+            let record = Record::builder()
+                .set_read_name(read_name.to_vec())
+                .set_read_length(4)
+                .set_bases(b"NNNN".to_vec())
+                .build();
+
+            writer.write_record(record).await?;
+        }
+
+        writer.shutdown().await?;
+
+        let mut reader = AsyncReader::new(&writer.get_ref()[..]);
+        reader.read_file_definition().await?;
+
+        let actual: sam::Header = reader.read_file_header().await?.parse()?;
+        assert_eq!(actual, header);
+
+        let mut read_names = Vec::new();
+        let mut container_count = 0;
+
+        while let Some(data_container) = reader.read_data_container().await? {
+            container_count += 1;
+
+            for slice in data_container.slices() {
+                for record in slice.records(data_container.compression_header())? {
+                    read_names.push(record.read_name().to_vec());
+                }
+            }
+        }
+
+        assert_eq!(container_count, 2);
+        assert_eq!(read_names, [b"r0", b"r1", b"r2"]);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,238 @@
+use noodles_fasta as fasta;
+use tokio::io::AsyncWrite;
+
+use super::Writer;
+use crate::{
+    data_container::DataSeries,
+    writer::{BlockCompression, ContainerBuilder, Options, ReferenceMode},
+};
+
+/// An async CRAM writer builder.
+pub struct Builder<W> {
+    inner: W,
+    reference_sequences: Vec<fasta::Record>,
+    options: Options,
+}
+
+impl<W> Builder<W>
+where
+    W: AsyncWrite + Unpin,
+{
+    pub(crate) fn new(inner: W) -> Self {
+        Self {
+            inner,
+            reference_sequences: Vec::new(),
+            options: Options::default(),
+        }
+    }
+
+    /// Sets the reference sequences.
+    ///
+    /// These are used to encode mapped records as differences to the reference and to calculate
+    /// the reference MD5 checksum of each slice. By default, there are no reference sequences.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_cram as cram;
+    /// use noodles_fasta as fasta;
+    ///
+    /// let reference_sequences = vec![fasta::Record::new(
+    ///     fasta::record::Definition::new("sq0", None),
+    ///     fasta::record::Sequence::from(b"ACGT".to_vec()),
+    /// )];
+    ///
+    /// let builder = cram::AsyncWriter::builder(Vec::new()).set_reference_sequences(reference_sequences);
+    /// ```
+    pub fn set_reference_sequences(mut self, reference_sequences: Vec<fasta::Record>) -> Self {
+        self.reference_sequences = reference_sequences;
+        self
+    }
+
+    /// Sets whether read names are written.
+    ///
+    /// This sets the read names included (`RN`) preservation map flag of each container. When
+    /// disabled, read names are only written for records with detached mates, and a reader
+    /// generates names for mate pairs.
+    ///
+    /// By default, read names are written.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_cram as cram;
+    /// let builder = cram::AsyncWriter::builder(Vec::new()).set_preserve_read_names(false);
+    /// ```
+    pub fn set_preserve_read_names(mut self, preserve_read_names: bool) -> Self {
+        self.options.preserve_read_names = preserve_read_names;
+        self
+    }
+
+    /// Sets whether alignment starts are written as deltas.
+    ///
+    /// This sets the `AP` data series delta (`AP`) preservation map flag of each container. When
+    /// enabled, the alignment start of a record is written as the difference to the previous
+    /// record's, which is smaller for coordinate-sorted input. Containers with multi-reference
+    /// slices always write absolute positions.
+    ///
+    /// By default, alignment starts are written as deltas.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_cram as cram;
+    /// let builder = cram::AsyncWriter::builder(Vec::new())
+    ///     .set_encode_alignment_start_positions_as_deltas(false);
+    /// ```
+    pub fn set_encode_alignment_start_positions_as_deltas(
+        mut self,
+        encode_alignment_start_positions_as_deltas: bool,
+    ) -> Self {
+        self.options.encode_alignment_start_positions_as_deltas =
+            encode_alignment_start_positions_as_deltas;
+        self
+    }
+
+    /// Sets the reference mode.
+    ///
+    /// The reference required (`RR`) preservation map flag is only set with
+    /// [`ReferenceMode::External`]. By default, the reference mode is [`ReferenceMode::External`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_cram::{self as cram, writer::ReferenceMode};
+    /// let builder = cram::AsyncWriter::builder(Vec::new()).set_reference_mode(ReferenceMode::None);
+    /// ```
+    pub fn set_reference_mode(mut self, reference_mode: ReferenceMode) -> Self {
+        self.options.reference_mode = reference_mode;
+        self
+    }
+
+    /// Sets the maximum number of records in a slice.
+    ///
+    /// By default, a slice holds up to 2560 records. A value of 0 is treated as 1.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_cram as cram;
+    /// let builder = cram::AsyncWriter::builder(Vec::new()).set_records_per_slice(10000);
+    /// ```
+    pub fn set_records_per_slice(mut self, records_per_slice: usize) -> Self {
+        self.options.records_per_slice = records_per_slice;
+        self
+    }
+
+    /// Sets the maximum number of slices in a container.
+    ///
+    /// By default, a container holds up to 4 slices. A value of 0 is treated as 1.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_cram as cram;
+    /// let builder = cram::AsyncWriter::builder(Vec::new()).set_slices_per_container(1);
+    /// ```
+    pub fn set_slices_per_container(mut self, slices_per_container: usize) -> Self {
+        self.options.slices_per_container = slices_per_container;
+        self
+    }
+
+    /// Sets whether slices can hold records from multiple reference sequences.
+    ///
+    /// When enabled, records with different reference sequences, including unmapped records, are
+    /// packed into the same slice. This avoids writing many small slices and containers for data
+    /// that is not coordinate-sorted or has many short reference sequences. Multi-reference slices
+    /// are not used with [`ReferenceMode::Embedded`].
+    ///
+    /// By default, each slice holds records from a single reference sequence.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_cram as cram;
+    /// let builder = cram::AsyncWriter::builder(Vec::new()).set_multi_reference_slices(true);
+    /// ```
+    pub fn set_multi_reference_slices(mut self, multi_reference_slices: bool) -> Self {
+        self.options.multi_reference_slices = multi_reference_slices;
+        self
+    }
+
+    /// Sets the default block compression.
+    ///
+    /// This is used for the core data block and for any external block that does not have a
+    /// data series or tag block compression set. By default, blocks are compressed using gzip at
+    /// level 6.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_cram::{self as cram, writer::BlockCompression};
+    /// let builder = cram::AsyncWriter::builder(Vec::new())
+    ///     .set_block_compression(BlockCompression::Bzip2(9));
+    /// ```
+    pub fn set_block_compression(mut self, block_compression: BlockCompression) -> Self {
+        self.options.block_compression = block_compression;
+        self
+    }
+
+    /// Sets the block compression of the external block of a data series.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_cram::{self as cram, data_container::DataSeries, writer::BlockCompression};
+    /// let builder = cram::AsyncWriter::builder(Vec::new())
+    ///     .set_data_series_block_compression(DataSeries::QualityScores, BlockCompression::RansOrder1);
+    /// ```
+    pub fn set_data_series_block_compression(
+        mut self,
+        data_series: DataSeries,
+        block_compression: BlockCompression,
+    ) -> Self {
+        self.options
+            .data_series_block_compressions
+            .insert(data_series, block_compression);
+
+        self
+    }
+
+    /// Sets the block compression of the external blocks of a tag.
+    ///
+    /// This applies to the tag with any value type.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_cram::{self as cram, writer::BlockCompression};
+    /// let builder = cram::AsyncWriter::builder(Vec::new())
+    ///     .set_tag_block_compression([b'N', b'M'], BlockCompression::Lzma(9));
+    /// ```
+    pub fn set_tag_block_compression(
+        mut self,
+        tag: [u8; 2],
+        block_compression: BlockCompression,
+    ) -> Self {
+        self.options
+            .tag_block_compressions
+            .insert(tag, block_compression);
+
+        self
+    }
+
+    /// Builds an async CRAM writer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_cram as cram;
+    /// let writer = cram::AsyncWriter::builder(Vec::new()).build();
+    /// ```
+    pub fn build(self) -> Writer<W> {
+        Writer {
+            inner: self.inner,
+            container_builder: ContainerBuilder::new(self.reference_sequences, self.options),
+        }
+    }
+}
@@ -0,0 +1,442 @@
+//! Bit-level integer codecs used by core data block encodings.
+//!
+//! Values are offset before being coded, i.e., the coded value is `value + offset`.
+
+use std::io::{self, Read, Write};
+
+use crate::{num::Itf8, BitReader, BitWriter};
+
+/// Decodes a value using the beta (binary) encoding.
+pub fn decode_beta<R>(reader: &mut BitReader<R>, offset: Itf8, len: u32) -> io::Result<Itf8>
+where
+    R: Read,
+{
+    reader
+        .read_u32(len)
+        .map(|n| (n as Itf8).wrapping_sub(offset))
+}
+
+/// Decodes a value using the Elias gamma encoding.
+pub fn decode_gamma<R>(reader: &mut BitReader<R>, offset: Itf8) -> io::Result<Itf8>
+where
+    R: Read,
+{
+    let mut n = 0;
+
+    while reader.read_bit()? == 0 {
+        n += 1;
+
+        if n >= 32 {
+            return Err(invalid_code_len());
+        }
+    }
+
+    let m = reader.read_u32(n)?;
+    let x = (1 << n) | m;
+
+    Ok((x as Itf8).wrapping_sub(offset))
+}
+
+/// Decodes a value using the subexponential encoding.
+pub fn decode_subexp<R>(reader: &mut BitReader<R>, offset: Itf8, k: Itf8) -> io::Result<Itf8>
+where
+    R: Read,
+{
+    let k = subexp_k(k)?;
+    let u = read_unary(reader)?;
+
+    let x = if u == 0 {
+        reader.read_u32(k)?
+    } else {
+        let b = u.saturating_add(k - 1);
+
+        if b >= 32 {
+            return Err(invalid_code_len());
+        }
+
+        (1 << b) | reader.read_u32(b)?
+    };
+
+    Ok((x as Itf8).wrapping_sub(offset))
+}
+
+/// Decodes a value using the Golomb encoding.
+pub fn decode_golomb<R>(reader: &mut BitReader<R>, offset: Itf8, m: Itf8) -> io::Result<Itf8>
+where
+    R: Read,
+{
+    let m = golomb_m(m)?;
+    let q = read_unary(reader)?;
+
+    let r = if m == 1 {
+        0
+    } else {
+        let b = ceil_log2(m);
+        let cutoff = (1 << b) - m;
+
+        let r = reader.read_u32(b - 1)?;
+
+        if r < cutoff {
+            r
+        } else {
+            ((r << 1) | u32::from(reader.read_bit()?)) - cutoff
+        }
+    };
+
+    let x = q
+        .checked_mul(m)
+        .and_then(|n| n.checked_add(r))
+        .ok_or_else(invalid_code_len)?;
+
+    Ok((x as Itf8).wrapping_sub(offset))
+}
+
+/// Decodes a value using the Golomb-Rice encoding.
+pub fn decode_golomb_rice<R>(
+    reader: &mut BitReader<R>,
+    offset: Itf8,
+    log2_m: Itf8,
+) -> io::Result<Itf8>
+where
+    R: Read,
+{
+    let log2_m = golomb_rice_log2_m(log2_m)?;
+    let q = read_unary(reader)?;
+    let r = reader.read_u32(log2_m)?;
+
+    let x = q
+        .checked_mul(1 << log2_m)
+        .map(|n| n | r)
+        .ok_or_else(invalid_code_len)?;
+
+    Ok((x as Itf8).wrapping_sub(offset))
+}
+
+/// Encodes a value using the beta (binary) encoding.
+pub fn encode_beta<W>(
+    writer: &mut BitWriter<W>,
+    offset: Itf8,
+    len: u32,
+    value: Itf8,
+) -> io::Result<()>
+where
+    W: Write,
+{
+    let x = offset_value(offset, value)?;
+
+    if len < 32 && x >> len != 0 {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("value does not fit in {} bits: {}", len, value),
+        ));
+    }
+
+    writer.write_u32(x, len as usize)
+}
+
+/// Encodes a value using the Elias gamma encoding.
+pub fn encode_gamma<W>(writer: &mut BitWriter<W>, offset: Itf8, value: Itf8) -> io::Result<()>
+where
+    W: Write,
+{
+    let x = offset_value(offset, value)?;
+
+    if x == 0 {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "gamma coded values must be > 0",
+        ));
+    }
+
+    let n = log2(x);
+
+    for _ in 0..n {
+        writer.write_bit(false)?;
+    }
+
+    writer.write_bit(true)?;
+    writer.write_u32(x, n as usize)
+}
+
+/// Encodes a value using the subexponential encoding.
+pub fn encode_subexp<W>(
+    writer: &mut BitWriter<W>,
+    offset: Itf8,
+    k: Itf8,
+    value: Itf8,
+) -> io::Result<()>
+where
+    W: Write,
+{
+    let k = subexp_k(k)?;
+    let x = offset_value(offset, value)?;
+
+    if x < (1 << k) {
+        write_unary(writer, 0)?;
+        writer.write_u32(x, k as usize)
+    } else {
+        let b = log2(x);
+        write_unary(writer, b - k + 1)?;
+        writer.write_u32(x, b as usize)
+    }
+}
+
+/// Encodes a value using the Golomb encoding.
+pub fn encode_golomb<W>(
+    writer: &mut BitWriter<W>,
+    offset: Itf8,
+    m: Itf8,
+    value: Itf8,
+) -> io::Result<()>
+where
+    W: Write,
+{
+    let m = golomb_m(m)?;
+    let x = offset_value(offset, value)?;
+
+    let q = x / m;
+    let r = x % m;
+
+    write_unary(writer, q)?;
+
+    if m > 1 {
+        let b = ceil_log2(m);
+        let cutoff = (1 << b) - m;
+
+        if r < cutoff {
+            writer.write_u32(r, (b - 1) as usize)?;
+        } else {
+            writer.write_u32(r + cutoff, b as usize)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Encodes a value using the Golomb-Rice encoding.
+pub fn encode_golomb_rice<W>(
+    writer: &mut BitWriter<W>,
+    offset: Itf8,
+    log2_m: Itf8,
+    value: Itf8,
+) -> io::Result<()>
+where
+    W: Write,
+{
+    let log2_m = golomb_rice_log2_m(log2_m)?;
+    let x = offset_value(offset, value)?;
+
+    write_unary(writer, x >> log2_m)?;
+    writer.write_u32(x & ((1 << log2_m) - 1), log2_m as usize)
+}
+
+// Reads the number of 1 bits before a 0 bit.
+fn read_unary<R>(reader: &mut BitReader<R>) -> io::Result<u32>
+where
+    R: Read,
+{
+    let mut n: u32 = 0;
+
+    while reader.read_bit()? == 1 {
+        n = n.checked_add(1).ok_or_else(invalid_code_len)?;
+    }
+
+    Ok(n)
+}
+
+fn write_unary<W>(writer: &mut BitWriter<W>, n: u32) -> io::Result<()>
+where
+    W: Write,
+{
+    for _ in 0..n {
+        writer.write_bit(true)?;
+    }
+
+    writer.write_bit(false)
+}
+
+fn invalid_code_len() -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, "invalid code length")
+}
+
+fn offset_value(offset: Itf8, value: Itf8) -> io::Result<u32> {
+    value
+        .checked_add(offset)
+        .and_then(|n| u32::try_from(n).ok())
+        .ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("invalid value: {} (offset = {})", value, offset),
+            )
+        })
+}
+
+fn subexp_k(k: Itf8) -> io::Result<u32> {
+    u32::try_from(k).ok().filter(|&n| n < 32).ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("invalid subexponential parameter: {}", k),
+        )
+    })
+}
+
+fn golomb_m(m: Itf8) -> io::Result<u32> {
+    u32::try_from(m).ok().filter(|&n| n > 0).ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("invalid Golomb parameter: {}", m),
+        )
+    })
+}
+
+fn golomb_rice_log2_m(log2_m: Itf8) -> io::Result<u32> {
+    u32::try_from(log2_m)
+        .ok()
+        .filter(|&n| n < 32)
+        .ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("invalid Golomb-Rice parameter: {}", log2_m),
+            )
+        })
+}
+
+// floor(log2(n)) for n > 0.
+fn log2(n: u32) -> u32 {
+    31 - n.leading_zeros()
+}
+
+// ceil(log2(n)) for n > 0.
+fn ceil_log2(n: u32) -> u32 {
+    32 - (n - 1).leading_zeros()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn encode<F>(f: F) -> io::Result<Vec<u8>>
+    where
+        F: FnOnce(&mut BitWriter<Vec<u8>>) -> io::Result<()>,
+    {
+        let mut writer = BitWriter::new(Vec::new());
+        f(&mut writer)?;
+        writer.finish()
+    }
+
+    #[test]
+    fn test_decode_gamma() -> io::Result<()> {
+        // 1 = 1, 2 = 010, 5 = 00101
+        let data = [0b10100010, 0b10000000];
+        let mut reader = BitReader::new(&data[..]);
+
+        assert_eq!(decode_gamma(&mut reader, 0)?, 1);
+        assert_eq!(decode_gamma(&mut reader, 0)?, 2);
+        assert_eq!(decode_gamma(&mut reader, 1)?, 4);
+
+        let data = [0x00; 8];
+        let mut reader = BitReader::new(&data[..]);
+        assert!(decode_gamma(&mut reader, 0).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_encode_gamma() -> io::Result<()> {
+        let actual = encode(|writer| {
+            encode_gamma(writer, 0, 1)?;
+            encode_gamma(writer, 0, 2)?;
+            encode_gamma(writer, 1, 4)
+        })?;
+
+        assert_eq!(actual, [0b10100010, 0b10000000]);
+
+        assert!(encode(|writer| encode_gamma(writer, 0, 0)).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_decode_golomb() -> io::Result<()> {
+        // m = 5: b = 3, cutoff = 3
+        // 7 = q 1, r 2 => 10 10
+        // 14 = q 2, r 4 => 110 111
+        let data = [0b10101101, 0b11000000];
+        let mut reader = BitReader::new(&data[..]);
+
+        assert_eq!(decode_golomb(&mut reader, 0, 5)?, 7);
+        assert_eq!(decode_golomb(&mut reader, 0, 5)?, 14);
+
+        let mut reader = BitReader::new(&data[..]);
+        assert!(decode_golomb(&mut reader, 0, 0).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_decode_golomb_rice() -> io::Result<()> {
+        // log2_m = 2
+        // 6 = q 1, r 2 => 10 10
+        // 3 = q 0, r 3 => 0 11
+        let data = [0b10100110];
+        let mut reader = BitReader::new(&data[..]);
+
+        assert_eq!(decode_golomb_rice(&mut reader, 0, 2)?, 6);
+        assert_eq!(decode_golomb_rice(&mut reader, 0, 2)?, 3);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_decode_subexp() -> io::Result<()> {
+        // k = 2
+        // 3 = u 0 => 0 11
+        // 9 = b 3, u 2 => 110 001
+        let data = [0b01111000, 0b10000000];
+        let mut reader = BitReader::new(&data[..]);
+
+        assert_eq!(decode_subexp(&mut reader, 0, 2)?, 3);
+        assert_eq!(decode_subexp(&mut reader, 0, 2)?, 9);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_self() -> io::Result<()> {
+        let values = [0, 1, 2, 3, 4, 5, 7, 8, 13, 31, 32, 100, 1000, 65535];
+
+        for &offset in &[0, 1] {
+            let data = encode(|writer| {
+                for &value in &values {
+                    encode_beta(writer, offset, 17, value)?;
+                    encode_gamma(writer, offset + 1, value)?;
+                    encode_subexp(writer, offset, 3, value)?;
+                    encode_golomb(writer, offset, 200, value >> 4)?;
+                    encode_golomb(writer, offset, 1, value & 0x0f)?;
+                    encode_golomb_rice(writer, offset, 12, value)?;
+                }
+
+                Ok(())
+            })?;
+
+            let mut reader = BitReader::new(&data[..]);
+
+            for &value in &values {
+                assert_eq!(decode_beta(&mut reader, offset, 17)?, value);
+                assert_eq!(decode_gamma(&mut reader, offset + 1)?, value);
+                assert_eq!(decode_subexp(&mut reader, offset, 3)?, value);
+                assert_eq!(decode_golomb(&mut reader, offset, 200)?, value >> 4);
+                assert_eq!(decode_golomb(&mut reader, offset, 1)?, value & 0x0f);
+                assert_eq!(decode_golomb_rice(&mut reader, offset, 12)?, value);
+            }
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_encode_beta_with_value_out_of_range() {
+        assert!(encode(|writer| encode_beta(writer, 0, 3, 8)).is_err());
+        assert!(encode(|writer| encode_beta(writer, 0, 3, -1)).is_err());
+    }
+}
@@ -34,7 +34,7 @@ where
         Ok(value)
     }
 
-    fn read_bit(&mut self) -> io::Result<u8> {
+    pub fn read_bit(&mut self) -> io::Result<u8> {
         if self.i >= 8 {
             self.buf = self.inner.read_u8()?;
             self.i = 0;
@@ -51,7 +51,7 @@ where
         Ok(())
     }
 
-    fn write_bit(&mut self, is_set: bool) -> io::Result<()> {
+    pub fn write_bit(&mut self, is_set: bool) -> io::Result<()> {
         if is_set {
             self.buf |= 0x01 << (8 - self.i - 1);
         }
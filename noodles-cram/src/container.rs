@@ -37,9 +37,13 @@ impl Container {
             .set_data(buf)
             .build();
 
-        let mut blocks = vec![block];
+        // Landmarks are the positions of the slices relative to the end of the container header,
+        // i.e., the first slice starts after the compression header block.
+        let mut landmark = block.len() as Itf8;
         let mut landmarks = Vec::new();
 
+        let mut blocks = vec![block];
+
         let mut container_reference_sequence_id: Option<ReferenceSequenceId> = None;
 
         let mut container_alignment_start = i32::MAX;
@@ -65,15 +69,14 @@ impl Container {
                 container_reference_sequence_id = Some(slice.header().reference_sequence_id());
             }
 
-            let slice_alignment_start = slice_header
-                .alignment_start()
-                .map(i32::from)
-                .unwrap_or_default();
+            // Unmapped slices have no alignment start.
+            if let Some(slice_alignment_start) = slice_header.alignment_start().map(i32::from) {
+                container_alignment_start =
+                    cmp::min(container_alignment_start, slice_alignment_start);
 
-            container_alignment_start = cmp::min(container_alignment_start, slice_alignment_start);
-
-            let slice_alignment_end = slice_alignment_start + slice_header.alignment_span() - 1;
-            container_alignment_end = cmp::max(container_alignment_end, slice_alignment_end);
+                let slice_alignment_end = slice_alignment_start + slice_header.alignment_span() - 1;
+                container_alignment_end = cmp::max(container_alignment_end, slice_alignment_end);
+            }
 
             container_record_count += slice_header.record_count() as Itf8;
 
@@ -99,24 +102,31 @@ impl Container {
                 slice_len += external_block.len() as Itf8;
             }
 
-            let last_landmark = landmarks.last().unwrap_or(&0);
-            let landmark = last_landmark + slice_len;
             landmarks.push(landmark);
+            landmark += slice_len;
         }
 
         let len = blocks.iter().map(|b| b.len() as i32).sum();
 
-        let container_alignment_span = container_alignment_end - container_alignment_start + 1;
-        let container_alignment_start = sam::record::Position::try_from(container_alignment_start)
-            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        let container_reference_sequence_id =
+            container_reference_sequence_id.expect("no slices in builder");
+
+        let mut header_builder = Header::builder();
 
-        let header = Header::builder()
+        if container_reference_sequence_id.is_some() && container_alignment_start != i32::MAX {
+            let container_alignment_span = container_alignment_end - container_alignment_start + 1;
+            let container_alignment_start =
+                sam::record::Position::try_from(container_alignment_start)
+                    .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+            header_builder = header_builder
+                .set_start_position(container_alignment_start)
+                .set_alignment_span(container_alignment_span);
+        }
+
+        let header = header_builder
             .set_length(len)
-            .set_reference_sequence_id(
-                container_reference_sequence_id.expect("no slices in builder"),
-            )
-            .set_start_position(container_alignment_start)
-            .set_alignment_span(container_alignment_span)
+            .set_reference_sequence_id(container_reference_sequence_id)
             .set_record_count(container_record_count)
             .set_record_counter(container_record_counter)
             .set_base_count(base_count)
@@ -138,6 +148,10 @@ impl Container {
     pub fn blocks(&self) -> &[Block] {
         &self.blocks
     }
+
+    pub fn into_parts(self) -> (Header, Vec<Block>) {
+        (self.header, self.blocks)
+    }
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
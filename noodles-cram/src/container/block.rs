@@ -17,8 +17,11 @@ use flate2::read::GzDecoder;
 use xz2::read::XzDecoder;
 
 use crate::{
+    aac, fqzcomp,
     num::{itf8, Itf8},
     rans::rans_decode,
+    rans_nx16::rans_decode_nx16,
+    tokenizer,
 };
 
 // § 9 End of file container (2020-06-22)
@@ -71,6 +74,10 @@ impl Block {
         &self.data
     }
 
+    pub fn crc32(&self) -> u32 {
+        self.crc32
+    }
+
     pub fn decompressed_data(&self) -> io::Result<Cow<'_, [u8]>> {
         match self.compression_method {
             CompressionMethod::None => Ok(Cow::from(self.data())),
@@ -96,6 +103,22 @@ impl Block {
                 let mut buf = self.data();
                 rans_decode(&mut buf).map(Cow::from)
             }
+            CompressionMethod::RansNx16 => {
+                let mut buf = self.data();
+                rans_decode_nx16(&mut buf, self.uncompressed_len).map(Cow::from)
+            }
+            CompressionMethod::AdaptiveArithmeticCoding => {
+                let mut buf = self.data();
+                aac::decode(&mut buf, self.uncompressed_len).map(Cow::from)
+            }
+            CompressionMethod::Fqzcomp => {
+                let mut buf = self.data();
+                fqzcomp::decode(&mut buf).map(Cow::from)
+            }
+            CompressionMethod::NameTokenizer => {
+                let mut buf = self.data();
+                tokenizer::decode(&mut buf).map(Cow::from)
+            }
         }
     }
 
@@ -116,6 +139,7 @@ impl Block {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::writer::BlockCompression;
 
     #[test]
     fn test_len() {
@@ -129,4 +153,50 @@ mod tests {
 
         assert_eq!(block.len(), 16);
     }
+
+    #[test]
+    fn test_decompressed_data_with_rans_nx16() -> io::Result<()> {
+        let data = b"noodles".to_vec();
+
+        let block = Block::builder()
+            .set_content_type(ContentType::ExternalData)
+            .compress_and_set_data(data.clone(), BlockCompression::RansNx16)?
+            .build();
+
+        assert_eq!(block.compression_method(), CompressionMethod::RansNx16);
+        assert_eq!(&block.decompressed_data()?[..], &data[..]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_decompressed_data_with_cram_3_1_codecs() -> io::Result<()> {
+        for (block_compression, compression_method, data) in [
+            (
+                BlockCompression::AdaptiveArithmeticCoding,
+                CompressionMethod::AdaptiveArithmeticCoding,
+                b"noodles".to_vec(),
+            ),
+            (
+                BlockCompression::Fqzcomp,
+                CompressionMethod::Fqzcomp,
+                vec![45, 35, 43, 50],
+            ),
+            (
+                BlockCompression::NameTokenizer,
+                CompressionMethod::NameTokenizer,
+                b"r0\0r1\0".to_vec(),
+            ),
+        ] {
+            let block = Block::builder()
+                .set_content_type(ContentType::ExternalData)
+                .compress_and_set_data(data.clone(), block_compression)?
+                .build();
+
+            assert_eq!(block.compression_method(), compression_method);
+            assert_eq!(&block.decompressed_data()?[..], &data[..]);
+        }
+
+        Ok(())
+    }
 }
@@ -1,15 +1,9 @@
-use std::io::{self, Write};
+use std::io;
 
-use crate::num::Itf8;
+use crate::{num::Itf8, writer::BlockCompression};
 
 use super::{Block, CompressionMethod, ContentType};
 
-use bzip2::write::BzEncoder;
-use flate2::write::GzEncoder;
-use xz2::write::XzEncoder;
-
-const DEFAULT_LZMA_COMPRESSION_LEVEL: u32 = 6;
-
 #[derive(Debug, Default)]
 pub struct Builder {
     compression_method: CompressionMethod,
@@ -51,41 +45,20 @@ impl Builder {
         self
     }
 
-    /// Compresses the given data using the given compression method.
+    /// Compresses the given data using the given block compression.
     ///
     /// This sets the compression method, the uncompressed size to the length of the given data,
     /// and the data to the compressed output of the given data.
     pub fn compress_and_set_data(
         mut self,
         data: Vec<u8>,
-        compression_method: CompressionMethod,
+        block_compression: BlockCompression,
     ) -> io::Result<Self> {
-        self.compression_method = compression_method;
-        self.uncompressed_len = data.len();
-
-        self.data = match compression_method {
-            CompressionMethod::None => data,
-            CompressionMethod::Gzip => {
-                let mut encoder = GzEncoder::new(Vec::new(), flate2::Compression::default());
-                encoder.write_all(&data)?;
-                encoder.finish()?
-            }
-            CompressionMethod::Bzip2 => {
-                let mut encoder = BzEncoder::new(Vec::new(), bzip2::Compression::default());
-                encoder.write_all(&data)?;
-                encoder.finish()?
-            }
-            CompressionMethod::Lzma => {
-                let mut encoder = XzEncoder::new(Vec::new(), DEFAULT_LZMA_COMPRESSION_LEVEL);
-                encoder.write_all(&data)?;
-                encoder.finish()?
-            }
-            _ => unimplemented!(
-                "compress_and_set_data: unhandled compression method: {:?}",
-                compression_method
-            ),
-        };
+        let block_compression = block_compression.resolve(&data);
 
+        self.compression_method = block_compression.compression_method();
+        self.uncompressed_len = data.len();
+        self.data = block_compression.compress(&data)?;
         Ok(self)
     }
 
@@ -18,6 +18,14 @@ pub enum CompressionMethod {
     Lzma,
     /// Ranged asymmetric numeral systems (rANS).
     Rans,
+    /// rANS Nx16 (CRAM 3.1).
+    RansNx16,
+    /// Adaptive arithmetic coding (CRAM 3.1).
+    AdaptiveArithmeticCoding,
+    /// fqzcomp quality score codec (CRAM 3.1).
+    Fqzcomp,
+    /// Read name tokenizer (tok3) (CRAM 3.1).
+    NameTokenizer,
 }
 
 impl Default for CompressionMethod {
@@ -35,7 +43,7 @@ impl fmt::Display for TryFromByteError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
             f,
-            "invalid compression method: expected 0..=4, got {}",
+            "invalid compression method: expected 0..=8, got {}",
             self.0
         )
     }
@@ -51,6 +59,10 @@ impl TryFrom<u8> for CompressionMethod {
             2 => Ok(Self::Bzip2),
             3 => Ok(Self::Lzma),
             4 => Ok(Self::Rans),
+            5 => Ok(Self::RansNx16),
+            6 => Ok(Self::AdaptiveArithmeticCoding),
+            7 => Ok(Self::Fqzcomp),
+            8 => Ok(Self::NameTokenizer),
             _ => Err(TryFromByteError(b)),
         }
     }
@@ -64,6 +76,10 @@ impl From<CompressionMethod> for u8 {
             CompressionMethod::Bzip2 => 2,
             CompressionMethod::Lzma => 3,
             CompressionMethod::Rans => 4,
+            CompressionMethod::RansNx16 => 5,
+            CompressionMethod::AdaptiveArithmeticCoding => 6,
+            CompressionMethod::Fqzcomp => 7,
+            CompressionMethod::NameTokenizer => 8,
         }
     }
 }
@@ -84,7 +100,23 @@ mod tests {
         assert_eq!(CompressionMethod::try_from(2), Ok(CompressionMethod::Bzip2));
         assert_eq!(CompressionMethod::try_from(3), Ok(CompressionMethod::Lzma));
         assert_eq!(CompressionMethod::try_from(4), Ok(CompressionMethod::Rans));
-        assert_eq!(CompressionMethod::try_from(5), Err(TryFromByteError(5)));
+        assert_eq!(
+            CompressionMethod::try_from(5),
+            Ok(CompressionMethod::RansNx16)
+        );
+        assert_eq!(
+            CompressionMethod::try_from(6),
+            Ok(CompressionMethod::AdaptiveArithmeticCoding)
+        );
+        assert_eq!(
+            CompressionMethod::try_from(7),
+            Ok(CompressionMethod::Fqzcomp)
+        );
+        assert_eq!(
+            CompressionMethod::try_from(8),
+            Ok(CompressionMethod::NameTokenizer)
+        );
+        assert_eq!(CompressionMethod::try_from(9), Err(TryFromByteError(9)));
     }
 
     #[test]
@@ -94,5 +126,9 @@ mod tests {
         assert_eq!(u8::from(CompressionMethod::Bzip2), 2);
         assert_eq!(u8::from(CompressionMethod::Lzma), 3);
         assert_eq!(u8::from(CompressionMethod::Rans), 4);
+        assert_eq!(u8::from(CompressionMethod::RansNx16), 5);
+        assert_eq!(u8::from(CompressionMethod::AdaptiveArithmeticCoding), 6);
+        assert_eq!(u8::from(CompressionMethod::Fqzcomp), 7);
+        assert_eq!(u8::from(CompressionMethod::NameTokenizer), 8);
     }
 }
@@ -6,6 +6,7 @@ const MIN: Itf8 = 0;
 const UNMAPPED: Itf8 = -1;
 const MULTIPLE_REFERENCE_SEQUENCES: Itf8 = -2;
 
+/// A CRAM container or slice reference sequence ID.
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum ReferenceSequenceId {
     /// A reference sequence ID.
@@ -17,14 +18,17 @@ pub enum ReferenceSequenceId {
 }
 
 impl ReferenceSequenceId {
+    /// Returns whether the reference sequence ID is a single reference sequence ID.
     pub fn is_some(self) -> bool {
         matches!(self, Self::Some(_))
     }
 
+    /// Returns whether the reference sequence ID is unmapped.
     pub fn is_none(self) -> bool {
         matches!(self, Self::None)
     }
 
+    /// Returns whether the reference sequence ID refers to multiple reference sequences.
     pub fn is_many(self) -> bool {
         matches!(self, Self::Many)
     }
@@ -3,9 +3,10 @@ use std::{io, mem};
 use noodles_fasta as fasta;
 
 use super::{compression_header, slice, CompressionHeader, DataContainer, Slice};
-use crate::Record;
-
-const MAX_SLICE_COUNT: usize = 4;
+use crate::{
+    writer::{Options, ReferenceMode},
+    Record,
+};
 
 #[derive(Debug)]
 pub struct Builder {
@@ -43,14 +44,15 @@ impl Builder {
 
     pub fn add_record(
         &mut self,
+        options: &Options,
         reference_sequence: &[u8],
         record: Record,
     ) -> Result<(), AddRecordError> {
-        if self.slice_builders.len() >= MAX_SLICE_COUNT {
+        if self.slice_builders.len() >= options.slices_per_container.max(1) {
             return Err(AddRecordError::ContainerFull(record));
         }
 
-        match self.slice_builder.add_record(record) {
+        match self.slice_builder.add_record(options, record) {
             Ok(r) => {
                 self.compression_header_builder
                     .update(reference_sequence, r);
@@ -72,18 +74,47 @@ impl Builder {
         }
     }
 
-    pub fn build(mut self, reference_sequences: &[fasta::Record]) -> io::Result<DataContainer> {
+    pub fn build(
+        mut self,
+        options: &Options,
+        reference_sequences: &[fasta::Record],
+    ) -> io::Result<DataContainer> {
         if !self.slice_builder.is_empty() {
             self.slice_builders.push(self.slice_builder);
         }
 
+        self.compression_header_builder
+            .set_read_names_included(options.preserve_read_names);
+
+        let reference_required = options.reference_mode == ReferenceMode::External;
+        self.compression_header_builder
+            .set_reference_required(reference_required);
+
+        // Alignment starts in multi-reference slices are not necessarily in order, and unmapped
+        // records have none, so they are written as absolute positions.
+        let ap_data_series_delta = options.encode_alignment_start_positions_as_deltas
+            && !self
+                .slice_builders
+                .iter()
+                .any(|builder| builder.is_multi_reference());
+
+        self.compression_header_builder
+            .set_ap_data_series_delta(ap_data_series_delta);
+
         let compression_header = self.compression_header_builder.build();
 
         let record_counter = self.record_counter;
         let slices = self
             .slice_builders
             .into_iter()
-            .map(|builder| builder.build(reference_sequences, &compression_header, record_counter))
+            .map(|builder| {
+                builder.build(
+                    options,
+                    reference_sequences,
+                    &compression_header,
+                    record_counter,
+                )
+            })
             .collect::<Result<_, _>>()?;
 
         Ok(DataContainer {
@@ -12,6 +12,21 @@ pub struct Builder {
 }
 
 impl Builder {
+    pub fn set_read_names_included(&mut self, read_names_included: bool) {
+        self.preservation_map_builder
+            .set_read_names_included(read_names_included);
+    }
+
+    pub fn set_ap_data_series_delta(&mut self, ap_data_series_delta: bool) {
+        self.preservation_map_builder
+            .set_ap_data_series_delta(ap_data_series_delta);
+    }
+
+    pub fn set_reference_required(&mut self, reference_required: bool) {
+        self.preservation_map_builder
+            .set_reference_required(reference_required);
+    }
+
     pub fn update(&mut self, reference_sequence: &[u8], record: &Record) {
         self.preservation_map_builder
             .update(reference_sequence, record);
@@ -1,5 +1,6 @@
 use std::{error, fmt};
 
+/// A CRAM data series.
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
 pub enum DataSeries {
     /// BAM bit flags (`BF`).
@@ -25,3 +25,20 @@ pub enum Encoding {
     // offset
     Gamma(i32),
 }
+
+impl Encoding {
+    /// Returns the external block content IDs the encoding reads from or writes to.
+    pub fn block_content_ids(&self) -> Vec<i32> {
+        match self {
+            Self::External(block_content_id) | Self::ByteArrayStop(_, block_content_id) => {
+                vec![*block_content_id]
+            }
+            Self::ByteArrayLen(len_encoding, value_encoding) => {
+                let mut block_content_ids = len_encoding.block_content_ids();
+                block_content_ids.extend(value_encoding.block_content_ids());
+                block_content_ids
+            }
+            _ => Vec::new(),
+        }
+    }
+}
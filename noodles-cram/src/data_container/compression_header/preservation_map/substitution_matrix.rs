@@ -2,7 +2,10 @@ mod base;
 mod builder;
 mod histogram;
 
-pub use self::{base::Base, builder::Builder};
+pub use self::{
+    base::Base,
+    builder::{substitution_reference_bases, Builder},
+};
 
 use std::{error, fmt};
 
@@ -23,6 +26,13 @@ impl SubstitutionMatrix {
     pub fn get(&self, reference_base: Base, substitution_code: u8) -> Base {
         self.substitutions[reference_base as usize][substitution_code as usize]
     }
+
+    pub fn find(&self, reference_base: Base, read_base: Base) -> Option<u8> {
+        self.substitutions[reference_base as usize]
+            .iter()
+            .position(|&base| base == read_base)
+            .map(|i| i as u8)
+    }
 }
 
 impl Default for SubstitutionMatrix {
@@ -183,6 +193,14 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_find() {
+        let matrix = SubstitutionMatrix::default();
+        assert_eq!(matrix.find(Base::A, Base::G), Some(1));
+        assert_eq!(matrix.find(Base::N, Base::T), Some(3));
+        assert_eq!(matrix.find(Base::A, Base::A), None);
+    }
+
     #[test]
     fn test_from_substitution_matrix_for_5_byte_array() {
         let matrix = SubstitutionMatrix {
@@ -10,15 +10,19 @@ pub struct Builder {
 
 impl Builder {
     pub fn update(&mut self, reference_sequence: &[u8], record: &Record) {
-        for feature in record.features() {
-            if let Feature::Substitution(pos, code) = feature {
-                // FIXME: pos = 1-based, position = 0-based
-                let reference_position = (pos - 1) as usize;
-                let base = reference_sequence[reference_position] as char;
-                let reference_base = Base::try_from(base).unwrap_or_default();
-                let read_base = self.substitution_matrix.get(reference_base, *code);
-                self.histogram.hit(reference_base, read_base);
-            }
+        let codes = record
+            .features()
+            .iter()
+            .filter_map(|feature| match feature {
+                Feature::Substitution(_, code) => Some(*code),
+                _ => None,
+            });
+
+        let reference_bases = substitution_reference_bases(reference_sequence, record);
+
+        for (reference_base, code) in reference_bases.into_iter().zip(codes) {
+            let read_base = self.substitution_matrix.get(reference_base, code);
+            self.histogram.hit(reference_base, read_base);
         }
     }
 
@@ -27,15 +31,75 @@ impl Builder {
     }
 }
 
+/// Returns the reference base of each substitution in the record, in order.
+///
+/// Positions outside the reference sequence are `N`.
+pub fn substitution_reference_bases(reference_sequence: &[u8], record: &Record) -> Vec<Base> {
+    let mut reference_bases = Vec::new();
+
+    let alignment_start = match record.alignment_start() {
+        Some(position) => i32::from(position) as usize,
+        None => return reference_bases,
+    };
+
+    let mut read_position = 1;
+    let mut reference_position = alignment_start - 1;
+
+    for feature in record.features() {
+        let feature_position = feature.position() as usize;
+
+        if feature_position > read_position {
+            reference_position += feature_position - read_position;
+            read_position = feature_position;
+        }
+
+        match feature {
+            Feature::Substitution(..) => {
+                let reference_base = reference_sequence
+                    .get(reference_position)
+                    .and_then(|&b| Base::try_from(char::from(b.to_ascii_uppercase())).ok())
+                    .unwrap_or_default();
+
+                reference_bases.push(reference_base);
+
+                read_position += 1;
+                reference_position += 1;
+            }
+            Feature::Bases(_, bases) => {
+                read_position += bases.len();
+                reference_position += bases.len();
+            }
+            Feature::ReadBase(..) => {
+                read_position += 1;
+                reference_position += 1;
+            }
+            Feature::Insertion(_, bases) | Feature::SoftClip(_, bases) => {
+                read_position += bases.len();
+            }
+            Feature::InsertBase(..) => read_position += 1,
+            Feature::Deletion(_, len) | Feature::ReferenceSkip(_, len) => {
+                reference_position += *len as usize;
+            }
+            Feature::Scores(..)
+            | Feature::QualityScore(..)
+            | Feature::Padding(..)
+            | Feature::HardClip(..) => {}
+        }
+    }
+
+    reference_bases
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
-    fn test_build() {
+    fn test_build() -> Result<(), Box<dyn std::error::Error>> {
         let reference_sequence = b"ACAGGAATAANNNNNN";
 
         let mut record = Record::default();
+        record.alignment_start = Some(noodles_sam::record::Position::try_from(1)?);
         record.add_feature(Feature::Substitution(1, 2)); // A => T
         record.add_feature(Feature::Substitution(3, 2)); // A => T
         record.add_feature(Feature::Substitution(6, 0)); // A => C
@@ -57,5 +121,7 @@ mod tests {
                 [Base::A, Base::C, Base::G, Base::T],
             ]
         );
+
+        Ok(())
     }
 }
@@ -5,10 +5,15 @@ pub use self::{builder::Builder, header::Header};
 
 use std::io::{self, Cursor};
 
-use noodles_sam as sam;
+use md5::{Digest, Md5};
+use noodles_fasta as fasta;
+use noodles_sam::{self as sam, header::reference_sequence::Md5Checksum};
 
 use super::CompressionHeader;
-use crate::{container::Block, BitReader, Record};
+use crate::{
+    container::Block, record::resolve::resolve_bases,
+    reference_sequence_repository::ReferenceSequenceRepository, BitReader, Record,
+};
 
 /// A CRAM data container slice.
 ///
@@ -131,6 +136,229 @@ impl Slice {
     pub fn resolve_mates(&self, records: Vec<Record>) -> Vec<Record> {
         resolve_mates(records)
     }
+
+    /// Resolves the read bases of records using reference sequences.
+    ///
+    /// The bases of mapped records are reconstructed from the reference sequence and the read
+    /// features. The reference sequence is taken from the embedded reference block of the slice,
+    /// if present, or otherwise from the repository. If the compression header does not require a
+    /// reference, the bases are resolved from the read features alone.
+    ///
+    /// If the slice has a reference MD5 checksum, it is verified against the reference sequence.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::{fs::File, io};
+    /// use noodles_cram::{self as cram, reference_sequence_repository::ReferenceSequenceRepository};
+    /// use noodles_sam as sam;
+    ///
+    /// let mut reference_sequence_repository = ReferenceSequenceRepository::default();
+    ///
+    /// let mut reader = File::open("sample.cram").map(cram::Reader::new)?;
+    /// reader.read_file_definition()?;
+    /// let header: sam::Header = reader.read_file_header()?.parse()?;
+    ///
+    /// while let Some(container) = reader.read_data_container()? {
+    ///     for slice in container.slices() {
+    ///         let mut records = slice.records(container.compression_header())?;
+    ///
+    ///         slice.resolve_records(
+    ///             &mut reference_sequence_repository,
+    ///             &header,
+    ///             container.compression_header(),
+    ///             &mut records,
+    ///         )?;
+    ///
+    ///         // ...
+    ///     }
+    /// }
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn resolve_records(
+        &self,
+        reference_sequence_repository: &mut ReferenceSequenceRepository,
+        header: &sam::Header,
+        compression_header: &CompressionHeader,
+        records: &mut [Record],
+    ) -> io::Result<()> {
+        let embedded_reference_sequence_record = self.embedded_reference_sequence_record()?;
+        let reference_required = compression_header.preservation_map().reference_required();
+
+        if self.header.reference_sequence_id().is_some()
+            && !is_md5_unset(self.header.reference_md5())
+        {
+            if let Some(record) = &embedded_reference_sequence_record {
+                self.validate_reference_md5(record.sequence().as_ref())?;
+            } else if reference_required {
+                let id = i32::from(self.header.reference_sequence_id());
+                let reference_sequence_record =
+                    get_reference_sequence_record(reference_sequence_repository, header, id)?;
+                let sequence = self.get_slice_reference_bases(reference_sequence_record)?;
+                self.validate_reference_md5(sequence)?;
+            }
+        }
+
+        let empty_reference_sequence_record = fasta::Record::new(
+            fasta::record::Definition::new("", None),
+            fasta::record::Sequence::default(),
+        );
+
+        let slice_alignment_start = self
+            .header
+            .alignment_start()
+            .map(i32::from)
+            .unwrap_or_default();
+
+        for record in records {
+            if record.bam_flags().is_unmapped()
+                || record.flags().decode_sequence_as_unknown()
+                || record.read_length() == 0
+            {
+                continue;
+            }
+
+            let id = match record.reference_sequence_id() {
+                Some(id) => i32::from(id),
+                None => continue,
+            };
+
+            let mut alignment_start = record.alignment_start().map(i32::from).unwrap_or_default();
+
+            let reference_sequence_record = if let Some(embedded_reference_sequence_record) =
+                &embedded_reference_sequence_record
+            {
+                // The embedded reference bases start at the slice alignment start.
+                alignment_start -= slice_alignment_start - 1;
+                embedded_reference_sequence_record
+            } else if reference_required {
+                get_reference_sequence_record(reference_sequence_repository, header, id)?
+            } else {
+                &empty_reference_sequence_record
+            };
+
+            record.bases = resolve_bases(
+                reference_sequence_record,
+                compression_header,
+                record.features(),
+                alignment_start,
+                record.read_length(),
+            )?;
+        }
+
+        Ok(())
+    }
+
+    fn embedded_reference_sequence_record(&self) -> io::Result<Option<fasta::Record>> {
+        let block_content_id = match *self.header.embedded_reference_bases_block_content_id() {
+            Some(id) => id,
+            None => return Ok(None),
+        };
+
+        let block = self
+            .external_blocks
+            .iter()
+            .find(|block| block.content_id() == block_content_id)
+            .ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!(
+                        "missing embedded reference bases block: {}",
+                        block_content_id
+                    ),
+                )
+            })?;
+
+        block.decompressed_data().map(|data| {
+            Some(fasta::Record::new(
+                fasta::record::Definition::new("", None),
+                fasta::record::Sequence::from(data.into_owned()),
+            ))
+        })
+    }
+
+    fn get_slice_reference_bases<'a>(
+        &self,
+        reference_sequence_record: &'a fasta::Record,
+    ) -> io::Result<&'a [u8]> {
+        let start = self
+            .header
+            .alignment_start()
+            .map(|position| (i32::from(position) - 1) as usize)
+            .unwrap_or_default();
+        let end = start + self.header.alignment_span() as usize;
+
+        reference_sequence_record
+            .sequence()
+            .as_ref()
+            .get(start..end)
+            .ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "slice alignment range is out of reference sequence bounds",
+                )
+            })
+    }
+
+    fn validate_reference_md5(&self, sequence: &[u8]) -> io::Result<()> {
+        let actual = calculate_reference_md5(sequence);
+
+        let expected = <[u8; 16]>::try_from(self.header.reference_md5())
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+        if actual == expected {
+            Ok(())
+        } else {
+            Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!(
+                    "reference sequence checksum mismatch: expected {}, got {}",
+                    Md5Checksum::from(expected),
+                    Md5Checksum::from(actual),
+                ),
+            ))
+        }
+    }
+}
+
+// Calculates the MD5 checksum of reference bases.
+//
+// Like the SAM `M5` reference sequence field, the checksum is of the uppercased bases, i.e.,
+// soft-masked (lowercase) bases do not change the checksum.
+pub(crate) fn calculate_reference_md5(bases: &[u8]) -> [u8; 16] {
+    let mut hasher = Md5::new();
+    hasher.update(bases.to_ascii_uppercase());
+    <[u8; 16]>::from(hasher.finalize())
+}
+
+fn is_md5_unset(md5: &[u8]) -> bool {
+    md5.iter().all(|&b| b == 0)
+}
+
+fn get_reference_sequence_record<'a>(
+    reference_sequence_repository: &'a mut ReferenceSequenceRepository,
+    header: &sam::Header,
+    id: i32,
+) -> io::Result<&'a fasta::Record> {
+    let reference_sequence = usize::try_from(id)
+        .ok()
+        .and_then(|i| header.reference_sequences().get_index(i))
+        .map(|(_, rs)| rs)
+        .ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("invalid reference sequence ID: {}", id),
+            )
+        })?;
+
+    reference_sequence_repository
+        .get(reference_sequence)?
+        .ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("missing reference sequence: {}", reference_sequence.name()),
+            )
+        })
 }
 
 fn resolve_mates(records: Vec<Record>) -> Vec<Record> {
@@ -213,6 +441,31 @@ mod tests {
 
     use super::*;
 
+    #[test]
+    fn test_validate_reference_md5() -> io::Result<()> {
+        // MD5("ACGTN")
+        let reference_md5 = [
+            0x25, 0x2f, 0xe4, 0xe1, 0xc9, 0xaa, 0x67, 0xce, 0x66, 0x04, 0x43, 0x05, 0x6d, 0xfa,
+            0x37, 0x99,
+        ];
+
+        let header = Header::builder().set_reference_md5(reference_md5).build();
+        let core_data_block = Block::builder()
+            .set_content_type(crate::container::block::ContentType::CoreData)
+            .build();
+        let slice = Slice::new(header, core_data_block, Vec::new());
+
+        slice.validate_reference_md5(b"ACGTN")?;
+        slice.validate_reference_md5(b"acgTn")?;
+
+        assert!(matches!(
+            slice.validate_reference_md5(b"ACGTA"),
+            Err(e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+
+        Ok(())
+    }
+
     #[test]
     fn test_resolve_mates() -> Result<(), Box<dyn std::error::Error>> {
         use crate::record::Flags;
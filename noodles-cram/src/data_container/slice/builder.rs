@@ -1,29 +1,28 @@
 use std::{cmp, collections::HashMap, io};
 
-use md5::{Digest, Md5};
 use noodles_fasta as fasta;
 use noodles_sam as sam;
 
 use crate::{
-    container::{
-        block::{self, CompressionMethod},
-        Block, ReferenceSequenceId,
+    container::{block, Block, ReferenceSequenceId},
+    data_container::{
+        compression_header::{data_series_encoding_map::DataSeries, SubstitutionMatrix},
+        CompressionHeader,
     },
-    data_container::{compression_header::data_series_encoding_map::DataSeries, CompressionHeader},
-    writer, BitWriter, Record,
+    writer::{self, BlockCompression, Options, ReferenceMode},
+    BitWriter, Record,
 };
 
-use super::{Header, Slice};
-
-use noodles_bam as bam;
+use super::{calculate_reference_md5, header::EmbeddedReferenceBasesBlockContentId, Header, Slice};
 
 const CORE_DATA_BLOCK_CONTENT_ID: i32 = 0;
-const MAX_RECORD_COUNT: usize = 2560;
+// The external block content IDs of the data series are 1..=DataSeries::LEN.
+const EMBEDDED_REFERENCE_BASES_BLOCK_CONTENT_ID: i32 = DataSeries::LEN as i32 + 1;
 
 #[derive(Debug, Default)]
 pub struct Builder {
     records: Vec<Record>,
-    reference_sequence_id: Option<Option<bam::record::ReferenceSequenceId>>,
+    reference_sequence_id: Option<ReferenceSequenceId>,
 }
 
 #[derive(Clone, Debug, PartialEq)]
@@ -37,51 +36,55 @@ impl Builder {
         self.records.is_empty()
     }
 
-    pub fn add_record(&mut self, record: Record) -> Result<&Record, AddRecordError> {
-        if self.records.len() >= MAX_RECORD_COUNT {
+    pub fn is_multi_reference(&self) -> bool {
+        matches!(self.reference_sequence_id, Some(ReferenceSequenceId::Many))
+    }
+
+    pub fn add_record(
+        &mut self,
+        options: &Options,
+        record: Record,
+    ) -> Result<&Record, AddRecordError> {
+        if self.records.len() >= options.records_per_slice.max(1) {
             return Err(AddRecordError::SliceFull(record));
         }
 
-        let record_reference_sequence_id = record.reference_sequence_id();
-
-        if self.reference_sequence_id.is_none() {
-            self.reference_sequence_id = Some(record_reference_sequence_id);
-        }
+        let record_reference_sequence_id = match record.reference_sequence_id() {
+            Some(id) => ReferenceSequenceId::Some(i32::from(id)),
+            None => ReferenceSequenceId::None,
+        };
 
-        match self.reference_sequence_id.unwrap() {
+        match self.reference_sequence_id {
+            None => self.reference_sequence_id = Some(record_reference_sequence_id),
+            Some(ReferenceSequenceId::Many) => {}
             Some(slice_reference_sequence_id) => {
-                match record_reference_sequence_id.map(i32::from) {
-                    Some(id) => {
-                        if i32::from(slice_reference_sequence_id) == id {
-                            self.records.push(record);
-                            Ok(self.records.last().unwrap())
-                        } else {
-                            Err(AddRecordError::ReferenceSequenceIdMismatch(record))
-                        }
+                if slice_reference_sequence_id != record_reference_sequence_id {
+                    // An embedded reference can only be used with a single reference sequence.
+                    if options.multi_reference_slices
+                        && options.reference_mode != ReferenceMode::Embedded
+                    {
+                        self.reference_sequence_id = Some(ReferenceSequenceId::Many);
+                    } else {
+                        return Err(AddRecordError::ReferenceSequenceIdMismatch(record));
                     }
-                    None => Err(AddRecordError::ReferenceSequenceIdMismatch(record)),
                 }
             }
-            None => match record_reference_sequence_id {
-                Some(_) => Err(AddRecordError::ReferenceSequenceIdMismatch(record)),
-                None => {
-                    self.records.push(record);
-                    Ok(self.records.last().unwrap())
-                }
-            },
         }
+
+        self.records.push(record);
+        Ok(self.records.last().unwrap())
     }
 
     pub fn build(
-        self,
+        mut self,
+        options: &Options,
         reference_sequences: &[fasta::Record],
         compression_header: &CompressionHeader,
         record_counter: i64,
     ) -> io::Result<Slice> {
-        let reference_sequence_id = match self.reference_sequence_id.unwrap() {
-            Some(id) => ReferenceSequenceId::Some(i32::from(id)),
-            None => ReferenceSequenceId::None,
-        };
+        let reference_sequence_id = self
+            .reference_sequence_id
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "no records in builder"))?;
 
         let alignment_start = self
             .records
@@ -89,6 +92,14 @@ impl Builder {
             .map(|r| r.alignment_start())
             .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "no records in builder"))?;
 
+        let substitution_matrix = compression_header.preservation_map().substitution_matrix();
+
+        if *substitution_matrix != SubstitutionMatrix::default() {
+            for record in &mut self.records {
+                recode_substitutions(record, reference_sequences, substitution_matrix)?;
+            }
+        }
+
         let mut core_data_writer = BitWriter::new(Vec::new());
 
         let mut external_data_writers = HashMap::new();
@@ -123,65 +134,194 @@ impl Builder {
         }
 
         let core_data_block = core_data_writer.finish().and_then(|buf| {
-            Block::builder()
-                .set_content_type(block::ContentType::CoreData)
-                .set_content_id(CORE_DATA_BLOCK_CONTENT_ID)
-                .compress_and_set_data(buf, CompressionMethod::Gzip)
-                .map(|builder| builder.build())
+            build_block(
+                block::ContentType::CoreData,
+                CORE_DATA_BLOCK_CONTENT_ID,
+                buf,
+                options.block_compression,
+            )
         })?;
 
+        let block_compressions = build_block_compressions(options, compression_header);
+
         let mut block_content_ids = vec![CORE_DATA_BLOCK_CONTENT_ID];
 
-        let external_blocks: Vec<_> = external_data_writers
+        let mut external_blocks: Vec<_> = external_data_writers
             .into_iter()
             .filter(|(_, buf)| !buf.is_empty())
             .map(|(block_content_id, buf)| {
-                Block::builder()
-                    .set_content_type(block::ContentType::ExternalData)
-                    .set_content_id(block_content_id)
-                    .compress_and_set_data(buf, CompressionMethod::Gzip)
-                    .map(|builder| builder.build())
+                let block_compression = block_compressions
+                    .get(&block_content_id)
+                    .copied()
+                    .unwrap_or(options.block_compression);
+
+                build_block(
+                    block::ContentType::ExternalData,
+                    block_content_id,
+                    buf,
+                    block_compression,
+                )
             })
             .collect::<Result<_, _>>()?;
 
+        let mut reference_md5 = [0; 16];
+        let mut embedded_reference_bases_block_content_id =
+            EmbeddedReferenceBasesBlockContentId::default();
+
+        if let ReferenceSequenceId::Some(id) = reference_sequence_id {
+            if options.reference_mode != ReferenceMode::None {
+                let reference_sequence = reference_sequences
+                    .get(id as usize)
+                    .map(|record| record.sequence())
+                    .ok_or_else(|| {
+                        io::Error::new(io::ErrorKind::InvalidInput, "missing reference sequence")
+                    })?;
+
+                let start = (slice_alignment_start - 1) as usize;
+                let end = (slice_alignment_end - 1) as usize;
+
+                let bases = reference_sequence
+                    .as_ref()
+                    .get(start..=end)
+                    .ok_or_else(|| {
+                        io::Error::new(
+                            io::ErrorKind::InvalidInput,
+                            "slice alignment range is out of reference sequence bounds",
+                        )
+                    })?;
+
+                reference_md5 = calculate_reference_md5(bases);
+
+                if options.reference_mode == ReferenceMode::Embedded {
+                    // Embedded bases are uppercased to match the reference MD5 checksum.
+                    let block = build_block(
+                        block::ContentType::ExternalData,
+                        EMBEDDED_REFERENCE_BASES_BLOCK_CONTENT_ID,
+                        bases.to_ascii_uppercase(),
+                        options.block_compression,
+                    )?;
+
+                    external_blocks.push(block);
+                    embedded_reference_bases_block_content_id =
+                        EmbeddedReferenceBasesBlockContentId::from(
+                            EMBEDDED_REFERENCE_BASES_BLOCK_CONTENT_ID,
+                        );
+                }
+            }
+        }
+
         for block in &external_blocks {
             block_content_ids.push(block.content_id());
         }
 
-        let reference_md5 = if let ReferenceSequenceId::Some(id) = reference_sequence_id {
-            let reference_sequence = reference_sequences
-                .get(id as usize)
-                .map(|record| record.sequence())
-                .ok_or_else(|| {
-                    io::Error::new(io::ErrorKind::InvalidInput, "missing reference sequence")
-                })?;
-
-            let start = (slice_alignment_start - 1) as usize;
-            let end = (slice_alignment_end - 1) as usize;
-
-            let mut hasher = Md5::new();
-            hasher.update(&reference_sequence.as_ref()[start..=end]);
-            <[u8; 16]>::from(hasher.finalize())
-        } else {
-            [0; 16]
-        };
+        let mut header_builder = Header::builder().set_reference_sequence_id(reference_sequence_id);
+
+        // An unmapped slice has no alignment start or span.
+        if reference_sequence_id.is_some() {
+            let slice_alignment_span = slice_alignment_end - slice_alignment_start + 1;
+            let slice_alignment_start = sam::record::Position::try_from(slice_alignment_start)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
 
-        let slice_alignment_span = slice_alignment_end - slice_alignment_start + 1;
-        let slice_alignment_start = sam::record::Position::try_from(slice_alignment_start)
-            .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+            header_builder = header_builder
+                .set_alignment_start(slice_alignment_start)
+                .set_alignment_span(slice_alignment_span);
+        }
 
-        let header = Header::builder()
-            .set_reference_sequence_id(reference_sequence_id)
-            .set_alignment_start(slice_alignment_start)
-            .set_alignment_span(slice_alignment_span)
+        let header = header_builder
             .set_record_count(self.records.len())
             .set_record_counter(record_counter)
             // external blocks + core data block
             .set_block_count(external_blocks.len() + 1)
             .set_block_content_ids(block_content_ids)
+            .set_embedded_reference_bases_block_content_id(
+                embedded_reference_bases_block_content_id,
+            )
             .set_reference_md5(reference_md5)
             .build();
 
         Ok(Slice::new(header, core_data_block, external_blocks))
     }
 }
+
+// Substitution codes of records to be written are relative to the default substitution matrix. They
+// are recoded using the substitution matrix built for the compression header.
+fn recode_substitutions(
+    record: &mut Record,
+    reference_sequences: &[fasta::Record],
+    substitution_matrix: &SubstitutionMatrix,
+) -> io::Result<()> {
+    use crate::{
+        data_container::compression_header::preservation_map::substitution_matrix::substitution_reference_bases,
+        record::Feature,
+    };
+
+    let reference_sequence = match record.reference_sequence_id() {
+        Some(id) => reference_sequences
+            .get(i32::from(id) as usize)
+            .map(|rs| rs.sequence().as_ref())
+            .unwrap_or_default(),
+        None => return Ok(()),
+    };
+
+    let reference_bases = substitution_reference_bases(reference_sequence, record);
+    let default_substitution_matrix = SubstitutionMatrix::default();
+
+    let codes = record
+        .features
+        .iter_mut()
+        .filter_map(|feature| match feature {
+            Feature::Substitution(_, code) => Some(code),
+            _ => None,
+        });
+
+    for (reference_base, code) in reference_bases.into_iter().zip(codes) {
+        let read_base = default_substitution_matrix.get(reference_base, *code);
+
+        *code = substitution_matrix
+            .find(reference_base, read_base)
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "invalid substitution"))?;
+    }
+
+    Ok(())
+}
+
+fn build_block_compressions(
+    options: &Options,
+    compression_header: &CompressionHeader,
+) -> HashMap<i32, BlockCompression> {
+    let mut block_compressions = HashMap::new();
+
+    for (&data_series, &block_compression) in &options.data_series_block_compressions {
+        // The external block content ID of a data series is its position + 1. See
+        // `DataSeriesEncodingMap::default`.
+        let block_content_id = data_series as i32 + 1;
+        block_compressions.insert(block_content_id, block_compression);
+    }
+
+    for &block_content_id in compression_header.tag_encoding_map().keys() {
+        // A tag block content ID is the tag and value type packed as a 24-bit integer.
+        let tag = [
+            (block_content_id >> 16) as u8,
+            (block_content_id >> 8) as u8,
+        ];
+
+        if let Some(&block_compression) = options.tag_block_compressions.get(&tag) {
+            block_compressions.insert(block_content_id, block_compression);
+        }
+    }
+
+    block_compressions
+}
+
+fn build_block(
+    content_type: block::ContentType,
+    content_id: i32,
+    data: Vec<u8>,
+    block_compression: BlockCompression,
+) -> io::Result<Block> {
+    Block::builder()
+        .set_content_type(content_type)
+        .set_content_id(content_id)
+        .compress_and_set_data(data, block_compression)
+        .map(|builder| builder.build())
+}
@@ -0,0 +1,138 @@
+//! fqzcomp quality score codec.
+
+mod decode;
+mod encode;
+mod parameters;
+
+pub use self::{decode::decode, encode::encode};
+
+use self::parameters::{Parameter, ParameterFlags, Parameters};
+use crate::aac::Model;
+
+struct Models {
+    qual: Vec<Option<Model>>,
+    qual_symbol_count: usize,
+    len: [Model; 4],
+    rev: Model,
+    dup: Model,
+    sel: Model,
+}
+
+impl Models {
+    fn new(parameters: &Parameters) -> Self {
+        Self {
+            qual: vec![None; 1 << 16],
+            qual_symbol_count: usize::from(parameters.max_sym()) + 1,
+            len: [
+                Model::new(256),
+                Model::new(256),
+                Model::new(256),
+                Model::new(256),
+            ],
+            rev: Model::new(2),
+            dup: Model::new(2),
+            sel: Model::new(usize::from(parameters.max_sel) + 1),
+        }
+    }
+
+    fn qual(&mut self, ctx: u16) -> &mut Model {
+        let symbol_count = self.qual_symbol_count;
+        self.qual[usize::from(ctx)].get_or_insert_with(|| Model::new(symbol_count))
+    }
+}
+
+// The context state of a record.
+struct State {
+    qctx: u32,
+    p: usize,
+    delta: u32,
+    prevq: u8,
+    s: u32,
+}
+
+impl State {
+    fn new(len: usize, s: u32) -> Self {
+        Self {
+            qctx: 0,
+            p: len,
+            delta: 0,
+            prevq: 0,
+            s,
+        }
+    }
+
+    // Updates the state with the quality score symbol `q` and returns the next context.
+    fn update(&mut self, parameter: &Parameter, q: u8) -> u16 {
+        let qmask = (1 << parameter.qbits) - 1;
+
+        // Only the low 16 bits of the context are used, so overflow is harmless.
+        self.qctx = (self.qctx << parameter.qshift).wrapping_add(parameter.qtab[usize::from(q)]);
+
+        let mut ctx = (self.qctx & qmask) << parameter.qloc;
+        ctx = ctx.wrapping_add(parameter.ptab[self.p.min(1023)] << parameter.ploc);
+        ctx = ctx.wrapping_add(parameter.dtab[self.delta.min(255) as usize] << parameter.dloc);
+
+        if parameter.flags.contains(ParameterFlags::DO_SEL) {
+            ctx = ctx.wrapping_add(self.s << parameter.sloc);
+        }
+
+        if self.prevq != q {
+            self.delta += 1;
+        }
+
+        self.prevq = q;
+        self.p -= 1;
+
+        (ctx & 0xffff) as u16
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io;
+
+    use super::*;
+
+    #[test]
+    fn test_self() -> io::Result<()> {
+        let lens = [8, 8, 8];
+        let data = [
+            40, 40, 38, 38, 30, 30, 20, 2, // 1
+            40, 40, 40, 38, 38, 30, 20, 2, // 2
+            40, 38, 38, 38, 30, 30, 20, 20, // 3
+        ];
+
+        let compressed_data = encode(&lens, &data)?;
+        let decompressed_data = decode(&mut &compressed_data[..])?;
+        assert_eq!(decompressed_data, data);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_self_with_variable_lengths() -> io::Result<()> {
+        let lens = [3, 0, 5, 1];
+        let data = [30, 31, 32, 0, 1, 2, 3, 255, 93];
+
+        let compressed_data = encode(&lens, &data)?;
+        let decompressed_data = decode(&mut &compressed_data[..])?;
+        assert_eq!(decompressed_data, data);
+
+        let compressed_data = encode(&[], &[])?;
+        let decompressed_data = decode(&mut &compressed_data[..])?;
+        assert!(decompressed_data.is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_encode_compresses() -> io::Result<()> {
+        let lens = vec![100; 64];
+        let data: Vec<_> = (0..6400).map(|i| [30, 35, 40][(i / 7) % 3]).collect();
+
+        let compressed_data = encode(&lens, &data)?;
+        assert!(compressed_data.len() < data.len() / 4);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,158 @@
+use std::io;
+
+use super::{
+    parameters::{read_parameters, Flags, ParameterFlags},
+    Models, State,
+};
+use crate::{aac::RangeCoder, rans_nx16::decode::read_uint7_as_usize};
+
+pub fn decode(src: &mut &[u8]) -> io::Result<Vec<u8>> {
+    let len = read_uint7_as_usize(src)?;
+    let parameters = read_parameters(src)?;
+
+    let mut models = Models::new(&parameters);
+
+    let mut range_coder = RangeCoder::default();
+    range_coder.range_decode_create(src);
+
+    let mut dst = vec![0; len];
+    let mut reversed_records = Vec::new();
+    let mut last_record_len = None;
+    let mut i = 0;
+
+    while i < len {
+        let s = if parameters.max_sel > 0 {
+            models.sel.decode(src, &mut range_coder).map(u32::from)?
+        } else {
+            0
+        };
+
+        let parameter = parameters
+            .selector_table
+            .get(s as usize)
+            .and_then(|&x| parameters.parameters.get(x as usize))
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "invalid selector"))?;
+
+        let record_len = match last_record_len {
+            Some(n) if parameter.flags.contains(ParameterFlags::FIXED_LEN) => n,
+            _ => decode_record_len(src, &mut range_coder, &mut models)?,
+        };
+
+        if record_len == 0 || i + record_len > len {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "invalid record length",
+            ));
+        }
+
+        last_record_len = Some(record_len);
+
+        if parameters.flags.contains(Flags::DO_REV)
+            && models.rev.decode(src, &mut range_coder)? == 1
+        {
+            reversed_records.push(i..i + record_len);
+        }
+
+        if parameter.flags.contains(ParameterFlags::DEDUP)
+            && models.dup.decode(src, &mut range_coder)? == 1
+        {
+            if record_len > i {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "invalid duplicate record",
+                ));
+            }
+
+            dst.copy_within(i - record_len..i, i);
+            i += record_len;
+
+            continue;
+        }
+
+        let mut state = State::new(record_len, s);
+        let mut ctx = parameter.context;
+
+        for q in &mut dst[i..i + record_len] {
+            let sym = models.qual(ctx).decode(src, &mut range_coder)?;
+
+            *q = parameter
+                .qmap
+                .get(usize::from(sym))
+                .copied()
+                .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "invalid symbol"))?;
+
+            ctx = state.update(parameter, sym);
+        }
+
+        i += record_len;
+    }
+
+    for range in reversed_records {
+        dst[range].reverse();
+    }
+
+    Ok(dst)
+}
+
+fn decode_record_len(
+    src: &mut &[u8],
+    range_coder: &mut RangeCoder,
+    models: &mut Models,
+) -> io::Result<usize> {
+    let mut buf = [0; 4];
+
+    for (b, model) in buf.iter_mut().zip(models.len.iter_mut()) {
+        *b = model.decode(src, range_coder)?;
+    }
+
+    usize::try_from(u32::from_le_bytes(buf))
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode() -> io::Result<()> {
+        let data = [
+            0x18, // uncompressed length = 24
+            0x05, // version = 5
+            0x00, // flags = {empty}
+            0x00, 0x00, // parameters[0].context = 0
+            0x36, // parameters[0].flags = HAVE_PTAB | HAVE_QMAP | FIXED_LEN | DEDUP
+            0x05, // parameters[0].max_sym = 5
+            0x84, // parameters[0].qbits = 8, parameters[0].qshift = 4
+            0x00, // parameters[0].qloc = 0, parameters[0].sloc = 0
+            0x80, // parameters[0].ploc = 8, parameters[0].dloc = 0
+            0x02, 0x14, 0x1e, 0x26, 0x28, // parameters[0].qmap = [2, 20, 30, 38, 40]
+            0x04, 0x04, 0x00, 0xff, 0xff, 0x01,
+            0xfb, // parameters[0].ptab = [0; 4] + [1; 4] + [2; 1016]
+            0x00, 0x07, 0xff, 0xff, 0xf8, 0x65, 0xab, 0x25, 0xda, 0xa5, 0xb9, 0x34,
+            0x00, // range coded data
+        ];
+
+        let expected = [
+            40, 40, 38, 38, 30, 30, 20, 2, // 1
+            40, 40, 40, 38, 38, 30, 20, 2, // 2
+            40, 40, 40, 38, 38, 30, 20, 2, // 3 (duplicate)
+        ];
+
+        assert_eq!(decode(&mut &data[..])?, expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_decode_with_invalid_version() {
+        let data = [
+            0x00, // uncompressed length = 0
+            0x04, // version = 4
+        ];
+
+        assert!(matches!(
+            decode(&mut &data[..]),
+            Err(ref e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+    }
+}
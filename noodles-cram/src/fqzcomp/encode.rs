@@ -0,0 +1,115 @@
+use std::io;
+
+use super::{
+    parameters::{write_parameters, Flags, Parameter, ParameterFlags, Parameters},
+    Models, State,
+};
+use crate::{aac::RangeCoder, rans_nx16::encode::write_uint7_as_usize};
+
+pub fn encode(lens: &[usize], src: &[u8]) -> io::Result<Vec<u8>> {
+    if lens.iter().sum::<usize>() != src.len() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "record lengths do not match the data length",
+        ));
+    }
+
+    let mut dst = Vec::new();
+    write_uint7_as_usize(&mut dst, src.len())?;
+
+    let parameters = build_parameters(lens, src);
+    write_parameters(&mut dst, &parameters)?;
+
+    let parameter = &parameters.parameters[0];
+    let is_fixed_len = parameter.flags.contains(ParameterFlags::FIXED_LEN);
+
+    let mut models = Models::new(&parameters);
+    let mut range_coder = RangeCoder::default();
+    let mut is_first_record = true;
+    let mut i = 0;
+
+    for &record_len in lens.iter().filter(|&&n| n > 0) {
+        if !is_fixed_len || is_first_record {
+            encode_record_len(&mut dst, &mut range_coder, &mut models, record_len)?;
+            is_first_record = false;
+        }
+
+        let mut state = State::new(record_len, 0);
+        let mut ctx = parameter.context;
+
+        for &q in &src[i..i + record_len] {
+            models.qual(ctx).encode(&mut dst, &mut range_coder, q)?;
+            ctx = state.update(parameter, q);
+        }
+
+        i += record_len;
+    }
+
+    range_coder.range_encode_end(&mut dst);
+
+    Ok(dst)
+}
+
+fn build_parameters(lens: &[usize], src: &[u8]) -> Parameters {
+    let mut flags = ParameterFlags::HAVE_PTAB | ParameterFlags::HAVE_DTAB;
+
+    let mut record_lens = lens.iter().filter(|&&n| n > 0);
+
+    if let Some(first_record_len) = record_lens.next() {
+        if record_lens.all(|n| n == first_record_len) {
+            flags.insert(ParameterFlags::FIXED_LEN);
+        }
+    }
+
+    let max_sym = src.iter().copied().max().unwrap_or(0);
+
+    Parameters {
+        flags: Flags::empty(),
+        max_sel: 0,
+        selector_table: vec![0; 256],
+        parameters: vec![Parameter {
+            context: 0,
+            flags,
+            max_sym,
+            qbits: 10,
+            qshift: 5,
+            qloc: 0,
+            sloc: 15,
+            ploc: 10,
+            dloc: 14,
+            qmap: (0..=255).collect(),
+            qtab: (0..256).collect(),
+            ptab: (0..1024).map(|i| (i >> 3).min(15)).collect(),
+            dtab: (0..256).map(|i| (i / 2).min(3)).collect(),
+        }],
+    }
+}
+
+fn encode_record_len(
+    dst: &mut Vec<u8>,
+    range_coder: &mut RangeCoder,
+    models: &mut Models,
+    record_len: usize,
+) -> io::Result<()> {
+    let n =
+        u32::try_from(record_len).map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+
+    for (&b, model) in n.to_le_bytes().iter().zip(models.len.iter_mut()) {
+        model.encode(dst, range_coder, b)?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode_with_mismatched_lengths() {
+        assert!(matches!(
+            encode(&[2], b"ndls"),
+            Err(ref e) if e.kind() == io::ErrorKind::InvalidInput
+        ));
+    }
+}
@@ -0,0 +1,434 @@
+use std::io::{self, Read, Write};
+
+use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
+
+const VERSION: u8 = 5;
+
+bitflags::bitflags! {
+    /// fqzcomp global parameter flags.
+    #[derive(Default)]
+    pub struct Flags: u8 {
+        /// Multiple parameter sets are used (`0x01`).
+        const MULTI_PARAM = 0x01;
+        /// A selector table is stored (`0x02`).
+        const HAVE_STAB = 0x02;
+        /// Records can be reverse complemented (`0x04`).
+        const DO_REV = 0x04;
+    }
+}
+
+bitflags::bitflags! {
+    /// fqzcomp parameter set flags.
+    #[derive(Default)]
+    pub struct ParameterFlags: u8 {
+        /// Records can be marked as duplicates of the previous record (`0x02`).
+        const DEDUP = 0x02;
+        /// All records have the same length, which is only stored once (`0x04`).
+        const FIXED_LEN = 0x04;
+        /// The selector is used as part of the context (`0x08`).
+        const DO_SEL = 0x08;
+        /// A quality value map is stored (`0x10`).
+        const HAVE_QMAP = 0x10;
+        /// A position context table is stored (`0x20`).
+        const HAVE_PTAB = 0x20;
+        /// A delta context table is stored (`0x40`).
+        const HAVE_DTAB = 0x40;
+        /// A quality value context table is stored (`0x80`).
+        const HAVE_QTAB = 0x80;
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Parameters {
+    pub flags: Flags,
+    pub max_sel: u8,
+    pub selector_table: Vec<u32>,
+    pub parameters: Vec<Parameter>,
+}
+
+impl Parameters {
+    pub fn max_sym(&self) -> u8 {
+        self.parameters.iter().map(|p| p.max_sym).max().unwrap_or(0)
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Parameter {
+    pub context: u16,
+    pub flags: ParameterFlags,
+    pub max_sym: u8,
+    pub qbits: u8,
+    pub qshift: u8,
+    pub qloc: u8,
+    pub sloc: u8,
+    pub ploc: u8,
+    pub dloc: u8,
+    pub qmap: Vec<u8>,
+    pub qtab: Vec<u32>,
+    pub ptab: Vec<u32>,
+    pub dtab: Vec<u32>,
+}
+
+pub fn read_parameters<R>(reader: &mut R) -> io::Result<Parameters>
+where
+    R: Read,
+{
+    let version = reader.read_u8()?;
+
+    if version != VERSION {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!(
+                "invalid fqzcomp version: expected {}, got {}",
+                VERSION, version
+            ),
+        ));
+    }
+
+    let flags = reader.read_u8().map(Flags::from_bits_truncate)?;
+
+    let parameter_count = if flags.contains(Flags::MULTI_PARAM) {
+        reader.read_u8().map(usize::from)?
+    } else {
+        1
+    };
+
+    if parameter_count == 0 {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "invalid fqzcomp parameter count",
+        ));
+    }
+
+    let (max_sel, selector_table) = if flags.contains(Flags::HAVE_STAB) {
+        let max_sel = reader.read_u8()?;
+        let selector_table = read_array(reader, 256)?;
+        (max_sel, selector_table)
+    } else {
+        let max_sel = if parameter_count > 1 {
+            parameter_count as u8
+        } else {
+            0
+        };
+
+        let selector_table = (0..256)
+            .map(|i| i.min(parameter_count as u32 - 1))
+            .collect();
+
+        (max_sel, selector_table)
+    };
+
+    let parameters = (0..parameter_count)
+        .map(|_| read_parameter(reader))
+        .collect::<io::Result<_>>()?;
+
+    Ok(Parameters {
+        flags,
+        max_sel,
+        selector_table,
+        parameters,
+    })
+}
+
+fn read_parameter<R>(reader: &mut R) -> io::Result<Parameter>
+where
+    R: Read,
+{
+    let context = reader.read_u16::<LittleEndian>()?;
+    let flags = reader.read_u8().map(ParameterFlags::from_bits_truncate)?;
+    let max_sym = reader.read_u8()?;
+
+    let (qbits, qshift) = reader.read_u8().map(split_nibbles)?;
+    let (qloc, sloc) = reader.read_u8().map(split_nibbles)?;
+    let (ploc, dloc) = reader.read_u8().map(split_nibbles)?;
+
+    let qmap = if flags.contains(ParameterFlags::HAVE_QMAP) {
+        let mut buf = vec![0; usize::from(max_sym)];
+        reader.read_exact(&mut buf)?;
+        buf
+    } else {
+        (0..=255).collect()
+    };
+
+    let qtab = if qbits > 0 && flags.contains(ParameterFlags::HAVE_QTAB) {
+        read_array(reader, 256)?
+    } else {
+        (0..256).collect()
+    };
+
+    let ptab = if flags.contains(ParameterFlags::HAVE_PTAB) {
+        read_array(reader, 1024)?
+    } else {
+        vec![0; 1024]
+    };
+
+    let dtab = if flags.contains(ParameterFlags::HAVE_DTAB) {
+        read_array(reader, 256)?
+    } else {
+        vec![0; 256]
+    };
+
+    Ok(Parameter {
+        context,
+        flags,
+        max_sym,
+        qbits,
+        qshift,
+        qloc,
+        sloc,
+        ploc,
+        dloc,
+        qmap,
+        qtab,
+        ptab,
+        dtab,
+    })
+}
+
+fn split_nibbles(n: u8) -> (u8, u8) {
+    (n >> 4, n & 0x0f)
+}
+
+// Reads a nondecreasing array of `len` values starting at 0.
+//
+// The array is stored as the run length of each value, where run lengths >= 255 are split into
+// parts, and the run lengths themselves are run-length encoded: a repeated byte is followed by
+// the number of additional copies.
+fn read_array<R>(reader: &mut R, len: usize) -> io::Result<Vec<u32>>
+where
+    R: Read,
+{
+    let mut runs = Vec::new();
+    let mut total = 0;
+    let mut last = None;
+
+    while total < len {
+        let run = reader.read_u8()?;
+        runs.push(run);
+        total += usize::from(run);
+
+        if Some(run) == last {
+            let copy = reader.read_u8()?;
+
+            for _ in 0..copy {
+                runs.push(run);
+            }
+
+            total += usize::from(run) * usize::from(copy);
+        }
+
+        last = Some(run);
+    }
+
+    let mut array = Vec::with_capacity(len);
+    let mut runs = runs.into_iter();
+    let mut value = 0;
+
+    while array.len() < len {
+        let mut run_len = 0;
+
+        loop {
+            let part = runs
+                .next()
+                .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "invalid array"))?;
+
+            run_len += usize::from(part);
+
+            if part < 255 {
+                break;
+            }
+        }
+
+        let n = run_len.min(len - array.len());
+        array.resize(array.len() + n, value);
+
+        value += 1;
+    }
+
+    Ok(array)
+}
+
+pub fn write_parameters<W>(writer: &mut W, parameters: &Parameters) -> io::Result<()>
+where
+    W: Write,
+{
+    writer.write_u8(VERSION)?;
+    writer.write_u8(parameters.flags.bits())?;
+
+    if parameters.flags.contains(Flags::MULTI_PARAM) {
+        let parameter_count = u8::try_from(parameters.parameters.len())
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+        writer.write_u8(parameter_count)?;
+    }
+
+    if parameters.flags.contains(Flags::HAVE_STAB) {
+        writer.write_u8(parameters.max_sel)?;
+        write_array(writer, &parameters.selector_table)?;
+    }
+
+    for parameter in &parameters.parameters {
+        write_parameter(writer, parameter)?;
+    }
+
+    Ok(())
+}
+
+fn write_parameter<W>(writer: &mut W, parameter: &Parameter) -> io::Result<()>
+where
+    W: Write,
+{
+    writer.write_u16::<LittleEndian>(parameter.context)?;
+    writer.write_u8(parameter.flags.bits())?;
+    writer.write_u8(parameter.max_sym)?;
+    writer.write_u8(parameter.qbits << 4 | parameter.qshift)?;
+    writer.write_u8(parameter.qloc << 4 | parameter.sloc)?;
+    writer.write_u8(parameter.ploc << 4 | parameter.dloc)?;
+
+    if parameter.flags.contains(ParameterFlags::HAVE_QMAP) {
+        writer.write_all(&parameter.qmap[..usize::from(parameter.max_sym)])?;
+    }
+
+    if parameter.qbits > 0 && parameter.flags.contains(ParameterFlags::HAVE_QTAB) {
+        write_array(writer, &parameter.qtab)?;
+    }
+
+    if parameter.flags.contains(ParameterFlags::HAVE_PTAB) {
+        write_array(writer, &parameter.ptab)?;
+    }
+
+    if parameter.flags.contains(ParameterFlags::HAVE_DTAB) {
+        write_array(writer, &parameter.dtab)?;
+    }
+
+    Ok(())
+}
+
+fn write_array<W>(writer: &mut W, array: &[u32]) -> io::Result<()>
+where
+    W: Write,
+{
+    let mut runs = Vec::new();
+    let mut i = 0;
+    let mut value = 0;
+
+    while i < array.len() {
+        let start = i;
+
+        while i < array.len() && array[i] == value {
+            i += 1;
+        }
+
+        if i == start && array[i] < value {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "array values must be nondecreasing",
+            ));
+        }
+
+        let mut run_len = i - start;
+
+        loop {
+            let part = run_len.min(255);
+            runs.push(part as u8);
+            run_len -= part;
+
+            if part < 255 {
+                break;
+            }
+        }
+
+        value += 1;
+    }
+
+    let mut last = None;
+    let mut j = 0;
+
+    while j < runs.len() {
+        let run = runs[j];
+        writer.write_u8(run)?;
+        j += 1;
+
+        if Some(run) == last {
+            let copy = runs[j..]
+                .iter()
+                .take(255)
+                .take_while(|&&r| r == run)
+                .count();
+
+            writer.write_u8(copy as u8)?;
+            j += copy;
+        }
+
+        last = Some(run);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_array() -> io::Result<()> {
+        let data = [
+            0x02, // run(0) = 2
+            0x01, // run(1) = 1
+            0x01, 0x02, // run(2) = 1, copy = 2
+        ];
+
+        let actual = read_array(&mut &data[..], 6)?;
+        assert_eq!(actual, [0, 0, 1, 2, 3, 4]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_write_array() -> io::Result<()> {
+        let mut buf = Vec::new();
+        write_array(&mut buf, &[0, 0, 1, 2, 3, 4])?;
+        assert_eq!(buf, [0x02, 0x01, 0x01, 0x02]);
+
+        let array: Vec<_> = (0..1024).map(|i| (i >> 3).min(15)).collect();
+        let mut buf = Vec::new();
+        write_array(&mut buf, &array)?;
+        assert_eq!(read_array(&mut &buf[..], array.len())?, array);
+
+        let mut buf = Vec::new();
+        assert!(write_array(&mut buf, &[1, 0]).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_self() -> io::Result<()> {
+        let parameters = Parameters {
+            flags: Flags::empty(),
+            max_sel: 0,
+            selector_table: vec![0; 256],
+            parameters: vec![Parameter {
+                context: 0,
+                flags: ParameterFlags::HAVE_PTAB | ParameterFlags::HAVE_DTAB,
+                max_sym: 40,
+                qbits: 10,
+                qshift: 5,
+                qloc: 0,
+                sloc: 15,
+                ploc: 10,
+                dloc: 14,
+                qmap: (0..=255).collect(),
+                qtab: (0..256).collect(),
+                ptab: (0..1024).map(|i| (i >> 3).min(15)).collect(),
+                dtab: (0..256).map(|i| (i / 2).min(3)).collect(),
+            }],
+        };
+
+        let mut buf = Vec::new();
+        write_parameters(&mut buf, &parameters)?;
+
+        let actual = read_parameters(&mut &buf[..])?;
+        assert_eq!(actual, parameters);
+
+        Ok(())
+    }
+}
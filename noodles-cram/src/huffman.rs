@@ -1,9 +1,9 @@
 use std::{
     collections::HashMap,
-    io::{self, Read},
+    io::{self, Read, Write},
 };
 
-use crate::{num::Itf8, BitReader};
+use crate::{num::Itf8, BitReader, BitWriter};
 
 type CodeBook = HashMap<Itf8, (Itf8, u32)>;
 
@@ -40,7 +40,7 @@ impl CanonicalHuffmanDecoder {
         for &len in sorted_lens {
             input_code <<= len - prev_len;
 
-            let b = reader.read_u32(len)? as i32;
+            let b = reader.read_u32(len - prev_len)? as i32;
             input_code |= b;
 
             let entry = code_book_by_len[&len]
@@ -61,6 +61,31 @@ impl CanonicalHuffmanDecoder {
     }
 }
 
+pub struct CanonicalHuffmanEncoder {
+    code_book: CodeBook,
+}
+
+impl CanonicalHuffmanEncoder {
+    pub fn new(alphabet: &[Itf8], bit_lens: &[u32]) -> Self {
+        let code_book = build_canonical_code_book(alphabet, bit_lens);
+        Self { code_book }
+    }
+
+    pub fn encode<W>(&self, writer: &mut BitWriter<W>, symbol: Itf8) -> io::Result<()>
+    where
+        W: Write,
+    {
+        let (code, len) = self.code_book.get(&symbol).ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("symbol not in alphabet: {}", symbol),
+            )
+        })?;
+
+        writer.write_u32(*code as u32, *len as usize)
+    }
+}
+
 fn build_canonical_code_book(alphabet: &[Itf8], bit_lens: &[u32]) -> CodeBook {
     let sorted_alphabet = {
         let mut pairs: Vec<_> = alphabet.iter().zip(bit_lens.iter()).collect();
@@ -71,7 +96,10 @@ fn build_canonical_code_book(alphabet: &[Itf8], bit_lens: &[u32]) -> CodeBook {
     let mut code_book = CodeBook::with_capacity(sorted_alphabet.len());
 
     let mut code = 0;
-    let mut prev_bit_len = *sorted_alphabet[0].1;
+    let mut prev_bit_len = sorted_alphabet
+        .first()
+        .map(|(_, &bit_len)| bit_len)
+        .unwrap_or_default();
 
     for (&symbol, &bit_len) in sorted_alphabet {
         if bit_len > prev_bit_len {
@@ -106,5 +134,32 @@ mod tests {
         assert_eq!(code_book[&68], (0b110, 3));
         assert_eq!(code_book[&69], (0b1110, 4));
         assert_eq!(code_book[&70], (0b1111, 4));
+
+        assert!(build_canonical_code_book(&[], &[]).is_empty());
+    }
+
+    #[test]
+    fn test_self() -> io::Result<()> {
+        let symbols = [65, 66, 67, 68, 69, 70];
+        let bit_lens = [1, 3, 3, 3, 4, 4];
+
+        let encoder = CanonicalHuffmanEncoder::new(&symbols, &bit_lens);
+        let mut writer = BitWriter::new(Vec::new());
+
+        for &symbol in &[70, 65, 67, 69] {
+            encoder.encode(&mut writer, symbol)?;
+        }
+
+        assert!(encoder.encode(&mut writer, 71).is_err());
+
+        let data = writer.finish()?;
+        let mut reader = BitReader::new(&data[..]);
+        let decoder = CanonicalHuffmanDecoder::new(&symbols, &bit_lens);
+
+        for &symbol in &[70, 65, 67, 69] {
+            assert_eq!(decoder.decode(&mut reader)?, symbol);
+        }
+
+        Ok(())
     }
 }
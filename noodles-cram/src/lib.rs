@@ -6,18 +6,25 @@
 #[allow(dead_code)]
 mod r#async;
 
+mod aac;
+mod bit_codecs;
 mod bit_reader;
 mod bit_writer;
 pub(crate) mod container;
 pub mod crai;
 pub mod data_container;
 pub mod file_definition;
+mod fqzcomp;
 mod huffman;
 mod num;
 mod rans;
+mod rans_nx16;
 pub mod reader;
 pub mod record;
-pub(crate) mod writer;
+pub mod reference_sequence_repository;
+pub mod summary;
+mod tokenizer;
+pub mod writer;
 
 pub use self::{
     data_container::DataContainer, file_definition::FileDefinition, reader::Reader, record::Record,
@@ -25,11 +32,17 @@ pub use self::{
 };
 
 #[cfg(feature = "async")]
-pub use self::r#async::Reader as AsyncReader;
+pub use self::r#async::{Reader as AsyncReader, Writer as AsyncWriter};
 
 pub(crate) use self::{bit_reader::BitReader, bit_writer::BitWriter, container::Container};
 
-use std::{cmp, collections::HashMap, fs::File, io, path::Path};
+use std::{
+    cmp,
+    collections::HashMap,
+    fs::File,
+    io::{self, Read, Seek},
+    path::Path,
+};
 
 use noodles_bam as bam;
 
@@ -52,7 +65,15 @@ where
     let mut reader = File::open(src).map(Reader::new)?;
     reader.read_file_definition()?;
     reader.read_file_header()?;
+    index_data_containers(&mut reader)
+}
 
+// Builds an index from the data containers of a reader. The stream is expected to be directly
+// after the file header container.
+fn index_data_containers<R>(reader: &mut Reader<R>) -> io::Result<crai::Index>
+where
+    R: Read + Seek,
+{
     let mut index = Vec::new();
     let mut container_position = reader.position()?;
 
@@ -155,6 +176,10 @@ fn push_index_records_for_multi_reference_slice(
             .entry(raw_reference_sequence_id)
             .or_default();
 
+        if raw_reference_sequence_id == bam::record::reference_sequence_id::UNMAPPED {
+            continue;
+        }
+
         let alignment_start = record.alignment_start().map(i32::from).unwrap_or_default();
         range.start = cmp::min(range.start, alignment_start);
         range.end = cmp::max(range.end, record.alignment_end());
@@ -167,14 +192,14 @@ fn push_index_records_for_multi_reference_slice(
     let reference_sequence_ids = raw_sorted_reference_sequence_ids
         .iter()
         .map(|&id| {
-            let range = &raw_reference_sequence_ids[&id];
-
-            let alignment_start = range.start;
-            let alignment_span = range.end - alignment_start + 1;
-
             if id == bam::record::reference_sequence_id::UNMAPPED {
-                Ok((None, alignment_start, alignment_span))
+                Ok((None, 0, 0))
             } else {
+                let range = &raw_reference_sequence_ids[&id];
+
+                let alignment_start = range.start;
+                let alignment_span = range.end - alignment_start + 1;
+
                 bam::record::ReferenceSequenceId::try_from(id)
                     .map(Some)
                     .map(|reference_sequence_id| {
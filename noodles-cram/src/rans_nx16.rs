@@ -0,0 +1,105 @@
+//! rANS Nx16 codec.
+//!
+//! This is the rANS codec introduced in CRAM 3.1. It uses 16-bit renormalization with 4 or 32
+//! interleaved states and supports optional stripe, RLE, and bit-packing transforms.
+
+pub(crate) mod decode;
+pub(crate) mod encode;
+mod flags;
+
+pub use self::{decode::rans_decode_nx16, encode::rans_encode_nx16, flags::Flags};
+
+// Lower bound `L`.
+const LOWER_BOUND: u32 = 1 << 15;
+
+#[cfg(test)]
+mod tests {
+    use std::io;
+
+    use super::*;
+
+    fn t(flags: Flags, data: &[u8]) -> io::Result<()> {
+        let compressed_data = rans_encode_nx16(flags, data)?;
+
+        let mut reader = &compressed_data[..];
+        let decompressed_data = rans_decode_nx16(&mut reader, 0)?;
+
+        assert_eq!(decompressed_data, data, "flags = {:?}", flags);
+        assert!(reader.is_empty(), "flags = {:?}", flags);
+
+        Ok(())
+    }
+
+    fn build_data() -> Vec<u8> {
+        let mut data = Vec::new();
+
+        for i in 0..2048u32 {
+            let b = match i % 13 {
+                0..=5 => b'A',
+                6..=8 => b'C',
+                9..=10 => b'G',
+                11 => b'T',
+                _ => (i % 251) as u8,
+            };
+
+            data.push(b);
+        }
+
+        data
+    }
+
+    #[test]
+    fn test_self() -> io::Result<()> {
+        let data = build_data();
+
+        let flag_sets = [
+            Flags::empty(),
+            Flags::ORDER,
+            Flags::N32,
+            Flags::ORDER | Flags::N32,
+            Flags::CAT,
+            Flags::RLE,
+            Flags::ORDER | Flags::RLE,
+            Flags::PACK,
+            Flags::PACK | Flags::RLE,
+            Flags::ORDER | Flags::PACK | Flags::RLE | Flags::N32,
+            Flags::STRIPE,
+            Flags::STRIPE | Flags::ORDER | Flags::RLE,
+        ];
+
+        for flags in flag_sets {
+            t(flags, b"")?;
+            t(flags, b"n")?;
+            t(flags, b"noodles")?;
+            t(flags, b"AAAAAAAACCCCGGGGTTTTNNNNNNNNNNNNN")?;
+            t(flags, &data)?;
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_self_with_pack() -> io::Result<()> {
+        for symbol_count in [1, 2, 3, 4, 5, 16] {
+            let data: Vec<_> = (0..1000u32)
+                .map(|i| b'a' + (i % symbol_count) as u8)
+                .collect();
+            t(Flags::PACK, &data)?;
+            t(Flags::PACK | Flags::ORDER, &data)?;
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_rans_encode_nx16_compresses() -> io::Result<()> {
+        let data = build_data();
+
+        for flags in [Flags::empty(), Flags::ORDER, Flags::RLE, Flags::PACK] {
+            let compressed_data = rans_encode_nx16(flags, &data)?;
+            assert!(compressed_data.len() < data.len(), "flags = {:?}", flags);
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,542 @@
+mod order_0;
+mod order_1;
+pub(crate) mod pack;
+mod rle;
+
+use std::io::{self, Read};
+
+use byteorder::{LittleEndian, ReadBytesExt};
+
+use super::{Flags, LOWER_BOUND};
+use crate::reader::num::read_uint7;
+
+pub fn rans_decode_nx16(src: &mut &[u8], mut len: usize) -> io::Result<Vec<u8>> {
+    let flags = src.read_u8().map(Flags::from_bits_truncate)?;
+
+    if !flags.contains(Flags::NO_SIZE) {
+        len = read_uint7_as_usize(src)?;
+    }
+
+    if flags.contains(Flags::STRIPE) {
+        return decode_stripe(src, len);
+    }
+
+    let n = if flags.contains(Flags::N32) { 32 } else { 4 };
+
+    let mut pack_context = None;
+
+    if flags.contains(Flags::PACK) {
+        let (mapping, packed_len) = pack::read_context(src)?;
+        pack_context = Some((mapping, len));
+        len = packed_len;
+    }
+
+    let mut rle_context = None;
+
+    if flags.contains(Flags::RLE) {
+        let (meta, literals_len) = rle::read_context(src)?;
+        rle_context = Some((meta, len));
+        len = literals_len;
+    }
+
+    let mut dst = if flags.contains(Flags::CAT) {
+        split_off(src, len).map(|buf| buf.to_vec())?
+    } else if len == 0 {
+        Vec::new()
+    } else if flags.contains(Flags::ORDER) {
+        order_1::decode(src, len, n)?
+    } else {
+        order_0::decode(src, len, n)?
+    };
+
+    if let Some((meta, len)) = rle_context {
+        dst = rle::decode(&dst, &meta, len)?;
+    }
+
+    if let Some((mapping, len)) = pack_context {
+        dst = pack::decode(&dst, &mapping, len)?;
+    }
+
+    Ok(dst)
+}
+
+fn decode_stripe(src: &mut &[u8], len: usize) -> io::Result<Vec<u8>> {
+    let n = src.read_u8().map(usize::from)?;
+
+    if n == 0 {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "invalid stripe count",
+        ));
+    }
+
+    let compressed_lens = (0..n)
+        .map(|_| read_uint7_as_usize(src))
+        .collect::<io::Result<Vec<_>>>()?;
+
+    let mut chunks = Vec::with_capacity(n);
+
+    for (j, compressed_len) in compressed_lens.into_iter().enumerate() {
+        let ulen = len / n + usize::from(len % n > j);
+
+        let mut buf = split_off(src, compressed_len)?;
+        let chunk = rans_decode_nx16(&mut buf, ulen)?;
+
+        if chunk.len() != ulen {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!(
+                    "stripe length mismatch: expected {}, got {}",
+                    ulen,
+                    chunk.len()
+                ),
+            ));
+        }
+
+        chunks.push(chunk);
+    }
+
+    // The output is only allocated once every stripe is decoded, i.e., its length is backed by
+    // the decoded data.
+    let mut dst = vec![0; len];
+
+    for (j, chunk) in chunks.into_iter().enumerate() {
+        for (i, b) in chunk.into_iter().enumerate() {
+            dst[i * n + j] = b;
+        }
+    }
+
+    Ok(dst)
+}
+
+pub(crate) fn read_uint7_as_usize<R>(reader: &mut R) -> io::Result<usize>
+where
+    R: Read,
+{
+    read_uint7(reader)
+        .and_then(|n| usize::try_from(n).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e)))
+}
+
+// Creates an output buffer for a decoded length read from the input.
+//
+// The length is not trusted, so the initial capacity is limited by the input size. The buffer
+// grows as symbols are decoded.
+fn output_buffer(src: &[u8], len: usize) -> Vec<u8> {
+    Vec::with_capacity(len.min(src.len()))
+}
+
+pub(crate) fn split_off<'a>(src: &mut &'a [u8], len: usize) -> io::Result<&'a [u8]> {
+    if len > src.len() {
+        return Err(io::Error::from(io::ErrorKind::UnexpectedEof));
+    }
+
+    let (buf, rest) = src.split_at(len);
+    *src = rest;
+
+    Ok(buf)
+}
+
+fn read_alphabet<R>(reader: &mut R) -> io::Result<[bool; 256]>
+where
+    R: Read,
+{
+    let mut alphabet = [false; 256];
+
+    let mut sym = reader.read_u8()?;
+    let mut last_sym = sym;
+    let mut rle = 0;
+
+    loop {
+        alphabet[usize::from(sym)] = true;
+
+        if rle > 0 {
+            rle -= 1;
+
+            sym = sym.checked_add(1).ok_or_else(|| {
+                io::Error::new(io::ErrorKind::InvalidData, "invalid alphabet run length")
+            })?;
+        } else {
+            sym = reader.read_u8()?;
+
+            if last_sym < 255 && sym == last_sym + 1 {
+                rle = reader.read_u8()?;
+            }
+        }
+
+        last_sym = sym;
+
+        if sym == 0 {
+            break;
+        }
+    }
+
+    Ok(alphabet)
+}
+
+fn read_states<R>(reader: &mut R, n: usize) -> io::Result<Vec<u32>>
+where
+    R: Read,
+{
+    let mut states = vec![0; n];
+    reader.read_u32_into::<LittleEndian>(&mut states)?;
+    Ok(states)
+}
+
+// Scales the frequencies up to a total of `1 << bits`.
+//
+// A table with no frequencies is left as is. Otherwise, the sum must be a power of two no larger
+// than the total.
+fn normalize_frequencies(freqs: &mut [u32], bits: u32) -> io::Result<()> {
+    let total = 1 << bits;
+    let mut sum: u64 = freqs.iter().copied().map(u64::from).sum();
+
+    if sum == 0 || sum == total {
+        return Ok(());
+    } else if sum > total {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("invalid frequency sum: expected <= {}, got {}", total, sum),
+        ));
+    }
+
+    let mut shift = 0;
+
+    while sum < total {
+        sum <<= 1;
+        shift += 1;
+    }
+
+    if sum != total {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!(
+                "invalid frequency sum: expected a power of two <= {}, got {}",
+                total,
+                sum >> shift
+            ),
+        ));
+    }
+
+    for f in freqs {
+        *f <<= shift;
+    }
+
+    Ok(())
+}
+
+fn build_cumulative_frequencies(freqs: &[u32]) -> Vec<u32> {
+    let mut cumulative_freqs = vec![0; freqs.len()];
+
+    for i in 0..freqs.len() - 1 {
+        cumulative_freqs[i + 1] = cumulative_freqs[i] + freqs[i];
+    }
+
+    cumulative_freqs
+}
+
+fn build_symbol_table(freqs: &[u32], cumulative_freqs: &[u32], bits: u32) -> io::Result<Vec<u8>> {
+    let mut table = vec![0; 1 << bits];
+
+    for (sym, (&f, &g)) in freqs.iter().zip(cumulative_freqs).enumerate() {
+        let start = g as usize;
+
+        let slots = start
+            .checked_add(f as usize)
+            .and_then(|end| table.get_mut(start..end))
+            .ok_or_else(|| {
+                io::Error::new(io::ErrorKind::InvalidData, "invalid symbol frequency")
+            })?;
+
+        for t in slots {
+            *t = sym as u8;
+        }
+    }
+
+    Ok(table)
+}
+
+// Invalid states from malformed input wrap instead of overflowing.
+fn rans_advance_step(r: u32, c: u32, f: u32, bits: u32) -> u32 {
+    let mask = (1 << bits) - 1;
+
+    f.wrapping_mul(r >> bits)
+        .wrapping_add(r & mask)
+        .wrapping_sub(c)
+}
+
+fn rans_renorm<R>(reader: &mut R, mut r: u32) -> io::Result<u32>
+where
+    R: Read,
+{
+    if r < LOWER_BOUND {
+        r = (r << 16) + reader.read_u16::<LittleEndian>().map(u32::from)?;
+    }
+
+    Ok(r)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rans_decode_nx16_with_cat() -> io::Result<()> {
+        let data = [
+            0x20, // flags = CAT
+            0x07, // uncompressed size = 7
+            b'n', b'o', b'o', b'd', b'l', b'e', b's',
+        ];
+
+        let mut src = &data[..];
+        assert_eq!(rans_decode_nx16(&mut src, 0)?, b"noodles");
+        assert!(src.is_empty());
+
+        let data = [
+            0x30, // flags = NO_SIZE | CAT
+            b'n', b'o', b'o', b'd', b'l', b'e', b's',
+        ];
+
+        let mut src = &data[..];
+        assert_eq!(rans_decode_nx16(&mut src, 7)?, b"noodles");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_rans_decode_nx16_with_order_0() -> io::Result<()> {
+        let data = [
+            0x00, // flags = {empty}
+            0x07, // uncompressed size = 7
+            0x64, 0x65, 0x00, 0x6c, 0x6e, 0x6f, 0x00, 0x73, 0x00, // alphabet = "delnos"
+            0x84, 0x49, 0x84, 0x49, 0x84, 0x49, 0x84, 0x49, 0x89, 0x13, 0x84,
+            0x49, // frequencies = [585, 585, 585, 585, 1171, 585]
+            0x1b, 0xa7, 0x18, 0x00, // states[0] = 1615643
+            0xe9, 0x4a, 0x0c, 0x00, // states[1] = 805609
+            0x31, 0x6d, 0x0c, 0x00, // states[2] = 814385
+            0x08, 0x80, 0x03, 0x00, // states[3] = 229384
+        ];
+
+        let mut src = &data[..];
+        assert_eq!(rans_decode_nx16(&mut src, 0)?, b"noodles");
+        assert!(src.is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_rans_decode_nx16_with_order_0_and_n32() -> io::Result<()> {
+        let mut data = vec![
+            0x04, // flags = N32
+            0x07, // uncompressed size = 7
+            0x64, 0x65, 0x00, 0x6c, 0x6e, 0x6f, 0x00, 0x73, 0x00, // alphabet = "delnos"
+            0x84, 0x49, 0x84, 0x49, 0x84, 0x49, 0x84, 0x49, 0x89, 0x13, 0x84,
+            0x49, // frequencies = [585, 585, 585, 585, 1171, 585]
+            0xe3, 0x86, 0x03, 0x00, // states[0] = 231139
+            0xa3, 0xbd, 0x01, 0x00, // states[1] = 114083
+            0xa3, 0xbd, 0x01, 0x00, // states[2] = 114083
+            0x08, 0x80, 0x03, 0x00, // states[3] = 229384
+            0x9a, 0x84, 0x03, 0x00, // states[4] = 230554
+            0x51, 0x82, 0x03, 0x00, // states[5] = 229969
+            0xbf, 0x8d, 0x03, 0x00, // states[6] = 232895
+        ];
+
+        // states[7..32] = 32768
+        data.extend([0x00, 0x80, 0x00, 0x00].repeat(25));
+
+        let mut src = &data[..];
+        assert_eq!(rans_decode_nx16(&mut src, 0)?, b"noodles");
+        assert!(src.is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_rans_decode_nx16_with_order_1() -> io::Result<()> {
+        let data = [
+            0x01, // flags = ORDER
+            0x0b, // uncompressed size = 11
+            0xc0, // frequency table bit count = 12, uncompressed
+            0x00, 0x61, 0x62, 0x02, 0x72, 0x00, // alphabet = "\0abcdr"
+            0x00, 0x00, 0x88, 0x00, 0x00, 0x00, 0x88, 0x00, 0x88, 0x00, 0x88, 0x00, // F[0]
+            0x00, 0x01, 0xa0, 0x00, 0x00, 0x02, // F['a']
+            0x00, 0x04, 0xa0, 0x00, // F['b']
+            0x00, 0x00, 0xa0, 0x00, 0x00, 0x03, // F['c']
+            0x00, 0x00, 0xa0, 0x00, 0x00, 0x03, // F['d']
+            0x00, 0x00, 0xa0, 0x00, 0x00, 0x03, // F['r']
+            0x00, 0x00, 0x02, 0x00, // states[0] = 131072
+            0x00, 0x0c, 0x02, 0x00, // states[1] = 134144
+            0x00, 0x04, 0x02, 0x00, // states[2] = 132096
+            0x00, 0x08, 0x02, 0x00, // states[3] = 133120
+        ];
+
+        let mut src = &data[..];
+        assert_eq!(rans_decode_nx16(&mut src, 0)?, b"abracadabra");
+        assert!(src.is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_rans_decode_nx16_with_rle() -> io::Result<()> {
+        let data = [
+            0x40, // flags = RLE
+            0x16, // uncompressed size = 22
+            0x13, // context length = 9, uncompressed
+            0x06, // literals length = 6
+            0x04, b'A', b'C', b'G', b'T', // run symbols = "ACGT"
+            0x07, 0x03, 0x03, 0x03, // run lengths = [7, 3, 3, 3]
+            0x41, 0x43, 0x47, 0x4e, 0x54, 0x00, // alphabet = "ACGNT"
+            0x85, 0x2a, 0x85, 0x2a, 0x85, 0x2a, 0x8a, 0x58, 0x85,
+            0x2a, // frequencies = [682, 682, 682, 1368, 682]
+            0x20, 0xf0, 0x08, 0x00, // states[0] = 585760
+            0xca, 0xf2, 0x08, 0x00, // states[1] = 586442
+            0x74, 0x05, 0x03, 0x00, // states[2] = 198004
+            0x76, 0x0d, 0x03, 0x00, // states[3] = 200054
+        ];
+
+        let mut src = &data[..];
+        assert_eq!(rans_decode_nx16(&mut src, 0)?, b"AAAAAAAACCCCGGGGTTTTNN");
+        assert!(src.is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_rans_decode_nx16_with_pack() -> io::Result<()> {
+        let data = [
+            0x80, // flags = PACK
+            0x10, // uncompressed size = 16
+            0x04, b'a', b'c', b'g', b't', // symbols = "acgt"
+            0x04, // packed length = 4
+            0x50, 0xe4, 0xfa, 0x00, // alphabet = [0x50, 0xe4, 0xfa]
+            0x88, 0x00, 0x90, 0x00, 0x88, 0x00, // frequencies = [1024, 2048, 1024]
+            0x00, 0x04, 0x01, 0x00, // states[0] = 66560
+            0x00, 0x04, 0x01, 0x00, // states[1] = 66560
+            0x00, 0x00, 0x02, 0x00, // states[2] = 131072
+            0x00, 0x0c, 0x02, 0x00, // states[3] = 134144
+        ];
+
+        let mut src = &data[..];
+        assert_eq!(rans_decode_nx16(&mut src, 0)?, b"acgtacgtaaccggtt");
+        assert!(src.is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_rans_decode_nx16_with_stripe() -> io::Result<()> {
+        let data = [
+            0x08, // flags = STRIPE
+            0x07, // uncompressed size = 7
+            0x02, // stripe count = 2
+            0x05, // compressed length 0 = 5
+            0x04, // compressed length 1 = 4
+            0x30, b'n', b'o', b'l', b's', // NO_SIZE | CAT, "nols"
+            0x30, b'o', b'd', b'e', // NO_SIZE | CAT, "ode"
+        ];
+
+        let mut src = &data[..];
+        assert_eq!(rans_decode_nx16(&mut src, 0)?, b"noodles");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_rans_decode_nx16_with_invalid_frequencies() {
+        let data = [
+            0x00, // flags = {empty}
+            0x07, // uncompressed size = 7
+            0x61, 0x62, 0x00, 0x00, // alphabet = "ab"
+            0x01, 0x02, // frequencies = [1, 2]
+            0x00, 0x00, 0x01, 0x00, // states[0] = 65536
+            0x00, 0x00, 0x01, 0x00, // states[1] = 65536
+            0x00, 0x00, 0x01, 0x00, // states[2] = 65536
+            0x00, 0x00, 0x01, 0x00, // states[3] = 65536
+        ];
+
+        let mut src = &data[..];
+        assert!(matches!(
+            rans_decode_nx16(&mut src, 0),
+            Err(e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+    }
+
+    #[test]
+    fn test_rans_decode_nx16_with_length_past_end_of_input() {
+        let data = [
+            0x00, // flags = {empty}
+            0xff, 0xff, 0xff, 0x7f, // uncompressed size = 268435455
+            0x61, 0x62, 0x00, 0x00, // alphabet = "ab"
+            0x01, 0x01, // frequencies = [1, 1]
+            0x00, 0x80, 0x00, 0x00, // states[0] = 32768
+            0x00, 0x80, 0x00, 0x00, // states[1] = 32768
+            0x00, 0x80, 0x00, 0x00, // states[2] = 32768
+            0x00, 0x80, 0x00, 0x00, // states[3] = 32768
+        ];
+
+        let mut src = &data[..];
+        assert!(matches!(
+            rans_decode_nx16(&mut src, 0),
+            Err(e) if e.kind() == io::ErrorKind::UnexpectedEof
+        ));
+    }
+
+    #[test]
+    fn test_build_symbol_table() -> io::Result<()> {
+        let freqs = [1, 2, 1];
+        let cumulative_freqs = build_cumulative_frequencies(&freqs);
+        assert_eq!(
+            build_symbol_table(&freqs, &cumulative_freqs, 2)?,
+            [0, 1, 1, 2]
+        );
+
+        let freqs = [2, 3];
+        let cumulative_freqs = build_cumulative_frequencies(&freqs);
+        assert!(matches!(
+            build_symbol_table(&freqs, &cumulative_freqs, 2),
+            Err(e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_alphabet() -> io::Result<()> {
+        let data = [
+            0x61, // 'a'
+            0x62, 0x02, // 'b', rle = 2
+            0x72, // 'r'
+            0x00, // end
+        ];
+
+        let alphabet = read_alphabet(&mut &data[..])?;
+
+        let expected: Vec<_> = b"abcdr".iter().map(|&b| usize::from(b)).collect();
+        let actual: Vec<_> = (0..256).filter(|&i| alphabet[i]).collect();
+        assert_eq!(actual, expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_normalize_frequencies() -> io::Result<()> {
+        let mut freqs = [1, 2, 0, 1];
+        normalize_frequencies(&mut freqs, 12)?;
+        assert_eq!(freqs, [1024, 2048, 0, 1024]);
+
+        let mut freqs = [4096, 1];
+        assert!(normalize_frequencies(&mut freqs, 12).is_err());
+
+        let mut freqs = [1, 2, 0];
+        assert!(matches!(
+            normalize_frequencies(&mut freqs, 12),
+            Err(e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+
+        let mut freqs = [0, 0];
+        normalize_frequencies(&mut freqs, 12)?;
+        assert_eq!(freqs, [0, 0]);
+
+        Ok(())
+    }
+}
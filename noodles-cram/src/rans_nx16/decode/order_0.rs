@@ -0,0 +1,74 @@
+use std::io::{self, Read};
+
+use super::{
+    build_cumulative_frequencies, build_symbol_table, normalize_frequencies, output_buffer,
+    rans_advance_step, rans_renorm, read_alphabet, read_states, read_uint7,
+};
+
+const BITS: u32 = 12;
+
+pub fn decode(src: &mut &[u8], len: usize, n: usize) -> io::Result<Vec<u8>> {
+    let freqs = read_frequencies(src)?;
+    let cumulative_freqs = build_cumulative_frequencies(&freqs);
+    let table = build_symbol_table(&freqs, &cumulative_freqs, BITS)?;
+
+    let mut states = read_states(src, n)?;
+    let mut dst = output_buffer(src, len);
+
+    for i in 0..len {
+        let j = i % n;
+
+        let f = states[j] & ((1 << BITS) - 1);
+        let s = table[f as usize];
+        dst.push(s);
+
+        let k = usize::from(s);
+        states[j] = rans_advance_step(states[j], cumulative_freqs[k], freqs[k], BITS);
+        states[j] = rans_renorm(src, states[j])?;
+    }
+
+    Ok(dst)
+}
+
+fn read_frequencies<R>(reader: &mut R) -> io::Result<Vec<u32>>
+where
+    R: Read,
+{
+    let alphabet = read_alphabet(reader)?;
+
+    let mut freqs = vec![0; alphabet.len()];
+
+    for (f, _) in freqs.iter_mut().zip(alphabet).filter(|(_, a)| *a) {
+        *f = read_uint7(reader)?;
+    }
+
+    normalize_frequencies(&mut freqs, BITS)?;
+
+    Ok(freqs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_frequencies() -> io::Result<()> {
+        let data = [
+            0x61, // 'a'
+            0x62, 0x01, // 'b', rle = 1
+            0x00, // end
+            0x02, // f['a'] = 2
+            0x01, // f['b'] = 1
+            0x01, // f['c'] = 1
+        ];
+
+        let freqs = read_frequencies(&mut &data[..])?;
+
+        assert_eq!(freqs[usize::from(b'a')], 2048);
+        assert_eq!(freqs[usize::from(b'b')], 1024);
+        assert_eq!(freqs[usize::from(b'c')], 1024);
+        assert_eq!(freqs.iter().sum::<u32>(), 4096);
+
+        Ok(())
+    }
+}
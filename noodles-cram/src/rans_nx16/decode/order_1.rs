@@ -0,0 +1,134 @@
+use std::io::{self, Read};
+
+use byteorder::ReadBytesExt;
+
+use super::{
+    build_cumulative_frequencies, build_symbol_table, normalize_frequencies, order_0,
+    output_buffer, rans_advance_step, rans_renorm, read_alphabet, read_states, read_uint7,
+    read_uint7_as_usize, split_off,
+};
+
+const MAX_BITS: u32 = 12;
+
+struct Context {
+    freqs: Vec<u32>,
+    cumulative_freqs: Vec<u32>,
+    table: Vec<u8>,
+}
+
+pub fn decode(src: &mut &[u8], len: usize, n: usize) -> io::Result<Vec<u8>> {
+    let (contexts, bits) = read_contexts(src)?;
+    let mut states = read_states(src, n)?;
+
+    let chunk_len = len / n;
+
+    // Each state decodes a contiguous chunk of the output. The last chunk includes the remainder.
+    let mut chunks: Vec<_> = (0..n).map(|_| output_buffer(src, chunk_len)).collect();
+    let mut prev_syms = vec![0; n];
+
+    let mut decode_symbol = |src: &mut &[u8], j: usize| -> io::Result<u8> {
+        let context = contexts[usize::from(prev_syms[j])]
+            .as_ref()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "missing context"))?;
+
+        let f = states[j] & ((1 << bits) - 1);
+        let s = context.table[f as usize];
+
+        let k = usize::from(s);
+        states[j] = rans_advance_step(
+            states[j],
+            context.cumulative_freqs[k],
+            context.freqs[k],
+            bits,
+        );
+        states[j] = rans_renorm(src, states[j])?;
+
+        prev_syms[j] = s;
+
+        Ok(s)
+    };
+
+    for _ in 0..chunk_len {
+        for (j, chunk) in chunks.iter_mut().enumerate() {
+            chunk.push(decode_symbol(src, j)?);
+        }
+    }
+
+    for _ in n * chunk_len..len {
+        let s = decode_symbol(src, n - 1)?;
+        chunks[n - 1].push(s);
+    }
+
+    Ok(chunks.concat())
+}
+
+fn read_contexts(src: &mut &[u8]) -> io::Result<(Vec<Option<Context>>, u32)> {
+    let b = src.read_u8()?;
+
+    let bits = u32::from(b >> 4);
+
+    if bits > MAX_BITS {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!(
+                "invalid frequency table bit count: expected <= {}, got {}",
+                MAX_BITS, bits
+            ),
+        ));
+    }
+
+    let is_compressed = b & 0x01 != 0;
+
+    let contexts = if is_compressed {
+        let uncompressed_len = read_uint7_as_usize(src)?;
+        let compressed_len = read_uint7_as_usize(src)?;
+
+        let mut buf = split_off(src, compressed_len)?;
+        let data = order_0::decode(&mut buf, uncompressed_len, 4)?;
+
+        read_frequencies(&mut &data[..], bits)?
+    } else {
+        read_frequencies(src, bits)?
+    };
+
+    Ok((contexts, bits))
+}
+
+fn read_frequencies<R>(reader: &mut R, bits: u32) -> io::Result<Vec<Option<Context>>>
+where
+    R: Read,
+{
+    let alphabet = read_alphabet(reader)?;
+
+    let mut contexts: Vec<_> = (0..alphabet.len()).map(|_| None).collect();
+
+    for (context, _) in contexts.iter_mut().zip(alphabet).filter(|(_, a)| *a) {
+        let mut freqs = vec![0; alphabet.len()];
+        let mut run = 0;
+
+        for (f, _) in freqs.iter_mut().zip(alphabet).filter(|(_, a)| *a) {
+            if run > 0 {
+                run -= 1;
+            } else {
+                *f = read_uint7(reader)?;
+
+                if *f == 0 {
+                    run = reader.read_u8()?;
+                }
+            }
+        }
+
+        normalize_frequencies(&mut freqs, bits)?;
+
+        let cumulative_freqs = build_cumulative_frequencies(&freqs);
+        let table = build_symbol_table(&freqs, &cumulative_freqs, bits)?;
+
+        *context = Some(Context {
+            freqs,
+            cumulative_freqs,
+            table,
+        });
+    }
+
+    Ok(contexts)
+}
@@ -0,0 +1,98 @@
+use std::io;
+
+use byteorder::ReadBytesExt;
+
+use super::{read_uint7_as_usize, split_off};
+
+pub fn read_context(src: &mut &[u8]) -> io::Result<(Vec<u8>, usize)> {
+    let n = src.read_u8().map(usize::from)?;
+    let mapping = split_off(src, n).map(|buf| buf.to_vec())?;
+    let packed_len = read_uint7_as_usize(src)?;
+    Ok((mapping, packed_len))
+}
+
+pub fn decode(src: &[u8], mapping: &[u8], len: usize) -> io::Result<Vec<u8>> {
+    let bits = match mapping.len() {
+        1 => return Ok(vec![mapping[0]; len]),
+        2 => 1,
+        3..=4 => 2,
+        5..=16 => 4,
+        n => {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("invalid pack symbol count: expected 1..=16, got {}", n),
+            ))
+        }
+    };
+
+    let symbols_per_byte = 8 / bits;
+    let mask = (1 << bits) - 1;
+
+    let expected_src_len = len / symbols_per_byte + usize::from(len % symbols_per_byte > 0);
+
+    if src.len() != expected_src_len {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!(
+                "packed length mismatch: expected {}, got {}",
+                expected_src_len,
+                src.len()
+            ),
+        ));
+    }
+
+    (0..len)
+        .map(|i| {
+            let b = src[i / symbols_per_byte];
+            let j = (b >> ((i % symbols_per_byte) * bits)) & mask;
+
+            mapping
+                .get(usize::from(j))
+                .copied()
+                .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "invalid packed symbol"))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode() -> io::Result<()> {
+        assert_eq!(decode(&[], b"n", 3)?, b"nnn");
+
+        // 0b0110
+        assert_eq!(decode(&[0x06], b"ln", 4)?, b"lnnl");
+
+        // 0b00_10_01_00, 0b11
+        assert_eq!(decode(&[0x24, 0x03], b"acgt", 5)?, b"acgat");
+
+        // 0x10, 0x32, 0x05
+        assert_eq!(decode(&[0x10, 0x32, 0x05], b"deilnos", 5)?, b"deilo");
+
+        assert!(decode(&[0x00], &[], 1).is_err());
+        assert!(decode(&[0x00, 0x00], b"ln", 4).is_err());
+        assert!(decode(&[0x0f], b"acgtn", 1).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_context() -> io::Result<()> {
+        let data = [
+            0x02, // symbol count = 2
+            b'l', b'n', // mapping
+            0x01, // packed length = 1
+        ];
+
+        let mut src = &data[..];
+        let (mapping, packed_len) = read_context(&mut src)?;
+
+        assert_eq!(mapping, b"ln");
+        assert_eq!(packed_len, 1);
+        assert!(src.is_empty());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,114 @@
+use std::io::{self, Read};
+
+use byteorder::ReadBytesExt;
+
+use super::{order_0, output_buffer, read_uint7_as_usize, split_off};
+
+pub fn read_context(src: &mut &[u8]) -> io::Result<(Vec<u8>, usize)> {
+    let context_len = read_uint7_as_usize(src)?;
+    let literals_len = read_uint7_as_usize(src)?;
+
+    let is_uncompressed = context_len & 0x01 != 0;
+    let uncompressed_len = context_len >> 1;
+
+    let meta = if is_uncompressed {
+        split_off(src, uncompressed_len).map(|buf| buf.to_vec())?
+    } else {
+        let compressed_len = read_uint7_as_usize(src)?;
+        let mut buf = split_off(src, compressed_len)?;
+        order_0::decode(&mut buf, uncompressed_len, 4)?
+    };
+
+    Ok((meta, literals_len))
+}
+
+pub fn decode(src: &[u8], mut meta: &[u8], len: usize) -> io::Result<Vec<u8>> {
+    let symbols = read_symbols(&mut meta)?;
+
+    let mut dst = output_buffer(src, len);
+
+    for &sym in src {
+        let run_len = if symbols[usize::from(sym)] {
+            read_uint7_as_usize(&mut meta)?
+        } else {
+            0
+        };
+
+        if dst.len() + run_len >= len {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "invalid run length",
+            ));
+        }
+
+        dst.resize(dst.len() + run_len + 1, sym);
+    }
+
+    if dst.len() != len {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("RLE length mismatch: expected {}, got {}", len, dst.len()),
+        ));
+    }
+
+    Ok(dst)
+}
+
+fn read_symbols<R>(reader: &mut R) -> io::Result<[bool; 256]>
+where
+    R: Read,
+{
+    let mut symbols = [false; 256];
+
+    let n = match reader.read_u8()? {
+        0 => 256,
+        n => usize::from(n),
+    };
+
+    for _ in 0..n {
+        let sym = reader.read_u8()?;
+        symbols[usize::from(sym)] = true;
+    }
+
+    Ok(symbols)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode() -> io::Result<()> {
+        let src = b"nodles";
+
+        let meta = [
+            0x01, // symbol count = 1
+            b'o', // symbols[0] = 'o'
+            0x01, // run length = 1
+        ];
+
+        assert_eq!(decode(src, &meta, 7)?, b"noodles");
+        assert!(decode(src, &meta, 6).is_err());
+        assert!(decode(src, &meta, 8).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_context() -> io::Result<()> {
+        let data = [
+            0x07, // context length = 3, uncompressed
+            0x06, // literals length = 6
+            0x01, b'o', 0x01, // context
+        ];
+
+        let mut src = &data[..];
+        let (meta, literals_len) = read_context(&mut src)?;
+
+        assert_eq!(meta, [0x01, b'o', 0x01]);
+        assert_eq!(literals_len, 6);
+        assert!(src.is_empty());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,284 @@
+mod order_0;
+mod order_1;
+pub(crate) mod pack;
+mod rle;
+
+use std::{
+    borrow::Cow,
+    io::{self, Write},
+};
+
+use byteorder::{BigEndian, LittleEndian, WriteBytesExt};
+
+use super::{Flags, LOWER_BOUND};
+use crate::writer::num::write_uint7;
+
+const STRIPE_COUNT: usize = 4;
+
+pub fn rans_encode_nx16(mut flags: Flags, src: &[u8]) -> io::Result<Vec<u8>> {
+    if src.is_empty() {
+        flags = (flags & Flags::NO_SIZE) | Flags::CAT;
+    }
+
+    let mut dst = Vec::new();
+
+    if flags.contains(Flags::STRIPE) {
+        write_header(&mut dst, flags, src.len())?;
+        encode_stripe(&mut dst, flags, src)?;
+        return Ok(dst);
+    }
+
+    let n = if flags.contains(Flags::N32) { 32 } else { 4 };
+
+    let mut context = Vec::new();
+    let mut data = Cow::from(src);
+
+    if flags.contains(Flags::PACK) {
+        match pack::build_mapping(&data) {
+            Some(mapping) => {
+                let packed_data = pack::encode(&data, &mapping);
+                pack::write_context(&mut context, &mapping, packed_data.len())?;
+                data = Cow::from(packed_data);
+            }
+            None => flags.remove(Flags::PACK),
+        }
+    }
+
+    if flags.contains(Flags::RLE) {
+        match rle::encode(&data)? {
+            Some((meta, literals)) => {
+                rle::write_context(&mut context, &meta, literals.len())?;
+                data = Cow::from(literals);
+            }
+            None => flags.remove(Flags::RLE),
+        }
+    }
+
+    write_header(&mut dst, flags, src.len())?;
+    dst.extend(context);
+
+    if flags.contains(Flags::CAT) {
+        dst.extend(data.iter());
+    } else if data.is_empty() {
+        // An empty stream has no frequency table or states.
+    } else if flags.contains(Flags::ORDER) {
+        order_1::encode(&mut dst, &data, n)?;
+    } else {
+        order_0::encode(&mut dst, &data, n)?;
+    }
+
+    Ok(dst)
+}
+
+fn write_header<W>(writer: &mut W, flags: Flags, len: usize) -> io::Result<()>
+where
+    W: Write,
+{
+    writer.write_u8(flags.bits())?;
+
+    if !flags.contains(Flags::NO_SIZE) {
+        write_uint7_as_usize(writer, len)?;
+    }
+
+    Ok(())
+}
+
+fn encode_stripe(dst: &mut Vec<u8>, flags: Flags, src: &[u8]) -> io::Result<()> {
+    let chunk_flags = (flags - Flags::STRIPE) | Flags::NO_SIZE;
+
+    let chunks = (0..STRIPE_COUNT)
+        .map(|j| {
+            let chunk: Vec<_> = src.iter().skip(j).step_by(STRIPE_COUNT).copied().collect();
+            rans_encode_nx16(chunk_flags, &chunk)
+        })
+        .collect::<io::Result<Vec<_>>>()?;
+
+    dst.write_u8(STRIPE_COUNT as u8)?;
+
+    for chunk in &chunks {
+        write_uint7_as_usize(dst, chunk.len())?;
+    }
+
+    for chunk in chunks {
+        dst.extend(chunk);
+    }
+
+    Ok(())
+}
+
+pub(crate) fn write_uint7_as_usize<W>(writer: &mut W, n: usize) -> io::Result<()>
+where
+    W: Write,
+{
+    let n = u32::try_from(n).map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+    write_uint7(writer, n)
+}
+
+fn write_alphabet<W>(writer: &mut W, alphabet: &[bool]) -> io::Result<()>
+where
+    W: Write,
+{
+    let mut rle = 0;
+
+    for (sym, _) in alphabet.iter().enumerate().filter(|(_, &a)| a) {
+        if rle > 0 {
+            rle -= 1;
+            continue;
+        }
+
+        writer.write_u8(sym as u8)?;
+
+        if sym > 0 && alphabet[sym - 1] {
+            rle = alphabet[sym + 1..]
+                .iter()
+                .position(|&a| !a)
+                .unwrap_or(alphabet.len() - sym - 1);
+
+            writer.write_u8(rle as u8)?;
+        }
+    }
+
+    writer.write_u8(0x00)?;
+
+    Ok(())
+}
+
+fn write_states<W>(writer: &mut W, states: &[u32]) -> io::Result<()>
+where
+    W: Write,
+{
+    for &state in states {
+        writer.write_u32::<LittleEndian>(state)?;
+    }
+
+    Ok(())
+}
+
+// Scales the frequencies to a total of `1 << bits`, keeping all nonzero frequencies nonzero.
+fn normalize_frequencies(freqs: &[u32], bits: u32) -> Vec<u32> {
+    let total = 1 << bits;
+    let sum: u64 = freqs.iter().copied().map(u64::from).sum();
+
+    if sum == 0 {
+        return vec![0; freqs.len()];
+    }
+
+    let mut normalized_freqs: Vec<_> = freqs
+        .iter()
+        .map(|&f| {
+            if f == 0 {
+                0
+            } else {
+                (u64::from(f) * total / sum).max(1) as u32
+            }
+        })
+        .collect();
+
+    let mut normalized_sum: u64 = normalized_freqs.iter().copied().map(u64::from).sum();
+
+    // Rounding up rare symbols can overshoot the total, and truncation can undershoot it. The
+    // difference is taken from or given to the most frequent symbols.
+    while normalized_sum > total {
+        if let Some(f) = normalized_freqs.iter_mut().max() {
+            *f -= 1;
+            normalized_sum -= 1;
+        }
+    }
+
+    if normalized_sum < total {
+        if let Some(f) = normalized_freqs.iter_mut().max() {
+            *f += (total - normalized_sum) as u32;
+        }
+    }
+
+    normalized_freqs
+}
+
+fn build_cumulative_frequencies(freqs: &[u32]) -> Vec<u32> {
+    let mut cumulative_freqs = vec![0; freqs.len()];
+
+    for i in 0..freqs.len() - 1 {
+        cumulative_freqs[i + 1] = cumulative_freqs[i] + freqs[i];
+    }
+
+    cumulative_freqs
+}
+
+fn normalize<W>(writer: &mut W, mut x: u32, freq_i: u32, bits: u32) -> io::Result<u32>
+where
+    W: Write,
+{
+    let x_max = ((LOWER_BOUND >> bits) << 16) * freq_i;
+
+    if x >= x_max {
+        // The output buffer is reversed when complete, so words are written big-endian here to
+        // be read little-endian when decoding.
+        writer.write_u16::<BigEndian>(x as u16)?;
+        x >>= 16;
+    }
+
+    Ok(x)
+}
+
+fn update(x: u32, freq_i: u32, cfreq_i: u32, bits: u32) -> u32 {
+    ((x / freq_i) << bits) + (x % freq_i) + cfreq_i
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rans_encode_nx16_with_cat() -> io::Result<()> {
+        let actual = rans_encode_nx16(Flags::CAT, b"noodles")?;
+        let expected = [0x20, 0x07, b'n', b'o', b'o', b'd', b'l', b'e', b's'];
+        assert_eq!(actual, expected);
+
+        let actual = rans_encode_nx16(Flags::ORDER, b"")?;
+        assert_eq!(actual, [0x20, 0x00]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_write_alphabet() -> io::Result<()> {
+        let mut alphabet = [false; 256];
+
+        for &sym in b"abcdr" {
+            alphabet[usize::from(sym)] = true;
+        }
+
+        let mut buf = Vec::new();
+        write_alphabet(&mut buf, &alphabet)?;
+
+        let expected = [
+            0x61, // 'a'
+            0x62, 0x02, // 'b', rle = 2
+            0x72, // 'r'
+            0x00, // end
+        ];
+
+        assert_eq!(buf, expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_normalize_frequencies() {
+        assert_eq!(
+            normalize_frequencies(&[1, 2, 0, 1], 12),
+            [1024, 2048, 0, 1024]
+        );
+        assert_eq!(
+            normalize_frequencies(&[1, 2, 3, 0], 12),
+            [682, 1365, 2049, 0]
+        );
+        assert_eq!(normalize_frequencies(&[0, 0], 12), [0, 0]);
+
+        let mut freqs = vec![1; 255];
+        freqs.push(1 << 20);
+        let normalized_freqs = normalize_frequencies(&freqs, 12);
+        assert!(normalized_freqs.iter().all(|&f| f > 0));
+        assert_eq!(normalized_freqs.iter().sum::<u32>(), 4096);
+    }
+}
@@ -0,0 +1,87 @@
+use std::io::{self, Write};
+
+use super::{
+    build_cumulative_frequencies, normalize, normalize_frequencies, update, write_alphabet,
+    write_states, write_uint7, LOWER_BOUND,
+};
+
+const BITS: u32 = 12;
+
+pub fn encode(dst: &mut Vec<u8>, src: &[u8], n: usize) -> io::Result<()> {
+    let frequencies = build_frequencies(src);
+
+    let freq = normalize_frequencies(&frequencies, BITS);
+    write_frequencies(dst, &freq)?;
+
+    let cfreq = build_cumulative_frequencies(&freq);
+
+    let mut buf = Vec::new();
+    let mut states = vec![LOWER_BOUND; n];
+
+    for (i, &sym) in src.iter().enumerate().rev() {
+        let j = i % n;
+        let k = usize::from(sym);
+
+        let x = normalize(&mut buf, states[j], freq[k], BITS)?;
+        states[j] = update(x, freq[k], cfreq[k], BITS);
+    }
+
+    write_states(dst, &states)?;
+    dst.extend(buf.iter().rev());
+
+    Ok(())
+}
+
+fn build_frequencies(src: &[u8]) -> Vec<u32> {
+    let mut frequencies = vec![0; 256];
+
+    for &b in src {
+        frequencies[usize::from(b)] += 1;
+    }
+
+    frequencies
+}
+
+fn write_frequencies<W>(writer: &mut W, frequencies: &[u32]) -> io::Result<()>
+where
+    W: Write,
+{
+    let alphabet: Vec<_> = frequencies.iter().map(|&f| f > 0).collect();
+    write_alphabet(writer, &alphabet)?;
+
+    for &f in frequencies.iter().filter(|&&f| f > 0) {
+        write_uint7(writer, f)?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_write_frequencies() -> io::Result<()> {
+        let frequencies = build_frequencies(b"abracadabra");
+        let normalized_frequencies = normalize_frequencies(&frequencies, BITS);
+
+        let mut buf = Vec::new();
+        write_frequencies(&mut buf, &normalized_frequencies)?;
+
+        let expected = [
+            0x61, // 'a'
+            0x62, 0x02, // 'b', rle = 2
+            0x72, // 'r'
+            0x00, // end
+            0x8e, 0x48, // f['a'] = 1864
+            0x85, 0x68, // f['b'] = 744
+            0x82, 0x74, // f['c'] = 372
+            0x82, 0x74, // f['d'] = 372
+            0x85, 0x68, // f['r'] = 744
+        ];
+
+        assert_eq!(buf, expected);
+
+        Ok(())
+    }
+}
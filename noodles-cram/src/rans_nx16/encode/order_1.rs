@@ -0,0 +1,194 @@
+use std::io::{self, Write};
+
+use byteorder::WriteBytesExt;
+
+use super::{
+    build_cumulative_frequencies, normalize, normalize_frequencies, order_0, update,
+    write_alphabet, write_states, write_uint7, write_uint7_as_usize, LOWER_BOUND,
+};
+
+const BITS: u32 = 12;
+
+pub fn encode(dst: &mut Vec<u8>, src: &[u8], n: usize) -> io::Result<()> {
+    let contexts = build_contexts(src, n);
+
+    let freq: Vec<_> = contexts
+        .iter()
+        .map(|frequencies| normalize_frequencies(frequencies, BITS))
+        .collect();
+
+    write_contexts(dst, &freq)?;
+
+    let cfreq: Vec<_> = freq
+        .iter()
+        .map(|f| build_cumulative_frequencies(f))
+        .collect();
+
+    let mut buf = Vec::new();
+    let mut states = vec![LOWER_BOUND; n];
+
+    let mut put = |buf: &mut Vec<u8>, j: usize, prev_sym: u8, sym: u8| -> io::Result<()> {
+        let (i, k) = (usize::from(prev_sym), usize::from(sym));
+        let x = normalize(buf, states[j], freq[i][k], BITS)?;
+        states[j] = update(x, freq[i][k], cfreq[i][k], BITS);
+        Ok(())
+    };
+
+    let chunk_len = src.len() / n;
+
+    for i in (n * chunk_len..src.len()).rev() {
+        let prev_sym = if i == 0 { 0 } else { src[i - 1] };
+        put(&mut buf, n - 1, prev_sym, src[i])?;
+    }
+
+    for i in (0..chunk_len).rev() {
+        for j in (0..n).rev() {
+            let k = j * chunk_len + i;
+            let prev_sym = if i == 0 { 0 } else { src[k - 1] };
+            put(&mut buf, j, prev_sym, src[k])?;
+        }
+    }
+
+    write_states(dst, &states)?;
+    dst.extend(buf.iter().rev());
+
+    Ok(())
+}
+
+// Each of the `n` states decodes a contiguous chunk of the input, starting with a context of 0.
+// The last state also decodes the remainder.
+fn build_contexts(src: &[u8], n: usize) -> Vec<Vec<u32>> {
+    let mut contexts = vec![vec![0; 256]; 256];
+
+    let chunk_len = src.len() / n;
+
+    for j in 0..n {
+        let start = j * chunk_len;
+        let end = if j == n - 1 {
+            src.len()
+        } else {
+            start + chunk_len
+        };
+
+        let mut prev_sym = 0;
+
+        for &sym in &src[start..end] {
+            contexts[usize::from(prev_sym)][usize::from(sym)] += 1;
+            prev_sym = sym;
+        }
+    }
+
+    contexts
+}
+
+fn write_contexts(dst: &mut Vec<u8>, contexts: &[Vec<u32>]) -> io::Result<()> {
+    let mut buf = Vec::new();
+    write_frequencies(&mut buf, contexts)?;
+
+    let mut compressed_buf = Vec::new();
+    order_0::encode(&mut compressed_buf, &buf, 4)?;
+
+    let bits = (BITS as u8) << 4;
+
+    if compressed_buf.len() < buf.len() {
+        dst.write_u8(bits | 0x01)?;
+        write_uint7_as_usize(dst, buf.len())?;
+        write_uint7_as_usize(dst, compressed_buf.len())?;
+        dst.extend(compressed_buf);
+    } else {
+        dst.write_u8(bits)?;
+        dst.extend(buf);
+    }
+
+    Ok(())
+}
+
+fn write_frequencies<W>(writer: &mut W, contexts: &[Vec<u32>]) -> io::Result<()>
+where
+    W: Write,
+{
+    let mut alphabet = vec![false; contexts.len()];
+
+    for (i, frequencies) in contexts.iter().enumerate() {
+        for (j, &f) in frequencies.iter().enumerate() {
+            if f > 0 {
+                alphabet[i] = true;
+                alphabet[j] = true;
+            }
+        }
+    }
+
+    write_alphabet(writer, &alphabet)?;
+
+    for (frequencies, _) in contexts.iter().zip(&alphabet).filter(|(_, &a)| a) {
+        let mut run = 0;
+
+        for (j, &f) in frequencies.iter().enumerate().filter(|(j, _)| alphabet[*j]) {
+            if run > 0 {
+                run -= 1;
+                continue;
+            }
+
+            write_uint7(writer, f)?;
+
+            if f == 0 {
+                run = frequencies[j + 1..]
+                    .iter()
+                    .zip(&alphabet[j + 1..])
+                    .filter(|(_, &a)| a)
+                    .take_while(|(&g, _)| g == 0)
+                    .take(255)
+                    .count();
+
+                writer.write_u8(run as u8)?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_contexts() {
+        let contexts = build_contexts(b"noodles", 4);
+
+        assert_eq!(contexts[0][usize::from(b'n')], 1);
+        assert_eq!(contexts[0][usize::from(b'o')], 2);
+        assert_eq!(contexts[0][usize::from(b'd')], 1);
+        assert_eq!(contexts[usize::from(b'd')][usize::from(b'l')], 1);
+        assert_eq!(contexts[usize::from(b'l')][usize::from(b'e')], 1);
+        assert_eq!(contexts[usize::from(b'e')][usize::from(b's')], 1);
+        assert_eq!(contexts.iter().flatten().sum::<u32>(), 7);
+    }
+
+    #[test]
+    fn test_write_frequencies() -> io::Result<()> {
+        let mut contexts = vec![vec![0; 256]; 256];
+        contexts[0][usize::from(b'a')] = 4096;
+        contexts[usize::from(b'a')][usize::from(b'b')] = 4096;
+
+        let mut buf = Vec::new();
+        write_frequencies(&mut buf, &contexts)?;
+
+        let expected = [
+            0x00, // 0x00
+            0x61, // 'a'
+            0x62, 0x00, // 'b', rle = 0
+            0x00, // end
+            0x00, 0x00, // f[0x00][0x00] = 0, run = 0
+            0xa0, 0x00, // f[0x00]['a'] = 4096
+            0x00, 0x00, // f[0x00]['b'] = 0, run = 0
+            0x00, 0x01, // f['a'][0x00] = 0, run = 1
+            0xa0, 0x00, // f['a']['b'] = 4096
+            0x00, 0x02, // f['b'][0x00] = 0, run = 2
+        ];
+
+        assert_eq!(buf, expected);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,80 @@
+use std::io::{self, Write};
+
+use byteorder::WriteBytesExt;
+
+use super::write_uint7_as_usize;
+
+const MAX_SYMBOL_COUNT: usize = 16;
+
+// Returns the sorted list of symbols in the input if there are few enough to be packed.
+pub fn build_mapping(src: &[u8]) -> Option<Vec<u8>> {
+    let mut alphabet = [false; 256];
+
+    for &b in src {
+        alphabet[usize::from(b)] = true;
+    }
+
+    let mapping: Vec<_> = (0..=255).filter(|&b| alphabet[usize::from(b)]).collect();
+
+    if mapping.is_empty() || mapping.len() > MAX_SYMBOL_COUNT {
+        None
+    } else {
+        Some(mapping)
+    }
+}
+
+pub fn encode(src: &[u8], mapping: &[u8]) -> Vec<u8> {
+    let bits = match mapping.len() {
+        1 => return Vec::new(),
+        2 => 1,
+        3..=4 => 2,
+        _ => 4,
+    };
+
+    let mut table = [0; 256];
+
+    for (i, &sym) in mapping.iter().enumerate() {
+        table[usize::from(sym)] = i as u8;
+    }
+
+    let symbols_per_byte = 8 / bits;
+    let packed_len = src.len() / symbols_per_byte + usize::from(src.len() % symbols_per_byte > 0);
+    let mut dst = vec![0; packed_len];
+
+    for (i, &sym) in src.iter().enumerate() {
+        let j = table[usize::from(sym)];
+        dst[i / symbols_per_byte] |= j << ((i % symbols_per_byte) * bits);
+    }
+
+    dst
+}
+
+pub fn write_context<W>(writer: &mut W, mapping: &[u8], packed_len: usize) -> io::Result<()>
+where
+    W: Write,
+{
+    writer.write_u8(mapping.len() as u8)?;
+    writer.write_all(mapping)?;
+    write_uint7_as_usize(writer, packed_len)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_mapping() {
+        assert_eq!(build_mapping(b"noodles"), Some(b"delnos".to_vec()));
+        assert!(build_mapping(b"").is_none());
+        assert!(build_mapping(b"abcdefghijklmnopq").is_none());
+    }
+
+    #[test]
+    fn test_encode() {
+        assert!(encode(b"nnn", b"n").is_empty());
+        assert_eq!(encode(b"lnnl", b"ln"), [0x06]);
+        assert_eq!(encode(b"acgat", b"acgt"), [0x24, 0x03]);
+        assert_eq!(encode(b"deilo", b"deilnos"), [0x10, 0x32, 0x05]);
+    }
+}
@@ -0,0 +1,113 @@
+use std::io;
+
+use byteorder::WriteBytesExt;
+
+use super::{order_0, write_uint7_as_usize};
+
+// Returns the RLE metadata (symbols and run lengths) and the literals. If no symbol benefits from
+// being run-length encoded, this returns `None`.
+pub fn encode(src: &[u8]) -> io::Result<Option<(Vec<u8>, Vec<u8>)>> {
+    let symbols = select_symbols(src);
+    let symbol_count = symbols.iter().filter(|&&s| s).count();
+
+    if symbol_count == 0 {
+        return Ok(None);
+    }
+
+    let mut meta = Vec::new();
+
+    // A symbol count of 0 represents 256 symbols.
+    meta.write_u8(symbol_count as u8)?;
+
+    for (sym, _) in symbols.iter().enumerate().filter(|(_, &s)| s) {
+        meta.write_u8(sym as u8)?;
+    }
+
+    let mut literals = Vec::new();
+    let mut i = 0;
+
+    while i < src.len() {
+        let sym = src[i];
+        literals.push(sym);
+
+        if symbols[usize::from(sym)] {
+            let run_len = src[i..].iter().take_while(|&&b| b == sym).count();
+            write_uint7_as_usize(&mut meta, run_len - 1)?;
+            i += run_len;
+        } else {
+            i += 1;
+        }
+    }
+
+    Ok(Some((meta, literals)))
+}
+
+// A symbol is selected if it is repeated more often than not.
+fn select_symbols(src: &[u8]) -> [bool; 256] {
+    let mut scores = [0i64; 256];
+    let mut prev_sym = None;
+
+    for &sym in src {
+        let score = &mut scores[usize::from(sym)];
+
+        if prev_sym == Some(sym) {
+            *score += 1;
+        } else {
+            *score -= 1;
+        }
+
+        prev_sym = Some(sym);
+    }
+
+    scores.map(|score| score > 0)
+}
+
+pub fn write_context(dst: &mut Vec<u8>, meta: &[u8], literals_len: usize) -> io::Result<()> {
+    let mut compressed_meta = Vec::new();
+    order_0::encode(&mut compressed_meta, meta, 4)?;
+
+    if compressed_meta.len() < meta.len() {
+        write_uint7_as_usize(dst, meta.len() << 1)?;
+        write_uint7_as_usize(dst, literals_len)?;
+        write_uint7_as_usize(dst, compressed_meta.len())?;
+        dst.extend(compressed_meta);
+    } else {
+        write_uint7_as_usize(dst, meta.len() << 1 | 0x01)?;
+        write_uint7_as_usize(dst, literals_len)?;
+        dst.extend(meta);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode() -> io::Result<()> {
+        assert!(encode(b"noodles")?.is_none());
+
+        let (meta, literals) = encode(b"nooodles")?.expect("missing RLE data");
+        assert_eq!(meta, [0x01, b'o', 0x02]);
+        assert_eq!(literals, b"nodles");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_write_context() -> io::Result<()> {
+        let mut buf = Vec::new();
+        write_context(&mut buf, &[0x01, b'o', 0x02], 6)?;
+
+        let expected = [
+            0x07, // context length = 3, uncompressed
+            0x06, // literals length = 6
+            0x01, b'o', 0x02, // context
+        ];
+
+        assert_eq!(buf, expected);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,20 @@
+bitflags::bitflags! {
+    /// rANS Nx16 format flags.
+    #[derive(Default)]
+    pub struct Flags: u8 {
+        /// Order-1 entropy coding (`0x01`).
+        const ORDER = 0x01;
+        /// Interleave 32 states instead of 4 (`0x04`).
+        const N32 = 0x04;
+        /// Interleave the data into `N` separately compressed streams (`0x08`).
+        const STRIPE = 0x08;
+        /// The uncompressed size is not stored (`0x10`).
+        const NO_SIZE = 0x10;
+        /// The data is not entropy coded (`0x20`).
+        const CAT = 0x20;
+        /// Run-length encode the data before entropy coding (`0x40`).
+        const RLE = 0x40;
+        /// Bit-pack the data before entropy coding (`0x80`).
+        const PACK = 0x80;
+    }
+}
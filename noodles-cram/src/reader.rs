@@ -1,24 +1,42 @@
 //! CRAM reader and record iterator.
 
+mod builder;
+pub(crate) mod checksum;
 mod container;
+mod container_summaries;
 pub(crate) mod data_container;
 pub(crate) mod num;
+pub(crate) mod query;
 pub(crate) mod record;
 mod records;
 
 use crate::data_container::DataContainer;
 
-pub use self::records::Records;
+pub use self::{
+    builder::Builder, checksum::ChecksumError, container_summaries::ContainerSummaries,
+    query::Query, records::Records,
+};
 
 use std::{
     io::{self, Read, Seek, SeekFrom},
+    ops::Bound,
     str,
 };
 
 use byteorder::{LittleEndian, ReadBytesExt};
+use noodles_bam as bam;
+use noodles_core::{region::Interval, Region};
+use noodles_sam::{self as sam, header::ReferenceSequences};
 
 use self::container::read_container;
-use super::{container::Block, file_definition::Version, FileDefinition, MAGIC_NUMBER};
+use super::{
+    container::Block, crai, file_definition::Version,
+    reference_sequence_repository::ReferenceSequenceRepository, Container, FileDefinition,
+    MAGIC_NUMBER,
+};
+
+// magic number (4) + format (2) + file ID (20)
+pub(crate) const FILE_DEFINITION_LEN: u64 = 26;
 
 /// A CRAM reader.
 ///
@@ -47,6 +65,9 @@ where
     R: Read,
 {
     inner: R,
+    reference_sequence_repository: ReferenceSequenceRepository,
+    validate_checksums: bool,
+    position: u64,
 }
 
 impl<R> Reader<R>
@@ -64,7 +85,21 @@ where
     /// # Ok::<(), io::Error>(())
     /// ```
     pub fn new(reader: R) -> Self {
-        Self { inner: reader }
+        Self::builder(reader).build()
+    }
+
+    /// Creates a CRAM reader builder.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_cram as cram;
+    /// let data = [];
+    /// let builder = cram::Reader::builder(&data[..]);
+    /// let reader = builder.build();
+    /// ```
+    pub fn builder(inner: R) -> Builder<R> {
+        Builder::new(inner)
     }
 
     /// Returns a reference to the underlying reader.
@@ -83,6 +118,10 @@ where
 
     /// Returns a mutable reference to the underlying reader.
     ///
+    /// The reader tracks the number of bytes it reads to report container offsets in checksum
+    /// errors. Reading from the underlying reader directly desynchronizes these offsets until the
+    /// next [`Self::seek`].
+    ///
     /// # Examples
     ///
     /// ```
@@ -109,6 +148,20 @@ where
         self.inner
     }
 
+    /// Returns a mutable reference to the reference sequence repository.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_cram as cram;
+    /// let data = [];
+    /// let mut reader = cram::Reader::new(&data[..]);
+    /// reader.reference_sequence_repository_mut().clear();
+    /// ```
+    pub fn reference_sequence_repository_mut(&mut self) -> &mut ReferenceSequenceRepository {
+        &mut self.reference_sequence_repository
+    }
+
     /// Reads the CRAM file definition.
     ///
     /// The CRAM magic number is also checked.
@@ -130,6 +183,8 @@ where
         let format = read_format(&mut self.inner)?;
         let file_id = read_file_id(&mut self.inner)?;
 
+        self.position += FILE_DEFINITION_LEN;
+
         Ok(FileDefinition::new(format, file_id))
     }
 
@@ -154,7 +209,7 @@ where
     /// # Ok::<(), io::Error>(())
     /// ```
     pub fn read_file_header(&mut self) -> io::Result<String> {
-        let container = read_container(&mut self.inner)?;
+        let container = self.read_container()?;
 
         if let Some(block) = container.blocks().first() {
             read_file_header_block(block)
@@ -169,8 +224,30 @@ where
     pub(crate) fn read_data_container_with_container_header(
         &mut self,
     ) -> io::Result<Option<(crate::container::Header, DataContainer)>> {
-        use self::data_container::read_data_container_with_container_header;
-        read_data_container_with_container_header(&mut self.inner)
+        use self::data_container::read_data_container;
+
+        let container = self.read_container()?;
+
+        if container.header().is_eof() {
+            return Ok(None);
+        }
+
+        read_data_container(container).map(Some)
+    }
+
+    // Reads a container and, if enabled, validates its checksums.
+    pub(crate) fn read_container(&mut self) -> io::Result<Container> {
+        use self::checksum::validate_checksums;
+
+        let container_offset = self.position;
+        let (container, checksums) = read_container(&mut self.inner)?;
+        self.position += checksums.len();
+
+        if self.validate_checksums {
+            validate_checksums(container_offset, &container, &checksums)?;
+        }
+
+        Ok(container)
     }
 
     /// Reads a data container.
@@ -194,15 +271,17 @@ where
     /// # Ok::<(), io::Error>(())
     /// ```
     pub fn read_data_container(&mut self) -> io::Result<Option<DataContainer>> {
-        use self::data_container::read_data_container;
-
-        read_data_container(&mut self.inner)
+        self.read_data_container_with_container_header()
+            .map(|result| result.map(|(_, data_container)| data_container))
     }
 
     /// Returns a iterator over records starting from the current stream position.
     ///
     /// The stream is expected to be at the start of a data container.
     ///
+    /// The read bases of mapped records are not resolved, so a reference sequence is not
+    /// required. Use [`Self::records_with_reference`] to resolve them.
+    ///
     /// # Examples
     ///
     /// ```no_run
@@ -220,7 +299,109 @@ where
     /// # Ok::<(), io::Error>(())
     /// ```
     pub fn records(&mut self) -> Records<'_, R> {
-        Records::new(self)
+        Records::new(self, None)
+    }
+
+    /// Returns a iterator over records starting from the current stream position, resolving the
+    /// read bases of mapped records.
+    ///
+    /// The stream is expected to be at the start of a data container.
+    ///
+    /// Read bases are resolved using the reader's reference sequence repository, where reference
+    /// sequences are looked up by name in the given header. An error is returned if a required
+    /// reference sequence is missing or does not match the slice reference MD5 checksum.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::{fs::File, io::BufReader};
+    /// use noodles_cram::{
+    ///     self as cram,
+    ///     reference_sequence_repository::{adapters, ReferenceSequenceRepository},
+    /// };
+    /// use noodles_fasta as fasta;
+    /// use noodles_sam as sam;
+    ///
+    /// let reference_sequence_records = File::open("reference.fa")
+    ///     .map(BufReader::new)
+    ///     .map(fasta::Reader::new)?
+    ///     .records()
+    ///     .collect::<Result<_, _>>()?;
+    ///
+    /// let repository =
+    ///     ReferenceSequenceRepository::new(adapters::Records::new(reference_sequence_records));
+    ///
+    /// let mut reader = File::open("sample.cram").map(|f| {
+    ///     cram::Reader::builder(f)
+    ///         .set_reference_sequence_repository(repository)
+    ///         .build()
+    /// })?;
+    /// reader.read_file_definition()?;
+    ///
+    /// let header: sam::Header = reader.read_file_header()?.parse()?;
+    ///
+    /// for result in reader.records_with_reference(&header) {
+    ///     let record = result?;
+    ///     println!("{:?}", record);
+    /// }
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn records_with_reference<'a>(&'a mut self, header: &'a sam::Header) -> Records<'a, R> {
+        Records::new(self, Some(header))
+    }
+
+    /// Returns an iterator over summaries of the data containers.
+    ///
+    /// The stream is expected to be directly after the file header container. Records are not
+    /// decoded, but the compression header and slice headers of each container are read.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::{fs::File, io};
+    /// use noodles_cram as cram;
+    ///
+    /// let mut reader = File::open("sample.cram").map(cram::Reader::new)?;
+    /// reader.read_file_definition()?;
+    /// reader.read_file_header()?;
+    ///
+    /// for result in reader.container_summaries() {
+    ///     let container = result?;
+    ///
+    ///     for slice in container.slices() {
+    ///         for block in slice.external_blocks() {
+    ///             println!(
+    ///                 "{}\t{:?}\t{}\t{}",
+    ///                 block.content_id(),
+    ///                 block.data_series(),
+    ///                 block.compressed_len(),
+    ///                 block.uncompressed_len()
+    ///             );
+    ///         }
+    ///     }
+    /// }
+    /// # Ok::<(), io::Error>(())
+    /// ```
+    pub fn container_summaries(&mut self) -> ContainerSummaries<'_, R> {
+        ContainerSummaries::new(self)
+    }
+
+    pub(crate) fn read_slice_records(
+        &mut self,
+        header: &sam::Header,
+        compression_header: &crate::data_container::CompressionHeader,
+        slice: &crate::data_container::Slice,
+    ) -> io::Result<Vec<crate::Record>> {
+        let mut records = slice.records(compression_header)?;
+
+        slice.resolve_records(
+            &mut self.reference_sequence_repository,
+            header,
+            compression_header,
+            &mut records,
+        )?;
+
+        Ok(slice.resolve_mates(records))
     }
 }
 
@@ -244,7 +425,9 @@ where
     /// # Ok::<(), io::Error>(())
     /// ```
     pub fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
-        self.inner.seek(pos)
+        let position = self.inner.seek(pos)?;
+        self.position = position;
+        Ok(position)
     }
 
     /// Returns the current position of the underlying reader.
@@ -263,6 +446,85 @@ where
     pub fn position(&mut self) -> io::Result<u64> {
         self.inner.seek(SeekFrom::Current(0))
     }
+
+    /// Returns an iterator over records that intersect the given region.
+    ///
+    /// Only the slices listed in the index that overlap the region are read. The unmapped region
+    /// (`*`) returns all records without a reference sequence.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::fs::File;
+    /// use noodles_core::{region::Interval, Region};
+    /// use noodles_cram::{self as cram, crai};
+    /// use noodles_sam as sam;
+    ///
+    /// let mut reader = File::open("sample.cram").map(cram::Reader::new)?;
+    /// reader.read_file_definition()?;
+    ///
+    /// let header: sam::Header = reader.read_file_header()?.parse()?;
+    ///
+    /// let index = crai::read("sample.cram.crai")?;
+    /// let region = Region::mapped("sq0", 8..=13);
+    /// let query = reader.query(&header, &index, &region)?;
+    ///
+    /// for result in query {
+    ///     let record = result?;
+    ///     println!("{:?}", record);
+    /// }
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn query<'a>(
+        &'a mut self,
+        header: &'a sam::Header,
+        index: &crai::Index,
+        region: &Region,
+    ) -> io::Result<Query<'a, R>> {
+        let (reference_sequence_id, interval) =
+            resolve_region(header.reference_sequences(), region)?;
+
+        Ok(Query::new(
+            self,
+            header,
+            index,
+            reference_sequence_id,
+            interval,
+        ))
+    }
+}
+
+pub(crate) fn resolve_region(
+    reference_sequences: &ReferenceSequences,
+    region: &Region,
+) -> io::Result<(Option<bam::record::ReferenceSequenceId>, Interval)> {
+    match region {
+        Region::Mapped(r) => {
+            let i = reference_sequences.get_index_of(r.name()).ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!(
+                        "region reference sequence does not exist in reference sequences: {:?}",
+                        region
+                    ),
+                )
+            })?;
+
+            let reference_sequence_id = i32::try_from(i)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))
+                .and_then(|id| {
+                    bam::record::ReferenceSequenceId::try_from(id)
+                        .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))
+                })?;
+
+            Ok((Some(reference_sequence_id), r.interval()))
+        }
+        Region::Unmapped => Ok((None, (Bound::Unbounded, Bound::Unbounded))),
+        Region::All => Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "region must be mapped or unmapped",
+        )),
+    }
 }
 
 fn read_magic_number<R>(reader: &mut R) -> io::Result<()>
@@ -408,4 +670,659 @@ mod tests {
             Err(ref e) if e.kind() == io::ErrorKind::InvalidData,
         ));
     }
+
+    fn build_reference_sequence_records() -> Vec<noodles_fasta::Record> {
+        use noodles_fasta::{
+            self as fasta,
+            record::{Definition, Sequence},
+        };
+
+        vec![fasta::Record::new(
+            Definition::new("sq0", None),
+            Sequence::from(b"TTCACCCA".to_vec()),
+        )]
+    }
+
+    fn build_data() -> Result<(sam::Header, Vec<u8>), Box<dyn std::error::Error>> {
+        let record = crate::Record::builder()
+            .set_bam_flags(sam::record::Flags::empty())
+            .set_flags(crate::record::Flags::QUALITY_SCORES_STORED_AS_ARRAY)
+            .set_reference_sequence_id(bam::record::ReferenceSequenceId::try_from(0)?)
+            .set_alignment_start(sam::record::Position::try_from(2)?)
+            .set_read_length(4)
+            .set_bases(b"TCAC".to_vec())
+            .set_quality_scores(vec![45, 35, 43, 50])
+            .build();
+
+        build_data_with_records(vec![record])
+    }
+
+    fn build_data_with_records(
+        records: Vec<crate::Record>,
+    ) -> Result<(sam::Header, Vec<u8>), Box<dyn std::error::Error>> {
+        build_data_with_reference_mode(records, crate::writer::ReferenceMode::External)
+    }
+
+    fn build_data_with_reference_mode(
+        records: Vec<crate::Record>,
+        reference_mode: crate::writer::ReferenceMode,
+    ) -> Result<(sam::Header, Vec<u8>), Box<dyn std::error::Error>> {
+        use md5::{Digest, Md5};
+        use sam::header::{reference_sequence::Md5Checksum, ReferenceSequence};
+
+        use crate::Writer;
+
+        let reference_sequence_records = build_reference_sequence_records();
+
+        let mut hasher = Md5::new();
+        hasher.update(reference_sequence_records[0].sequence());
+        let md5_checksum = Md5Checksum::from(<[u8; 16]>::from(hasher.finalize()));
+
+        let header = sam::Header::builder()
+            .add_reference_sequence(
+                ReferenceSequence::builder()
+                    .set_name("sq0".parse()?)
+                    .set_length(8)
+                    .set_md5_checksum(md5_checksum)
+                    .build()?,
+            )
+            .build();
+
+        let mut writer = Writer::builder(Vec::new())
+            .set_reference_sequences(reference_sequence_records)
+            .set_reference_mode(reference_mode)
+            .build();
+
+        writer.write_file_definition()?;
+        writer.write_file_header(&header)?;
+
+        for record in records {
+            writer.write_record(record)?;
+        }
+
+        writer.try_finish()?;
+
+        Ok((header, writer.get_ref().clone()))
+    }
+
+    #[test]
+    fn test_records() -> Result<(), Box<dyn std::error::Error>> {
+        let (_, data) = build_data()?;
+
+        // Read bases are not resolved, so no reference sequence is required.
+        let mut reader = Reader::new(&data[..]);
+        reader.read_file_definition()?;
+        reader.read_file_header()?;
+
+        let records: Vec<_> = reader.records().collect::<Result<_, _>>()?;
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].read_length(), 4);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_records_with_reference_sequence_repository() -> Result<(), Box<dyn std::error::Error>> {
+        use crate::reference_sequence_repository::adapters;
+
+        let (header, data) = build_data()?;
+
+        let repository = ReferenceSequenceRepository::new(adapters::Records::new(
+            build_reference_sequence_records(),
+        ));
+
+        let mut reader = Reader::builder(&data[..])
+            .set_reference_sequence_repository(repository)
+            .build();
+
+        reader.read_file_definition()?;
+        reader.read_file_header()?;
+
+        let records: Vec<_> = reader
+            .records_with_reference(&header)
+            .collect::<Result<_, _>>()?;
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].bases(), b"TCAC");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_records_with_missing_or_mismatched_reference_sequence(
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        use noodles_fasta::{
+            self as fasta,
+            record::{Definition, Sequence},
+        };
+
+        use crate::reference_sequence_repository::adapters;
+
+        let (header, data) = build_data()?;
+
+        let mut reader = Reader::new(&data[..]);
+        reader.read_file_definition()?;
+        reader.read_file_header()?;
+        assert!(matches!(
+            reader.records_with_reference(&header).next(),
+            Some(Err(e)) if e.kind() == io::ErrorKind::InvalidData
+        ));
+
+        let repository =
+            ReferenceSequenceRepository::new(adapters::Records::new(vec![fasta::Record::new(
+                Definition::new("sq0", None),
+                Sequence::from(b"TTCAGGGA".to_vec()),
+            )]));
+
+        let mut reader = Reader::builder(&data[..])
+            .set_reference_sequence_repository(repository)
+            .build();
+        reader.read_file_definition()?;
+        reader.read_file_header()?;
+        assert!(matches!(
+            reader.records_with_reference(&header).next(),
+            Some(Err(e)) if e.kind() == io::ErrorKind::InvalidData
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_records_with_soft_masked_reference_sequence() -> Result<(), Box<dyn std::error::Error>>
+    {
+        use noodles_fasta::{
+            self as fasta,
+            record::{Definition, Sequence},
+        };
+
+        use crate::{reference_sequence_repository::adapters, Writer};
+
+        let build_reference_sequence_records = || {
+            vec![fasta::Record::new(
+                Definition::new("sq0", None),
+                Sequence::from(b"ttcaCCCA".to_vec()),
+            )]
+        };
+
+        let (header, _) = build_data()?;
+
+        let record = crate::Record::builder()
+            .set_bam_flags(sam::record::Flags::empty())
+            .set_reference_sequence_id(bam::record::ReferenceSequenceId::try_from(0)?)
+            .set_alignment_start(sam::record::Position::try_from(2)?)
+            .set_read_length(4)
+            .set_bases(b"TCAC".to_vec())
+            .build();
+
+        let mut writer = Writer::builder(Vec::new())
+            .set_reference_sequences(build_reference_sequence_records())
+            .build();
+
+        writer.write_file_definition()?;
+        writer.write_file_header(&header)?;
+        writer.write_record(record)?;
+        writer.try_finish()?;
+
+        let data = writer.get_ref();
+
+        // The reference MD5 checksum is of the uppercased bases.
+        let repository =
+            ReferenceSequenceRepository::new(adapters::Records::new(vec![fasta::Record::new(
+                Definition::new("sq0", None),
+                Sequence::from(b"TTCACCCA".to_vec()),
+            )]));
+
+        let mut reader = Reader::builder(&data[..])
+            .set_reference_sequence_repository(repository)
+            .build();
+        reader.read_file_definition()?;
+        reader.read_file_header()?;
+
+        let records: Vec<_> = reader
+            .records_with_reference(&header)
+            .collect::<Result<_, _>>()?;
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].bases(), b"TCAC");
+
+        let repository = ReferenceSequenceRepository::new(adapters::Records::new(
+            build_reference_sequence_records(),
+        ));
+
+        let mut reader = Reader::builder(&data[..])
+            .set_reference_sequence_repository(repository)
+            .build();
+        reader.read_file_definition()?;
+        reader.read_file_header()?;
+
+        let records: Vec<_> = reader
+            .records_with_reference(&header)
+            .collect::<Result<_, _>>()?;
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].bases(), b"TCAC");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_records_with_read_features() -> Result<(), Box<dyn std::error::Error>> {
+        use crate::{
+            record::{Feature, Flags},
+            reference_sequence_repository::adapters,
+        };
+
+        const MISSING: u8 = 0xff;
+
+        let reference_sequence_id = bam::record::ReferenceSequenceId::try_from(0)?;
+        let alignment_start = sam::record::Position::try_from(1)?;
+
+        let build_record = |features, quality_scores: Vec<u8>| {
+            let flags = if quality_scores.is_empty() {
+                Flags::empty()
+            } else {
+                Flags::QUALITY_SCORES_STORED_AS_ARRAY
+            };
+
+            crate::Record::builder()
+                .set_bam_flags(sam::record::Flags::empty())
+                .set_flags(flags)
+                .set_reference_sequence_id(reference_sequence_id)
+                .set_alignment_start(alignment_start)
+                .set_read_length(4)
+                .set_features(features)
+                .set_quality_scores(quality_scores)
+                .build()
+        };
+
+        let quality_scores = vec![45, 35, 43, 50];
+
+        // reference sequence = TTCACCCA
+        let cases: Vec<(Vec<Feature>, &[u8], Vec<u8>)> = vec![
+            (vec![], b"TTCA", quality_scores.clone()),
+            (
+                vec![Feature::Bases(2, b"GG".to_vec())],
+                b"TGGA",
+                quality_scores.clone(),
+            ),
+            (vec![Feature::ReadBase(1, b'G', 30)], b"GTCA", Vec::new()),
+            (
+                vec![Feature::Insertion(2, b"AA".to_vec())],
+                b"TAAT",
+                quality_scores.clone(),
+            ),
+            (
+                vec![Feature::Deletion(3, 2)],
+                b"TTCC",
+                quality_scores.clone(),
+            ),
+            (
+                vec![Feature::InsertBase(2, b'G')],
+                b"TGTC",
+                quality_scores.clone(),
+            ),
+            (
+                vec![
+                    Feature::QualityScore(2, 40),
+                    Feature::Scores(3, vec![10, 20]),
+                ],
+                b"TTCA",
+                Vec::new(),
+            ),
+            (
+                vec![Feature::ReferenceSkip(2, 3)],
+                b"TCCC",
+                quality_scores.clone(),
+            ),
+            (
+                vec![Feature::SoftClip(1, b"GG".to_vec())],
+                b"GGTT",
+                quality_scores.clone(),
+            ),
+            (
+                vec![Feature::HardClip(1, 5), Feature::Padding(2, 1)],
+                b"TTCA",
+                quality_scores.clone(),
+            ),
+        ];
+
+        let records = cases
+            .iter()
+            .map(|(features, _, quality_scores)| {
+                build_record(features.clone(), quality_scores.clone())
+            })
+            .collect();
+
+        let (header, data) = build_data_with_records(records)?;
+
+        let repository = ReferenceSequenceRepository::new(adapters::Records::new(
+            build_reference_sequence_records(),
+        ));
+
+        let mut reader = Reader::builder(&data[..])
+            .set_reference_sequence_repository(repository)
+            .build();
+
+        reader.read_file_definition()?;
+        reader.read_file_header()?;
+
+        let records: Vec<_> = reader
+            .records_with_reference(&header)
+            .collect::<Result<_, _>>()?;
+        assert_eq!(records.len(), cases.len());
+
+        for (record, (features, expected_bases, _)) in records.iter().zip(&cases) {
+            assert_eq!(record.features(), &features[..]);
+            assert_eq!(record.bases(), *expected_bases);
+        }
+
+        assert_eq!(records[0].quality_scores(), quality_scores);
+        assert_eq!(records[2].quality_scores(), [30, MISSING, MISSING, MISSING]);
+        assert_eq!(records[6].quality_scores(), [MISSING, 40, 10, 20]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_records_with_embedded_reference() -> Result<(), Box<dyn std::error::Error>> {
+        use crate::{record::Feature, writer::ReferenceMode};
+
+        let reference_sequence_id = bam::record::ReferenceSequenceId::try_from(0)?;
+
+        let build_record = |alignment_start, features| -> Result<_, Box<dyn std::error::Error>> {
+            Ok(crate::Record::builder()
+                .set_bam_flags(sam::record::Flags::empty())
+                .set_reference_sequence_id(reference_sequence_id)
+                .set_alignment_start(sam::record::Position::try_from(alignment_start)?)
+                .set_read_length(4)
+                .set_features(features)
+                .build())
+        };
+
+        // reference sequence = TTCACCCA
+        let records = vec![
+            build_record(2, Vec::new())?,
+            build_record(3, vec![Feature::Bases(2, b"GG".to_vec())])?,
+            build_record(4, vec![Feature::Deletion(2, 1)])?,
+        ];
+
+        let (header, data) = build_data_with_reference_mode(records, ReferenceMode::Embedded)?;
+
+        // The reference sequence repository is empty.
+        let mut reader = Reader::new(&data[..]);
+        reader.read_file_definition()?;
+        reader.read_file_header()?;
+
+        let records: Vec<_> = reader
+            .records_with_reference(&header)
+            .collect::<Result<_, _>>()?;
+        let actual: Vec<_> = records.iter().map(|record| record.bases()).collect();
+        let expected: [&[u8]; 3] = [b"TCAC", b"CGGC", b"ACCA"];
+        assert_eq!(actual, expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_records_with_no_reference() -> Result<(), Box<dyn std::error::Error>> {
+        use crate::{record::Feature, writer::ReferenceMode};
+
+        let reference_sequence_id = bam::record::ReferenceSequenceId::try_from(0)?;
+        let alignment_start = sam::record::Position::try_from(1)?;
+
+        let build_record = |features, bases: &[u8]| {
+            crate::Record::builder()
+                .set_bam_flags(sam::record::Flags::empty())
+                .set_reference_sequence_id(reference_sequence_id)
+                .set_alignment_start(alignment_start)
+                .set_read_length(4)
+                .set_features(features)
+                .set_bases(bases.to_vec())
+                .build()
+        };
+
+        let records = vec![
+            build_record(Vec::new(), b"ACGT"),
+            build_record(vec![Feature::Deletion(3, 2)], b"GGAA"),
+            build_record(vec![Feature::SoftClip(1, b"N".to_vec())], b"NCGT"),
+        ];
+
+        let (header, data) = build_data_with_reference_mode(records, ReferenceMode::None)?;
+
+        // The reference sequence repository is empty.
+        let mut reader = Reader::new(&data[..]);
+        reader.read_file_definition()?;
+        reader.read_file_header()?;
+
+        let records: Vec<_> = reader
+            .records_with_reference(&header)
+            .collect::<Result<_, _>>()?;
+        let actual: Vec<_> = records.iter().map(|record| record.bases()).collect();
+        let expected: [&[u8]; 3] = [b"ACGT", b"GGAA", b"NCGT"];
+        assert_eq!(actual, expected);
+
+        assert_eq!(
+            records[1].features(),
+            [
+                Feature::Bases(1, b"GG".to_vec()),
+                Feature::Deletion(3, 2),
+                Feature::Bases(3, b"AA".to_vec()),
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_container_with_non_minimal_itf8_values() -> io::Result<()> {
+        use flate2::Crc;
+
+        fn push_crc32(buf: &mut Vec<u8>) {
+            let mut crc = Crc::new();
+            crc.update(buf);
+            buf.extend(crc.sum().to_le_bytes());
+        }
+
+        let mut block = vec![
+            0x00, // compression method = none (0)
+            0x04, // content type = external data (4)
+            0x80, 0x01, // block content ID = 1
+            0x80, 0x04, // size in bytes = 4 bytes
+            0x80, 0x04, // raw size in bytes = 4 bytes
+            0x6e, 0x64, 0x6c, 0x73, // data = b"ndls"
+        ];
+        push_crc32(&mut block);
+
+        let mut container = (block.len() as i32).to_le_bytes().to_vec();
+        container.extend([
+            0x80, 0x00, // reference sequence ID = 0
+            0x80, 0x00, // starting position on the reference = 0
+            0x80, 0x00, // alignment span = 0
+            0x80, 0x00, // number of records = 0
+            0x80, 0x00, // record counter = 0
+            0x80, 0x00, // bases = 0
+            0x80, 0x01, // number of blocks = 1
+            0x80, 0x00, // landmark count = 0
+        ]);
+        push_crc32(&mut container);
+        container.extend(&block);
+
+        let data = [&container[..], &container[..]].concat();
+        let mut reader = Reader::new(&data[..]);
+
+        reader.read_container()?;
+        assert_eq!(reader.position, container.len() as u64);
+
+        reader.read_container()?;
+        assert_eq!(reader.position, data.len() as u64);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_data_container_with_checksum_mismatch() -> Result<(), Box<dyn std::error::Error>> {
+        use std::io::Cursor;
+
+        let (_, mut data) = build_data()?;
+
+        let mut reader = Reader::new(Cursor::new(&data[..]));
+        reader.read_file_definition()?;
+        reader.read_file_header()?;
+        let container_offset = reader.position()?;
+        reader.read_data_container()?;
+        let container_end = reader.position()?;
+
+        // Corrupt the CRC32 of the last block in the data container.
+        data[container_end as usize - 1] ^= 0xff;
+
+        let mut reader = Reader::new(&data[..]);
+        reader.read_file_definition()?;
+        reader.read_file_header()?;
+
+        let e = match reader.read_data_container() {
+            Ok(_) => panic!("expected checksum error"),
+            Err(e) => e,
+        };
+        assert_eq!(e.kind(), io::ErrorKind::InvalidData);
+
+        let checksum_error = e
+            .get_ref()
+            .and_then(|e| e.downcast_ref::<ChecksumError>())
+            .expect("missing checksum error");
+        assert_eq!(checksum_error.container_offset(), container_offset);
+        assert!(checksum_error.block_index().is_some());
+
+        let mut reader = Reader::builder(&data[..])
+            .set_validate_checksums(false)
+            .build();
+        reader.read_file_definition()?;
+        reader.read_file_header()?;
+        assert!(reader.read_data_container()?.is_some());
+        assert!(reader.read_data_container()?.is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_query_with_multi_reference_slices() -> Result<(), Box<dyn std::error::Error>> {
+        use std::io::Cursor;
+
+        use md5::{Digest, Md5};
+        use noodles_core::Region;
+        use noodles_fasta::{
+            self as fasta,
+            record::{Definition, Sequence},
+        };
+        use sam::header::{reference_sequence::Md5Checksum, ReferenceSequence};
+
+        use crate::{reference_sequence_repository::adapters, Writer};
+
+        let reference_sequence_records = vec![
+            fasta::Record::new(
+                Definition::new("sq0", None),
+                Sequence::from(b"TTCACCCA".to_vec()),
+            ),
+            fasta::Record::new(
+                Definition::new("sq1", None),
+                Sequence::from(b"GGATTACA".to_vec()),
+            ),
+        ];
+
+        let mut header_builder = sam::Header::builder();
+
+        for record in &reference_sequence_records {
+            let mut hasher = Md5::new();
+            hasher.update(record.sequence());
+            let md5_checksum = Md5Checksum::from(<[u8; 16]>::from(hasher.finalize()));
+
+            header_builder = header_builder.add_reference_sequence(
+                ReferenceSequence::builder()
+                    .set_name(record.name().parse()?)
+                    .set_length(8)
+                    .set_md5_checksum(md5_checksum)
+                    .build()?,
+            );
+        }
+
+        let header = header_builder.build();
+
+        let build_record = |reference_sequence_id: Option<i32>, alignment_start: i32| {
+            let mut builder = crate::Record::builder().set_read_length(4);
+
+            if let Some(id) = reference_sequence_id {
+                builder = builder
+                    .set_bam_flags(sam::record::Flags::empty())
+                    .set_reference_sequence_id(bam::record::ReferenceSequenceId::try_from(id)?)
+                    .set_alignment_start(sam::record::Position::try_from(alignment_start)?);
+            } else {
+                builder = builder.set_bases(b"NNNN".to_vec());
+            }
+
+            Ok::<_, Box<dyn std::error::Error>>(builder.build())
+        };
+
+        let records = vec![
+            build_record(Some(1), 3)?,
+            build_record(Some(0), 2)?,
+            build_record(None, 0)?,
+            build_record(Some(1), 1)?,
+        ];
+
+        let mut writer = Writer::builder(Vec::new())
+            .set_reference_sequences(reference_sequence_records.clone())
+            .set_multi_reference_slices(true)
+            .build();
+
+        writer.write_file_definition()?;
+        writer.write_file_header(&header)?;
+
+        for record in records {
+            writer.write_record(record)?;
+        }
+
+        writer.try_finish()?;
+
+        let data = writer.get_ref().clone();
+
+        let mut reader = Reader::new(Cursor::new(&data[..]));
+        reader.read_file_definition()?;
+        reader.read_file_header()?;
+        let index = crate::index_data_containers(&mut reader)?;
+
+        let actual: Vec<_> = index
+            .iter()
+            .map(|record| {
+                (
+                    record.reference_sequence_id().map(i32::from),
+                    record.alignment_start(),
+                    record.alignment_span(),
+                )
+            })
+            .collect();
+
+        assert_eq!(actual, [(None, 0, 0), (Some(0), 2, 4), (Some(1), 1, 6)]);
+
+        let repository =
+            ReferenceSequenceRepository::new(adapters::Records::new(reference_sequence_records));
+
+        let mut reader = Reader::builder(Cursor::new(&data[..]))
+            .set_reference_sequence_repository(repository)
+            .build();
+
+        reader.read_file_definition()?;
+        reader.read_file_header()?;
+
+        let region = "sq1:1-2".parse()?;
+        let records: Vec<_> = reader
+            .query(&header, &index, &region)?
+            .collect::<Result<_, _>>()?;
+        let actual: Vec<_> = records.iter().map(|record| record.bases()).collect();
+        let expected: [&[u8]; 1] = [b"GGAT"];
+        assert_eq!(actual, expected);
+
+        let records: Vec<_> = reader
+            .query(&header, &index, &Region::Unmapped)?
+            .collect::<Result<_, _>>()?;
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].bases(), b"NNNN");
+
+        Ok(())
+    }
 }
@@ -0,0 +1,88 @@
+use std::io::Read;
+
+use super::Reader;
+use crate::reference_sequence_repository::ReferenceSequenceRepository;
+
+/// A CRAM reader builder.
+#[derive(Debug)]
+pub struct Builder<R> {
+    inner: R,
+    reference_sequence_repository: ReferenceSequenceRepository,
+    validate_checksums: bool,
+}
+
+impl<R> Builder<R>
+where
+    R: Read,
+{
+    pub(crate) fn new(inner: R) -> Self {
+        Self {
+            inner,
+            reference_sequence_repository: ReferenceSequenceRepository::default(),
+            validate_checksums: true,
+        }
+    }
+
+    /// Sets the reference sequence repository.
+    ///
+    /// The repository is used to resolve the read bases of mapped records. By default, the
+    /// repository is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_cram::{
+    ///     self as cram,
+    ///     reference_sequence_repository::{adapters, ReferenceSequenceRepository},
+    /// };
+    ///
+    /// let repository = ReferenceSequenceRepository::new(adapters::Records::default());
+    ///
+    /// let data = [];
+    /// let builder = cram::Reader::builder(&data[..])
+    ///     .set_reference_sequence_repository(repository);
+    /// ```
+    pub fn set_reference_sequence_repository(
+        mut self,
+        reference_sequence_repository: ReferenceSequenceRepository,
+    ) -> Self {
+        self.reference_sequence_repository = reference_sequence_repository;
+        self
+    }
+
+    /// Sets whether to validate the CRC32 checksums of containers and blocks.
+    ///
+    /// When enabled, reading a container with a mismatched checksum returns an error with a
+    /// [`super::ChecksumError`]. Disabling validation skips calculating the checksums of the block
+    /// data. By default, checksums are validated.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_cram as cram;
+    /// let data = [];
+    /// let builder = cram::Reader::builder(&data[..]).set_validate_checksums(false);
+    /// ```
+    pub fn set_validate_checksums(mut self, validate_checksums: bool) -> Self {
+        self.validate_checksums = validate_checksums;
+        self
+    }
+
+    /// Builds a CRAM reader.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_cram as cram;
+    /// let data = [];
+    /// let reader = cram::Reader::builder(&data[..]).build();
+    /// ```
+    pub fn build(self) -> Reader<R> {
+        Reader {
+            inner: self.inner,
+            reference_sequence_repository: self.reference_sequence_repository,
+            validate_checksums: self.validate_checksums,
+            position: 0,
+        }
+    }
+}
@@ -0,0 +1,237 @@
+use std::{error, fmt, io};
+
+use flate2::Crc;
+
+use crate::Container;
+
+/// A CRC32 checksum mismatch in a CRAM container.
+///
+/// This is the inner error of an [`io::Error`] of kind [`io::ErrorKind::InvalidData`] returned
+/// when reading a container with checksum validation enabled.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use std::{fs::File, io};
+/// use noodles_cram::{self as cram, reader::ChecksumError};
+///
+/// let mut reader = File::open("sample.cram").map(cram::Reader::new)?;
+/// reader.read_file_definition()?;
+/// reader.read_file_header()?;
+///
+/// if let Err(e) = reader.read_data_container() {
+///     if let Some(checksum_error) = e.get_ref().and_then(|e| e.downcast_ref::<ChecksumError>()) {
+///         eprintln!("corrupt container at offset {}", checksum_error.container_offset());
+///     }
+/// }
+/// # Ok::<(), io::Error>(())
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ChecksumError {
+    container_offset: u64,
+    block_index: Option<usize>,
+    expected: u32,
+    actual: u32,
+}
+
+impl ChecksumError {
+    /// Returns the position of the container in the stream.
+    pub fn container_offset(&self) -> u64 {
+        self.container_offset
+    }
+
+    /// Returns the index of the block in the container with the mismatched checksum.
+    ///
+    /// This is `None` if the checksum of the container header does not match.
+    pub fn block_index(&self) -> Option<usize> {
+        self.block_index
+    }
+
+    /// Returns the checksum stored in the stream.
+    pub fn expected(&self) -> u32 {
+        self.expected
+    }
+
+    /// Returns the checksum calculated from the data read.
+    pub fn actual(&self) -> u32 {
+        self.actual
+    }
+}
+
+impl error::Error for ChecksumError {}
+
+impl fmt::Display for ChecksumError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.block_index {
+            Some(i) => write!(f, "block {} checksum mismatch", i)?,
+            None => f.write_str("container header checksum mismatch")?,
+        }
+
+        write!(
+            f,
+            " in container at offset {}: expected {:08x}, got {:08x}",
+            self.container_offset, self.expected, self.actual
+        )
+    }
+}
+
+// The size of the CRC32 stored at the end of a container header or block.
+const CRC32_LEN: u64 = 4;
+
+// The CRC32 calculated from the bytes read for a container header or block.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) struct Checksum {
+    crc32: u32,
+    len: u64,
+}
+
+impl Checksum {
+    // Creates a checksum from the CRC of the bytes read up to, but not including, the stored
+    // CRC32.
+    pub(crate) fn new(crc: &Crc) -> Self {
+        Self {
+            crc32: crc.sum(),
+            len: u64::from(crc.amount()) + CRC32_LEN,
+        }
+    }
+
+    pub(crate) fn crc32(&self) -> u32 {
+        self.crc32
+    }
+
+    // Returns the number of bytes read, including the stored CRC32.
+    pub(crate) fn len(&self) -> u64 {
+        self.len
+    }
+}
+
+// The checksums of a container header and its blocks.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) struct Checksums {
+    header: Checksum,
+    blocks: Vec<Checksum>,
+}
+
+impl Checksums {
+    pub(crate) fn new(header: Checksum, blocks: Vec<Checksum>) -> Self {
+        Self { header, blocks }
+    }
+
+    // Returns the number of bytes read for the container.
+    pub(crate) fn len(&self) -> u64 {
+        self.header.len() + self.blocks.iter().map(|block| block.len()).sum::<u64>()
+    }
+}
+
+// Validates the CRC32s of a container header and its blocks against the checksums calculated
+// from the bytes read.
+pub(crate) fn validate_checksums(
+    container_offset: u64,
+    container: &Container,
+    checksums: &Checksums,
+) -> io::Result<()> {
+    let expected = container.header().crc32();
+    let actual = checksums.header.crc32();
+
+    if actual != expected {
+        return Err(checksum_error(container_offset, None, expected, actual));
+    }
+
+    for (i, (block, checksum)) in container.blocks().iter().zip(&checksums.blocks).enumerate() {
+        let actual = checksum.crc32();
+
+        if actual != block.crc32() {
+            return Err(checksum_error(
+                container_offset,
+                Some(i),
+                block.crc32(),
+                actual,
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+fn checksum_error(
+    container_offset: u64,
+    block_index: Option<usize>,
+    expected: u32,
+    actual: u32,
+) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        ChecksumError {
+            container_offset,
+            block_index,
+            expected,
+            actual,
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::container::Block;
+
+    fn build_container() -> io::Result<(Vec<u8>, Container, Checksums)> {
+        let mut buf = Vec::new();
+        crate::writer::container::write_container(&mut buf, &Container::eof())?;
+        let (container, checksums) = crate::reader::container::read_container(&mut &buf[..])?;
+        Ok((buf, container, checksums))
+    }
+
+    #[test]
+    fn test_validate_checksums() -> io::Result<()> {
+        let (_, container, checksums) = build_container()?;
+        assert!(validate_checksums(0, &container, &checksums).is_ok());
+
+        let header_checksum = Checksum {
+            crc32: 0,
+            ..checksums.header
+        };
+        let invalid_checksums = Checksums::new(header_checksum, checksums.blocks.clone());
+
+        let e = validate_checksums(8, &container, &invalid_checksums).unwrap_err();
+        assert_eq!(e.kind(), io::ErrorKind::InvalidData);
+        assert_eq!(
+            e.get_ref().and_then(|e| e.downcast_ref::<ChecksumError>()),
+            Some(&ChecksumError {
+                container_offset: 8,
+                block_index: None,
+                expected: container.header().crc32(),
+                actual: 0,
+            })
+        );
+
+        let (header, mut blocks) = container.into_parts();
+        let block = &blocks[0];
+        blocks[0] = Block::builder()
+            .set_compression_method(block.compression_method())
+            .set_content_type(block.content_type())
+            .set_content_id(block.content_id())
+            .set_uncompressed_len(block.uncompressed_len())
+            .set_data(block.data().to_vec())
+            .set_crc32(block.crc32() ^ 1)
+            .build();
+        let container = Container::new(header, blocks);
+
+        let e = validate_checksums(8, &container, &checksums).unwrap_err();
+        let checksum_error = e
+            .get_ref()
+            .and_then(|e| e.downcast_ref::<ChecksumError>())
+            .expect("missing checksum error");
+        assert_eq!(checksum_error.block_index(), Some(0));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_checksums_len() -> io::Result<()> {
+        let (buf, _, checksums) = build_container()?;
+        assert_eq!(checksums.header.len(), 23);
+        assert_eq!(checksums.len(), buf.len() as u64);
+        Ok(())
+    }
+}
@@ -5,21 +5,27 @@ pub use self::{block::read_block, header::read_header};
 
 use std::io::{self, Read};
 
+use super::checksum::Checksums;
 use crate::Container;
 
-pub fn read_container<R>(reader: &mut R) -> io::Result<Container>
+pub fn read_container<R>(reader: &mut R) -> io::Result<(Container, Checksums)>
 where
     R: Read,
 {
-    let header = read_header(reader)?;
+    let (header, header_checksum) = read_header(reader)?;
 
     let blocks_len = header.block_count();
     let mut blocks = Vec::with_capacity(blocks_len);
+    let mut block_checksums = Vec::with_capacity(blocks_len);
 
     for _ in 0..blocks_len {
-        let block = read_block(reader)?;
+        let (block, checksum) = read_block(reader)?;
         blocks.push(block);
+        block_checksums.push(checksum);
     }
 
-    Ok(Container::new(header, blocks))
+    let container = Container::new(header, blocks);
+    let checksums = Checksums::new(header_checksum, block_checksums);
+
+    Ok((container, checksums))
 }
@@ -1,19 +1,22 @@
 use std::io::{self, Read};
 
 use byteorder::{LittleEndian, ReadBytesExt};
+use flate2::CrcReader;
 
 use crate::{
     container::{
         block::{CompressionMethod, ContentType},
         Block,
     },
-    reader::num::read_itf8,
+    reader::{checksum::Checksum, num::read_itf8},
 };
 
-pub fn read_block<R>(reader: &mut R) -> io::Result<Block>
+pub fn read_block<R>(reader: &mut R) -> io::Result<(Block, Checksum)>
 where
     R: Read,
 {
+    let reader = &mut CrcReader::new(reader);
+
     let method = reader.read_u8().and_then(|b| {
         CompressionMethod::try_from(b).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
     })?;
@@ -35,16 +38,19 @@ where
     let mut data = vec![0; size_in_bytes];
     reader.read_exact(&mut data)?;
 
-    let crc32 = reader.read_u32::<LittleEndian>()?;
+    let checksum = Checksum::new(reader.crc());
+    let crc32 = reader.get_mut().read_u32::<LittleEndian>()?;
 
-    Ok(Block::builder()
+    let block = Block::builder()
         .set_compression_method(method)
         .set_content_type(block_content_type_id)
         .set_content_id(block_content_id)
         .set_uncompressed_len(raw_size_in_bytes)
         .set_data(data)
         .set_crc32(crc32)
-        .build())
+        .build();
+
+    Ok((block, checksum))
 }
 
 #[cfg(test)]
@@ -63,7 +69,7 @@ mod tests {
             0xfd, 0x38, 0x27, 0xb5, // CRC32
         ];
         let mut reader = &data[..];
-        let actual = read_block(&mut reader)?;
+        let (actual, checksum) = read_block(&mut reader)?;
 
         let expected = Block::builder()
             .set_compression_method(CompressionMethod::None)
@@ -75,6 +81,7 @@ mod tests {
             .build();
 
         assert_eq!(actual, expected);
+        assert_eq!(checksum.len(), data.len() as u64);
 
         Ok(())
     }
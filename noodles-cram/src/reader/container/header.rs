@@ -3,17 +3,23 @@ use noodles_sam as sam;
 use std::io::{self, Read};
 
 use byteorder::{LittleEndian, ReadBytesExt};
+use flate2::CrcReader;
 
 use crate::{
     container::{Header, ReferenceSequenceId},
     num::Itf8,
-    reader::num::{read_itf8, read_ltf8},
+    reader::{
+        checksum::Checksum,
+        num::{read_itf8, read_ltf8},
+    },
 };
 
-pub fn read_header<R>(reader: &mut R) -> io::Result<Header>
+pub fn read_header<R>(reader: &mut R) -> io::Result<(Header, Checksum)>
 where
     R: Read,
 {
+    let reader = &mut CrcReader::new(reader);
+
     let length = reader.read_i32::<LittleEndian>()?;
 
     let reference_sequence_id = read_itf8(reader).and_then(|n| {
@@ -40,7 +46,9 @@ where
     })?;
 
     let landmarks = read_landmarks(reader)?;
-    let crc32 = reader.read_u32::<LittleEndian>()?;
+
+    let checksum = Checksum::new(reader.crc());
+    let crc32 = reader.get_mut().read_u32::<LittleEndian>()?;
 
     let mut builder = Header::builder()
         .set_length(length)
@@ -57,7 +65,7 @@ where
         builder = builder.set_start_position(position);
     }
 
-    Ok(builder.build())
+    Ok((builder.build(), checksum))
 }
 
 fn read_landmarks<R>(reader: &mut R) -> io::Result<Vec<Itf8>>
@@ -98,7 +106,7 @@ mod tests {
             0xb4, 0x9f, 0x9c, 0xda, // CRC32
         ];
         let mut reader = &data[..];
-        let actual = read_header(&mut reader)?;
+        let (actual, checksum) = read_header(&mut reader)?;
 
         let expected = Header::builder()
             .set_length(144)
@@ -114,6 +122,7 @@ mod tests {
             .build();
 
         assert_eq!(actual, expected);
+        assert_eq!(checksum.len(), data.len() as u64);
 
         Ok(())
     }
@@ -0,0 +1,202 @@
+use std::io::{self, Read};
+
+use super::Reader;
+use crate::summary::ContainerSummary;
+
+/// An iterator over data container summaries of a CRAM reader.
+///
+/// This is created by calling [`Reader::container_summaries`].
+pub struct ContainerSummaries<'a, R>
+where
+    R: Read,
+{
+    reader: &'a mut Reader<R>,
+}
+
+impl<'a, R> ContainerSummaries<'a, R>
+where
+    R: Read,
+{
+    pub(crate) fn new(reader: &'a mut Reader<R>) -> Self {
+        Self { reader }
+    }
+
+    fn read_container_summary(&mut self) -> io::Result<Option<ContainerSummary>> {
+        let offset = self.reader.position;
+        let container = self.reader.read_container()?;
+
+        if container.header().is_eof() {
+            return Ok(None);
+        }
+
+        ContainerSummary::try_from_container(offset, &container).map(Some)
+    }
+}
+
+impl<'a, R> Iterator for ContainerSummaries<'a, R>
+where
+    R: Read,
+{
+    type Item = io::Result<ContainerSummary>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.read_container_summary().transpose()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use noodles_sam as sam;
+
+    use super::*;
+    use crate::{data_container::DataSeries, summary::ReferenceSequenceId, Record, Writer};
+
+    #[test]
+    fn test_next() -> io::Result<()> {
+        let mut writer = Writer::builder(Vec::new())
+            .set_records_per_slice(2)
+            .set_slices_per_container(1)
+            .build();
+
+        writer.write_file_definition()?;
+        writer.write_file_header(&sam::Header::default())?;
+
+        for _ in 0..3 {
+            let record = Record::builder()
+                .set_read_length(4)
+                .set_bases(b"NNNN".to_vec())
+                .build();
+
+            writer.write_record(record)?;
+        }
+
+        writer.try_finish()?;
+
+        let data = writer.get_ref().clone();
+        let mut reader = Reader::new(Cursor::new(&data[..]));
+        reader.read_file_definition()?;
+        reader.read_file_header()?;
+
+        let first_offset = reader.position()?;
+
+        let summaries: Vec<_> = reader.container_summaries().collect::<io::Result<_>>()?;
+        assert_eq!(summaries.len(), 2);
+
+        let container = &summaries[0];
+        assert_eq!(container.offset(), first_offset);
+        assert_eq!(container.reference_sequence_id(), ReferenceSequenceId::None);
+        assert_eq!(container.record_count(), 2);
+        assert_eq!(container.record_counter(), 0);
+        assert_eq!(container.slices().len(), 1);
+
+        let slice = &container.slices()[0];
+        assert_eq!(slice.record_count(), 2);
+        assert_eq!(
+            container.compression_header_block().len() + slice.len(),
+            container.len()
+        );
+
+        let block = slice
+            .external_blocks()
+            .iter()
+            .find(|block| block.data_series().contains(&DataSeries::Bases))
+            .expect("missing bases block");
+        assert_eq!(block.uncompressed_len(), 8);
+
+        let container = &summaries[1];
+        assert_eq!(container.record_count(), 1);
+        assert_eq!(container.record_counter(), 2);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_next_with_embedded_reference_bases() -> Result<(), Box<dyn std::error::Error>> {
+        use noodles_bam as bam;
+        use noodles_fasta::{
+            self as fasta,
+            record::{Definition, Sequence},
+        };
+        use sam::header::{reference_sequence::Md5Checksum, ReferenceSequence};
+
+        use crate::{
+            container::Container,
+            data_container::slice::calculate_reference_md5,
+            summary::{ContainerSummary, ContentType},
+            writer::ReferenceMode,
+        };
+
+        const REFERENCE_BASES: &[u8] = b"TTCACCCA";
+
+        let header = sam::Header::builder()
+            .add_reference_sequence(
+                ReferenceSequence::builder()
+                    .set_name("sq0".parse()?)
+                    .set_length(8)
+                    .set_md5_checksum(Md5Checksum::from(calculate_reference_md5(REFERENCE_BASES)))
+                    .build()?,
+            )
+            .build();
+
+        let mut writer = Writer::builder(Vec::new())
+            .set_reference_sequences(vec![fasta::Record::new(
+                Definition::new("sq0", None),
+                Sequence::from(REFERENCE_BASES.to_vec()),
+            )])
+            .set_reference_mode(ReferenceMode::Embedded)
+            .build();
+
+        writer.write_file_definition()?;
+        writer.write_file_header(&header)?;
+
+        let record = Record::builder()
+            .set_bam_flags(sam::record::Flags::empty())
+            .set_reference_sequence_id(bam::record::ReferenceSequenceId::try_from(0)?)
+            .set_alignment_start(sam::record::Position::try_from(2)?)
+            .set_read_length(4)
+            .set_bases(b"TCAC".to_vec())
+            .build();
+
+        writer.write_record(record)?;
+        writer.try_finish()?;
+
+        let data = writer.get_ref().clone();
+        let mut reader = Reader::new(&data[..]);
+        reader.read_file_definition()?;
+        reader.read_file_header()?;
+
+        let (container_header, mut blocks) = reader.read_container()?.into_parts();
+
+        // Move the core data block after the external blocks. The first two blocks are the
+        // compression header and slice header blocks.
+        blocks[2..].rotate_left(1);
+        let container = Container::new(container_header, blocks);
+
+        let summary = ContainerSummary::try_from_container(0, &container)?;
+        let slice = &summary.slices()[0];
+
+        assert_eq!(
+            slice.core_data_block().content_type(),
+            ContentType::CoreData
+        );
+
+        assert!(slice
+            .external_blocks()
+            .iter()
+            .all(|block| block.content_type() == ContentType::ExternalData));
+
+        let embedded_reference_bases_blocks: Vec<_> = slice
+            .external_blocks()
+            .iter()
+            .filter(|block| block.is_embedded_reference_bases())
+            .collect();
+
+        assert_eq!(embedded_reference_bases_blocks.len(), 1);
+        // Only the reference bases that the slice spans are embedded.
+        assert_eq!(embedded_reference_bases_blocks[0].uncompressed_len(), 4);
+
+        Ok(())
+    }
+}
@@ -2,70 +2,45 @@ pub mod compression_header;
 pub mod slice;
 
 pub use self::{compression_header::read_compression_header, slice::read_slice};
-use crate::data_container::CompressionHeader;
 
-use std::io::{self, Read};
+use std::{io, vec};
 
-use super::container;
-use crate::DataContainer;
+use crate::{
+    container::{self, Block},
+    data_container::CompressionHeader,
+    Container, DataContainer,
+};
 
-pub fn read_data_container<R>(reader: &mut R) -> io::Result<Option<DataContainer>>
-where
-    R: Read,
-{
-    let header = container::read_header(reader)?;
+// Builds a data container from the blocks of a container.
+pub fn read_data_container(container: Container) -> io::Result<(container::Header, DataContainer)> {
+    let (header, blocks) = container.into_parts();
+    let mut blocks = blocks.into_iter();
 
-    if header.is_eof() {
-        return Ok(None);
-    }
-
-    let compression_header = read_compression_header_from_block(reader)?;
-
-    let slice_count = header.landmarks().len();
-    let mut slices = Vec::with_capacity(slice_count);
-
-    for _ in 0..slice_count {
-        let slice = read_slice(reader)?;
-        slices.push(slice);
-    }
-
-    Ok(Some(DataContainer::new(compression_header, slices)))
-}
-
-pub fn read_data_container_with_container_header<R>(
-    reader: &mut R,
-) -> io::Result<Option<(crate::container::Header, DataContainer)>>
-where
-    R: Read,
-{
-    let header = container::read_header(reader)?;
-
-    if header.is_eof() {
-        return Ok(None);
-    }
-
-    let compression_header = read_compression_header_from_block(reader)?;
+    let compression_header = read_compression_header_from_block(&mut blocks)?;
 
     let slice_count = header.landmarks().len();
     let mut slices = Vec::with_capacity(slice_count);
 
     for _ in 0..slice_count {
-        let slice = read_slice(reader)?;
+        let slice = read_slice(&mut blocks)?;
         slices.push(slice);
     }
 
     let data_container = DataContainer::new(compression_header, slices);
 
-    Ok(Some((header, data_container)))
+    Ok((header, data_container))
 }
 
-fn read_compression_header_from_block<R>(reader: &mut R) -> io::Result<CompressionHeader>
-where
-    R: Read,
-{
-    use super::container::read_block;
+fn read_compression_header_from_block(
+    blocks: &mut vec::IntoIter<Block>,
+) -> io::Result<CompressionHeader> {
+    let block = blocks.next().ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            "invalid data container: missing compression header block",
+        )
+    })?;
 
-    let block = read_block(reader)?;
     let data = block.decompressed_data()?;
     let mut data_reader = &data[..];
     read_compression_header(&mut data_reader)
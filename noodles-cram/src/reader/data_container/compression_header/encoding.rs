@@ -13,13 +13,13 @@ where
     match raw_kind {
         0 => Ok(Encoding::Null),
         1 => read_external_encoding(reader),
-        2 => unimplemented!("GOLOMB"),
+        2 => read_golomb_encoding(reader),
         3 => read_huffman_encoding(reader),
         4 => read_byte_array_len_encoding(reader),
         5 => read_byte_array_stop_encoding(reader),
         6 => read_beta_encoding(reader),
         7 => read_subexp_encoding(reader),
-        8 => unimplemented!("GOLOMB_RICE"),
+        8 => read_golomb_rice_encoding(reader),
         9 => read_gamma_encoding(reader),
         _ => Err(io::Error::new(
             io::ErrorKind::InvalidData,
@@ -52,6 +52,19 @@ where
     Ok(Encoding::External(block_content_id))
 }
 
+fn read_golomb_encoding<R>(reader: &mut R) -> io::Result<Encoding>
+where
+    R: Read,
+{
+    let args = read_args(reader)?;
+    let mut args_reader = &args[..];
+
+    let offset = read_itf8(&mut args_reader)?;
+    let m = read_itf8(&mut args_reader)?;
+
+    Ok(Encoding::Golomb(offset, m))
+}
+
 fn read_byte_array_len_encoding<R>(reader: &mut R) -> io::Result<Encoding>
 where
     R: Read,
@@ -142,6 +155,19 @@ where
     Ok(Encoding::Subexp(offset, k))
 }
 
+fn read_golomb_rice_encoding<R>(reader: &mut R) -> io::Result<Encoding>
+where
+    R: Read,
+{
+    let args = read_args(reader)?;
+    let mut args_reader = &args[..];
+
+    let offset = read_itf8(&mut args_reader)?;
+    let log2_m = read_itf8(&mut args_reader)?;
+
+    Ok(Encoding::GolombRice(offset, log2_m))
+}
+
 fn read_gamma_encoding<R>(reader: &mut R) -> io::Result<Encoding>
 where
     R: Read,
@@ -186,6 +212,22 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_read_golomb_encoding() -> io::Result<()> {
+        let data = [
+            2, // Golomb encoding ID
+            2, // args.len
+            1, // offset
+            5, // m
+        ];
+        let mut reader = &data[..];
+
+        let encoding = read_encoding(&mut reader)?;
+        assert_eq!(encoding, Encoding::Golomb(1, 5));
+
+        Ok(())
+    }
+
     #[test]
     fn test_read_huffman_encoding() -> io::Result<()> {
         let data = [
@@ -279,6 +321,22 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_read_golomb_rice_encoding() -> io::Result<()> {
+        let data = [
+            8, // Golomb-Rice encoding ID
+            2, // args.len
+            1, // offset
+            3, // log2_m
+        ];
+        let mut reader = &data[..];
+
+        let encoding = read_encoding(&mut reader)?;
+        assert_eq!(encoding, Encoding::GolombRice(1, 3));
+
+        Ok(())
+    }
+
     #[test]
     fn test_read_gamma_encoding() -> io::Result<()> {
         let data = [
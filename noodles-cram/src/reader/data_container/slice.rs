@@ -2,47 +2,40 @@ mod header;
 
 pub use self::header::read_header;
 
-use std::io::{self, Read};
+use std::{io, vec};
 
 use crate::{
     container::Block,
     data_container::{slice, Slice},
-    reader::container::read_block,
 };
 
-pub fn read_slice<R>(reader: &mut R) -> io::Result<Slice>
-where
-    R: Read,
-{
-    let header = read_header_from_block(reader)?;
-    let core_data_block = read_block(reader)?;
+pub fn read_slice(blocks: &mut vec::IntoIter<Block>) -> io::Result<Slice> {
+    let header = read_header_from_block(blocks)?;
+    let core_data_block = next_block(blocks)?;
 
-    let external_block_count = header.block_count() - 1;
-    let external_blocks = read_external_blocks(reader, external_block_count)?;
+    let external_block_count = header.block_count().checked_sub(1).ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            "invalid slice: missing core data block",
+        )
+    })?;
+
+    let external_blocks = (0..external_block_count)
+        .map(|_| next_block(blocks))
+        .collect::<io::Result<_>>()?;
 
     Ok(Slice::new(header, core_data_block, external_blocks))
 }
 
-fn read_header_from_block<R>(reader: &mut R) -> io::Result<slice::Header>
-where
-    R: Read,
-{
-    let block = read_block(reader)?;
+fn read_header_from_block(blocks: &mut vec::IntoIter<Block>) -> io::Result<slice::Header> {
+    let block = next_block(blocks)?;
     let data = block.decompressed_data()?;
     let mut data_reader = &data[..];
     read_header(&mut data_reader)
 }
 
-fn read_external_blocks<R>(reader: &mut R, len: usize) -> io::Result<Vec<Block>>
-where
-    R: Read,
-{
-    let mut external_blocks = Vec::with_capacity(len);
-
-    for _ in 0..len {
-        let block = read_block(reader)?;
-        external_blocks.push(block);
-    }
-
-    Ok(external_blocks)
+fn next_block(blocks: &mut vec::IntoIter<Block>) -> io::Result<Block> {
+    blocks
+        .next()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "invalid slice: missing block"))
 }
@@ -1,4 +1,5 @@
 mod itf8;
 mod ltf8;
+mod uint7;
 
-pub use self::{itf8::read_itf8, ltf8::read_ltf8};
+pub use self::{itf8::read_itf8, ltf8::read_ltf8, uint7::read_uint7};
@@ -0,0 +1,59 @@
+use std::io::{self, Read};
+
+use byteorder::ReadBytesExt;
+
+pub fn read_uint7<R>(reader: &mut R) -> io::Result<u32>
+where
+    R: Read,
+{
+    let mut value: u32 = 0;
+
+    loop {
+        let b = reader.read_u8().map(u32::from)?;
+
+        value = value
+            .checked_mul(1 << 7)
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "invalid uint7"))?;
+
+        value |= b & 0x7f;
+
+        if b & 0x80 == 0 {
+            break;
+        }
+    }
+
+    Ok(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_uint7() -> io::Result<()> {
+        fn t(mut data: &[u8], expected: u32) -> io::Result<()> {
+            assert_eq!(read_uint7(&mut data)?, expected);
+            Ok(())
+        }
+
+        t(&[0x00], 0)?;
+        t(&[0x55], 85)?;
+        t(&[0x8d, 0x55], 1749)?;
+        t(&[0x81, 0x80, 0x00], 16384)?;
+        t(&[0x8f, 0xff, 0xff, 0xff, 0x7f], u32::MAX)?;
+
+        let data = [0x90, 0x80, 0x80, 0x80, 0x00];
+        assert!(matches!(
+            read_uint7(&mut &data[..]),
+            Err(ref e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+
+        let data = [0x80];
+        assert!(matches!(
+            read_uint7(&mut &data[..]),
+            Err(ref e) if e.kind() == io::ErrorKind::UnexpectedEof
+        ));
+
+        Ok(())
+    }
+}
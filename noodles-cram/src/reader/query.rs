@@ -0,0 +1,285 @@
+use std::{
+    collections::BTreeMap,
+    io::{self, Read, Seek, SeekFrom},
+    ops::Bound,
+    vec,
+};
+
+use noodles_bam as bam;
+use noodles_core::region::Interval;
+use noodles_sam as sam;
+
+use super::Reader;
+use crate::{crai, Record};
+
+/// An iterator over records of a CRAM reader that intersects a given region.
+///
+/// This is created by calling [`Reader::query`].
+pub struct Query<'a, R>
+where
+    R: Read + Seek,
+{
+    reader: &'a mut Reader<R>,
+    header: &'a sam::Header,
+
+    containers: vec::IntoIter<(u64, Vec<u64>)>,
+
+    reference_sequence_id: Option<bam::record::ReferenceSequenceId>,
+    start: i32,
+    end: i32,
+
+    records: vec::IntoIter<Record>,
+}
+
+impl<'a, R> Query<'a, R>
+where
+    R: Read + Seek,
+{
+    pub(super) fn new(
+        reader: &'a mut Reader<R>,
+        header: &'a sam::Header,
+        index: &[crai::Record],
+        reference_sequence_id: Option<bam::record::ReferenceSequenceId>,
+        interval: Interval,
+    ) -> Self {
+        let (start, end) = resolve_interval(interval);
+        let containers = filter_index_records(index, reference_sequence_id, start, end);
+
+        Self {
+            reader,
+            header,
+
+            containers: containers.into_iter(),
+
+            reference_sequence_id,
+            start,
+            end,
+
+            records: Vec::new().into_iter(),
+        }
+    }
+
+    fn read_container_records(&mut self) -> io::Result<bool> {
+        let (offset, landmarks) = match self.containers.next() {
+            Some(c) => c,
+            None => return Ok(true),
+        };
+
+        self.reader.seek(SeekFrom::Start(offset))?;
+
+        let (container_header, data_container) =
+            match self.reader.read_data_container_with_container_header()? {
+                Some(c) => c,
+                None => {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        format!("unexpected EOF container at offset {}", offset),
+                    ))
+                }
+            };
+
+        let compression_header = data_container.compression_header();
+        let mut records = Vec::new();
+
+        for (slice, &landmark) in data_container
+            .slices()
+            .iter()
+            .zip(container_header.landmarks())
+        {
+            if !landmarks.contains(&(landmark as u64)) {
+                continue;
+            }
+
+            let slice_records =
+                self.reader
+                    .read_slice_records(self.header, compression_header, slice)?;
+
+            records.extend(slice_records.into_iter().filter(|record| {
+                intersects(record, self.reference_sequence_id, self.start, self.end)
+            }));
+        }
+
+        self.records = records.into_iter();
+
+        Ok(false)
+    }
+}
+
+impl<'a, R> Iterator for Query<'a, R>
+where
+    R: Read + Seek,
+{
+    type Item = io::Result<Record>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            match self.records.next() {
+                Some(r) => return Some(Ok(r)),
+                None => match self.read_container_records() {
+                    Ok(true) => return None,
+                    Ok(false) => {}
+                    Err(e) => return Some(Err(e)),
+                },
+            }
+        }
+    }
+}
+
+pub(crate) fn resolve_interval(interval: Interval) -> (i32, i32) {
+    let start = match interval.0 {
+        Bound::Included(s) => s,
+        Bound::Excluded(s) => s + 1,
+        Bound::Unbounded => 1,
+    };
+
+    let end = match interval.1 {
+        Bound::Included(e) => e,
+        Bound::Excluded(e) => e - 1,
+        Bound::Unbounded => i32::MAX,
+    };
+
+    (start, end)
+}
+
+// Returns a list of container offsets and the landmarks of the slices in each container that
+// intersect the given region, ordered by offset.
+pub(crate) fn filter_index_records(
+    index: &[crai::Record],
+    reference_sequence_id: Option<bam::record::ReferenceSequenceId>,
+    start: i32,
+    end: i32,
+) -> Vec<(u64, Vec<u64>)> {
+    let mut containers: BTreeMap<u64, Vec<u64>> = BTreeMap::new();
+
+    for record in index {
+        if record.reference_sequence_id() != reference_sequence_id {
+            continue;
+        }
+
+        if reference_sequence_id.is_some() {
+            let record_start = record.alignment_start();
+            let record_end = record_start + record.alignment_span() - 1;
+
+            if !in_interval(record_start, record_end, start, end) {
+                continue;
+            }
+        }
+
+        let landmarks = containers.entry(record.offset()).or_default();
+
+        // Multi-reference slices have an index record for each reference sequence.
+        if !landmarks.contains(&record.landmark()) {
+            landmarks.push(record.landmark());
+        }
+    }
+
+    containers.into_iter().collect()
+}
+
+pub(crate) fn intersects(
+    record: &Record,
+    reference_sequence_id: Option<bam::record::ReferenceSequenceId>,
+    interval_start: i32,
+    interval_end: i32,
+) -> bool {
+    if record.reference_sequence_id() != reference_sequence_id {
+        return false;
+    }
+
+    if reference_sequence_id.is_none() {
+        return true;
+    }
+
+    match record.alignment_start().map(i32::from) {
+        Some(start) => in_interval(start, record.alignment_end(), interval_start, interval_end),
+        None => false,
+    }
+}
+
+fn in_interval(a_start: i32, a_end: i32, b_start: i32, b_end: i32) -> bool {
+    a_start <= b_end && b_start <= a_end
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_interval() {
+        assert_eq!(
+            resolve_interval((Bound::Included(8), Bound::Included(13))),
+            (8, 13)
+        );
+        assert_eq!(
+            resolve_interval((Bound::Included(8), Bound::Unbounded)),
+            (8, i32::MAX)
+        );
+        assert_eq!(
+            resolve_interval((Bound::Unbounded, Bound::Unbounded)),
+            (1, i32::MAX)
+        );
+    }
+
+    #[test]
+    fn test_filter_index_records() -> Result<(), bam::record::reference_sequence_id::TryFromIntError>
+    {
+        let sq0 = bam::record::ReferenceSequenceId::try_from(0).map(Some)?;
+        let sq1 = bam::record::ReferenceSequenceId::try_from(1).map(Some)?;
+
+        let index = [
+            crai::Record::new(sq0, 1, 10, 100, 200, 300),
+            crai::Record::new(sq0, 11, 10, 100, 500, 300),
+            crai::Record::new(sq0, 21, 10, 900, 200, 300),
+            // multi-reference slice
+            crai::Record::new(sq0, 31, 10, 1400, 200, 300),
+            crai::Record::new(sq1, 1, 10, 1400, 200, 300),
+            crai::Record::new(None, 0, 0, 1400, 200, 300),
+            crai::Record::new(None, 0, 0, 1900, 200, 300),
+        ];
+
+        assert_eq!(
+            filter_index_records(&index, sq0, 8, 13),
+            [(100, vec![200, 500])]
+        );
+
+        assert_eq!(
+            filter_index_records(&index, sq0, 15, 35),
+            [(100, vec![500]), (900, vec![200]), (1400, vec![200])]
+        );
+
+        assert_eq!(filter_index_records(&index, sq1, 13, 21), []);
+
+        assert_eq!(
+            filter_index_records(&index, None, 1, i32::MAX),
+            [(1400, vec![200]), (1900, vec![200])]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_intersects() -> Result<(), Box<dyn std::error::Error>> {
+        use noodles_sam as sam;
+
+        let sq0 = bam::record::ReferenceSequenceId::try_from(0).map(Some)?;
+        let sq1 = bam::record::ReferenceSequenceId::try_from(1).map(Some)?;
+
+        let record = Record::builder()
+            .set_reference_sequence_id(bam::record::ReferenceSequenceId::try_from(0)?)
+            .set_alignment_start(sam::record::Position::try_from(8)?)
+            .set_read_length(4)
+            .build();
+
+        assert!(intersects(&record, sq0, 1, 8));
+        assert!(intersects(&record, sq0, 11, 13));
+        assert!(!intersects(&record, sq0, 12, 13));
+        assert!(!intersects(&record, sq1, 8, 13));
+        assert!(!intersects(&record, None, 1, i32::MAX));
+
+        let record = Record::default();
+        assert!(!intersects(&record, sq0, 1, i32::MAX));
+        assert!(intersects(&record, None, 1, i32::MAX));
+
+        Ok(())
+    }
+}
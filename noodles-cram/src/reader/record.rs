@@ -13,6 +13,7 @@ use noodles_sam as sam;
 
 use super::num::read_itf8;
 use crate::{
+    bit_codecs,
     container::ReferenceSequenceId,
     data_container::{
         compression_header::{data_series_encoding_map::DataSeries, encoding::Encoding},
@@ -20,7 +21,10 @@ use crate::{
     },
     huffman::CanonicalHuffmanDecoder,
     num::Itf8,
-    record::{feature, tag, Feature, Flags, NextMateFlags, ReadGroupId, Tag},
+    record::{
+        feature, resolve::resolve_quality_scores, tag, Feature, Flags, NextMateFlags, ReadGroupId,
+        Tag,
+    },
     BitReader, Record,
 };
 
@@ -30,6 +34,7 @@ pub enum ReadRecordError {
     MissingDataSeriesEncoding(DataSeries),
     MissingTagEncoding(tag::Key),
     MissingExternalBlock(i32),
+    UnsupportedEncoding(Encoding),
 }
 
 impl error::Error for ReadRecordError {}
@@ -44,6 +49,9 @@ impl fmt::Display for ReadRecordError {
             Self::MissingExternalBlock(block_content_id) => {
                 write!(f, "missing external block: {}", block_content_id)
             }
+            Self::UnsupportedEncoding(encoding) => {
+                write!(f, "unsupported encoding: {:?}", encoding)
+            }
         }
     }
 }
@@ -510,6 +518,8 @@ where
                 let score = self.read_quality_score()?;
                 record.quality_scores.push(score);
             }
+        } else {
+            record.quality_scores = resolve_quality_scores(record.features(), read_length)?;
         }
 
         Ok(())
@@ -925,18 +935,7 @@ where
 
             reader.read_u8()
         }
-        Encoding::Huffman(alphabet, bit_lens) => {
-            if alphabet.len() == 1 {
-                Ok(alphabet[0] as u8)
-            } else {
-                let decoder = CanonicalHuffmanDecoder::new(alphabet, bit_lens);
-                decoder.decode(core_data_reader).map(|i| i as u8)
-            }
-        }
-        Encoding::Beta(offset, len) => core_data_reader
-            .read_u32(*len)
-            .map(|i| (i as i32 - offset) as u8),
-        _ => todo!("decode_byte: {:?}", encoding),
+        _ => decode_itf8_with_core_data(encoding, core_data_reader).map(|n| n as u8),
     }
 }
 
@@ -962,6 +961,20 @@ where
 
             read_itf8(reader)
         }
+        _ => decode_itf8_with_core_data(encoding, core_data_reader),
+    }
+}
+
+// Decodes a value from the core data block, i.e., using an encoding that is not stored in an
+// external block.
+pub fn decode_itf8_with_core_data<CDR>(
+    encoding: &Encoding,
+    core_data_reader: &mut BitReader<CDR>,
+) -> io::Result<Itf8>
+where
+    CDR: Read,
+{
+    match encoding {
         Encoding::Huffman(alphabet, bit_lens) => {
             if alphabet.len() == 1 {
                 Ok(alphabet[0])
@@ -970,8 +983,17 @@ where
                 decoder.decode(core_data_reader)
             }
         }
-        Encoding::Beta(offset, len) => core_data_reader.read_u32(*len).map(|i| (i as i32 - offset)),
-        _ => todo!("decode_itf8: {:?}", encoding),
+        Encoding::Golomb(offset, m) => bit_codecs::decode_golomb(core_data_reader, *offset, *m),
+        Encoding::Beta(offset, len) => bit_codecs::decode_beta(core_data_reader, *offset, *len),
+        Encoding::Subexp(offset, k) => bit_codecs::decode_subexp(core_data_reader, *offset, *k),
+        Encoding::GolombRice(offset, log2_m) => {
+            bit_codecs::decode_golomb_rice(core_data_reader, *offset, *log2_m)
+        }
+        Encoding::Gamma(offset) => bit_codecs::decode_gamma(core_data_reader, *offset),
+        _ => Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            ReadRecordError::UnsupportedEncoding(encoding.clone()),
+        )),
     }
 }
 
@@ -996,7 +1018,13 @@ where
                     )
                 })?;
 
-            let mut buf = buf.expect("missing buf");
+            let mut buf = buf.ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    ReadRecordError::UnsupportedEncoding(encoding.clone()),
+                )
+            })?;
+
             reader.read_exact(&mut buf)?;
 
             Ok(buf)
@@ -1032,7 +1060,20 @@ where
 
             Ok(buf)
         }
-        _ => todo!("decode_byte_array: {:?}", encoding),
+        _ => match buf {
+            // The values of a byte array can be decoded using any byte encoding.
+            Some(mut buf) => {
+                for b in &mut buf {
+                    *b = decode_byte(encoding, core_data_reader, external_data_readers)?;
+                }
+
+                Ok(buf)
+            }
+            None => Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                ReadRecordError::UnsupportedEncoding(encoding.clone()),
+            )),
+        },
     }
 }
 
@@ -1084,6 +1125,15 @@ mod tests {
         t(&Encoding::External(1), 13)?;
         t(&Encoding::Huffman(vec![0x4e], vec![0]), 0x4e)?;
         t(&Encoding::Beta(1, 3), 3)?;
+        t(&Encoding::Golomb(0, 3), 3)?;
+        t(&Encoding::Subexp(0, 1), 2)?;
+        t(&Encoding::GolombRice(0, 1), 2)?;
+        t(&Encoding::Gamma(0), 1)?;
+
+        assert!(matches!(
+            t(&Encoding::Null, 0),
+            Err(ref e) if e.kind() == io::ErrorKind::InvalidData
+        ));
 
         Ok(())
     }
@@ -1123,6 +1173,19 @@ mod tests {
             b"ndls",
         )?;
 
+        let len_encoding = Encoding::External(1);
+        let value_encoding = Encoding::Huffman(vec![0x6e], vec![0]);
+        t(
+            &[0x02],
+            &Encoding::ByteArrayLen(Box::new(len_encoding), Box::new(value_encoding)),
+            b"nn",
+        )?;
+
+        assert!(matches!(
+            t(&[], &Encoding::External(1), b""),
+            Err(ref e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+
         Ok(())
     }
 }
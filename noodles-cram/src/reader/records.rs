@@ -3,6 +3,8 @@ use std::{
     vec,
 };
 
+use noodles_sam as sam;
+
 use crate::Record;
 
 use super::Reader;
@@ -15,6 +17,7 @@ where
     R: Read,
 {
     reader: &'a mut Reader<R>,
+    header: Option<&'a sam::Header>,
     records: vec::IntoIter<Record>,
 }
 
@@ -22,9 +25,10 @@ impl<'a, R> Records<'a, R>
 where
     R: Read,
 {
-    pub(crate) fn new(reader: &'a mut Reader<R>) -> Records<'_, R> {
+    pub(crate) fn new(reader: &'a mut Reader<R>, header: Option<&'a sam::Header>) -> Self {
         Self {
             reader,
+            header,
             records: Vec::new().into_iter(),
         }
     }
@@ -35,19 +39,23 @@ where
             None => return Ok(true),
         };
 
-        self.records = container
-            .slices()
-            .iter()
-            .map(|slice| {
-                slice
+        let mut records = Vec::new();
+
+        for slice in container.slices() {
+            let slice_records = match self.header {
+                Some(header) => {
+                    self.reader
+                        .read_slice_records(header, container.compression_header(), slice)?
+                }
+                None => slice
                     .records(container.compression_header())
-                    .map(|r| slice.resolve_mates(r))
-            })
-            .collect::<Result<Vec<_>, _>>()?
-            .into_iter()
-            .flatten()
-            .collect::<Vec<_>>()
-            .into_iter();
+                    .map(|r| slice.resolve_mates(r))?,
+            };
+
+            records.extend(slice_records);
+        }
+
+        self.records = records.into_iter();
 
         Ok(false)
     }
@@ -1,6 +1,7 @@
 //! CRAM record and fields.
 
 mod builder;
+mod conversion_options;
 mod convert;
 pub mod feature;
 mod flags;
@@ -10,8 +11,8 @@ pub mod resolve;
 pub mod tag;
 
 pub use self::{
-    builder::Builder, feature::Feature, flags::Flags, next_mate_flags::NextMateFlags,
-    read_group_id::ReadGroupId, tag::Tag,
+    builder::Builder, conversion_options::ConversionOptions, feature::Feature, flags::Flags,
+    next_mate_flags::NextMateFlags, read_group_id::ReadGroupId, tag::Tag,
 };
 
 use std::{fmt, str};
@@ -0,0 +1,160 @@
+/// Options for converting between CRAM and SAM records.
+///
+/// These control the fields that are not necessarily kept verbatim when round-tripping a SAM
+/// record through CRAM. By default, read names and CIGAR operations are preserved, `MD` and `NM`
+/// data fields are stored as given, and mismatched bases are stored as read bases.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct ConversionOptions {
+    preserve_read_names: bool,
+    regenerate_md_and_nm: bool,
+    preserve_cigar: bool,
+    encode_substitutions: bool,
+}
+
+impl ConversionOptions {
+    /// Returns whether read names are preserved.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_cram::record::ConversionOptions;
+    /// let options = ConversionOptions::default();
+    /// assert!(options.preserve_read_names());
+    /// ```
+    pub fn preserve_read_names(&self) -> bool {
+        self.preserve_read_names
+    }
+
+    /// Sets whether read names are preserved.
+    ///
+    /// When disabled, read names are discarded when converting a SAM record to a CRAM record, and
+    /// CRAM records are converted to SAM records without a read name.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_cram::record::ConversionOptions;
+    ///
+    /// let mut options = ConversionOptions::default();
+    /// options.set_preserve_read_names(false);
+    ///
+    /// assert!(!options.preserve_read_names());
+    /// ```
+    pub fn set_preserve_read_names(&mut self, preserve_read_names: bool) {
+        self.preserve_read_names = preserve_read_names;
+    }
+
+    /// Returns whether `MD` and `NM` data fields are regenerated from the reference sequence.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_cram::record::ConversionOptions;
+    /// let options = ConversionOptions::default();
+    /// assert!(!options.regenerate_md_and_nm());
+    /// ```
+    pub fn regenerate_md_and_nm(&self) -> bool {
+        self.regenerate_md_and_nm
+    }
+
+    /// Sets whether `MD` and `NM` data fields are regenerated from the reference sequence.
+    ///
+    /// When enabled, a SAM record that ends with `MD` and `NM` data fields matching its alignment
+    /// has them dropped when converted to a CRAM record. When converting a mapped CRAM record with
+    /// neither field to a SAM record, `MD` and `NM` are calculated from the reference sequence and
+    /// appended to the data.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_cram::record::ConversionOptions;
+    ///
+    /// let mut options = ConversionOptions::default();
+    /// options.set_regenerate_md_and_nm(true);
+    ///
+    /// assert!(options.regenerate_md_and_nm());
+    /// ```
+    pub fn set_regenerate_md_and_nm(&mut self, regenerate_md_and_nm: bool) {
+        self.regenerate_md_and_nm = regenerate_md_and_nm;
+    }
+
+    /// Returns whether CIGAR operations are preserved.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_cram::record::ConversionOptions;
+    /// let options = ConversionOptions::default();
+    /// assert!(options.preserve_cigar());
+    /// ```
+    pub fn preserve_cigar(&self) -> bool {
+        self.preserve_cigar
+    }
+
+    /// Sets whether CIGAR operations are preserved.
+    ///
+    /// Read features can only describe a CIGAR where alignment matches are `M` operations and
+    /// adjacent alignment matches are merged. When enabled, a SAM record with a CIGAR that cannot
+    /// be described by its read features, e.g., one with `=` or `X` operations, has its CIGAR
+    /// stored in a `CG` data field appended to the data. When converting a CRAM record to a SAM
+    /// record, a `CG` data field that is last and equivalent to the CIGAR of the read features
+    /// replaces the CIGAR and is removed from the data.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_cram::record::ConversionOptions;
+    ///
+    /// let mut options = ConversionOptions::default();
+    /// options.set_preserve_cigar(false);
+    ///
+    /// assert!(!options.preserve_cigar());
+    /// ```
+    pub fn set_preserve_cigar(&mut self, preserve_cigar: bool) {
+        self.preserve_cigar = preserve_cigar;
+    }
+
+    /// Returns whether mismatched bases are encoded as substitutions.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_cram::record::ConversionOptions;
+    /// let options = ConversionOptions::default();
+    /// assert!(!options.encode_substitutions());
+    /// ```
+    pub fn encode_substitutions(&self) -> bool {
+        self.encode_substitutions
+    }
+
+    /// Sets whether mismatched bases are encoded as substitutions.
+    ///
+    /// When enabled, a mismatched read base is stored as a substitution code (`SM`) when both it
+    /// and the reference base are one of `A`, `C`, `G`, `T`, or `N`. Otherwise, mismatches are
+    /// stored as read bases.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_cram::record::ConversionOptions;
+    ///
+    /// let mut options = ConversionOptions::default();
+    /// options.set_encode_substitutions(true);
+    ///
+    /// assert!(options.encode_substitutions());
+    /// ```
+    pub fn set_encode_substitutions(&mut self, encode_substitutions: bool) {
+        self.encode_substitutions = encode_substitutions;
+    }
+}
+
+impl Default for ConversionOptions {
+    fn default() -> Self {
+        Self {
+            preserve_read_names: true,
+            regenerate_md_and_nm: false,
+            preserve_cigar: true,
+            encode_substitutions: false,
+        }
+    }
+}
@@ -1,38 +1,405 @@
 use std::{io, str};
 
-use noodles_bam::record::ReferenceSequenceId;
+use noodles_bam::{
+    self as bam,
+    record::{data::field::Value as BamValue, ReferenceSequenceId},
+};
 use noodles_fasta as fasta;
 use noodles_sam::{
     self as sam,
-    record::{Data, QualityScores, Sequence},
+    record::{
+        cigar::{op::Kind, Op},
+        data::{
+            field::{Tag as SamTag, Value as SamValue},
+            Field,
+        },
+        Cigar, Data, Position, QualityScores, Sequence,
+    },
 };
 
-use crate::data_container::CompressionHeader;
+use crate::data_container::{
+    compression_header::{preservation_map::substitution_matrix::Base, SubstitutionMatrix},
+    CompressionHeader,
+};
 
 use super::{
     resolve::{resolve_bases, resolve_features},
-    Record, Tag,
+    tag, ConversionOptions, Feature, Flags, NextMateFlags, Record, Tag,
 };
 
+const MISSING_READ_NAME: &[u8] = b"*";
+
 impl Record {
+    /// Converts a SAM record to a CRAM record.
+    ///
+    /// This uses the default conversion options. See [`Self::try_from_sam_record_with_options`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_cram as cram;
+    /// use noodles_sam as sam;
+    ///
+    /// let reference_sequences = sam::header::ReferenceSequences::default();
+    /// let sam_record = sam::Record::default();
+    ///
+    /// let record = cram::Record::try_from_sam_record(&[], &reference_sequences, &sam_record)?;
+    /// assert_eq!(record.read_name(), b"*");
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn try_from_sam_record(
+        reference_assembly: &[fasta::Record],
+        reference_sequences: &sam::header::ReferenceSequences,
+        sam_record: &sam::Record,
+    ) -> io::Result<Self> {
+        Self::try_from_sam_record_with_options(
+            reference_assembly,
+            reference_sequences,
+            sam_record,
+            &ConversionOptions::default(),
+        )
+    }
+
+    /// Converts a SAM record to a CRAM record using the given conversion options.
+    ///
+    /// A mapped record is encoded as differences to its reference sequence in the reference
+    /// assembly, where mismatches are stored as read bases. If the reference sequence is not in
+    /// the reference assembly, all aligned read bases are stored.
+    ///
+    /// The mate fields are always stored as given, i.e., the resulting record is detached.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_cram::{self as cram, record::ConversionOptions};
+    /// use noodles_sam as sam;
+    ///
+    /// let reference_sequences = sam::header::ReferenceSequences::default();
+    /// let sam_record = sam::Record::builder().set_read_name("r0".parse()?).build()?;
+    ///
+    /// let mut options = ConversionOptions::default();
+    /// options.set_preserve_read_names(false);
+    ///
+    /// let record = cram::Record::try_from_sam_record_with_options(
+    ///     &[],
+    ///     &reference_sequences,
+    ///     &sam_record,
+    ///     &options,
+    /// )?;
+    ///
+    /// assert_eq!(record.read_name(), b"*");
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn try_from_sam_record_with_options(
+        reference_assembly: &[fasta::Record],
+        reference_sequences: &sam::header::ReferenceSequences,
+        sam_record: &sam::Record,
+        options: &ConversionOptions,
+    ) -> io::Result<Self> {
+        Self::from_sam_record(
+            reference_assembly,
+            reference_sequences,
+            sam_record,
+            None,
+            options,
+        )
+    }
+
+    /// Converts a BAM record to a CRAM record using the given conversion options.
+    ///
+    /// This is the same as [`Self::try_from_sam_record_with_options`], except the types of the
+    /// data field values are kept, e.g., an integer stored as an `Int32` is not narrowed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_bam as bam;
+    /// use noodles_cram::{self as cram, record::ConversionOptions};
+    /// use noodles_sam as sam;
+    ///
+    /// let reference_sequences = sam::header::ReferenceSequences::default();
+    /// let bam_record = bam::Record::default();
+    ///
+    /// let record = cram::Record::try_from_bam_record_with_options(
+    ///     &[],
+    ///     &reference_sequences,
+    ///     &bam_record,
+    ///     &ConversionOptions::default(),
+    /// )?;
+    ///
+    /// assert_eq!(record.read_name(), b"*");
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn try_from_bam_record_with_options(
+        reference_assembly: &[fasta::Record],
+        reference_sequences: &sam::header::ReferenceSequences,
+        bam_record: &bam::Record,
+        options: &ConversionOptions,
+    ) -> io::Result<Self> {
+        let sam_record = bam_record.try_into_sam_record(reference_sequences)?;
+
+        Self::from_sam_record(
+            reference_assembly,
+            reference_sequences,
+            &sam_record,
+            Some(bam_record.data()),
+            options,
+        )
+    }
+
+    fn from_sam_record(
+        reference_assembly: &[fasta::Record],
+        reference_sequences: &sam::header::ReferenceSequences,
+        sam_record: &sam::Record,
+        bam_data: Option<&bam::record::Data>,
+        options: &ConversionOptions,
+    ) -> io::Result<Self> {
+        let mut builder = Self::builder();
+
+        let bam_flags = sam_record.flags();
+        builder = builder.set_bam_flags(bam_flags);
+
+        let read_name = match sam_record.read_name() {
+            Some(read_name) if options.preserve_read_names() => read_name.as_bytes().to_vec(),
+            _ => MISSING_READ_NAME.to_vec(),
+        };
+
+        builder = builder.set_read_name(read_name);
+
+        let reference_sequence_id =
+            get_reference_sequence_id(reference_sequences, sam_record.reference_sequence_name())?;
+
+        if let Some(id) = reference_sequence_id {
+            builder = builder.set_reference_sequence_id(id);
+        }
+
+        if let Some(position) = sam_record.position() {
+            builder = builder.set_alignment_start(position);
+        }
+
+        if let Some(mapping_quality) = sam_record.mapping_quality() {
+            builder = builder.set_mapping_quality(mapping_quality);
+        }
+
+        let mut next_mate_flags = NextMateFlags::default();
+
+        if bam_flags.is_mate_reverse_complemented() {
+            next_mate_flags |= NextMateFlags::ON_NEGATIVE_STRAND;
+        }
+
+        if bam_flags.is_mate_unmapped() {
+            next_mate_flags |= NextMateFlags::UNMAPPED;
+        }
+
+        builder = builder.set_next_mate_flags(next_mate_flags);
+
+        if let Some(id) = get_reference_sequence_id(
+            reference_sequences,
+            sam_record.mate_reference_sequence_name(),
+        )? {
+            builder = builder.set_next_fragment_reference_sequence_id(id);
+        }
+
+        if let Some(mate_position) = sam_record.mate_position() {
+            builder = builder.set_next_mate_alignment_start(mate_position);
+        }
+
+        builder = builder.set_template_size(sam_record.template_length());
+
+        let mut flags = Flags::DETACHED;
+
+        let bases: Vec<_> = sam_record
+            .sequence()
+            .iter()
+            .map(|&base| char::from(base) as u8)
+            .collect();
+
+        let is_unmapped = bam_flags.is_unmapped();
+
+        let read_length = if bases.is_empty() && !is_unmapped {
+            flags |= Flags::DECODE_SEQUENCE_AS_UNKNOWN;
+            sam_record.cigar().read_len() as usize
+        } else {
+            bases.len()
+        };
+
+        builder = builder.set_read_length(read_length);
+
+        let reference_sequence = reference_sequence_id
+            .and_then(|id| get_reference_sequence_record(reference_assembly, id).ok())
+            .map(|record| record.sequence().as_ref());
+
+        let data = sam_record.data();
+        let mut field_count = data.len();
+        let mut resolved_cigar = None;
+
+        if !is_unmapped {
+            let alignment_start = sam_record.position().ok_or_else(|| {
+                io::Error::new(io::ErrorKind::InvalidInput, "missing alignment start")
+            })?;
+
+            let read_bases = if flags.decode_sequence_as_unknown() {
+                None
+            } else {
+                Some(&bases[..])
+            };
+
+            let features = build_features(
+                reference_sequence,
+                alignment_start,
+                sam_record.cigar(),
+                read_bases,
+                options.encode_substitutions(),
+            )?;
+
+            if options.preserve_cigar() {
+                resolved_cigar = Some(resolve_features(&features, read_length as i32));
+            }
+
+            builder = builder.set_features(features);
+
+            if let (true, Some(reference_sequence), Some(read_bases)) = (
+                options.regenerate_md_and_nm(),
+                reference_sequence,
+                read_bases,
+            ) {
+                if can_regenerate_md_and_nm(
+                    reference_sequence,
+                    alignment_start,
+                    sam_record.cigar(),
+                    read_bases,
+                    data,
+                ) {
+                    field_count -= 2;
+                }
+            }
+        }
+
+        let quality_scores = sam_record.quality_scores();
+
+        if !quality_scores.is_empty() {
+            flags |= Flags::QUALITY_SCORES_STORED_AS_ARRAY;
+            let scores = quality_scores.iter().copied().map(u8::from).collect();
+            builder = builder.set_quality_scores(scores);
+        }
+
+        let mut tags = match bam_data {
+            Some(bam_data) => bam_data_to_tags(bam_data, field_count)?,
+            None => data_to_tags(data.values().take(field_count))?,
+        };
+
+        if let Some(resolved_cigar) = resolved_cigar {
+            push_cigar_tag(&mut tags, sam_record.cigar(), &resolved_cigar)?;
+        }
+
+        Ok(builder
+            .set_flags(flags)
+            .set_bases(bases)
+            .set_tags(tags)
+            .build())
+    }
+
     /// Converts this CRAM record to a SAM record.
+    ///
+    /// This uses the default conversion options. See [`Self::try_into_sam_record_with_options`].
     pub fn try_into_sam_record(
         &self,
         reference_assembly: &[fasta::Record],
         reference_sequences: &sam::header::ReferenceSequences,
         compression_header: &CompressionHeader,
     ) -> io::Result<sam::Record> {
-        let mut builder = sam::Record::builder();
+        self.try_into_sam_record_with_options(
+            reference_assembly,
+            reference_sequences,
+            compression_header,
+            &ConversionOptions::default(),
+        )
+    }
 
-        let raw_read_name = str::from_utf8(self.read_name())
-            .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+    /// Converts this CRAM record to a SAM record using the given conversion options.
+    ///
+    /// Regenerating `MD` and `NM` data fields requires the reference sequence of the record to be
+    /// in the reference assembly.
+    pub fn try_into_sam_record_with_options(
+        &self,
+        reference_assembly: &[fasta::Record],
+        reference_sequences: &sam::header::ReferenceSequences,
+        compression_header: &CompressionHeader,
+        options: &ConversionOptions,
+    ) -> io::Result<sam::Record> {
+        self.to_sam_record(
+            reference_assembly,
+            reference_sequences,
+            compression_header,
+            options,
+        )
+        .map(|(sam_record, _)| sam_record)
+    }
+
+    /// Converts this CRAM record to a BAM record using the given conversion options.
+    ///
+    /// This is the same as [`Self::try_into_sam_record_with_options`], except the types of the
+    /// data field values are kept as stored in the CRAM record.
+    pub fn try_into_bam_record_with_options(
+        &self,
+        reference_assembly: &[fasta::Record],
+        reference_sequences: &sam::header::ReferenceSequences,
+        compression_header: &CompressionHeader,
+        options: &ConversionOptions,
+    ) -> io::Result<bam::Record> {
+        let (sam_record, tags) = self.to_sam_record(
+            reference_assembly,
+            reference_sequences,
+            compression_header,
+            options,
+        )?;
+
+        let mut bam_record = bam::Record::try_from_sam_record(reference_sequences, &sam_record)?;
+
+        // Regenerated fields follow the fields of the CRAM record.
+        let fields = sam_record
+            .data()
+            .values()
+            .enumerate()
+            .map(|(i, field)| {
+                let value = match tags.get(i) {
+                    Some(tag) => tag.value().clone(),
+                    None => sam_value_to_bam_value(field.value())?,
+                };
+
+                Ok(bam::record::data::Field::new(field.tag(), value))
+            })
+            .collect::<io::Result<Vec<_>>>()?;
+
+        *bam_record.data_mut() = bam::record::Data::try_from(fields)?;
+
+        Ok(bam_record)
+    }
+
+    // Returns the SAM record and the CRAM record tags that are its data fields.
+    fn to_sam_record(
+        &self,
+        reference_assembly: &[fasta::Record],
+        reference_sequences: &sam::header::ReferenceSequences,
+        compression_header: &CompressionHeader,
+        options: &ConversionOptions,
+    ) -> io::Result<(sam::Record, &[Tag])> {
+        let mut builder = sam::Record::builder();
 
-        if raw_read_name != "*" {
-            let read_name = raw_read_name
-                .parse()
+        if options.preserve_read_names() {
+            let raw_read_name = str::from_utf8(self.read_name())
                 .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
 
-            builder = builder.set_read_name(read_name);
+            if !raw_read_name.is_empty() && raw_read_name.as_bytes() != MISSING_READ_NAME {
+                let read_name = raw_read_name
+                    .parse()
+                    .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+
+                builder = builder.set_read_name(read_name);
+            }
         }
 
         builder = builder.set_flags(self.bam_flags());
@@ -51,8 +418,30 @@ impl Record {
             builder = builder.set_mapping_quality(mapping_quality);
         }
 
-        let cigar = resolve_features(self.features(), self.read_length() as i32);
-        builder = builder.set_cigar(cigar);
+        let is_unmapped = self.bam_flags().is_unmapped();
+
+        let mut tags = self.tags();
+
+        let cigar = if is_unmapped {
+            Cigar::default()
+        } else {
+            let resolved_cigar = resolve_features(self.features(), self.read_length() as i32);
+
+            let stored_cigar = tags
+                .split_last()
+                .filter(|_| options.preserve_cigar())
+                .and_then(|(tag, rest)| restore_cigar(tag, &resolved_cigar).map(|c| (c, rest)));
+
+            match stored_cigar {
+                Some((cigar, rest)) => {
+                    tags = rest;
+                    cigar
+                }
+                None => resolved_cigar,
+            }
+        };
+
+        builder = builder.set_cigar(cigar.clone());
 
         if let Some(mate_reference_sequence_name) = get_reference_sequence_name(
             reference_sequences,
@@ -67,29 +456,27 @@ impl Record {
 
         builder = builder.set_template_length(self.template_size());
 
-        if self.read_length() > 0 {
-            let sequence = if let Some(reference_sequence_id) = self.reference_sequence_id() {
-                let reference_sequence_record = usize::try_from(i32::from(reference_sequence_id))
-                    .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
-                    .and_then(|id| {
-                        reference_assembly.get(id).ok_or_else(|| {
-                            io::Error::new(io::ErrorKind::InvalidData, "missing reference sequence")
-                        })
-                    })?;
+        let mut raw_bases = Vec::new();
+
+        if self.read_length() > 0 && !self.flags().decode_sequence_as_unknown() {
+            raw_bases = if !self.bases().is_empty() {
+                self.bases().to_vec()
+            } else if let Some(reference_sequence_id) = self.reference_sequence_id() {
+                let reference_sequence_record =
+                    get_reference_sequence_record(reference_assembly, reference_sequence_id)?;
 
-                let raw_bases = resolve_bases(
+                resolve_bases(
                     reference_sequence_record,
                     compression_header,
                     self.features(),
                     self.alignment_start().map(i32::from).unwrap_or_default(),
-                    self.read_length() as usize,
-                );
-
-                bytes_to_sequence(&raw_bases)?
+                    self.read_length(),
+                )?
             } else {
-                bytes_to_sequence(self.bases())?
+                Vec::new()
             };
 
+            let sequence = bytes_to_sequence(&raw_bases)?;
             builder = builder.set_sequence(sequence);
         }
 
@@ -98,17 +485,66 @@ impl Record {
             builder = builder.set_quality_scores(quality_scores);
         }
 
-        if !self.tags().is_empty() {
-            let data = tags_to_data(self.tags())?;
+        let mut data = tags_to_data(tags)?;
+
+        if options.regenerate_md_and_nm()
+            && !is_unmapped
+            && !raw_bases.is_empty()
+            && data.get(SamTag::MismatchedPositions).is_none()
+            && data.get(SamTag::EditDistance).is_none()
+        {
+            if let (Some(reference_sequence_id), Some(alignment_start)) =
+                (self.reference_sequence_id(), self.alignment_start())
+            {
+                let reference_sequence_record =
+                    get_reference_sequence_record(reference_assembly, reference_sequence_id)?;
+
+                let (md, nm) = calculate_md_and_nm(
+                    reference_sequence_record.sequence().as_ref(),
+                    alignment_start,
+                    &cigar,
+                    &raw_bases,
+                )?;
+
+                data.insert(Field::new(
+                    SamTag::MismatchedPositions,
+                    SamValue::String(md),
+                ));
+                data.insert(Field::new(SamTag::EditDistance, SamValue::Int(nm)));
+            }
+        }
+
+        if !data.is_empty() {
             builder = builder.set_data(data);
         }
 
         builder
             .build()
+            .map(|sam_record| (sam_record, tags))
             .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
     }
 }
 
+fn get_reference_sequence_id(
+    reference_sequences: &sam::header::ReferenceSequences,
+    reference_sequence_name: Option<&sam::record::ReferenceSequenceName>,
+) -> io::Result<Option<ReferenceSequenceId>> {
+    reference_sequence_name
+        .map(|name| {
+            reference_sequences
+                .get_index_of(name.as_str())
+                .and_then(|i| i32::try_from(i).ok())
+                .and_then(|i| ReferenceSequenceId::try_from(i).ok())
+                .ok_or_else(|| {
+                    io::Error::new(
+                        io::ErrorKind::InvalidInput,
+                        format!("invalid reference sequence name: {}", name.as_str()),
+                    )
+                })
+        })
+        .transpose()
+}
+
 fn get_reference_sequence_name(
     reference_sequences: &sam::header::ReferenceSequences,
     reference_sequence_id: Option<ReferenceSequenceId>,
@@ -126,6 +562,392 @@ fn get_reference_sequence_name(
         .transpose()
 }
 
+fn get_reference_sequence_record(
+    reference_assembly: &[fasta::Record],
+    reference_sequence_id: ReferenceSequenceId,
+) -> io::Result<&fasta::Record> {
+    usize::try_from(i32::from(reference_sequence_id))
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+        .and_then(|id| {
+            reference_assembly.get(id).ok_or_else(|| {
+                io::Error::new(io::ErrorKind::InvalidData, "missing reference sequence")
+            })
+        })
+}
+
+fn build_features(
+    reference_sequence: Option<&[u8]>,
+    alignment_start: Position,
+    cigar: &Cigar,
+    bases: Option<&[u8]>,
+    encode_substitutions: bool,
+) -> io::Result<Vec<Feature>> {
+    fn position_to_i32(n: usize) -> io::Result<i32> {
+        i32::try_from(n).map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))
+    }
+
+    // Bases are unknown when the sequence is missing, but insertions and soft clips still need
+    // them to describe the alignment.
+    fn get_bases(bases: Option<&[u8]>, read_position: usize, len: usize) -> io::Result<Vec<u8>> {
+        match bases {
+            Some(bases) => get_read_bases(bases, read_position, len).map(|b| b.to_vec()),
+            None => Ok(vec![b'N'; len]),
+        }
+    }
+
+    let mut features = Vec::new();
+
+    let mut read_position = 1;
+    let mut reference_position = i32::from(alignment_start) as usize;
+
+    for op in cigar.iter() {
+        let len = op.len() as usize;
+        let position = position_to_i32(read_position)?;
+
+        match op.kind() {
+            Kind::Match | Kind::SeqMatch | Kind::SeqMismatch => {
+                if let Some(bases) = bases {
+                    let read_bases = get_read_bases(bases, read_position, len)?;
+
+                    if let Some(reference_sequence) = reference_sequence {
+                        let start = reference_position - 1;
+                        let end = start + len;
+
+                        let reference_bases =
+                            reference_sequence.get(start..end).ok_or_else(|| {
+                                io::Error::new(
+                                    io::ErrorKind::InvalidInput,
+                                    "alignment exceeds reference sequence",
+                                )
+                            })?;
+
+                        push_mismatches(
+                            &mut features,
+                            read_position,
+                            read_bases,
+                            reference_bases,
+                            encode_substitutions,
+                        )?;
+                    } else {
+                        features.push(Feature::Bases(position, read_bases.to_vec()));
+                    }
+                }
+
+                read_position += len;
+                reference_position += len;
+            }
+            Kind::Insertion => {
+                let read_bases = get_bases(bases, read_position, len)?;
+                features.push(Feature::Insertion(position, read_bases));
+                read_position += len;
+            }
+            Kind::Deletion => {
+                features.push(Feature::Deletion(position, position_to_i32(len)?));
+                reference_position += len;
+            }
+            Kind::Skip => {
+                features.push(Feature::ReferenceSkip(position, position_to_i32(len)?));
+                reference_position += len;
+            }
+            Kind::SoftClip => {
+                let read_bases = get_bases(bases, read_position, len)?;
+                features.push(Feature::SoftClip(position, read_bases));
+                read_position += len;
+            }
+            Kind::HardClip => {
+                features.push(Feature::HardClip(position, position_to_i32(len)?));
+            }
+            Kind::Pad => {
+                features.push(Feature::Padding(position, position_to_i32(len)?));
+            }
+        }
+    }
+
+    Ok(features)
+}
+
+fn get_read_bases(bases: &[u8], read_position: usize, len: usize) -> io::Result<&[u8]> {
+    let start = read_position - 1;
+    let end = start + len;
+
+    bases.get(start..end).ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "CIGAR read length exceeds sequence length",
+        )
+    })
+}
+
+// Adds a feature for each run of read bases that differ from the reference bases.
+//
+// Read bases are uppercase, so reference bases are compared case-insensitively. When encoding
+// substitutions, mismatches that have a substitution code are added as substitutions using the
+// default substitution matrix, and the remaining runs are added as bases.
+fn push_mismatches(
+    features: &mut Vec<Feature>,
+    read_position: usize,
+    read_bases: &[u8],
+    reference_bases: &[u8],
+    encode_substitutions: bool,
+) -> io::Result<()> {
+    let substitution_matrix = SubstitutionMatrix::default();
+
+    let is_match = |i: usize| read_bases[i] == reference_bases[i].to_ascii_uppercase();
+
+    let substitution_code = |i: usize| {
+        if encode_substitutions {
+            get_substitution_code(&substitution_matrix, reference_bases[i], read_bases[i])
+        } else {
+            None
+        }
+    };
+
+    let mut i = 0;
+
+    while i < read_bases.len() {
+        if is_match(i) {
+            i += 1;
+            continue;
+        }
+
+        let position = i32::try_from(read_position + i)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+
+        if let Some(code) = substitution_code(i) {
+            features.push(Feature::Substitution(position, code));
+            i += 1;
+            continue;
+        }
+
+        let start = i;
+
+        while i < read_bases.len() && !is_match(i) && substitution_code(i).is_none() {
+            i += 1;
+        }
+
+        features.push(Feature::Bases(position, read_bases[start..i].to_vec()));
+    }
+
+    Ok(())
+}
+
+fn get_substitution_code(
+    substitution_matrix: &SubstitutionMatrix,
+    reference_base: u8,
+    read_base: u8,
+) -> Option<u8> {
+    let reference_base = Base::try_from(char::from(reference_base.to_ascii_uppercase())).ok()?;
+    let read_base = Base::try_from(char::from(read_base)).ok()?;
+    substitution_matrix.find(reference_base, read_base)
+}
+
+// A CIGAR that cannot be resolved from the read features is appended as a `CG` data field.
+fn push_cigar_tag(tags: &mut Vec<Tag>, cigar: &Cigar, resolved_cigar: &Cigar) -> io::Result<()> {
+    let has_cigar_tag = |tags: &[Tag]| {
+        tags.iter()
+            .any(|tag| tag.key().tag() == *SamTag::Cigar.as_ref())
+    };
+
+    if cigar == resolved_cigar {
+        // A trailing `CG` data field would otherwise be read back as the CIGAR.
+        if let Some(tag) = tags.last() {
+            if restore_cigar(tag, resolved_cigar).is_some() {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "CG data field is ambiguous with a preserved CIGAR",
+                ));
+            }
+        }
+
+        return Ok(());
+    }
+
+    if has_cigar_tag(tags) {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "CG data field conflicts with a preserved CIGAR",
+        ));
+    }
+
+    let raw_ops = cigar
+        .iter()
+        .map(|op| {
+            bam::record::cigar::Op::new(op.kind(), op.len())
+                .map(u32::from)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))
+        })
+        .collect::<io::Result<_>>()?;
+
+    let value = BamValue::UInt32Array(raw_ops);
+    let key = tag::Key::new(*SamTag::Cigar.as_ref(), value.ty());
+    tags.push(Tag::new(key, value));
+
+    Ok(())
+}
+
+// Returns the CIGAR stored in a `CG` data field if it is equivalent to but differs from the CIGAR
+// resolved from the read features.
+fn restore_cigar(tag: &Tag, resolved_cigar: &Cigar) -> Option<Cigar> {
+    if tag.key().tag() != *SamTag::Cigar.as_ref() {
+        return None;
+    }
+
+    let raw_ops = match tag.value() {
+        BamValue::UInt32Array(raw_ops) => raw_ops,
+        _ => return None,
+    };
+
+    let ops = raw_ops
+        .iter()
+        .map(|&n| bam::record::cigar::Op::try_from(n).ok().map(Op::from))
+        .collect::<Option<Vec<_>>>()?;
+
+    let cigar = Cigar::from(ops);
+
+    if cigar != *resolved_cigar && normalize_cigar(&cigar) == normalize_cigar(resolved_cigar) {
+        Some(cigar)
+    } else {
+        None
+    }
+}
+
+// Merges adjacent operations of the same kind, where sequence matches and mismatches are alignment
+// matches, and removes empty operations.
+fn normalize_cigar(cigar: &Cigar) -> Vec<Op> {
+    let mut ops: Vec<Op> = Vec::new();
+
+    for op in cigar.iter().filter(|op| !op.is_empty()) {
+        let kind = match op.kind() {
+            Kind::SeqMatch | Kind::SeqMismatch => Kind::Match,
+            kind => kind,
+        };
+
+        match ops.last_mut() {
+            Some(last_op) if last_op.kind() == kind => {
+                *last_op = Op::new(kind, last_op.len() + op.len());
+            }
+            _ => ops.push(Op::new(kind, op.len())),
+        }
+    }
+
+    ops
+}
+
+// `MD` and `NM` are only dropped when they are the last data fields and match the calculated
+// values, so that appending the regenerated fields reproduces the original data.
+fn can_regenerate_md_and_nm(
+    reference_sequence: &[u8],
+    alignment_start: Position,
+    cigar: &Cigar,
+    sequence: &[u8],
+    data: &Data,
+) -> bool {
+    if data.len() < 2 {
+        return false;
+    }
+
+    let is_at_end = data
+        .keys()
+        .skip(data.len() - 2)
+        .eq([SamTag::MismatchedPositions, SamTag::EditDistance]);
+
+    if !is_at_end {
+        return false;
+    }
+
+    match calculate_md_and_nm(reference_sequence, alignment_start, cigar, sequence) {
+        Ok((md, nm)) => {
+            data.get(SamTag::MismatchedPositions)
+                .map(|field| field.value())
+                == Some(&SamValue::String(md))
+                && data.get(SamTag::EditDistance).map(|field| field.value())
+                    == Some(&SamValue::Int(nm))
+        }
+        Err(_) => false,
+    }
+}
+
+// Calculates the mismatched positions (`MD`) and edit distance (`NM`) of an alignment.
+fn calculate_md_and_nm(
+    reference_sequence: &[u8],
+    alignment_start: Position,
+    cigar: &Cigar,
+    sequence: &[u8],
+) -> io::Result<(String, i64)> {
+    fn is_match(read_base: u8, reference_base: u8) -> bool {
+        let read_base = read_base.to_ascii_uppercase();
+        let reference_base = reference_base.to_ascii_uppercase();
+        read_base == b'=' || (read_base == reference_base && reference_base != b'N')
+    }
+
+    let get_reference_bases = |start: usize, len: usize| {
+        reference_sequence.get(start..start + len).ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "alignment exceeds reference sequence",
+            )
+        })
+    };
+
+    let mut md = String::new();
+    let mut nm = 0;
+    let mut match_count = 0;
+
+    let mut read_position = 0;
+    let mut reference_position = i32::from(alignment_start) as usize - 1;
+
+    for op in cigar.iter() {
+        let len = op.len() as usize;
+
+        match op.kind() {
+            Kind::Match | Kind::SeqMatch | Kind::SeqMismatch => {
+                let read_bases = get_read_bases(sequence, read_position + 1, len)?;
+                let reference_bases = get_reference_bases(reference_position, len)?;
+
+                for (&read_base, &reference_base) in read_bases.iter().zip(reference_bases) {
+                    if is_match(read_base, reference_base) {
+                        match_count += 1;
+                    } else {
+                        md.push_str(&match_count.to_string());
+                        md.push(char::from(reference_base.to_ascii_uppercase()));
+                        match_count = 0;
+                        nm += 1;
+                    }
+                }
+
+                read_position += len;
+                reference_position += len;
+            }
+            Kind::Insertion => {
+                nm += len as i64;
+                read_position += len;
+            }
+            Kind::SoftClip => read_position += len,
+            Kind::Deletion => {
+                let reference_bases = get_reference_bases(reference_position, len)?;
+
+                md.push_str(&match_count.to_string());
+                md.push('^');
+                md.extend(
+                    reference_bases
+                        .iter()
+                        .map(|&b| char::from(b.to_ascii_uppercase())),
+                );
+
+                match_count = 0;
+                nm += len as i64;
+                reference_position += len;
+            }
+            Kind::Skip => reference_position += len,
+            Kind::HardClip | Kind::Pad => {}
+        }
+    }
+
+    md.push_str(&match_count.to_string());
+
+    Ok((md, nm))
+}
+
 fn bytes_to_sequence(data: &[u8]) -> io::Result<Sequence> {
     use sam::record::sequence::Base;
 
@@ -149,8 +971,6 @@ fn bytes_to_quality_scores(data: &[u8]) -> io::Result<QualityScores> {
 }
 
 fn tags_to_data(tags: &[Tag]) -> io::Result<Data> {
-    use sam::record::data::Field;
-
     let mut fields = Vec::with_capacity(tags.len());
 
     for tag in tags {
@@ -171,3 +991,564 @@ fn tags_to_data(tags: &[Tag]) -> io::Result<Data> {
 
     Data::try_from(fields).map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))
 }
+
+fn bam_data_to_tags(data: &bam::record::Data, field_count: usize) -> io::Result<Vec<Tag>> {
+    data.values()
+        .take(field_count)
+        .map(|result| {
+            result.map(|field| {
+                let value = field.value().clone();
+                let key = tag::Key::new(*field.tag().as_ref(), value.ty());
+                Tag::new(key, value)
+            })
+        })
+        .collect()
+}
+
+fn data_to_tags<'a, I>(fields: I) -> io::Result<Vec<Tag>>
+where
+    I: Iterator<Item = &'a Field>,
+{
+    fields
+        .map(|field| {
+            let value = sam_value_to_bam_value(field.value())?;
+            let key = tag::Key::new(*field.tag().as_ref(), value.ty());
+            Ok(Tag::new(key, value))
+        })
+        .collect()
+}
+
+// Integers use the smallest type that can hold the value, as done when writing BAM records.
+fn sam_value_to_bam_value(value: &SamValue) -> io::Result<BamValue> {
+    match value {
+        SamValue::Char(c) => Ok(BamValue::Char(*c)),
+        SamValue::Int(n) => {
+            let n = *n;
+
+            if n >= 0 {
+                if let Ok(m) = u8::try_from(n) {
+                    return Ok(BamValue::UInt8(m));
+                } else if let Ok(m) = u16::try_from(n) {
+                    return Ok(BamValue::UInt16(m));
+                } else if let Ok(m) = u32::try_from(n) {
+                    return Ok(BamValue::UInt32(m));
+                }
+            } else if let Ok(m) = i8::try_from(n) {
+                return Ok(BamValue::Int8(m));
+            } else if let Ok(m) = i16::try_from(n) {
+                return Ok(BamValue::Int16(m));
+            } else if let Ok(m) = i32::try_from(n) {
+                return Ok(BamValue::Int32(m));
+            }
+
+            Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("invalid integer value: {}", n),
+            ))
+        }
+        SamValue::Float(n) => Ok(BamValue::Float(*n)),
+        SamValue::String(s) => Ok(BamValue::String(s.clone())),
+        SamValue::Hex(s) => Ok(BamValue::Hex(s.clone())),
+        SamValue::Int8Array(values) => Ok(BamValue::Int8Array(values.clone())),
+        SamValue::UInt8Array(values) => Ok(BamValue::UInt8Array(values.clone())),
+        SamValue::Int16Array(values) => Ok(BamValue::Int16Array(values.clone())),
+        SamValue::UInt16Array(values) => Ok(BamValue::UInt16Array(values.clone())),
+        SamValue::Int32Array(values) => Ok(BamValue::Int32Array(values.clone())),
+        SamValue::UInt32Array(values) => Ok(BamValue::UInt32Array(values.clone())),
+        SamValue::FloatArray(values) => Ok(BamValue::FloatArray(values.clone())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CORPUS: &[&str] = &[
+        "r0\t99\tsq0\t1\t60\t4M\t=\t5\t8\tACGT\tNDLS\tMD:Z:4\tNM:i:0",
+        "r1\t0\tsq0\t2\t30\t1S2M1I1M1D2M1H\t*\t0\t0\tTCGAGCT\tABCDEFG\tRG:Z:rg0\tXA:B:s,-1,2\tXF:f:1.5\tXC:A:c\tMD:Z:2T0^A1G0\tNM:i:4",
+        "r0\t147\tsq0\t5\t60\t4M\t=\t1\t-8\tACTT\tABCD\tMD:Z:2G1\tNM:i:1",
+        "r2\t16\tsq0\t10\t0\t4M2N2M\t*\t0\t0\tCGTTTA\t%%%%%%\tNM:i:1\tMD:Z:3A2",
+        "r3\t0\tsq1\t2\t60\t3M\t*\t0\t0\t*\t*",
+        "r4\t69\tsq1\t4\t255\t*\t=\t4\t0\tACGT\t####\tRG:Z:rg0",
+        "r4\t137\tsq1\t4\t60\t4M\t=\t4\t0\tTTAC\t$$$$\tMD:Z:4\tNM:i:0",
+        "r5\t4\t*\t0\t255\t*\t*\t0\t0\tNNNN\t!!!!",
+        "r6\t0\tsq0\t1\t60\t2=1X1=\t*\t0\t0\tACTT\tABCD\tMD:Z:2G1\tNM:i:1",
+        "r7\t0\tsq0\t1\t60\t2M2M\t*\t0\t0\tACGT\tABCD\tMD:Z:4\tNM:i:0",
+        "r8\t0\tsq2\t1\t60\t4M\t*\t0\t0\tACGA\tABCD\tMD:Z:3T0\tNM:i:1",
+        "r9\t0\tsq2\t3\t60\t6M\t*\t0\t0\tGTNNAN\tABCDEF\tMD:Z:2N0N0N0N0\tNM:i:4",
+    ];
+
+    fn build_reference_sequences() -> Vec<fasta::Record> {
+        use fasta::record::{Definition, Sequence};
+
+        vec![
+            fasta::Record::new(
+                Definition::new("sq0", None),
+                Sequence::from(b"ACGTACGTACGTACGTACGT".to_vec()),
+            ),
+            fasta::Record::new(
+                Definition::new("sq1", None),
+                Sequence::from(b"GGATTACA".to_vec()),
+            ),
+            fasta::Record::new(
+                Definition::new("sq2", None),
+                Sequence::from(b"acgtNNNNacgt".to_vec()),
+            ),
+        ]
+    }
+
+    fn build_header(
+        reference_sequences: &[fasta::Record],
+    ) -> Result<sam::Header, Box<dyn std::error::Error>> {
+        use md5::{Digest, Md5};
+        use sam::header::{reference_sequence::Md5Checksum, ReferenceSequence};
+
+        let mut builder = sam::Header::builder();
+
+        for record in reference_sequences {
+            let sequence = record.sequence().as_ref();
+            let md5_checksum = Md5Checksum::from(<[u8; 16]>::from(Md5::digest(sequence)));
+
+            builder = builder.add_reference_sequence(
+                ReferenceSequence::builder()
+                    .set_name(record.name().parse()?)
+                    .set_length(sequence.len() as i32)
+                    .set_md5_checksum(md5_checksum)
+                    .build()?,
+            );
+        }
+
+        Ok(builder.build())
+    }
+
+    // Writes the records to a CRAM file and reads them back with their compression headers.
+    fn write_and_read_records(
+        records: Vec<Record>,
+        options: &ConversionOptions,
+    ) -> Result<Vec<(Record, CompressionHeader)>, Box<dyn std::error::Error>> {
+        use crate::{Reader, Writer};
+
+        let reference_sequences = build_reference_sequences();
+        let header = build_header(&reference_sequences)?;
+
+        let mut writer = Writer::builder(Vec::new())
+            .set_reference_sequences(reference_sequences)
+            .set_preserve_read_names(options.preserve_read_names())
+            .build();
+
+        writer.write_file_definition()?;
+        writer.write_file_header(&header)?;
+
+        for record in records {
+            writer.write_record(record)?;
+        }
+
+        writer.try_finish()?;
+
+        let mut reader = Reader::new(&writer.get_ref()[..]);
+        reader.read_file_definition()?;
+        reader.read_file_header()?;
+
+        let mut actual = Vec::new();
+
+        while let Some(container) = reader.read_data_container()? {
+            let compression_header = container.compression_header();
+
+            for slice in container.slices() {
+                for record in slice.records(compression_header)? {
+                    actual.push((record, compression_header.clone()));
+                }
+            }
+        }
+
+        Ok(actual)
+    }
+
+    fn round_trip(
+        sam_records: &[sam::Record],
+        options: &ConversionOptions,
+    ) -> Result<Vec<sam::Record>, Box<dyn std::error::Error>> {
+        let reference_sequences = build_reference_sequences();
+        let header = build_header(&reference_sequences)?;
+
+        let records = sam_records
+            .iter()
+            .map(|sam_record| {
+                Record::try_from_sam_record_with_options(
+                    &reference_sequences,
+                    header.reference_sequences(),
+                    sam_record,
+                    options,
+                )
+            })
+            .collect::<io::Result<_>>()?;
+
+        write_and_read_records(records, options)?
+            .into_iter()
+            .map(|(record, compression_header)| {
+                record
+                    .try_into_sam_record_with_options(
+                        &reference_sequences,
+                        header.reference_sequences(),
+                        &compression_header,
+                        options,
+                    )
+                    .map_err(|e| e.into())
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_round_trip() -> Result<(), Box<dyn std::error::Error>> {
+        use noodles_bam as bam;
+
+        let header = build_header(&build_reference_sequences())?;
+        let reference_sequences = header.reference_sequences();
+
+        // BAM -> CRAM -> BAM
+        let bam_records: Vec<_> = CORPUS
+            .iter()
+            .map(|line| {
+                line.parse()
+                    .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+                    .and_then(|sam_record| {
+                        bam::Record::try_from_sam_record(reference_sequences, &sam_record)
+                    })
+            })
+            .collect::<io::Result<_>>()?;
+
+        let sam_records: Vec<_> = bam_records
+            .iter()
+            .map(|record| record.try_into_sam_record(reference_sequences))
+            .collect::<io::Result<_>>()?;
+
+        let mut options = ConversionOptions::default();
+
+        for (regenerate_md_and_nm, encode_substitutions) in
+            [(false, false), (true, false), (false, true), (true, true)]
+        {
+            options.set_regenerate_md_and_nm(regenerate_md_and_nm);
+            options.set_encode_substitutions(encode_substitutions);
+
+            let actual = round_trip(&sam_records, &options)?;
+
+            let actual_lines: Vec<_> = actual.iter().map(|record| record.to_string()).collect();
+            assert_eq!(actual_lines, CORPUS);
+
+            let actual_bam_records: Vec<_> = actual
+                .iter()
+                .map(|sam_record| bam::Record::try_from_sam_record(reference_sequences, sam_record))
+                .collect::<io::Result<_>>()?;
+
+            assert_eq!(actual_bam_records, bam_records);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_round_trip_bam_records() -> Result<(), Box<dyn std::error::Error>> {
+        use noodles_bam as bam;
+
+        let reference_sequences = build_reference_sequences();
+        let header = build_header(&reference_sequences)?;
+
+        let mut bam_records = Vec::new();
+
+        for line in &CORPUS[..4] {
+            let sam_record = line.parse()?;
+            let mut bam_record =
+                bam::Record::try_from_sam_record(header.reference_sequences(), &sam_record)?;
+
+            // Integer values are not stored using the smallest type.
+            let data = bam_record.data_mut();
+            data.insert(bam::record::data::Field::new(
+                SamTag::AlignmentHitCount,
+                BamValue::Int32(1),
+            ))
+            .transpose()?;
+            data.insert(bam::record::data::Field::new(
+                SamTag::AlignmentScore,
+                BamValue::UInt16(8),
+            ))
+            .transpose()?;
+
+            bam_records.push(bam_record);
+        }
+
+        let options = ConversionOptions::default();
+
+        let records = bam_records
+            .iter()
+            .map(|bam_record| {
+                Record::try_from_bam_record_with_options(
+                    &reference_sequences,
+                    header.reference_sequences(),
+                    bam_record,
+                    &options,
+                )
+            })
+            .collect::<io::Result<_>>()?;
+
+        let actual: Vec<_> = write_and_read_records(records, &options)?
+            .into_iter()
+            .map(|(record, compression_header)| {
+                record.try_into_bam_record_with_options(
+                    &reference_sequences,
+                    header.reference_sequences(),
+                    &compression_header,
+                    &options,
+                )
+            })
+            .collect::<io::Result<_>>()?;
+
+        assert_eq!(actual, bam_records);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_round_trip_without_preserved_cigar() -> Result<(), Box<dyn std::error::Error>> {
+        let sam_records: Vec<_> = CORPUS[8..10]
+            .iter()
+            .map(|line| line.parse())
+            .collect::<Result<_, _>>()?;
+
+        let mut options = ConversionOptions::default();
+        options.set_preserve_cigar(false);
+
+        let actual = round_trip(&sam_records, &options)?;
+        let cigars: Vec<_> = actual.iter().map(|r| r.cigar().to_string()).collect();
+        assert_eq!(cigars, ["4M", "4M"]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_round_trip_without_read_names() -> Result<(), Box<dyn std::error::Error>> {
+        let sam_records: Vec<_> = CORPUS
+            .iter()
+            .map(|line| line.parse())
+            .collect::<Result<_, _>>()?;
+
+        let mut options = ConversionOptions::default();
+        options.set_preserve_read_names(false);
+
+        let actual = round_trip(&sam_records, &options)?;
+
+        for (record, line) in actual.iter().zip(CORPUS) {
+            let (_, expected) = line.split_once('\t').unwrap();
+            assert_eq!(record.to_string(), format!("*\t{}", expected));
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_try_from_sam_record_with_regenerated_md_and_nm(
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let reference_sequences = build_reference_sequences();
+        let header = build_header(&reference_sequences)?;
+
+        let mut options = ConversionOptions::default();
+        options.set_regenerate_md_and_nm(true);
+
+        let t = |line: &str, expected_tag_count: usize| -> Result<(), Box<dyn std::error::Error>> {
+            let sam_record = line.parse()?;
+            let record = Record::try_from_sam_record_with_options(
+                &reference_sequences,
+                header.reference_sequences(),
+                &sam_record,
+                &options,
+            )?;
+            assert_eq!(record.tags().len(), expected_tag_count);
+            Ok(())
+        };
+
+        // MD and NM are the last data fields and match the alignment.
+        t(CORPUS[1], 4)?;
+        // MD and NM are not in order.
+        t(CORPUS[3], 2)?;
+        // MD does not match the alignment.
+        t(
+            "r0\t0\tsq0\t1\t60\t4M\t*\t0\t0\tACGT\tNDLS\tMD:Z:3A0\tNM:i:0",
+            2,
+        )?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_build_features() -> Result<(), Box<dyn std::error::Error>> {
+        let reference_sequence = b"ACGTACGTAC";
+        let alignment_start = Position::try_from(2)?;
+
+        let cigar = "1S2M1I1M1D2M1H".parse()?;
+        let bases = b"TCGAGCT";
+
+        let actual = build_features(
+            Some(reference_sequence),
+            alignment_start,
+            &cigar,
+            Some(bases),
+            false,
+        )?;
+
+        let expected = [
+            Feature::SoftClip(1, b"T".to_vec()),
+            Feature::Insertion(4, b"A".to_vec()),
+            Feature::Bases(5, b"G".to_vec()),
+            Feature::Deletion(6, 1),
+            Feature::Bases(7, b"T".to_vec()),
+            Feature::HardClip(8, 1),
+        ];
+
+        assert_eq!(actual, expected);
+
+        let actual = build_features(
+            Some(b"acgtacgtac"),
+            alignment_start,
+            &cigar,
+            Some(bases),
+            false,
+        )?;
+
+        assert_eq!(actual, expected);
+
+        let actual = build_features(
+            Some(reference_sequence),
+            alignment_start,
+            &cigar,
+            Some(bases),
+            true,
+        )?;
+
+        let expected = [
+            Feature::SoftClip(1, b"T".to_vec()),
+            Feature::Insertion(4, b"A".to_vec()),
+            Feature::Substitution(5, 2),
+            Feature::Deletion(6, 1),
+            Feature::Substitution(7, 2),
+            Feature::HardClip(8, 1),
+        ];
+
+        assert_eq!(actual, expected);
+
+        let actual = build_features(None, alignment_start, &cigar, Some(bases), false)?;
+
+        let expected = [
+            Feature::SoftClip(1, b"T".to_vec()),
+            Feature::Bases(2, b"CG".to_vec()),
+            Feature::Insertion(4, b"A".to_vec()),
+            Feature::Bases(5, b"G".to_vec()),
+            Feature::Deletion(6, 1),
+            Feature::Bases(6, b"CT".to_vec()),
+            Feature::HardClip(8, 1),
+        ];
+
+        assert_eq!(actual, expected);
+
+        let actual = build_features(
+            Some(reference_sequence),
+            alignment_start,
+            &cigar,
+            None,
+            false,
+        )?;
+
+        let expected = [
+            Feature::SoftClip(1, b"N".to_vec()),
+            Feature::Insertion(4, b"N".to_vec()),
+            Feature::Deletion(6, 1),
+            Feature::HardClip(8, 1),
+        ];
+
+        assert_eq!(actual, expected);
+
+        let cigar = "8M".parse()?;
+        assert!(build_features(
+            Some(reference_sequence),
+            alignment_start,
+            &cigar,
+            Some(bases),
+            false
+        )
+        .is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_normalize_cigar() -> Result<(), Box<dyn std::error::Error>> {
+        let t = |raw_cigar: &str, expected: &str| -> Result<(), Box<dyn std::error::Error>> {
+            let cigar = raw_cigar.parse()?;
+            let expected: Cigar = expected.parse()?;
+            assert_eq!(normalize_cigar(&cigar), &expected[..]);
+            Ok(())
+        };
+
+        t("4M", "4M")?;
+        t("2=1X1=", "4M")?;
+        t("2M0I2M", "4M")?;
+        t("1S2M2M1I1I", "1S4M2I")?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_calculate_md_and_nm() -> Result<(), Box<dyn std::error::Error>> {
+        let reference_sequence = b"ACGTACGTAC";
+        let alignment_start = Position::try_from(2)?;
+
+        let cigar = "1S2M1I1M1D2M".parse()?;
+        let actual = calculate_md_and_nm(reference_sequence, alignment_start, &cigar, b"TCGAGCT")?;
+        assert_eq!(actual, (String::from("2T0^A1G0"), 4));
+
+        let cigar = "4M2N2M".parse()?;
+        let actual = calculate_md_and_nm(reference_sequence, alignment_start, &cigar, b"CGTTTA")?;
+        assert_eq!(actual, (String::from("3A2"), 1));
+
+        let cigar = "16M".parse()?;
+        assert!(
+            calculate_md_and_nm(reference_sequence, alignment_start, &cigar, &[b'A'; 16]).is_err()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_sam_value_to_bam_value() -> io::Result<()> {
+        assert_eq!(
+            sam_value_to_bam_value(&SamValue::Int(0))?,
+            BamValue::UInt8(0)
+        );
+        assert_eq!(
+            sam_value_to_bam_value(&SamValue::Int(256))?,
+            BamValue::UInt16(256)
+        );
+        assert_eq!(
+            sam_value_to_bam_value(&SamValue::Int(65536))?,
+            BamValue::UInt32(65536)
+        );
+        assert_eq!(
+            sam_value_to_bam_value(&SamValue::Int(-1))?,
+            BamValue::Int8(-1)
+        );
+        assert_eq!(
+            sam_value_to_bam_value(&SamValue::Int(-129))?,
+            BamValue::Int16(-129)
+        );
+        assert_eq!(
+            sam_value_to_bam_value(&SamValue::Int(-32769))?,
+            BamValue::Int32(-32769)
+        );
+        assert!(sam_value_to_bam_value(&SamValue::Int(i64::MAX)).is_err());
+
+        assert_eq!(
+            sam_value_to_bam_value(&SamValue::String(String::from("noodles")))?,
+            BamValue::String(String::from("noodles"))
+        );
+
+        Ok(())
+    }
+}
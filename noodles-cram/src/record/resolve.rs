@@ -1,19 +1,26 @@
 //! CRAM record field resolvers.
 
+use std::io;
+
 use noodles_fasta as fasta;
 use noodles_sam::record::Cigar;
 
 use super::Feature;
 use crate::data_container::CompressionHeader;
 
+// § 10.5 "Quality score value 0xff is used to indicate missing quality scores."
+const MISSING_QUALITY_SCORE: u8 = 0xff;
+
 /// Resolves the read bases.
+///
+/// Bases not covered by read features are copied from the reference sequence.
 pub fn resolve_bases(
     reference_sequence_record: &fasta::Record,
     compression_header: &CompressionHeader,
     features: &[Feature],
     alignment_start: i32,
     read_len: usize,
-) -> Vec<u8> {
+) -> io::Result<Vec<u8>> {
     let reference_sequence = reference_sequence_record.sequence();
     let substitution_matrix = compression_header.preservation_map().substitution_matrix();
 
@@ -37,69 +44,183 @@ mod internal {
         features: &[Feature],
         alignment_start: i32,
         read_length: usize,
-    ) -> Vec<u8> {
+    ) -> io::Result<Vec<u8>> {
         use crate::data_container::compression_header::preservation_map::substitution_matrix::Base;
 
         let raw_reference_sequence = reference_sequence.as_ref();
 
         let mut buf = vec![b'-'; read_length];
 
-        let mut ref_pos = (alignment_start - 1) as usize;
+        let mut ref_pos = position_to_index(alignment_start)?;
         let mut read_pos = 0;
 
         for feature in features {
-            let feature_pos = feature.position() as usize;
-
-            while read_pos < feature_pos - 1 {
-                buf[read_pos] = raw_reference_sequence[ref_pos];
-                ref_pos += 1;
-                read_pos += 1;
+            let feature_pos = position_to_index(feature.position())?;
+
+            if feature_pos > read_pos {
+                let len = feature_pos - read_pos;
+                copy_reference_bases(
+                    &mut buf,
+                    &mut read_pos,
+                    raw_reference_sequence,
+                    &mut ref_pos,
+                    len,
+                )?;
             }
 
             match feature {
+                Feature::Bases(_, bases) => {
+                    write_bases(&mut buf, &mut read_pos, bases)?;
+                    ref_pos += bases.len();
+                }
+                Feature::ReadBase(_, base, _) => {
+                    write_bases(&mut buf, &mut read_pos, &[*base])?;
+                    ref_pos += 1;
+                }
                 Feature::Substitution(_, code) => {
-                    let base = raw_reference_sequence[ref_pos] as char;
-                    let reference_base = Base::try_from(base).unwrap_or_default();
+                    let base = get_reference_base(raw_reference_sequence, ref_pos);
+                    let reference_base = Base::try_from(char::from(base)).unwrap_or_default();
 
                     let read_base = substitution_matrix.get(reference_base, *code);
-                    buf[read_pos] = char::from(read_base) as u8;
+                    write_bases(&mut buf, &mut read_pos, &[char::from(read_base) as u8])?;
 
                     ref_pos += 1;
-                    read_pos += 1;
-                }
-                Feature::Insertion(_, bases) => {
-                    for &base in bases {
-                        buf[read_pos] = base;
-                        read_pos += 1;
-                    }
                 }
-                Feature::Deletion(_, len) => {
-                    ref_pos += *len as usize;
+                Feature::Insertion(_, bases) | Feature::SoftClip(_, bases) => {
+                    write_bases(&mut buf, &mut read_pos, bases)?;
                 }
                 Feature::InsertBase(_, base) => {
-                    buf[read_pos] = *base;
-                    read_pos += 1;
-                }
-                Feature::ReferenceSkip(_, len) => {
-                    ref_pos += *len as usize;
+                    write_bases(&mut buf, &mut read_pos, &[*base])?;
                 }
-                Feature::SoftClip(_, bases) => {
-                    for &base in bases {
-                        buf[read_pos] = base;
-                        read_pos += 1;
-                    }
+                Feature::Deletion(_, len) | Feature::ReferenceSkip(_, len) => {
+                    ref_pos += usize::try_from(*len)
+                        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
                 }
-                Feature::HardClip(..) => {}
-                _ => todo!("resolve_bases: {:?}", feature),
+                Feature::Scores(..)
+                | Feature::QualityScore(..)
+                | Feature::Padding(..)
+                | Feature::HardClip(..) => {}
             }
         }
 
-        for base in buf.iter_mut().skip(read_pos) {
-            *base = raw_reference_sequence[ref_pos];
-            ref_pos += 1;
+        let len = read_length - read_pos.min(read_length);
+        copy_reference_bases(
+            &mut buf,
+            &mut read_pos,
+            raw_reference_sequence,
+            &mut ref_pos,
+            len,
+        )?;
+
+        Ok(buf)
+    }
+
+    fn position_to_index(position: i32) -> io::Result<usize> {
+        usize::try_from(position)
+            .ok()
+            .and_then(|n| n.checked_sub(1))
+            .ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("invalid position: {}", position),
+                )
+            })
+    }
+
+    // Positions past the end of the reference sequence are padded with `N`.
+    fn get_reference_base(raw_reference_sequence: &[u8], ref_pos: usize) -> u8 {
+        raw_reference_sequence
+            .get(ref_pos)
+            .map(|base| base.to_ascii_uppercase())
+            .unwrap_or(b'N')
+    }
+
+    fn copy_reference_bases(
+        buf: &mut [u8],
+        read_pos: &mut usize,
+        raw_reference_sequence: &[u8],
+        ref_pos: &mut usize,
+        len: usize,
+    ) -> io::Result<()> {
+        let end = *read_pos + len;
+
+        let dst = buf.get_mut(*read_pos..end).ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                "read features exceed read length",
+            )
+        })?;
+
+        for (i, base) in dst.iter_mut().enumerate() {
+            *base = get_reference_base(raw_reference_sequence, *ref_pos + i);
         }
 
-        buf
+        *read_pos = end;
+        *ref_pos += len;
+
+        Ok(())
+    }
+
+    fn write_bases(buf: &mut [u8], read_pos: &mut usize, bases: &[u8]) -> io::Result<()> {
+        let end = *read_pos + bases.len();
+
+        let dst = buf.get_mut(*read_pos..end).ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                "read features exceed read length",
+            )
+        })?;
+
+        dst.copy_from_slice(bases);
+        *read_pos = end;
+
+        Ok(())
+    }
+}
+
+/// Resolves the quality scores from the read features.
+///
+/// This is used when the quality scores are not stored as an array. Positions not covered by a
+/// read feature are set to 0xff (missing). If no read features have quality scores, the result
+/// is empty.
+pub fn resolve_quality_scores(features: &[Feature], read_len: usize) -> io::Result<Vec<u8>> {
+    let mut buf = vec![MISSING_QUALITY_SCORE; read_len];
+    let mut has_quality_scores = false;
+
+    for feature in features {
+        let scores = match feature {
+            Feature::Scores(_, scores) => &scores[..],
+            Feature::ReadBase(_, _, score) | Feature::QualityScore(_, score) => {
+                std::slice::from_ref(score)
+            }
+            _ => continue,
+        };
+
+        let start = usize::try_from(feature.position())
+            .ok()
+            .and_then(|n| n.checked_sub(1))
+            .ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("invalid position: {}", feature.position()),
+                )
+            })?;
+
+        let dst = buf.get_mut(start..start + scores.len()).ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                "read features exceed read length",
+            )
+        })?;
+
+        dst.copy_from_slice(scores);
+        has_quality_scores = true;
+    }
+
+    if has_quality_scores {
+        Ok(buf)
+    } else {
+        Ok(Vec::new())
     }
 }
 
@@ -110,11 +231,24 @@ pub fn resolve_features(features: &[Feature], read_len: i32) -> Cigar {
     let mut ops = Vec::new();
     let mut i = 1;
 
+    // Substitutions and read bases are part of an alignment match, which may be split over
+    // multiple features, so consecutive matches are merged into a single operation.
+    fn push_op(ops: &mut Vec<Op>, op: Op) {
+        if let Some(last_op) = ops.last_mut() {
+            if last_op.kind() == Kind::Match && op.kind() == Kind::Match {
+                *last_op = Op::new(Kind::Match, last_op.len() + op.len());
+                return;
+            }
+        }
+
+        ops.push(op);
+    }
+
     for feature in features {
         if feature.position() > i {
             let len = feature.position() - i;
             let op = Op::new(Kind::Match, len as u32);
-            ops.push(op);
+            push_op(&mut ops, op);
 
             i = feature.position();
         }
@@ -132,7 +266,7 @@ pub fn resolve_features(features: &[Feature], read_len: i32) -> Cigar {
         };
 
         let op = Op::new(kind, len as u32);
-        ops.push(op);
+        push_op(&mut ops, op);
 
         if matches!(
             kind,
@@ -145,7 +279,7 @@ pub fn resolve_features(features: &[Feature], read_len: i32) -> Cigar {
     if i <= read_len {
         let len = read_len - i + 1;
         let op = Op::new(Kind::Match, len as u32);
-        ops.push(op);
+        push_op(&mut ops, op);
     }
 
     Cigar::from(ops)
@@ -156,24 +290,112 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test_resolve_bases() {
+    fn test_resolve_bases() -> io::Result<()> {
         let reference_sequence = fasta::record::Sequence::from(b"ACGTACGT".to_vec());
         let substitution_matrix = Default::default();
 
-        let t = |features: &[Feature], expected: &[u8]| {
+        let t = |features: &[Feature], expected: &[u8]| -> io::Result<()> {
             let actual =
-                internal::resolve_bases(&reference_sequence, &substitution_matrix, features, 1, 4);
+                internal::resolve_bases(&reference_sequence, &substitution_matrix, features, 1, 4)?;
             assert_eq!(actual, expected);
+            Ok(())
+        };
+
+        t(&[], b"ACGT")?;
+        t(&[Feature::Bases(2, b"TT".to_vec())], b"ATTT")?;
+        t(&[Feature::Scores(2, vec![8, 13])], b"ACGT")?;
+        t(&[Feature::ReadBase(2, b'N', 8)], b"ANGT")?;
+        t(&[Feature::Substitution(2, 1)], b"AGGT")?;
+        t(&[Feature::Insertion(2, b"GG".to_vec())], b"AGGC")?;
+        t(&[Feature::Deletion(2, 2)], b"ATAC")?;
+        t(&[Feature::InsertBase(2, b'G')], b"AGCG")?;
+        t(&[Feature::QualityScore(2, 8)], b"ACGT")?;
+        t(&[Feature::ReferenceSkip(2, 2)], b"ATAC")?;
+        t(&[Feature::SoftClip(3, b"GG".to_vec())], b"ACGG")?;
+        t(&[Feature::Padding(2, 2)], b"ACGT")?;
+        t(&[Feature::HardClip(1, 2)], b"ACGT")?;
+
+        t(
+            &[
+                Feature::SoftClip(1, b"T".to_vec()),
+                Feature::Insertion(2, b"C".to_vec()),
+                Feature::Deletion(3, 1),
+                Feature::ReadBase(4, b'A', 8),
+            ],
+            b"TCCA",
+        )?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_resolve_bases_with_invalid_features() {
+        let reference_sequence = fasta::record::Sequence::from(b"ACGTACGT".to_vec());
+        let substitution_matrix = Default::default();
+
+        let t = |features: &[Feature], alignment_start: i32| {
+            internal::resolve_bases(
+                &reference_sequence,
+                &substitution_matrix,
+                features,
+                alignment_start,
+                4,
+            )
         };
 
-        t(&[], b"ACGT");
-        t(&[Feature::Substitution(2, 1)], b"AGGT");
-        t(&[Feature::Insertion(2, b"GG".to_vec())], b"AGGC");
-        t(&[Feature::Deletion(2, 2)], b"ATAC");
-        t(&[Feature::InsertBase(2, b'G')], b"AGCG");
-        t(&[Feature::ReferenceSkip(2, 2)], b"ATAC");
-        t(&[Feature::SoftClip(3, b"GG".to_vec())], b"ACGG");
-        t(&[Feature::HardClip(1, 2)], b"ACGT");
+        assert!(t(&[Feature::Insertion(3, b"GGG".to_vec())], 1).is_err());
+        assert!(t(&[Feature::Substitution(0, 1)], 1).is_err());
+        assert!(t(&[], 0).is_err());
+    }
+
+    #[test]
+    fn test_resolve_bases_past_reference_sequence_end() -> io::Result<()> {
+        let reference_sequence = fasta::record::Sequence::from(b"ACGTACGT".to_vec());
+        let substitution_matrix = Default::default();
+
+        let t = |features: &[Feature], alignment_start: i32, expected: &[u8]| -> io::Result<()> {
+            let actual = internal::resolve_bases(
+                &reference_sequence,
+                &substitution_matrix,
+                features,
+                alignment_start,
+                4,
+            )?;
+
+            assert_eq!(actual, expected);
+
+            Ok(())
+        };
+
+        t(&[], 6, b"CGTN")?;
+        t(&[], 10, b"NNNN")?;
+        t(&[Feature::Deletion(2, 8)], 1, b"ANNN")?;
+        t(&[Feature::Substitution(4, 1)], 7, b"GTNC")?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_resolve_quality_scores() -> io::Result<()> {
+        assert!(resolve_quality_scores(&[], 4)?.is_empty());
+        assert!(resolve_quality_scores(&[Feature::Deletion(2, 2)], 4)?.is_empty());
+
+        let features = [
+            Feature::Scores(1, vec![8, 13]),
+            Feature::ReadBase(3, b'A', 21),
+        ];
+        assert_eq!(resolve_quality_scores(&features, 4)?, [8, 13, 21, 0xff]);
+
+        let features = [Feature::QualityScore(4, 34)];
+        assert_eq!(
+            resolve_quality_scores(&features, 4)?,
+            [0xff, 0xff, 0xff, 34]
+        );
+
+        let features = [Feature::Scores(4, vec![8, 13])];
+        assert!(resolve_quality_scores(&features, 4).is_err());
+
+        Ok(())
     }
 
     #[test]
@@ -204,30 +426,28 @@ mod tests {
             Cigar::from(vec![Op::new(Kind::HardClip, 2), Op::new(Kind::Match, 4)]),
         );
 
-        // FIXME
         let features = [
             Feature::SoftClip(1, b"A".to_vec()),
             Feature::Substitution(3, 0),
         ];
         assert_eq!(
             resolve_features(&features, 4),
-            Cigar::from(vec![
-                Op::new(Kind::SoftClip, 1),
-                Op::new(Kind::Match, 1),
-                Op::new(Kind::Match, 1),
-                Op::new(Kind::Match, 1),
-            ])
+            Cigar::from(vec![Op::new(Kind::SoftClip, 1), Op::new(Kind::Match, 3)])
+        );
+
+        let features = [
+            Feature::Bases(2, b"GT".to_vec()),
+            Feature::Insertion(4, b"A".to_vec()),
+        ];
+        assert_eq!(
+            resolve_features(&features, 4),
+            Cigar::from(vec![Op::new(Kind::Match, 3), Op::new(Kind::Insertion, 1),])
         );
 
-        // FIXME
         let features = [Feature::Substitution(2, 0)];
         assert_eq!(
             resolve_features(&features, 4),
-            Cigar::from(vec![
-                Op::new(Kind::Match, 1),
-                Op::new(Kind::Match, 1),
-                Op::new(Kind::Match, 2)
-            ])
+            Cigar::from(vec![Op::new(Kind::Match, 4)])
         );
     }
 }
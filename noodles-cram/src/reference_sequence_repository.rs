@@ -0,0 +1,191 @@
+//! CRAM reference sequence repository.
+
+pub mod adapters;
+
+use std::{collections::HashMap, fmt, io};
+
+use noodles_fasta as fasta;
+use noodles_sam as sam;
+
+/// A reference sequence repository adapter.
+///
+/// An adapter is a source of reference sequences, e.g., an indexed FASTA file, a list of
+/// in-memory records, or a local MD5-keyed cache.
+pub trait Adapter {
+    /// Returns the reference sequence record for the given SAM header reference sequence.
+    ///
+    /// This returns `None` if the adapter does not have the reference sequence.
+    fn get(
+        &mut self,
+        reference_sequence: &sam::header::ReferenceSequence,
+    ) -> io::Result<Option<fasta::Record>>;
+}
+
+/// A reference sequence repository.
+///
+/// A repository is used by the CRAM reader to resolve read bases from reference sequences.
+/// Reference sequences are fetched from an [`Adapter`] and cached by name.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_cram::reference_sequence_repository::{
+///     adapters, ReferenceSequenceRepository,
+/// };
+/// use noodles_fasta::{self as fasta, record::{Definition, Sequence}};
+/// use noodles_sam as sam;
+///
+/// let records = vec![fasta::Record::new(
+///     Definition::new("sq0", None),
+///     Sequence::from(b"ACGT".to_vec()),
+/// )];
+///
+/// let mut repository = ReferenceSequenceRepository::new(adapters::Records::new(records));
+///
+/// let reference_sequence = sam::header::ReferenceSequence::new("sq0".parse()?, 4)?;
+/// let record = repository.get(&reference_sequence)?;
+/// assert_eq!(record.map(|r| r.sequence().as_ref()), Some(&b"ACGT"[..]));
+/// # Ok::<_, Box<dyn std::error::Error>>(())
+/// ```
+pub struct ReferenceSequenceRepository {
+    adapter: Box<dyn Adapter>,
+    cache: HashMap<String, fasta::Record>,
+}
+
+impl ReferenceSequenceRepository {
+    /// Creates a reference sequence repository with the given adapter.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_cram::reference_sequence_repository::{
+    ///     adapters, ReferenceSequenceRepository,
+    /// };
+    ///
+    /// let repository = ReferenceSequenceRepository::new(adapters::Records::default());
+    /// ```
+    pub fn new<A>(adapter: A) -> Self
+    where
+        A: Adapter + 'static,
+    {
+        Self {
+            adapter: Box::new(adapter),
+            cache: HashMap::new(),
+        }
+    }
+
+    /// Returns the reference sequence record for the given SAM header reference sequence.
+    ///
+    /// The record is read from the adapter on the first request and cached for subsequent
+    /// requests. This returns `None` if the reference sequence is not available.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_cram::reference_sequence_repository::ReferenceSequenceRepository;
+    /// use noodles_sam as sam;
+    ///
+    /// let mut repository = ReferenceSequenceRepository::default();
+    ///
+    /// let reference_sequence = sam::header::ReferenceSequence::new("sq0".parse()?, 8)?;
+    /// assert!(repository.get(&reference_sequence)?.is_none());
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn get(
+        &mut self,
+        reference_sequence: &sam::header::ReferenceSequence,
+    ) -> io::Result<Option<&fasta::Record>> {
+        let name = reference_sequence.name().to_string();
+
+        if !self.cache.contains_key(&name) {
+            match self.adapter.get(reference_sequence)? {
+                Some(record) => {
+                    self.cache.insert(name.clone(), record);
+                }
+                None => return Ok(None),
+            }
+        }
+
+        Ok(self.cache.get(&name))
+    }
+
+    /// Removes all cached reference sequence records.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_cram::reference_sequence_repository::ReferenceSequenceRepository;
+    /// let mut repository = ReferenceSequenceRepository::default();
+    /// repository.clear();
+    /// ```
+    pub fn clear(&mut self) {
+        self.cache.clear();
+    }
+}
+
+impl Default for ReferenceSequenceRepository {
+    fn default() -> Self {
+        Self::new(adapters::Records::default())
+    }
+}
+
+impl fmt::Debug for ReferenceSequenceRepository {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ReferenceSequenceRepository")
+            .field("cache", &self.cache.keys())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::Cell, rc::Rc};
+
+    use noodles_fasta::record::{Definition, Sequence};
+
+    use super::*;
+
+    struct CountingAdapter {
+        records: adapters::Records,
+        count: Rc<Cell<usize>>,
+    }
+
+    impl Adapter for CountingAdapter {
+        fn get(
+            &mut self,
+            reference_sequence: &sam::header::ReferenceSequence,
+        ) -> io::Result<Option<fasta::Record>> {
+            self.count.set(self.count.get() + 1);
+            self.records.get(reference_sequence)
+        }
+    }
+
+    #[test]
+    fn test_get() -> Result<(), Box<dyn std::error::Error>> {
+        let count = Rc::new(Cell::new(0));
+
+        let mut repository = ReferenceSequenceRepository::new(CountingAdapter {
+            records: adapters::Records::new(vec![fasta::Record::new(
+                Definition::new("sq0", None),
+                Sequence::from(b"ACGT".to_vec()),
+            )]),
+            count: count.clone(),
+        });
+
+        let sq0 = sam::header::ReferenceSequence::new("sq0".parse()?, 4)?;
+        let sq1 = sam::header::ReferenceSequence::new("sq1".parse()?, 4)?;
+
+        assert!(repository.get(&sq0)?.is_some());
+        assert!(repository.get(&sq0)?.is_some());
+        assert_eq!(count.get(), 1);
+
+        assert!(repository.get(&sq1)?.is_none());
+        assert_eq!(count.get(), 2);
+
+        repository.clear();
+        assert!(repository.get(&sq0)?.is_some());
+        assert_eq!(count.get(), 3);
+
+        Ok(())
+    }
+}
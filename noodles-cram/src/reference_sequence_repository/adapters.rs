@@ -0,0 +1,7 @@
+//! CRAM reference sequence repository adapters.
+
+mod cache;
+mod indexed_reader;
+mod records;
+
+pub use self::{cache::Cache, indexed_reader::IndexedReader, records::Records};
@@ -0,0 +1,186 @@
+use std::{fs, io, path::PathBuf};
+
+use noodles_fasta::{
+    self as fasta,
+    record::{Definition, Sequence},
+};
+use noodles_sam as sam;
+
+use crate::reference_sequence_repository::Adapter;
+
+/// A reference sequence repository adapter backed by a local MD5-keyed cache.
+///
+/// This uses the same layout as the `REF_CACHE` used by htslib. The path template is expanded
+/// using the MD5 checksum (`M5`) of the SAM header reference sequence, where `%s` is replaced by
+/// the remaining characters of the hex-encoded checksum; `%<n>s`, the next `n` characters; and
+/// `%%`, a literal `%`. For example, the template `/tmp/hts-ref/%2s/%2s/%s` and checksum
+/// `d7eba311421bbc9d3ada44709dd61534` resolve to
+/// `/tmp/hts-ref/d7/eb/a311421bbc9d3ada44709dd61534`.
+///
+/// Each file is expected to hold the raw sequence, i.e., uppercase bases without line breaks.
+/// Reference sequences without an MD5 checksum or without a cached file are not available.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_cram::reference_sequence_repository::{adapters, ReferenceSequenceRepository};
+/// let adapter = adapters::Cache::new("/tmp/hts-ref/%2s/%2s/%s");
+/// let repository = ReferenceSequenceRepository::new(adapter);
+/// ```
+#[derive(Clone, Debug)]
+pub struct Cache {
+    template: String,
+}
+
+impl Cache {
+    /// Creates an MD5-keyed cache adapter with the given path template.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_cram::reference_sequence_repository::adapters;
+    /// let adapter = adapters::Cache::new("/tmp/hts-ref/%2s/%2s/%s");
+    /// ```
+    pub fn new<S>(template: S) -> Self
+    where
+        S: Into<String>,
+    {
+        Self {
+            template: template.into(),
+        }
+    }
+}
+
+impl Adapter for Cache {
+    fn get(
+        &mut self,
+        reference_sequence: &sam::header::ReferenceSequence,
+    ) -> io::Result<Option<fasta::Record>> {
+        let md5_checksum = match reference_sequence.md5_checksum() {
+            Some(checksum) => checksum.to_string(),
+            None => return Ok(None),
+        };
+
+        let src = expand_template(&self.template, &md5_checksum)?;
+
+        let buf = match fs::read(src) {
+            Ok(buf) => buf,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(e),
+        };
+
+        let name: &str = reference_sequence.name();
+        let definition = Definition::new(name, None);
+
+        Ok(Some(fasta::Record::new(definition, Sequence::from(buf))))
+    }
+}
+
+fn expand_template(template: &str, md5_checksum: &str) -> io::Result<PathBuf> {
+    let mut dst = String::new();
+    let mut rest = md5_checksum;
+    let mut chars = template.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if c != '%' {
+            dst.push(c);
+            continue;
+        }
+
+        let mut n = String::new();
+
+        while let Some(d) = chars.next_if(|d| d.is_ascii_digit()) {
+            n.push(d);
+        }
+
+        match chars.next() {
+            Some('%') if n.is_empty() => dst.push('%'),
+            Some('s') => {
+                let len = if n.is_empty() {
+                    rest.len()
+                } else {
+                    n.parse::<usize>()
+                        .map(|len| len.min(rest.len()))
+                        .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?
+                };
+
+                let (head, tail) = rest.split_at(len);
+                dst.push_str(head);
+                rest = tail;
+            }
+            _ => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("invalid cache path template: {}", template),
+                ))
+            }
+        }
+    }
+
+    Ok(PathBuf::from(dst))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+
+    use super::*;
+
+    const MD5_CHECKSUM: &str = "d7eba311421bbc9d3ada44709dd61534";
+
+    #[test]
+    fn test_get() -> Result<(), Box<dyn std::error::Error>> {
+        let dir = env::temp_dir().join(format!("noodles-cram-cache-{}", std::process::id()));
+        fs::create_dir_all(dir.join("d7").join("eb"))?;
+        fs::write(
+            dir.join("d7")
+                .join("eb")
+                .join("a311421bbc9d3ada44709dd61534"),
+            b"ACGT",
+        )?;
+
+        let template = format!("{}/%2s/%2s/%s", dir.display());
+        let mut adapter = Cache::new(template);
+
+        let reference_sequence = sam::header::ReferenceSequence::builder()
+            .set_name("sq0".parse()?)
+            .set_length(4)
+            .set_md5_checksum(MD5_CHECKSUM.parse()?)
+            .build()?;
+
+        let actual = adapter.get(&reference_sequence)?;
+        let expected = fasta::Record::new(
+            Definition::new("sq0", None),
+            Sequence::from(b"ACGT".to_vec()),
+        );
+        assert_eq!(actual, Some(expected));
+
+        let reference_sequence = sam::header::ReferenceSequence::builder()
+            .set_name("sq1".parse()?)
+            .set_length(4)
+            .set_md5_checksum("00000000000000000000000000000000".parse()?)
+            .build()?;
+        assert!(adapter.get(&reference_sequence)?.is_none());
+
+        let reference_sequence = sam::header::ReferenceSequence::new("sq0".parse()?, 4)?;
+        assert!(adapter.get(&reference_sequence)?.is_none());
+
+        fs::remove_dir_all(dir)?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_expand_template() -> io::Result<()> {
+        assert_eq!(
+            expand_template("/tmp/hts-ref/%2s/%2s/%s", MD5_CHECKSUM)?,
+            PathBuf::from("/tmp/hts-ref/d7/eb/a311421bbc9d3ada44709dd61534")
+        );
+        assert_eq!(
+            expand_template("/tmp/%%/%s", MD5_CHECKSUM)?,
+            PathBuf::from("/tmp/%/d7eba311421bbc9d3ada44709dd61534")
+        );
+        assert!(expand_template("/tmp/%d", MD5_CHECKSUM).is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,107 @@
+use std::io::{self, BufRead, Seek};
+
+use noodles_core::Region;
+use noodles_fasta::{self as fasta, fai};
+use noodles_sam as sam;
+
+use crate::reference_sequence_repository::Adapter;
+
+/// A reference sequence repository adapter backed by an indexed FASTA reader.
+///
+/// Only the requested reference sequences are read, using the associated FASTA index.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use std::{fs::File, io::{self, BufReader}};
+/// use noodles_cram::reference_sequence_repository::{adapters, ReferenceSequenceRepository};
+/// use noodles_fasta::{self as fasta, fai};
+///
+/// let reader = File::open("reference.fa")
+///     .map(BufReader::new)
+///     .map(fasta::Reader::new)?;
+/// let index = fai::read("reference.fa.fai")?;
+///
+/// let adapter = adapters::IndexedReader::new(reader, index);
+/// let repository = ReferenceSequenceRepository::new(adapter);
+/// # Ok::<_, io::Error>(())
+/// ```
+pub struct IndexedReader<R> {
+    inner: fasta::Reader<R>,
+    index: fai::Index,
+}
+
+impl<R> IndexedReader<R>
+where
+    R: BufRead + Seek,
+{
+    /// Creates an indexed FASTA reader adapter.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_cram::reference_sequence_repository::adapters;
+    /// use noodles_fasta::{self as fasta, fai};
+    ///
+    /// let data = [];
+    /// let reader = fasta::Reader::new(std::io::Cursor::new(data));
+    /// let adapter = adapters::IndexedReader::new(reader, fai::Index::default());
+    /// ```
+    pub fn new(inner: fasta::Reader<R>, index: fai::Index) -> Self {
+        Self { inner, index }
+    }
+}
+
+impl<R> Adapter for IndexedReader<R>
+where
+    R: BufRead + Seek,
+{
+    fn get(
+        &mut self,
+        reference_sequence: &sam::header::ReferenceSequence,
+    ) -> io::Result<Option<fasta::Record>> {
+        let name: &str = reference_sequence.name();
+
+        if !self.index.iter().any(|record| record.name() == name) {
+            return Ok(None);
+        }
+
+        let region = Region::mapped(name, ..);
+        self.inner.query(&self.index, &region).map(Some)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use noodles_fasta::record::{Definition, Sequence};
+
+    use super::*;
+
+    #[test]
+    fn test_get() -> Result<(), Box<dyn std::error::Error>> {
+        let data = b">sq0\nNNNN\n>sq1\nACGT\nAC\n";
+        let index = vec![
+            fai::Record::new(String::from("sq0"), 4, 5, 4, 5),
+            fai::Record::new(String::from("sq1"), 6, 15, 4, 5),
+        ];
+
+        let reader = fasta::Reader::new(Cursor::new(&data[..]));
+        let mut adapter = IndexedReader::new(reader, index);
+
+        let reference_sequence = sam::header::ReferenceSequence::new("sq1".parse()?, 6)?;
+        assert_eq!(
+            adapter.get(&reference_sequence)?,
+            Some(fasta::Record::new(
+                Definition::new("sq1", None),
+                Sequence::from(b"ACGTAC".to_vec())
+            ))
+        );
+
+        let reference_sequence = sam::header::ReferenceSequence::new("sq2".parse()?, 4)?;
+        assert!(adapter.get(&reference_sequence)?.is_none());
+
+        Ok(())
+    }
+}
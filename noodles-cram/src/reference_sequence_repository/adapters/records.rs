@@ -0,0 +1,77 @@
+use std::io;
+
+use noodles_fasta as fasta;
+use noodles_sam as sam;
+
+use crate::reference_sequence_repository::Adapter;
+
+/// A reference sequence repository adapter backed by a list of in-memory FASTA records.
+///
+/// Records are matched to SAM header reference sequences by name.
+#[derive(Clone, Debug, Default)]
+pub struct Records {
+    records: Vec<fasta::Record>,
+}
+
+impl Records {
+    /// Creates an in-memory reference sequence repository adapter.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_cram::reference_sequence_repository::adapters;
+    /// use noodles_fasta::{self as fasta, record::{Definition, Sequence}};
+    ///
+    /// let adapter = adapters::Records::new(vec![fasta::Record::new(
+    ///     Definition::new("sq0", None),
+    ///     Sequence::from(b"ACGT".to_vec()),
+    /// )]);
+    /// ```
+    pub fn new(records: Vec<fasta::Record>) -> Self {
+        Self { records }
+    }
+}
+
+impl Adapter for Records {
+    fn get(
+        &mut self,
+        reference_sequence: &sam::header::ReferenceSequence,
+    ) -> io::Result<Option<fasta::Record>> {
+        let name: &str = reference_sequence.name();
+
+        Ok(self
+            .records
+            .iter()
+            .find(|record| record.name() == name)
+            .cloned())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use noodles_fasta::record::{Definition, Sequence};
+
+    use super::*;
+
+    #[test]
+    fn test_get() -> Result<(), Box<dyn std::error::Error>> {
+        let sq0 = fasta::Record::new(
+            Definition::new("sq0", None),
+            Sequence::from(b"ACGT".to_vec()),
+        );
+        let sq1 = fasta::Record::new(
+            Definition::new("sq1", None),
+            Sequence::from(b"NNNN".to_vec()),
+        );
+
+        let mut adapter = Records::new(vec![sq0, sq1.clone()]);
+
+        let reference_sequence = sam::header::ReferenceSequence::new("sq1".parse()?, 4)?;
+        assert_eq!(adapter.get(&reference_sequence)?, Some(sq1));
+
+        let reference_sequence = sam::header::ReferenceSequence::new("sq2".parse()?, 4)?;
+        assert!(adapter.get(&reference_sequence)?.is_none());
+
+        Ok(())
+    }
+}
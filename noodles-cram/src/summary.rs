@@ -0,0 +1,264 @@
+//! CRAM container and slice summaries.
+//!
+//! Summaries describe the layout of data containers without decoding their records, e.g., the
+//! reference spans and record counts of slices and the sizes and compression methods of blocks.
+//! They are created by calling [`crate::Reader::container_summaries`].
+
+mod block;
+mod slice;
+
+pub use self::{block::BlockSummary, slice::SliceSummary};
+
+pub use crate::container::{
+    block::{CompressionMethod, ContentType},
+    ReferenceSequenceId,
+};
+
+use std::{collections::HashMap, io};
+
+use noodles_sam as sam;
+
+use crate::{
+    container::{Block, Container},
+    data_container::{CompressionHeader, DataSeries},
+    num::{Itf8, Ltf8},
+    reader::data_container::{read_compression_header, slice::read_header},
+};
+
+/// A summary of a CRAM data container.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ContainerSummary {
+    offset: u64,
+    len: usize,
+    reference_sequence_id: ReferenceSequenceId,
+    alignment_start: Option<sam::record::Position>,
+    alignment_span: Itf8,
+    record_count: Itf8,
+    record_counter: Ltf8,
+    base_count: Ltf8,
+    crc32: u32,
+    compression_header_block: BlockSummary,
+    slices: Vec<SliceSummary>,
+}
+
+impl ContainerSummary {
+    pub(crate) fn try_from_container(offset: u64, container: &Container) -> io::Result<Self> {
+        let header = container.header();
+        let mut blocks = container.blocks().iter();
+
+        let compression_header_block = blocks.next().ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                "invalid data container: missing compression header block",
+            )
+        })?;
+
+        let compression_header = compression_header_block
+            .decompressed_data()
+            .and_then(|data| read_compression_header(&mut &data[..]))?;
+
+        let block_content_ids = build_block_content_id_map(&compression_header);
+
+        let mut slices = Vec::with_capacity(header.landmarks().len());
+
+        for &landmark in header.landmarks() {
+            let slice_header_block = blocks.next().ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "invalid slice: missing slice header block",
+                )
+            })?;
+
+            let slice_header = slice_header_block
+                .decompressed_data()
+                .and_then(|data| read_header(&mut &data[..]))?;
+
+            let embedded_reference_bases_block_content_id =
+                *slice_header.embedded_reference_bases_block_content_id();
+
+            let mut core_data_block = None;
+            let mut external_blocks = Vec::with_capacity(slice_header.block_count());
+
+            for _ in 0..slice_header.block_count() {
+                let block = blocks.next().ok_or_else(|| {
+                    io::Error::new(io::ErrorKind::InvalidData, "invalid slice: missing block")
+                })?;
+
+                // The core data block is not necessarily the first block of the slice.
+                if block.content_type() == ContentType::CoreData && core_data_block.is_none() {
+                    core_data_block = Some(BlockSummary::new(block, Vec::new(), Vec::new()));
+                } else {
+                    external_blocks.push(summarize_block(
+                        &block_content_ids,
+                        embedded_reference_bases_block_content_id,
+                        block,
+                    ));
+                }
+            }
+
+            let core_data_block = core_data_block.ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "invalid slice: missing core data block",
+                )
+            })?;
+
+            slices.push(SliceSummary::new(
+                landmark,
+                &slice_header,
+                BlockSummary::new(slice_header_block, Vec::new(), Vec::new()),
+                core_data_block,
+                external_blocks,
+            ));
+        }
+
+        let len = usize::try_from(header.len())
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+        Ok(Self {
+            offset,
+            len,
+            reference_sequence_id: header.reference_sequence_id(),
+            alignment_start: header.start_position(),
+            alignment_span: header.alignment_span(),
+            record_count: header.record_count(),
+            record_counter: header.record_counter(),
+            base_count: header.base_count(),
+            crc32: header.crc32(),
+            compression_header_block: BlockSummary::new(
+                compression_header_block,
+                Vec::new(),
+                Vec::new(),
+            ),
+            slices,
+        })
+    }
+
+    /// Returns the position of the container in the stream.
+    pub fn offset(&self) -> u64 {
+        self.offset
+    }
+
+    /// Returns the size of the container blocks, in bytes.
+    ///
+    /// This does not include the size of the container header.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns whether the container has no records.
+    pub fn is_empty(&self) -> bool {
+        self.record_count == 0
+    }
+
+    /// Returns the reference sequence ID of the container.
+    pub fn reference_sequence_id(&self) -> ReferenceSequenceId {
+        self.reference_sequence_id
+    }
+
+    /// Returns the start position of the container.
+    ///
+    /// This is `None` for unmapped and multi-reference containers.
+    pub fn alignment_start(&self) -> Option<sam::record::Position> {
+        self.alignment_start
+    }
+
+    /// Returns the number of bases on the reference the container covers.
+    pub fn alignment_span(&self) -> Itf8 {
+        self.alignment_span
+    }
+
+    /// Returns the number of records in the container.
+    pub fn record_count(&self) -> Itf8 {
+        self.record_count
+    }
+
+    /// Returns the 0-based index of the first record in the container.
+    pub fn record_counter(&self) -> Ltf8 {
+        self.record_counter
+    }
+
+    /// Returns the number of read bases in the container.
+    pub fn base_count(&self) -> Ltf8 {
+        self.base_count
+    }
+
+    /// Returns the CRC32 of the container header as read.
+    pub fn crc32(&self) -> u32 {
+        self.crc32
+    }
+
+    /// Returns a summary of the compression header block.
+    pub fn compression_header_block(&self) -> &BlockSummary {
+        &self.compression_header_block
+    }
+
+    /// Returns summaries of the slices in the container.
+    pub fn slices(&self) -> &[SliceSummary] {
+        &self.slices
+    }
+}
+
+#[derive(Default)]
+struct BlockContents {
+    data_series: Vec<DataSeries>,
+    tags: Vec<[u8; 2]>,
+}
+
+// Maps external block content IDs to the data series and tags that are encoded in them.
+fn build_block_content_id_map(
+    compression_header: &CompressionHeader,
+) -> HashMap<Itf8, BlockContents> {
+    let mut map: HashMap<Itf8, BlockContents> = HashMap::new();
+
+    for (data_series, encoding) in compression_header.data_series_encoding_map().iter() {
+        for block_content_id in encoding.block_content_ids() {
+            let contents = map.entry(block_content_id).or_default();
+
+            if !contents.data_series.contains(&data_series) {
+                contents.data_series.push(data_series);
+            }
+        }
+    }
+
+    for (&key, encoding) in compression_header.tag_encoding_map().iter() {
+        // The key is the tag and value type packed as 3 bytes.
+        let tag = [(key >> 16) as u8, (key >> 8) as u8];
+
+        for block_content_id in encoding.block_content_ids() {
+            let contents = map.entry(block_content_id).or_default();
+
+            if !contents.tags.contains(&tag) {
+                contents.tags.push(tag);
+            }
+        }
+    }
+
+    for contents in map.values_mut() {
+        contents.tags.sort_unstable();
+    }
+
+    map
+}
+
+fn summarize_block(
+    block_content_ids: &HashMap<Itf8, BlockContents>,
+    embedded_reference_bases_block_content_id: Option<Itf8>,
+    block: &Block,
+) -> BlockSummary {
+    if block.content_type() != ContentType::ExternalData {
+        return BlockSummary::new(block, Vec::new(), Vec::new());
+    }
+
+    let summary = match block_content_ids.get(&block.content_id()) {
+        Some(contents) => {
+            BlockSummary::new(block, contents.data_series.clone(), contents.tags.clone())
+        }
+        None => BlockSummary::new(block, Vec::new(), Vec::new()),
+    };
+
+    let is_embedded_reference_bases =
+        embedded_reference_bases_block_content_id == Some(block.content_id());
+
+    summary.set_embedded_reference_bases(is_embedded_reference_bases)
+}
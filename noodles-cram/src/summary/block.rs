@@ -0,0 +1,114 @@
+use crate::{
+    container::{
+        block::{CompressionMethod, ContentType},
+        Block,
+    },
+    data_container::DataSeries,
+    num::Itf8,
+};
+
+/// A summary of a CRAM container block.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BlockSummary {
+    compression_method: CompressionMethod,
+    content_type: ContentType,
+    content_id: Itf8,
+    len: usize,
+    compressed_len: usize,
+    uncompressed_len: usize,
+    crc32: u32,
+    data_series: Vec<DataSeries>,
+    tags: Vec<[u8; 2]>,
+    is_embedded_reference_bases: bool,
+}
+
+impl BlockSummary {
+    pub(crate) fn new(block: &Block, data_series: Vec<DataSeries>, tags: Vec<[u8; 2]>) -> Self {
+        Self {
+            compression_method: block.compression_method(),
+            content_type: block.content_type(),
+            content_id: block.content_id(),
+            len: block.len(),
+            compressed_len: block.data().len(),
+            uncompressed_len: block.uncompressed_len(),
+            crc32: block.crc32(),
+            data_series,
+            tags,
+            is_embedded_reference_bases: false,
+        }
+    }
+
+    pub(crate) fn set_embedded_reference_bases(
+        mut self,
+        is_embedded_reference_bases: bool,
+    ) -> Self {
+        self.is_embedded_reference_bases = is_embedded_reference_bases;
+        self
+    }
+
+    /// Returns the compression method of the block data.
+    pub fn compression_method(&self) -> CompressionMethod {
+        self.compression_method
+    }
+
+    /// Returns the content type of the block.
+    pub fn content_type(&self) -> ContentType {
+        self.content_type
+    }
+
+    /// Returns the block content ID.
+    pub fn content_id(&self) -> Itf8 {
+        self.content_id
+    }
+
+    /// Returns the size of the entire block, in bytes.
+    ///
+    /// This includes the block header and CRC32.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns whether the block has no data.
+    pub fn is_empty(&self) -> bool {
+        self.compressed_len == 0
+    }
+
+    /// Returns the size of the (compressed) block data, in bytes.
+    pub fn compressed_len(&self) -> usize {
+        self.compressed_len
+    }
+
+    /// Returns the size of the uncompressed block data, in bytes.
+    pub fn uncompressed_len(&self) -> usize {
+        self.uncompressed_len
+    }
+
+    /// Returns the CRC32 of the block as read.
+    pub fn crc32(&self) -> u32 {
+        self.crc32
+    }
+
+    /// Returns the data series that are encoded in this block.
+    ///
+    /// This is only set for external blocks and is determined by the data series encodings in
+    /// the compression header.
+    pub fn data_series(&self) -> &[DataSeries] {
+        &self.data_series
+    }
+
+    /// Returns the tags of the data fields that are encoded in this block.
+    ///
+    /// This is only set for external blocks and is determined by the tag encodings in the
+    /// compression header.
+    pub fn tags(&self) -> &[[u8; 2]] {
+        &self.tags
+    }
+
+    /// Returns whether this block holds the reference bases embedded in the slice.
+    ///
+    /// This is only set for the external block that the slice header lists as the embedded
+    /// reference bases block.
+    pub fn is_embedded_reference_bases(&self) -> bool {
+        self.is_embedded_reference_bases
+    }
+}
@@ -0,0 +1,110 @@
+use noodles_sam as sam;
+
+use super::BlockSummary;
+use crate::{
+    container::ReferenceSequenceId,
+    data_container::slice,
+    num::{Itf8, Ltf8},
+};
+
+/// A summary of a CRAM slice.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SliceSummary {
+    landmark: Itf8,
+    reference_sequence_id: ReferenceSequenceId,
+    alignment_start: Option<sam::record::Position>,
+    alignment_span: Itf8,
+    record_count: usize,
+    record_counter: Ltf8,
+    header_block: BlockSummary,
+    core_data_block: BlockSummary,
+    external_blocks: Vec<BlockSummary>,
+}
+
+impl SliceSummary {
+    pub(crate) fn new(
+        landmark: Itf8,
+        header: &slice::Header,
+        header_block: BlockSummary,
+        core_data_block: BlockSummary,
+        external_blocks: Vec<BlockSummary>,
+    ) -> Self {
+        Self {
+            landmark,
+            reference_sequence_id: header.reference_sequence_id(),
+            alignment_start: header.alignment_start(),
+            alignment_span: header.alignment_span(),
+            record_count: header.record_count(),
+            record_counter: header.record_counter(),
+            header_block,
+            core_data_block,
+            external_blocks,
+        }
+    }
+
+    /// Returns the position of the slice relative to the end of the container header.
+    pub fn landmark(&self) -> Itf8 {
+        self.landmark
+    }
+
+    /// Returns the reference sequence ID of the slice.
+    pub fn reference_sequence_id(&self) -> ReferenceSequenceId {
+        self.reference_sequence_id
+    }
+
+    /// Returns the start position of the slice.
+    ///
+    /// This is `None` for unmapped and multi-reference slices.
+    pub fn alignment_start(&self) -> Option<sam::record::Position> {
+        self.alignment_start
+    }
+
+    /// Returns the number of bases on the reference the slice covers.
+    pub fn alignment_span(&self) -> Itf8 {
+        self.alignment_span
+    }
+
+    /// Returns the number of records in the slice.
+    pub fn record_count(&self) -> usize {
+        self.record_count
+    }
+
+    /// Returns the 0-based index of the first record in the slice.
+    pub fn record_counter(&self) -> Ltf8 {
+        self.record_counter
+    }
+
+    /// Returns a summary of the slice header block.
+    pub fn header_block(&self) -> &BlockSummary {
+        &self.header_block
+    }
+
+    /// Returns a summary of the core data block.
+    pub fn core_data_block(&self) -> &BlockSummary {
+        &self.core_data_block
+    }
+
+    /// Returns summaries of the external blocks.
+    pub fn external_blocks(&self) -> &[BlockSummary] {
+        &self.external_blocks
+    }
+
+    /// Returns an iterator over summaries of all the blocks in the slice.
+    ///
+    /// This includes the slice header block.
+    pub fn blocks(&self) -> impl Iterator<Item = &BlockSummary> {
+        [&self.header_block, &self.core_data_block]
+            .into_iter()
+            .chain(self.external_blocks.iter())
+    }
+
+    /// Returns the size of the slice, in bytes.
+    pub fn len(&self) -> usize {
+        self.blocks().map(|block| block.len()).sum()
+    }
+
+    /// Returns whether the slice has no records.
+    pub fn is_empty(&self) -> bool {
+        self.record_count == 0
+    }
+}
@@ -0,0 +1,167 @@
+//! Read name tokenizer codec (tok3).
+
+mod decode;
+mod encode;
+
+pub use self::{decode::decode, encode::encode};
+
+use std::io;
+
+// The number of token types.
+const TYPE_COUNT: usize = 13;
+
+/// A token type.
+///
+/// Each token type is also the index of its data stream in a token position.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum TokenType {
+    /// The stream of token types.
+    Type = 0,
+    /// A NUL-terminated string of characters.
+    Alpha,
+    /// A single character.
+    Char,
+    /// A number with leading zeros.
+    Digits0,
+    /// The width of a zero-padded number.
+    DZLen,
+    /// The name is a duplicate of a previous name.
+    Dup,
+    /// The name is tokenized against a previous name.
+    Diff,
+    /// A number.
+    Digits,
+    /// A number that is a small delta from the number in the previous name.
+    Delta,
+    /// A zero-padded number that is a small delta from the number in the previous name.
+    Delta0,
+    /// The token is the same as the one in the previous name.
+    Match,
+    /// An empty token.
+    Nop,
+    /// The end of the name.
+    End,
+}
+
+impl TryFrom<u8> for TokenType {
+    type Error = io::Error;
+
+    fn try_from(n: u8) -> Result<Self, Self::Error> {
+        match n {
+            0 => Ok(Self::Type),
+            1 => Ok(Self::Alpha),
+            2 => Ok(Self::Char),
+            3 => Ok(Self::Digits0),
+            4 => Ok(Self::DZLen),
+            5 => Ok(Self::Dup),
+            6 => Ok(Self::Diff),
+            7 => Ok(Self::Digits),
+            8 => Ok(Self::Delta),
+            9 => Ok(Self::Delta0),
+            10 => Ok(Self::Match),
+            11 => Ok(Self::Nop),
+            12 => Ok(Self::End),
+            _ => Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("invalid token type: expected 0..=12, got {}", n),
+            )),
+        }
+    }
+}
+
+/// A tokenized part of a read name.
+#[derive(Clone, Debug, Eq, PartialEq)]
+enum Token {
+    Alpha(Vec<u8>),
+    Char(u8),
+    Digits(u32),
+    PaddedDigits(u32, u8),
+    Nop,
+}
+
+impl Token {
+    fn digits(&self) -> Option<u32> {
+        match self {
+            Self::Digits(n) | Self::PaddedDigits(n, _) => Some(*n),
+            _ => None,
+        }
+    }
+
+    fn write(&self, dst: &mut Vec<u8>) {
+        match self {
+            Self::Alpha(s) => dst.extend(s),
+            Self::Char(c) => dst.push(*c),
+            Self::Digits(n) => dst.extend(n.to_string().bytes()),
+            Self::PaddedDigits(n, width) => {
+                let s = format!("{:0width$}", n, width = usize::from(*width));
+                dst.extend(s.bytes());
+            }
+            Self::Nop => {}
+        }
+    }
+
+    fn len(&self) -> usize {
+        let mut buf = Vec::new();
+        self.write(&mut buf);
+        buf.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_self() -> io::Result<()> {
+        let data = b"\
+I17_08765:2:123:61541:01763#9\0\
+I17_08765:2:123:1636:08611#9\0\
+I17_08765:2:124:45613:16161#9\0\
+I17_08765:2:124:45613:16161#9\0\
+I17_08765:2:124:45614:16162#9\0\
+SRR1234567.1\0\
+SRR1234567.2\0\
+SRR1234567.10\0\
+x\0\
+\0\
+read_0001234567890123\0\
+";
+
+        let compressed_data = encode(data)?;
+        let decompressed_data = decode(&mut &compressed_data[..])?;
+        assert_eq!(decompressed_data, &data[..]);
+
+        let compressed_data = encode(b"")?;
+        let decompressed_data = decode(&mut &compressed_data[..])?;
+        assert!(decompressed_data.is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_encode_compresses() -> io::Result<()> {
+        let mut data = Vec::new();
+
+        for i in 0..1024 {
+            data.extend(
+                format!("HSQ1004:134:C0D8DACXX:1:1101:{}:{}\0", 1000 + i, 2000 + i).bytes(),
+            );
+        }
+
+        let compressed_data = encode(&data)?;
+        assert!(compressed_data.len() < data.len() / 8);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_token_write() {
+        let mut buf = Vec::new();
+        Token::Alpha(b"ndls".to_vec()).write(&mut buf);
+        Token::Char(b':').write(&mut buf);
+        Token::Digits(8).write(&mut buf);
+        Token::PaddedDigits(13, 4).write(&mut buf);
+        Token::Nop.write(&mut buf);
+        assert_eq!(buf, b"ndls:80013");
+    }
+}
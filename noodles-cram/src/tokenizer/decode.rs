@@ -0,0 +1,378 @@
+use std::io::{self, BufRead};
+
+use byteorder::{LittleEndian, ReadBytesExt};
+
+use super::{Token, TokenType, TYPE_COUNT};
+use crate::{
+    aac,
+    rans_nx16::{
+        decode::{read_uint7_as_usize, split_off},
+        rans_decode_nx16,
+    },
+};
+
+const NEW_TOKEN: u8 = 0x80;
+const DUP_TOKEN: u8 = 0x40;
+const TYPE_MASK: u8 = 0x3f;
+
+pub fn decode(src: &mut &[u8]) -> io::Result<Vec<u8>> {
+    let len = src.read_u32::<LittleEndian>().and_then(|n| {
+        usize::try_from(n).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    })?;
+
+    let name_count = src.read_u32::<LittleEndian>().and_then(|n| {
+        usize::try_from(n).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    })?;
+
+    let use_arith = src.read_u8()? != 0;
+
+    let streams = read_token_streams(src, name_count, use_arith)?;
+
+    let mut cursors: Vec<[&[u8]; TYPE_COUNT]> = streams
+        .iter()
+        .map(|token_streams| {
+            let mut cursors: [&[u8]; TYPE_COUNT] = [&[]; TYPE_COUNT];
+
+            for (cursor, buf) in cursors.iter_mut().zip(token_streams) {
+                *cursor = &buf[..];
+            }
+
+            cursors
+        })
+        .collect();
+
+    // The uncompressed length is not trusted, so the initial capacity is limited by the size of
+    // the decoded token streams.
+    let streams_len = streams.iter().flatten().map(|buf| buf.len()).sum();
+
+    let mut names: Vec<Vec<Token>> = Vec::with_capacity(name_count);
+    let mut dst = Vec::with_capacity(len.min(streams_len));
+
+    for n in 0..name_count {
+        let tokens = decode_name(&mut cursors, &names, n)?;
+
+        for token in &tokens {
+            token.write(&mut dst);
+        }
+
+        dst.push(0x00);
+
+        if dst.len() > len {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!(
+                    "length mismatch: expected {}, got at least {}",
+                    len,
+                    dst.len()
+                ),
+            ));
+        }
+
+        names.push(tokens);
+    }
+
+    if dst.len() != len {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("length mismatch: expected {}, got {}", len, dst.len()),
+        ));
+    }
+
+    Ok(dst)
+}
+
+fn read_token_streams(
+    src: &mut &[u8],
+    name_count: usize,
+    use_arith: bool,
+) -> io::Result<Vec<[Vec<u8>; TYPE_COUNT]>> {
+    let mut streams: Vec<[Vec<u8>; TYPE_COUNT]> = Vec::new();
+
+    // The positions and types of streams that are filled with a single token type. These are
+    // built once the name count is validated.
+    let mut elided_streams: Vec<(usize, usize, u8)> = Vec::new();
+
+    while !src.is_empty() {
+        let ttype = src.read_u8()?;
+        let ty = usize::from(ttype & TYPE_MASK);
+
+        if ty >= TYPE_COUNT {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("invalid token type: {}", ty),
+            ));
+        }
+
+        if ttype & NEW_TOKEN != 0 {
+            streams.push(Default::default());
+
+            // The type stream is elided when all tokens in the position have the same type.
+            if ty != TokenType::Type as usize {
+                elided_streams.push((streams.len() - 1, TokenType::Type as usize, ty as u8));
+            }
+        }
+
+        let pos = streams
+            .len()
+            .checked_sub(1)
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "missing new token flag"))?;
+
+        elided_streams.retain(|&(p, t, _)| (p, t) != (pos, ty));
+
+        let buf = if ttype & DUP_TOKEN != 0 {
+            let dup_pos = src.read_u8().map(usize::from)?;
+            let dup_type = src.read_u8().map(usize::from)?;
+
+            if let Some(&(_, _, value)) = elided_streams
+                .iter()
+                .find(|&&(p, t, _)| (p, t) == (dup_pos, dup_type))
+            {
+                elided_streams.push((pos, ty, value));
+                continue;
+            }
+
+            streams
+                .get(dup_pos)
+                .and_then(|s| s.get(dup_type))
+                .cloned()
+                .ok_or_else(|| {
+                    io::Error::new(io::ErrorKind::InvalidData, "invalid duplicate token stream")
+                })?
+        } else {
+            let compressed_len = read_uint7_as_usize(src)?;
+            let mut buf = split_off(src, compressed_len)?;
+
+            if use_arith {
+                aac::decode(&mut buf, 0)?
+            } else {
+                rans_decode_nx16(&mut buf, 0)?
+            }
+        };
+
+        streams[pos][ty] = buf;
+    }
+
+    // Each name reads a 4-byte distance from the first position.
+    let distances_len = streams
+        .first()
+        .map(|s| s[TokenType::Dup as usize].len() + s[TokenType::Diff as usize].len())
+        .unwrap_or_default();
+
+    if name_count > distances_len / 4 {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!(
+                "invalid name count: expected <= {}, got {}",
+                distances_len / 4,
+                name_count
+            ),
+        ));
+    }
+
+    for (pos, ty, value) in elided_streams {
+        streams[pos][ty] = vec![value; name_count];
+    }
+
+    Ok(streams)
+}
+
+fn decode_name(
+    cursors: &mut [[&[u8]; TYPE_COUNT]],
+    names: &[Vec<Token>],
+    n: usize,
+) -> io::Result<Vec<Token>> {
+    let ty = read_type(cursors, 0)?;
+
+    let dist = match ty {
+        TokenType::Dup | TokenType::Diff => cursor(cursors, 0, ty)?
+            .read_u32::<LittleEndian>()
+            .and_then(|n| {
+                usize::try_from(n).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+            })?,
+        _ => {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("invalid name type: {:?}", ty),
+            ))
+        }
+    };
+
+    let m = n
+        .checked_sub(dist)
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "invalid name distance"))?;
+
+    // The first name references itself, i.e., there is no previous name.
+    let prev_tokens = names.get(m).map(|tokens| &tokens[..]).unwrap_or_default();
+
+    if ty == TokenType::Dup {
+        return Ok(prev_tokens.to_vec());
+    }
+
+    let mut tokens = Vec::new();
+
+    for t in 1.. {
+        let ty = read_type(cursors, t)?;
+        let prev_token = prev_tokens.get(t - 1);
+
+        let token = match ty {
+            TokenType::Alpha => {
+                let src = cursor(cursors, t, ty)?;
+                let mut buf = Vec::new();
+                src.read_until(0x00, &mut buf)?;
+
+                if buf.pop() != Some(0x00) {
+                    return Err(io::Error::from(io::ErrorKind::UnexpectedEof));
+                }
+
+                Token::Alpha(buf)
+            }
+            TokenType::Char => cursor(cursors, t, ty)?.read_u8().map(Token::Char)?,
+            TokenType::Digits => cursor(cursors, t, ty)?
+                .read_u32::<LittleEndian>()
+                .map(Token::Digits)?,
+            TokenType::Digits0 => {
+                let value = cursor(cursors, t, ty)?.read_u32::<LittleEndian>()?;
+                let width = cursor(cursors, t, TokenType::DZLen)?.read_u8()?;
+                Token::PaddedDigits(value, width)
+            }
+            TokenType::Delta => {
+                let delta = cursor(cursors, t, ty)?.read_u8()?;
+                let value = add_delta(prev_token, delta)?;
+                Token::Digits(value)
+            }
+            TokenType::Delta0 => {
+                let delta = cursor(cursors, t, ty)?.read_u8()?;
+                let value = add_delta(prev_token, delta)?;
+                let width = prev_token.map(|token| token.len()).unwrap_or_default();
+                let width = u8::try_from(width)
+                    .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+                Token::PaddedDigits(value, width)
+            }
+            TokenType::Match => prev_token.cloned().ok_or_else(|| {
+                io::Error::new(io::ErrorKind::InvalidData, "missing token to match")
+            })?,
+            TokenType::Nop => Token::Nop,
+            TokenType::End => break,
+            _ => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("invalid token type: {:?}", ty),
+                ))
+            }
+        };
+
+        tokens.push(token);
+    }
+
+    Ok(tokens)
+}
+
+fn cursor<'a, 'b>(
+    cursors: &'a mut [[&'b [u8]; TYPE_COUNT]],
+    t: usize,
+    ty: TokenType,
+) -> io::Result<&'a mut &'b [u8]> {
+    cursors
+        .get_mut(t)
+        .map(|c| &mut c[ty as usize])
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "missing token stream"))
+}
+
+fn read_type(cursors: &mut [[&[u8]; TYPE_COUNT]], t: usize) -> io::Result<TokenType> {
+    cursor(cursors, t, TokenType::Type)?
+        .read_u8()
+        .and_then(TokenType::try_from)
+}
+
+fn add_delta(prev_token: Option<&Token>, delta: u8) -> io::Result<u32> {
+    prev_token
+        .and_then(|token| token.digits())
+        .and_then(|n| n.checked_add(u32::from(delta)))
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "invalid delta token"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode() -> io::Result<()> {
+        let data = [
+            0x0d, 0x00, 0x00, 0x00, // uncompressed length = 13
+            0x02, 0x00, 0x00, 0x00, // name count = 2
+            0x00, // use_arith = false
+            0x80, 0x04, 0x20, 0x02, 0x06, 0x06, // t = 0, TYPE, CAT [DIFF, DIFF]
+            0x06, 0x0a, 0x20, 0x08, 0x00, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00,
+            0x00, // t = 0, DIFF, CAT [0, 1]
+            0x81, 0x0c, 0x20, 0x0a, b'r', b'e', b'a', b'd', 0x00, b'r', b'e', b'a', b'd',
+            0x00, // t = 1, ALPHA, CAT ["read", "read"]
+            0x80, 0x04, 0x20, 0x02, 0x07, 0x08, // t = 2, TYPE, CAT [DIGITS, DELTA]
+            0x07, 0x06, 0x20, 0x04, 0x08, 0x00, 0x00, 0x00, // t = 2, DIGITS, CAT [8]
+            0x08, 0x03, 0x20, 0x01, 0x05, // t = 2, DELTA, CAT [5]
+            0x80, 0x04, 0x20, 0x02, 0x0c, 0x0c, // t = 3, TYPE, CAT [END, END]
+        ];
+
+        let actual = decode(&mut &data[..])?;
+        assert_eq!(actual, b"read8\0read13\0");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_decode_with_invalid_name_count() {
+        let data = [
+            0x0d, 0x00, 0x00, 0x00, // uncompressed length = 13
+            0xff, 0xff, 0xff, 0xff, // name count = 4294967295
+            0x00, // use_arith = false
+            0x86, 0x0a, 0x20, 0x08, 0x00, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00,
+            0x00, // t = 0, DIFF, CAT [0, 1]
+        ];
+
+        assert!(matches!(
+            decode(&mut &data[..]),
+            Err(e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+
+        let data = [
+            0xff, 0xff, 0xff, 0xff, // uncompressed length = 4294967295
+            0xff, 0xff, 0xff, 0xff, // name count = 4294967295
+            0x00, // use_arith = false
+        ];
+
+        assert!(matches!(
+            decode(&mut &data[..]),
+            Err(e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+    }
+
+    #[test]
+    fn test_decode_with_arith() -> io::Result<()> {
+        let data = [
+            0x1e, 0x00, 0x00, 0x00, // uncompressed length = 30
+            0x03, 0x00, 0x00, 0x00, // name count = 3
+            0x01, // use_arith = true
+            0x80, 0x09, 0x00, 0x03, 0x07, 0x00, 0xfd, 0xb7, 0x82, 0x55,
+            0x00, // t = 0, TYPE, [DIFF, DIFF, DUP]
+            0x06, 0x09, 0x00, 0x08, 0x02, 0x00, 0x71, 0x3e, 0x93, 0xc6,
+            0x00, // t = 0, DIFF, [0, 1]
+            0x05, 0x08, 0x00, 0x04, 0x02, 0x00, 0xfc, 0x71, 0xc7, 0x16, // t = 0, DUP, [1]
+            0x81, 0x0e, 0x00, 0x0a, 0x73, 0x00, 0xff, 0x80, 0x62, 0xc7, 0x13, 0xb9, 0x7f, 0x60,
+            0xc6, 0x00, // t = 1, ALPHA, ["read", "read"]
+            0x80, 0x09, 0x00, 0x02, 0x0b, 0x00, 0x44, 0xf4, 0xcb, 0x62,
+            0x00, // t = 2, TYPE, [CHAR, MATCH]
+            0x02, 0x08, 0x00, 0x01, 0x3b, 0x00, 0xfb, 0xa9, 0x38, 0x36, // t = 2, CHAR, [':']
+            0x80, 0x09, 0x00, 0x02, 0x0a, 0x00, 0x65, 0x6a, 0x56, 0x8b,
+            0x00, // t = 3, TYPE, [DIGITS0, DELTA0]
+            0x43, 0x00, 0x05, // t = 3, DIGITS0, duplicate of t = 0, DUP ([1])
+            0x04, 0x08, 0x00, 0x01, 0x05, 0x00, 0xcc, 0xcc, 0xcc, 0xcc, // t = 3, DZLEN, [4]
+            0x09, 0x08, 0x00, 0x01, 0x02, 0x00, 0x7f, 0xff, 0xff, 0xff, // t = 3, DELTA0, [1]
+            0x80, 0x08, 0x00, 0x02, 0x0d, 0x00, 0xf3, 0xc6, 0xf5,
+            0xc6, // t = 4, TYPE, [END, END]
+        ];
+
+        let actual = decode(&mut &data[..])?;
+        assert_eq!(actual, b"read:0001\0read:0002\0read:0002\0");
+
+        Ok(())
+    }
+}
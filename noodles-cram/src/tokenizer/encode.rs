@@ -0,0 +1,314 @@
+use std::io::{self, Write};
+
+use byteorder::{LittleEndian, WriteBytesExt};
+
+use super::{Token, TokenType, TYPE_COUNT};
+use crate::rans_nx16::{self, encode::write_uint7_as_usize, rans_encode_nx16};
+
+const NEW_TOKEN: u8 = 0x80;
+const DUP_TOKEN: u8 = 0x40;
+
+// The max number of tokens in a name. The remainder of a long name is stored as a single token.
+const MAX_TOKEN_COUNT: usize = 128;
+
+// Numbers are limited to 9 digits to fit in a u32.
+const MAX_DIGITS_LEN: usize = 9;
+
+pub fn encode(src: &[u8]) -> io::Result<Vec<u8>> {
+    let names: Vec<_> = match src.split_last() {
+        Some((0x00, rest)) => rest.split(|&b| b == 0x00).collect(),
+        Some(_) => {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "missing name terminator",
+            ))
+        }
+        None => Vec::new(),
+    };
+
+    let mut streams: Vec<[Vec<u8>; TYPE_COUNT]> = vec![Default::default()];
+
+    let mut prev_name = None;
+    let mut prev_tokens = Vec::new();
+
+    for (n, &name) in names.iter().enumerate() {
+        if prev_name == Some(name) {
+            streams[0][TokenType::Type as usize].push(TokenType::Dup as u8);
+            streams[0][TokenType::Dup as usize].write_u32::<LittleEndian>(1)?;
+            continue;
+        }
+
+        // The first name references itself, i.e., there is no previous name.
+        let dist = if n == 0 { 0 } else { 1 };
+        streams[0][TokenType::Type as usize].push(TokenType::Diff as u8);
+        streams[0][TokenType::Diff as usize].write_u32::<LittleEndian>(dist)?;
+
+        let tokens = tokenize(name);
+
+        for (i, token) in tokens.iter().enumerate() {
+            let token_streams = token_streams_mut(&mut streams, i + 1);
+            encode_token(token_streams, token, prev_tokens.get(i))?;
+        }
+
+        let token_streams = token_streams_mut(&mut streams, tokens.len() + 1);
+        token_streams[TokenType::Type as usize].push(TokenType::End as u8);
+
+        prev_name = Some(name);
+        prev_tokens = tokens;
+    }
+
+    let len =
+        u32::try_from(src.len()).map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+
+    let name_count =
+        u32::try_from(names.len()).map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+
+    let mut dst = Vec::new();
+    dst.write_u32::<LittleEndian>(len)?;
+    dst.write_u32::<LittleEndian>(name_count)?;
+    dst.write_u8(0)?; // use_arith = false
+
+    if !names.is_empty() {
+        write_token_streams(&mut dst, &streams)?;
+    }
+
+    Ok(dst)
+}
+
+fn token_streams_mut(
+    streams: &mut Vec<[Vec<u8>; TYPE_COUNT]>,
+    t: usize,
+) -> &mut [Vec<u8>; TYPE_COUNT] {
+    if t >= streams.len() {
+        streams.resize_with(t + 1, Default::default);
+    }
+
+    &mut streams[t]
+}
+
+fn tokenize(name: &[u8]) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while i < name.len() {
+        let rest = &name[i..];
+
+        if tokens.len() == MAX_TOKEN_COUNT - 1 {
+            tokens.push(Token::Alpha(rest.to_vec()));
+            break;
+        }
+
+        let c = rest[0];
+
+        let (token, len) = if c.is_ascii_alphabetic() {
+            let len = rest.iter().take_while(|b| b.is_ascii_alphabetic()).count();
+
+            (Token::Alpha(rest[..len].to_vec()), len)
+        } else if c.is_ascii_digit() {
+            let len = rest
+                .iter()
+                .take(MAX_DIGITS_LEN)
+                .take_while(|b| b.is_ascii_digit())
+                .count();
+
+            let value = rest[..len]
+                .iter()
+                .fold(0, |n, &b| n * 10 + u32::from(b - b'0'));
+
+            if c == b'0' && len > 1 {
+                (Token::PaddedDigits(value, len as u8), len)
+            } else {
+                (Token::Digits(value), len)
+            }
+        } else {
+            (Token::Char(c), 1)
+        };
+
+        tokens.push(token);
+        i += len;
+    }
+
+    tokens
+}
+
+fn encode_token(
+    token_streams: &mut [Vec<u8>; TYPE_COUNT],
+    token: &Token,
+    prev_token: Option<&Token>,
+) -> io::Result<()> {
+    let delta = match (token.digits(), prev_token.and_then(|t| t.digits())) {
+        (Some(n), Some(m)) => n.checked_sub(m).and_then(|d| u8::try_from(d).ok()),
+        _ => None,
+    };
+
+    let ty = if prev_token == Some(token) {
+        TokenType::Match
+    } else {
+        match (token, delta) {
+            (Token::Digits(_), Some(d)) => {
+                token_streams[TokenType::Delta as usize].push(d);
+                TokenType::Delta
+            }
+            (Token::PaddedDigits(..), Some(d))
+                if prev_token.map(|t| t.len()) == Some(token.len()) =>
+            {
+                token_streams[TokenType::Delta0 as usize].push(d);
+                TokenType::Delta0
+            }
+            (Token::Alpha(s), _) => {
+                let buf = &mut token_streams[TokenType::Alpha as usize];
+                buf.extend(s);
+                buf.push(0x00);
+                TokenType::Alpha
+            }
+            (Token::Char(c), _) => {
+                token_streams[TokenType::Char as usize].push(*c);
+                TokenType::Char
+            }
+            (Token::Digits(n), _) => {
+                token_streams[TokenType::Digits as usize].write_u32::<LittleEndian>(*n)?;
+                TokenType::Digits
+            }
+            (Token::PaddedDigits(n, width), _) => {
+                token_streams[TokenType::Digits0 as usize].write_u32::<LittleEndian>(*n)?;
+                token_streams[TokenType::DZLen as usize].push(*width);
+                TokenType::Digits0
+            }
+            (Token::Nop, _) => TokenType::Nop,
+        }
+    };
+
+    token_streams[TokenType::Type as usize].push(ty as u8);
+
+    Ok(())
+}
+
+fn write_token_streams<W>(writer: &mut W, streams: &[[Vec<u8>; TYPE_COUNT]]) -> io::Result<()>
+where
+    W: Write,
+{
+    let mut written_streams: Vec<(&[u8], usize, usize)> = Vec::new();
+
+    for (t, token_streams) in streams.iter().enumerate() {
+        let elided_type = elided_type(token_streams);
+        let mut is_new_token = true;
+
+        for (ty, buf) in token_streams.iter().enumerate() {
+            if buf.is_empty() || (ty == TokenType::Type as usize && elided_type.is_some()) {
+                continue;
+            }
+
+            let mut ttype = ty as u8;
+
+            if is_new_token {
+                ttype |= NEW_TOKEN;
+                is_new_token = false;
+            }
+
+            if let Some(&(_, dup_pos, dup_type)) =
+                written_streams.iter().find(|(b, _, _)| *b == &buf[..])
+            {
+                writer.write_u8(ttype | DUP_TOKEN)?;
+                writer.write_u8(dup_pos as u8)?;
+                writer.write_u8(dup_type as u8)?;
+            } else {
+                let compressed_data = compress(buf)?;
+                writer.write_u8(ttype)?;
+                write_uint7_as_usize(writer, compressed_data.len())?;
+                writer.write_all(&compressed_data)?;
+            }
+
+            written_streams.push((buf, t, ty));
+        }
+    }
+
+    Ok(())
+}
+
+// Returns the token type of a position if all its tokens have the same type and that type has a
+// data stream.
+//
+// The decoder rebuilds the type stream from the type of the first stream in the position.
+fn elided_type(token_streams: &[Vec<u8>; TYPE_COUNT]) -> Option<u8> {
+    let types = &token_streams[TokenType::Type as usize];
+    let (&ty, rest) = types.split_first()?;
+
+    if rest.iter().all(|&t| t == ty)
+        && token_streams
+            .get(usize::from(ty))
+            .map(|buf| !buf.is_empty())
+            .unwrap_or_default()
+    {
+        Some(ty)
+    } else {
+        None
+    }
+}
+
+fn compress(src: &[u8]) -> io::Result<Vec<u8>> {
+    use rans_nx16::Flags;
+
+    let mut candidates = [
+        Flags::empty(),
+        Flags::ORDER,
+        Flags::PACK,
+        Flags::PACK | Flags::ORDER,
+        Flags::RLE,
+        Flags::PACK | Flags::RLE,
+        Flags::CAT,
+    ]
+    .into_iter()
+    .map(|flags| rans_encode_nx16(flags, src));
+
+    let mut dst = candidates.next().expect("missing candidate")?;
+
+    for candidate in candidates {
+        let buf = candidate?;
+
+        if buf.len() < dst.len() {
+            dst = buf;
+        }
+    }
+
+    Ok(dst)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode_with_missing_name_terminator() {
+        assert!(matches!(
+            encode(b"r0"),
+            Err(ref e) if e.kind() == io::ErrorKind::InvalidInput
+        ));
+    }
+
+    #[test]
+    fn test_tokenize() {
+        assert_eq!(
+            tokenize(b"SRR1234567.0012"),
+            [
+                Token::Alpha(b"SRR".to_vec()),
+                Token::Digits(1234567),
+                Token::Char(b'.'),
+                Token::PaddedDigits(12, 4),
+            ]
+        );
+
+        assert_eq!(
+            tokenize(b"r12345678901"),
+            [
+                Token::Alpha(b"r".to_vec()),
+                Token::Digits(123456789),
+                Token::PaddedDigits(1, 2),
+            ]
+        );
+
+        let name = vec![b'.'; 256];
+        let tokens = tokenize(&name);
+        assert_eq!(tokens.len(), MAX_TOKEN_COUNT);
+        assert_eq!(tokens[MAX_TOKEN_COUNT - 1], Token::Alpha(vec![b'.'; 129]));
+    }
+}
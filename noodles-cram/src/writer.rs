@@ -1,23 +1,33 @@
-mod container;
+//! CRAM writer.
+
+mod block_compression;
+mod builder;
+pub(crate) mod container;
+mod container_builder;
 pub(crate) mod data_container;
 pub(crate) mod num;
+mod options;
 pub(crate) mod record;
+mod reference_mode;
 
-use std::{
-    io::{self, Write},
-    mem,
+pub use self::{
+    block_compression::BlockCompression, builder::Builder, reference_mode::ReferenceMode,
 };
 
+pub(crate) use self::{container_builder::ContainerBuilder, options::Options};
+
+use std::io::{self, Write};
+
 use noodles_fasta as fasta;
 use noodles_sam as sam;
 
 use self::container::write_container;
 use super::{
-    container::Container, file_definition::Version, DataContainer, FileDefinition, Record,
+    container::Container, file_definition::Version, record::Feature, FileDefinition, Record,
     MAGIC_NUMBER,
 };
 
-const RECORD_COUNTER_START: i64 = 0;
+pub(crate) const RECORD_COUNTER_START: i64 = 0;
 
 /// A CRAM writer.
 ///
@@ -44,9 +54,7 @@ where
     W: Write,
 {
     inner: W,
-    reference_sequences: Vec<fasta::Record>,
-    data_container_builder: crate::data_container::Builder,
-    record_counter: i64,
+    container_builder: ContainerBuilder,
 }
 
 impl<W> Writer<W>
@@ -62,12 +70,22 @@ where
     /// let writer = cram::Writer::new(Vec::new(), Vec::new());
     /// ```
     pub fn new(inner: W, reference_sequences: Vec<fasta::Record>) -> Self {
-        Self {
-            inner,
-            reference_sequences,
-            data_container_builder: DataContainer::builder(RECORD_COUNTER_START),
-            record_counter: RECORD_COUNTER_START,
-        }
+        Self::builder(inner)
+            .set_reference_sequences(reference_sequences)
+            .build()
+    }
+
+    /// Creates a CRAM writer builder.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_cram as cram;
+    /// let builder = cram::Writer::builder(Vec::new());
+    /// let writer = builder.build();
+    /// ```
+    pub fn builder(inner: W) -> Builder<W> {
+        Builder::new(inner)
     }
 
     /// Returns a reference to the underlying writer.
@@ -106,7 +124,8 @@ where
 
     /// Writes a CRAM file definition.
     ///
-    /// The file ID is set as a blank value (`[0x00; 20]`).
+    /// The file ID is set as a blank value (`[0x00; 20]`). The format version is 3.1 if any CRAM 3.1
+    /// block compression method is used; otherwise, it is 3.0.
     ///
     /// # Examples
     ///
@@ -129,7 +148,8 @@ where
     /// # Ok::<(), io::Error>(())
     /// ```
     pub fn write_file_definition(&mut self) -> io::Result<()> {
-        let file_definition = FileDefinition::default();
+        let version = self.container_builder.options().version();
+        let file_definition = FileDefinition::new(version, Default::default());
 
         // magic number
         self.inner.write_all(MAGIC_NUMBER)?;
@@ -169,6 +189,9 @@ where
 
     /// Writes a CRAM record.
     ///
+    /// If the reference mode is [`ReferenceMode::None`], the bases of mapped records are required
+    /// and are stored verbatim.
+    ///
     /// # Examples
     ///
     /// ```
@@ -179,50 +202,18 @@ where
     /// writer.write_record(record)?;
     /// # Ok::<(), io::Error>(())
     /// ```
-    pub fn write_record(&mut self, mut record: Record) -> io::Result<()> {
-        use super::data_container::builder::AddRecordError;
-
-        loop {
-            match add_record(
-                &mut self.data_container_builder,
-                &self.reference_sequences,
-                record,
-            ) {
-                Ok(_) => {
-                    self.record_counter += 1;
-                    return Ok(());
-                }
-                Err(e) => match e {
-                    AddRecordError::ContainerFull(r) => {
-                        record = r;
-                        self.flush()?;
-                    }
-                    AddRecordError::SliceFull(r) => {
-                        record = r;
-                    }
-                },
-            }
+    pub fn write_record(&mut self, record: Record) -> io::Result<()> {
+        match self.container_builder.add_record(record)? {
+            Some(container) => write_container(&mut self.inner, &container),
+            None => Ok(()),
         }
     }
 
     fn flush(&mut self) -> io::Result<()> {
-        if self.data_container_builder.is_empty() {
-            return Ok(());
+        match self.container_builder.flush()? {
+            Some(container) => write_container(&mut self.inner, &container),
+            None => Ok(()),
         }
-
-        let data_container_builder = mem::replace(
-            &mut self.data_container_builder,
-            DataContainer::builder(self.record_counter),
-        );
-
-        let base_count = data_container_builder.base_count();
-
-        data_container_builder
-            .build(&self.reference_sequences)
-            .and_then(|data_container| {
-                Container::try_from_data_container(&data_container, base_count)
-            })
-            .and_then(|container| write_container(&mut self.inner, &container))
     }
 }
 
@@ -235,19 +226,91 @@ where
     }
 }
 
-fn add_record(
-    data_container_builder: &mut crate::data_container::Builder,
-    reference_sequences: &[fasta::Record],
-    record: Record,
-) -> Result<(), crate::data_container::builder::AddRecordError> {
-    let reference_sequence = record
-        .reference_sequence_id()
-        .map(i32::from)
-        .and_then(|id| reference_sequences.get(id as usize))
-        .map(|rs| rs.sequence().as_ref())
-        .unwrap_or_default();
-
-    data_container_builder.add_record(reference_sequence, record)
+pub(crate) fn is_bases_encoded_as_features(record: &Record) -> bool {
+    !record.bam_flags().is_unmapped()
+        && !record.flags().decode_sequence_as_unknown()
+        && record.read_length() > 0
+}
+
+// Replaces read features that depend on the reference sequence, i.e., substitutions and implicit
+// reference matches, with the read bases.
+pub(crate) fn build_reference_free_features(record: &Record) -> io::Result<Vec<Feature>> {
+    let bases = record.bases();
+
+    if bases.len() != record.read_length() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!(
+                "invalid read bases length: expected {}, got {}",
+                record.read_length(),
+                bases.len()
+            ),
+        ));
+    }
+
+    let mut features = Vec::with_capacity(record.features().len());
+    let mut read_pos = 1;
+
+    for feature in record.features() {
+        let feature_pos = usize::try_from(feature.position())
+            .ok()
+            .filter(|&pos| pos > 0)
+            .ok_or_else(|| {
+                io::Error::new(io::ErrorKind::InvalidInput, "invalid read feature position")
+            })?;
+
+        if feature_pos > read_pos {
+            let gap = bases.get(read_pos - 1..feature_pos - 1).ok_or_else(|| {
+                io::Error::new(io::ErrorKind::InvalidInput, "invalid read feature position")
+            })?;
+
+            push_bases(&mut features, read_pos, gap);
+            read_pos = feature_pos;
+        }
+
+        match feature {
+            Feature::Substitution(..) => {
+                let base = bases.get(feature_pos - 1).copied().ok_or_else(|| {
+                    io::Error::new(io::ErrorKind::InvalidInput, "invalid read feature position")
+                })?;
+
+                push_bases(&mut features, feature_pos, &[base]);
+                read_pos = feature_pos + 1;
+                continue;
+            }
+            Feature::Bases(_, bases)
+            | Feature::Insertion(_, bases)
+            | Feature::SoftClip(_, bases) => {
+                read_pos = feature_pos + bases.len();
+            }
+            Feature::ReadBase(..) | Feature::InsertBase(..) => read_pos = feature_pos + 1,
+            Feature::Deletion(..)
+            | Feature::ReferenceSkip(..)
+            | Feature::Scores(..)
+            | Feature::QualityScore(..)
+            | Feature::Padding(..)
+            | Feature::HardClip(..) => {}
+        }
+
+        features.push(feature.clone());
+    }
+
+    if read_pos <= bases.len() {
+        push_bases(&mut features, read_pos, &bases[read_pos - 1..]);
+    }
+
+    Ok(features)
+}
+
+fn push_bases(features: &mut Vec<Feature>, position: usize, bases: &[u8]) {
+    if let Some(Feature::Bases(last_position, last_bases)) = features.last_mut() {
+        if *last_position as usize + last_bases.len() == position {
+            last_bases.extend_from_slice(bases);
+            return;
+        }
+    }
+
+    features.push(Feature::Bases(position as i32, bases.to_vec()));
 }
 
 fn write_format<W>(writer: &mut W, version: Version) -> io::Result<()>
@@ -257,3 +320,205 @@ where
     let format = [version.major(), version.minor()];
     writer.write_all(&format)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_reference_free_features() -> Result<(), Box<dyn std::error::Error>> {
+        use noodles_bam as bam;
+
+        let build_record = |features| -> Result<_, Box<dyn std::error::Error>> {
+            Ok(Record::builder()
+                .set_reference_sequence_id(bam::record::ReferenceSequenceId::try_from(0)?)
+                .set_alignment_start(sam::record::Position::try_from(1)?)
+                .set_read_length(6)
+                .set_bases(b"ACGTAC".to_vec())
+                .set_features(features)
+                .build())
+        };
+
+        let record = build_record(Vec::new())?;
+        assert_eq!(
+            build_reference_free_features(&record)?,
+            [Feature::Bases(1, b"ACGTAC".to_vec())]
+        );
+
+        let record = build_record(vec![
+            Feature::Substitution(2, 0),
+            Feature::Insertion(4, b"TA".to_vec()),
+            Feature::QualityScore(4, 8),
+            Feature::Deletion(6, 3),
+        ])?;
+        assert_eq!(
+            build_reference_free_features(&record)?,
+            [
+                Feature::Bases(1, b"ACG".to_vec()),
+                Feature::Insertion(4, b"TA".to_vec()),
+                Feature::QualityScore(4, 8),
+                Feature::Deletion(6, 3),
+                Feature::Bases(6, b"C".to_vec()),
+            ]
+        );
+
+        let record = Record::builder().set_read_length(4).build();
+        assert!(matches!(
+            build_reference_free_features(&record),
+            Err(e) if e.kind() == io::ErrorKind::InvalidInput
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_write_file_definition_with_cram_3_1_block_compression(
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        use crate::{data_container::DataSeries, record::Flags, Reader};
+
+        let mut writer = Writer::new(Vec::new(), Vec::new());
+        writer.write_file_definition()?;
+        let mut reader = Reader::new(&writer.get_ref()[..]);
+        assert_eq!(reader.read_file_definition()?.version(), Version::new(3, 0));
+
+        let records: Vec<_> = (0..3)
+            .map(|i| {
+                Record::builder()
+                    .set_flags(Flags::QUALITY_SCORES_STORED_AS_ARRAY)
+                    .set_read_length(4)
+                    .set_read_name(format!("r{}", i).into_bytes())
+                    .set_bases(b"ACGT".to_vec())
+                    .set_quality_scores(vec![45, 35, 43, 50])
+                    .build()
+            })
+            .collect();
+
+        let mut writer = Writer::builder(Vec::new())
+            .set_block_compression(BlockCompression::NameTokenizer)
+            .set_data_series_block_compression(DataSeries::QualityScores, BlockCompression::Fqzcomp)
+            .build();
+
+        writer.write_file_definition()?;
+        writer.write_file_header(&sam::Header::default())?;
+
+        for record in records.iter().cloned() {
+            writer.write_record(record)?;
+        }
+
+        writer.try_finish()?;
+
+        let data = writer.get_ref().clone();
+        let mut reader = Reader::new(&data[..]);
+        assert_eq!(reader.read_file_definition()?.version(), Version::new(3, 1));
+        reader.read_file_header()?;
+
+        let actual: Vec<_> = reader.records().collect::<Result<_, _>>()?;
+        assert_eq!(actual.len(), records.len());
+
+        for (actual_record, expected_record) in actual.iter().zip(&records) {
+            assert_eq!(actual_record.read_name(), expected_record.read_name());
+            assert_eq!(actual_record.bases(), expected_record.bases());
+            assert_eq!(
+                actual_record.quality_scores(),
+                expected_record.quality_scores()
+            );
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_write_record_with_options() -> Result<(), Box<dyn std::error::Error>> {
+        use noodles_bam::record::data::field::{value::Type, Value};
+
+        use crate::{
+            container::block::CompressionMethod,
+            data_container::DataSeries,
+            record::{tag::Key, Flags, Tag},
+            Reader,
+        };
+
+        let nh = Key::new([b'N', b'H'], Type::Int8);
+
+        let records: Vec<_> = (0..5)
+            .map(|i| {
+                Record::builder()
+                    .set_flags(Flags::QUALITY_SCORES_STORED_AS_ARRAY)
+                    .set_read_length(4)
+                    .set_read_name(format!("r{}", i).into_bytes())
+                    .set_bases(b"ACGT".to_vec())
+                    .set_quality_scores(vec![45, 35, 43, 50])
+                    .add_tag(Tag::new(nh, Value::Int8(i)))
+                    .build()
+            })
+            .collect();
+
+        let mut writer = Writer::builder(Vec::new())
+            .set_records_per_slice(2)
+            .set_slices_per_container(2)
+            .set_block_compression(BlockCompression::Bzip2(1))
+            .set_data_series_block_compression(
+                DataSeries::QualityScores,
+                BlockCompression::RansOrder1,
+            )
+            .set_tag_block_compression([b'N', b'H'], BlockCompression::Lzma(9))
+            .build();
+
+        writer.write_file_definition()?;
+        writer.write_file_header(&sam::Header::default())?;
+
+        for record in records.iter().cloned() {
+            writer.write_record(record)?;
+        }
+
+        writer.try_finish()?;
+
+        let data = writer.get_ref().clone();
+        let mut reader = Reader::new(&data[..]);
+        reader.read_file_definition()?;
+        reader.read_file_header()?;
+
+        let mut slice_counts = Vec::new();
+        let mut actual = Vec::new();
+
+        while let Some(data_container) = reader.read_data_container()? {
+            slice_counts.push(data_container.slices().len());
+
+            for slice in data_container.slices() {
+                assert_eq!(
+                    slice.core_data_block().compression_method(),
+                    CompressionMethod::Bzip2
+                );
+
+                for block in slice.external_blocks() {
+                    let expected_compression_method = if block.content_id() == nh.id() {
+                        CompressionMethod::Lzma
+                    } else if block.content_id() == DataSeries::QualityScores as i32 + 1 {
+                        CompressionMethod::Rans
+                    } else {
+                        CompressionMethod::Bzip2
+                    };
+
+                    assert_eq!(block.compression_method(), expected_compression_method);
+                }
+
+                actual.extend(slice.records(data_container.compression_header())?);
+            }
+        }
+
+        assert_eq!(slice_counts, [2, 1]);
+        assert_eq!(actual.len(), records.len());
+
+        for (actual_record, expected_record) in actual.iter().zip(&records) {
+            assert_eq!(actual_record.read_name(), expected_record.read_name());
+            assert_eq!(actual_record.bases(), expected_record.bases());
+            assert_eq!(
+                actual_record.quality_scores(),
+                expected_record.quality_scores()
+            );
+            assert_eq!(actual_record.tags(), expected_record.tags());
+        }
+
+        Ok(())
+    }
+}
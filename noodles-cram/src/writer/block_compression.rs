@@ -0,0 +1,189 @@
+use std::io::{self, Write};
+
+use bzip2::write::BzEncoder;
+use flate2::write::GzEncoder;
+use xz2::write::XzEncoder;
+
+use crate::{
+    aac,
+    container::block::CompressionMethod,
+    file_definition::Version,
+    fqzcomp,
+    rans::{rans_encode, Order},
+    rans_nx16::{self, rans_encode_nx16},
+    tokenizer,
+};
+
+const MAX_LEVEL: u32 = 9;
+
+/// A block compression method and its parameters.
+///
+/// This is used to compress the data of a block when writing.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum BlockCompression {
+    /// Uncompressed.
+    None,
+    /// gzip with a compression level (0-9).
+    Gzip(u32),
+    /// bzip2 with a compression level (1-9).
+    Bzip2(u32),
+    /// LZMA with a compression preset level (0-9).
+    Lzma(u32),
+    /// Order-0 rANS.
+    RansOrder0,
+    /// Order-1 rANS.
+    RansOrder1,
+    /// rANS Nx16 (CRAM 3.1).
+    RansNx16,
+    /// Adaptive arithmetic coding (CRAM 3.1).
+    AdaptiveArithmeticCoding,
+    /// fqzcomp quality score codec (CRAM 3.1).
+    Fqzcomp,
+    /// Read name tokenizer (tok3) (CRAM 3.1).
+    NameTokenizer,
+}
+
+impl BlockCompression {
+    pub(crate) fn compression_method(self) -> CompressionMethod {
+        match self {
+            Self::None => CompressionMethod::None,
+            Self::Gzip(_) => CompressionMethod::Gzip,
+            Self::Bzip2(_) => CompressionMethod::Bzip2,
+            Self::Lzma(_) => CompressionMethod::Lzma,
+            Self::RansOrder0 | Self::RansOrder1 => CompressionMethod::Rans,
+            Self::RansNx16 => CompressionMethod::RansNx16,
+            Self::AdaptiveArithmeticCoding => CompressionMethod::AdaptiveArithmeticCoding,
+            Self::Fqzcomp => CompressionMethod::Fqzcomp,
+            Self::NameTokenizer => CompressionMethod::NameTokenizer,
+        }
+    }
+
+    // Returns the minimum file definition version that supports the compression method.
+    pub(crate) fn version(self) -> Version {
+        match self {
+            Self::RansNx16
+            | Self::AdaptiveArithmeticCoding
+            | Self::Fqzcomp
+            | Self::NameTokenizer => Version::new(3, 1),
+            _ => Version::new(3, 0),
+        }
+    }
+
+    // Returns the block compression to use for the given data.
+    //
+    // The name tokenizer only encodes NUL-terminated names. Other data is compressed using rANS
+    // Nx16 instead.
+    pub(crate) fn resolve(self, data: &[u8]) -> Self {
+        match self {
+            Self::NameTokenizer if !matches!(data.last(), None | Some(0x00)) => Self::RansNx16,
+            _ => self,
+        }
+    }
+
+    pub(crate) fn compress(self, data: &[u8]) -> io::Result<Vec<u8>> {
+        match self {
+            Self::None => Ok(data.to_vec()),
+            Self::Gzip(level) => {
+                let compression = flate2::Compression::new(level.min(MAX_LEVEL));
+                let mut encoder = GzEncoder::new(Vec::new(), compression);
+                encoder.write_all(data)?;
+                encoder.finish()
+            }
+            Self::Bzip2(level) => {
+                let compression = bzip2::Compression::new(level.clamp(1, MAX_LEVEL));
+                let mut encoder = BzEncoder::new(Vec::new(), compression);
+                encoder.write_all(data)?;
+                encoder.finish()
+            }
+            Self::Lzma(level) => {
+                let mut encoder = XzEncoder::new(Vec::new(), level.min(MAX_LEVEL));
+                encoder.write_all(data)?;
+                encoder.finish()
+            }
+            Self::RansOrder0 => rans_encode(Order::Zero, data),
+            Self::RansOrder1 => rans_encode(Order::One, data),
+            Self::RansNx16 => rans_encode_nx16(rans_nx16::Flags::empty(), data),
+            Self::AdaptiveArithmeticCoding => aac::encode(aac::Flags::empty(), data),
+            // The block is encoded as a single record.
+            Self::Fqzcomp => fqzcomp::encode(&[data.len()], data),
+            Self::NameTokenizer => tokenizer::encode(data),
+        }
+    }
+}
+
+impl Default for BlockCompression {
+    fn default() -> Self {
+        Self::Gzip(flate2::Compression::default().level())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::container::{block::ContentType, Block};
+
+    #[test]
+    fn test_compress() -> io::Result<()> {
+        fn t(
+            data: &[u8],
+            block_compression: BlockCompression,
+            expected: CompressionMethod,
+        ) -> io::Result<()> {
+            let block = Block::builder()
+                .set_content_type(ContentType::ExternalData)
+                .compress_and_set_data(data.to_vec(), block_compression)?
+                .build();
+
+            assert_eq!(block.compression_method(), expected);
+            assert_eq!(&block.decompressed_data()?[..], data);
+
+            Ok(())
+        }
+
+        let data = b"noodles";
+
+        for block_compression in [
+            BlockCompression::None,
+            BlockCompression::Gzip(1),
+            BlockCompression::Bzip2(9),
+            BlockCompression::Lzma(0),
+            BlockCompression::RansOrder0,
+            BlockCompression::RansOrder1,
+            BlockCompression::RansNx16,
+            BlockCompression::AdaptiveArithmeticCoding,
+            BlockCompression::Fqzcomp,
+        ] {
+            t(
+                data,
+                block_compression,
+                block_compression.compression_method(),
+            )?;
+        }
+
+        t(
+            b"r0\x00r1\x00",
+            BlockCompression::NameTokenizer,
+            CompressionMethod::NameTokenizer,
+        )?;
+
+        // Data that are not NUL-terminated names fall back to rANS Nx16.
+        t(
+            data,
+            BlockCompression::NameTokenizer,
+            CompressionMethod::RansNx16,
+        )?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_version() {
+        assert_eq!(BlockCompression::Gzip(6).version(), Version::new(3, 0));
+        assert_eq!(BlockCompression::RansOrder1.version(), Version::new(3, 0));
+        assert_eq!(BlockCompression::RansNx16.version(), Version::new(3, 1));
+        assert_eq!(
+            BlockCompression::NameTokenizer.version(),
+            Version::new(3, 1)
+        );
+    }
+}
@@ -0,0 +1,124 @@
+use std::{io, mem};
+
+use noodles_fasta as fasta;
+
+use super::{
+    build_reference_free_features, is_bases_encoded_as_features, Options, ReferenceMode,
+    RECORD_COUNTER_START,
+};
+use crate::{
+    container::Container,
+    data_container::{self, builder::AddRecordError},
+    DataContainer, Record,
+};
+
+/// A builder of containers from records.
+///
+/// This holds the state shared by the sync and async writers. Records are added to a data
+/// container, and full data containers are returned as containers to be written.
+#[derive(Debug)]
+pub(crate) struct ContainerBuilder {
+    reference_sequences: Vec<fasta::Record>,
+    options: Options,
+    data_container_builder: data_container::Builder,
+    record_counter: i64,
+}
+
+impl ContainerBuilder {
+    pub fn new(reference_sequences: Vec<fasta::Record>, options: Options) -> Self {
+        Self {
+            reference_sequences,
+            options,
+            data_container_builder: DataContainer::builder(RECORD_COUNTER_START),
+            record_counter: RECORD_COUNTER_START,
+        }
+    }
+
+    pub fn options(&self) -> &Options {
+        &self.options
+    }
+
+    /// Adds a record.
+    ///
+    /// If the current data container is full, it is returned as a container, and the record is
+    /// added to a new data container.
+    pub fn add_record(&mut self, mut record: Record) -> io::Result<Option<Container>> {
+        if self.options.reference_mode == ReferenceMode::None
+            && is_bases_encoded_as_features(&record)
+        {
+            record.features = build_reference_free_features(&record)?;
+        }
+
+        let reference_sequence = match self.options.reference_mode {
+            ReferenceMode::None => &[][..],
+            _ => record
+                .reference_sequence_id()
+                .map(i32::from)
+                .and_then(|id| self.reference_sequences.get(id as usize))
+                .map(|rs| rs.sequence().as_ref())
+                .unwrap_or_default(),
+        };
+
+        let mut container = None;
+
+        loop {
+            match self
+                .data_container_builder
+                .add_record(&self.options, reference_sequence, record)
+            {
+                Ok(_) => {
+                    self.record_counter += 1;
+                    return Ok(container);
+                }
+                Err(AddRecordError::ContainerFull(r)) => {
+                    record = r;
+
+                    let data_container_builder = mem::replace(
+                        &mut self.data_container_builder,
+                        DataContainer::builder(self.record_counter),
+                    );
+
+                    container = Some(build_container(
+                        data_container_builder,
+                        &self.options,
+                        &self.reference_sequences,
+                    )?);
+                }
+                Err(AddRecordError::SliceFull(r)) => {
+                    record = r;
+                }
+            }
+        }
+    }
+
+    /// Returns the current data container as a container, if it has any records.
+    pub fn flush(&mut self) -> io::Result<Option<Container>> {
+        if self.data_container_builder.is_empty() {
+            return Ok(None);
+        }
+
+        let data_container_builder = mem::replace(
+            &mut self.data_container_builder,
+            DataContainer::builder(self.record_counter),
+        );
+
+        build_container(
+            data_container_builder,
+            &self.options,
+            &self.reference_sequences,
+        )
+        .map(Some)
+    }
+}
+
+fn build_container(
+    data_container_builder: data_container::Builder,
+    options: &Options,
+    reference_sequences: &[fasta::Record],
+) -> io::Result<Container> {
+    let base_count = data_container_builder.base_count();
+
+    data_container_builder
+        .build(options, reference_sequences)
+        .and_then(|data_container| Container::try_from_data_container(&data_container, base_count))
+}
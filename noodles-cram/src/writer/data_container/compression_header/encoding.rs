@@ -15,7 +15,7 @@ where
     match encoding {
         Encoding::Null => write_null_encoding(writer),
         Encoding::External(block_content_id) => write_external_encoding(writer, *block_content_id),
-        Encoding::Golomb(..) => unimplemented!("GOLOMB"),
+        Encoding::Golomb(offset, m) => write_golomb_encoding(writer, *offset, *m),
         Encoding::Huffman(alphabet, bit_lens) => write_huffman_encoding(writer, alphabet, bit_lens),
         Encoding::ByteArrayLen(len_encoding, value_encoding) => {
             write_byte_array_len_encoding(writer, len_encoding, value_encoding)
@@ -25,7 +25,9 @@ where
         }
         Encoding::Beta(offset, len) => write_beta_encoding(writer, *offset, *len),
         Encoding::Subexp(offset, k) => write_subexp_encoding(writer, *offset, *k),
-        Encoding::GolombRice(..) => unimplemented!("GOLOMB_RICE"),
+        Encoding::GolombRice(offset, log2_m) => {
+            write_golomb_rice_encoding(writer, *offset, *log2_m)
+        }
         Encoding::Gamma(offset) => write_gamma_encoding(writer, *offset),
     }
 }
@@ -59,6 +61,20 @@ where
     Ok(())
 }
 
+fn write_golomb_encoding<W>(writer: &mut W, offset: Itf8, m: Itf8) -> io::Result<()>
+where
+    W: Write,
+{
+    let mut args = Vec::new();
+    write_itf8(&mut args, offset)?;
+    write_itf8(&mut args, m)?;
+
+    write_itf8(writer, i32::from(encoding::Kind::Golomb))?;
+    write_args(writer, &args)?;
+
+    Ok(())
+}
+
 fn write_huffman_encoding<W>(writer: &mut W, alphabet: &[i32], bit_lens: &[u32]) -> io::Result<()>
 where
     W: Write,
@@ -154,6 +170,20 @@ where
     Ok(())
 }
 
+fn write_golomb_rice_encoding<W>(writer: &mut W, offset: Itf8, log2_m: Itf8) -> io::Result<()>
+where
+    W: Write,
+{
+    let mut args = Vec::new();
+    write_itf8(&mut args, offset)?;
+    write_itf8(&mut args, log2_m)?;
+
+    write_itf8(writer, i32::from(encoding::Kind::GolombRice))?;
+    write_args(writer, &args)?;
+
+    Ok(())
+}
+
 fn write_gamma_encoding<W>(writer: &mut W, offset: Itf8) -> io::Result<()>
 where
     W: Write,
@@ -201,6 +231,23 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_write_golomb_encoding() -> io::Result<()> {
+        let mut buf = Vec::new();
+        write_golomb_encoding(&mut buf, 1, 5)?;
+
+        let expected = [
+            2, // Golomb encoding ID
+            2, // args.len
+            1, // offset
+            5, // m
+        ];
+
+        assert_eq!(buf, expected);
+
+        Ok(())
+    }
+
     #[test]
     fn test_write_huffman_encoding() -> io::Result<()> {
         let mut buf = Vec::new();
@@ -296,6 +343,23 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_write_golomb_rice_encoding() -> io::Result<()> {
+        let mut buf = Vec::new();
+        write_golomb_rice_encoding(&mut buf, 1, 3)?;
+
+        let expected = [
+            8, // Golomb-Rice encoding ID
+            2, // args.len
+            1, // offset
+            3, // log2_m
+        ];
+
+        assert_eq!(buf, expected);
+
+        Ok(())
+    }
+
     #[test]
     fn test_write_gamma_encoding() -> io::Result<()> {
         let mut buf = Vec::new();
@@ -1,4 +1,5 @@
 mod itf8;
 mod ltf8;
+mod uint7;
 
-pub use self::{itf8::write_itf8, ltf8::write_ltf8};
+pub use self::{itf8::write_itf8, ltf8::write_ltf8, uint7::write_uint7};
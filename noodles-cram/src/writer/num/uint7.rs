@@ -0,0 +1,46 @@
+use std::io::{self, Write};
+
+use byteorder::WriteBytesExt;
+
+pub fn write_uint7<W>(writer: &mut W, value: u32) -> io::Result<()>
+where
+    W: Write,
+{
+    let mut n = 1;
+
+    while n < 5 && value >> (7 * n) != 0 {
+        n += 1;
+    }
+
+    for i in (1..n).rev() {
+        let b = ((value >> (7 * i)) & 0x7f) as u8;
+        writer.write_u8(b | 0x80)?;
+    }
+
+    writer.write_u8((value & 0x7f) as u8)?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_write_uint7() -> io::Result<()> {
+        fn t(value: u32, expected: &[u8]) -> io::Result<()> {
+            let mut buf = Vec::new();
+            write_uint7(&mut buf, value)?;
+            assert_eq!(buf, expected);
+            Ok(())
+        }
+
+        t(0, &[0x00])?;
+        t(85, &[0x55])?;
+        t(1749, &[0x8d, 0x55])?;
+        t(16384, &[0x81, 0x80, 0x00])?;
+        t(u32::MAX, &[0x8f, 0xff, 0xff, 0xff, 0x7f])?;
+
+        Ok(())
+    }
+}
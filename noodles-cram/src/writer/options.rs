@@ -0,0 +1,51 @@
+use std::collections::HashMap;
+
+use super::{BlockCompression, ReferenceMode};
+use crate::{data_container::DataSeries, file_definition::Version};
+
+const DEFAULT_RECORDS_PER_SLICE: usize = 2560;
+const DEFAULT_SLICES_PER_CONTAINER: usize = 4;
+
+#[derive(Clone, Debug)]
+pub(crate) struct Options {
+    pub(crate) preserve_read_names: bool,
+    pub(crate) encode_alignment_start_positions_as_deltas: bool,
+    pub(crate) reference_mode: ReferenceMode,
+    pub(crate) records_per_slice: usize,
+    pub(crate) slices_per_container: usize,
+    pub(crate) multi_reference_slices: bool,
+    pub(crate) block_compression: BlockCompression,
+    pub(crate) data_series_block_compressions: HashMap<DataSeries, BlockCompression>,
+    pub(crate) tag_block_compressions: HashMap<[u8; 2], BlockCompression>,
+}
+
+impl Options {
+    // Returns the file definition version required by the block compression methods.
+    pub(crate) fn version(&self) -> Version {
+        let block_compressions = [self.block_compression]
+            .into_iter()
+            .chain(self.data_series_block_compressions.values().copied())
+            .chain(self.tag_block_compressions.values().copied());
+
+        block_compressions
+            .map(|block_compression| block_compression.version())
+            .max_by_key(|version| (version.major(), version.minor()))
+            .unwrap_or_default()
+    }
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            preserve_read_names: true,
+            encode_alignment_start_positions_as_deltas: true,
+            reference_mode: ReferenceMode::default(),
+            records_per_slice: DEFAULT_RECORDS_PER_SLICE,
+            slices_per_container: DEFAULT_SLICES_PER_CONTAINER,
+            multi_reference_slices: false,
+            block_compression: BlockCompression::default(),
+            data_series_block_compressions: HashMap::new(),
+            tag_block_compressions: HashMap::new(),
+        }
+    }
+}
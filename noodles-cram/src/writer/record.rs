@@ -13,11 +13,13 @@ use noodles_sam as sam;
 
 use super::num::write_itf8;
 use crate::{
+    bit_codecs,
     container::ReferenceSequenceId,
     data_container::{
         compression_header::{data_series_encoding_map::DataSeries, Encoding},
         CompressionHeader,
     },
+    huffman::CanonicalHuffmanEncoder,
     num::Itf8,
     record::{self, feature, Feature, Flags, NextMateFlags},
     BitWriter, Record,
@@ -29,6 +31,7 @@ pub enum WriteRecordError {
     MissingDataSeriesEncoding(DataSeries),
     MissingTagEncoding(record::tag::Key),
     MissingExternalBlock(i32),
+    UnsupportedEncoding(Encoding),
 }
 
 impl error::Error for WriteRecordError {}
@@ -43,6 +46,9 @@ impl fmt::Display for WriteRecordError {
             Self::MissingExternalBlock(block_content_id) => {
                 write!(f, "missing external block: {}", block_content_id)
             }
+            Self::UnsupportedEncoding(encoding) => {
+                write!(f, "unsupported encoding: {:?}", encoding)
+            }
         }
     }
 }
@@ -869,7 +875,7 @@ where
 
 fn encode_byte<W, X>(
     encoding: &Encoding,
-    _core_data_writer: &mut BitWriter<W>,
+    core_data_writer: &mut BitWriter<W>,
     external_data_writers: &mut HashMap<Itf8, X>,
     value: u8,
 ) -> io::Result<()>
@@ -890,13 +896,13 @@ where
 
             writer.write_u8(value)
         }
-        _ => todo!("encode_byte: {:?}", encoding),
+        _ => encode_itf8_with_core_data(encoding, core_data_writer, Itf8::from(value)),
     }
 }
 
 fn encode_itf8<W, X>(
     encoding: &Encoding,
-    _core_data_writer: &mut BitWriter<W>,
+    core_data_writer: &mut BitWriter<W>,
     external_data_writers: &mut HashMap<Itf8, X>,
     value: Itf8,
 ) -> io::Result<()>
@@ -917,7 +923,42 @@ where
 
             write_itf8(writer, value)
         }
-        _ => todo!("encode_itf8: {:?}", encoding),
+        _ => encode_itf8_with_core_data(encoding, core_data_writer, value),
+    }
+}
+
+// Encodes a value to the core data block, i.e., using an encoding that is not stored in an
+// external block.
+fn encode_itf8_with_core_data<W>(
+    encoding: &Encoding,
+    core_data_writer: &mut BitWriter<W>,
+    value: Itf8,
+) -> io::Result<()>
+where
+    W: Write,
+{
+    match encoding {
+        Encoding::Golomb(offset, m) => {
+            bit_codecs::encode_golomb(core_data_writer, *offset, *m, value)
+        }
+        Encoding::Huffman(alphabet, bit_lens) => {
+            let encoder = CanonicalHuffmanEncoder::new(alphabet, bit_lens);
+            encoder.encode(core_data_writer, value)
+        }
+        Encoding::Beta(offset, len) => {
+            bit_codecs::encode_beta(core_data_writer, *offset, *len, value)
+        }
+        Encoding::Subexp(offset, k) => {
+            bit_codecs::encode_subexp(core_data_writer, *offset, *k, value)
+        }
+        Encoding::GolombRice(offset, log2_m) => {
+            bit_codecs::encode_golomb_rice(core_data_writer, *offset, *log2_m, value)
+        }
+        Encoding::Gamma(offset) => bit_codecs::encode_gamma(core_data_writer, *offset, value),
+        _ => Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            WriteRecordError::UnsupportedEncoding(encoding.clone()),
+        )),
     }
 }
 
@@ -948,12 +989,21 @@ where
             let len = data.len() as Itf8;
             encode_itf8(len_encoding, core_data_writer, external_data_writers, len)?;
 
-            encode_byte_array(
-                value_encoding,
-                core_data_writer,
-                external_data_writers,
-                data,
-            )
+            if let Encoding::External(_) = **value_encoding {
+                encode_byte_array(
+                    value_encoding,
+                    core_data_writer,
+                    external_data_writers,
+                    data,
+                )
+            } else {
+                // The values of a byte array can be encoded using any byte encoding.
+                for &b in data {
+                    encode_byte(value_encoding, core_data_writer, external_data_writers, b)?;
+                }
+
+                Ok(())
+            }
         }
         Encoding::ByteArrayStop(stop_byte, block_content_id) => {
             let writer = external_data_writers
@@ -970,6 +1020,72 @@ where
 
             Ok(())
         }
-        _ => todo!("encode_byte_array: {:?}", encoding),
+        _ => Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            WriteRecordError::UnsupportedEncoding(encoding.clone()),
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode_itf8() -> io::Result<()> {
+        fn t(encoding: &Encoding, value: Itf8, expected: &[u8]) -> io::Result<()> {
+            let mut core_data_writer = BitWriter::new(Vec::new());
+            let mut external_data_writers: HashMap<Itf8, Vec<u8>> = HashMap::new();
+
+            encode_itf8(
+                encoding,
+                &mut core_data_writer,
+                &mut external_data_writers,
+                value,
+            )?;
+
+            let actual = core_data_writer.finish()?;
+            assert_eq!(actual, expected);
+
+            Ok(())
+        }
+
+        t(&Encoding::Huffman(vec![0x4e], vec![0]), 0x4e, &[])?;
+        t(&Encoding::Beta(1, 3), 3, &[0b10000000])?;
+        t(&Encoding::Golomb(0, 3), 3, &[0b10000000])?;
+        t(&Encoding::Subexp(0, 1), 2, &[0b10000000])?;
+        t(&Encoding::GolombRice(0, 1), 2, &[0b10000000])?;
+        t(&Encoding::Gamma(0), 1, &[0b10000000])?;
+
+        assert!(matches!(
+            t(&Encoding::Null, 0, &[]),
+            Err(ref e) if e.kind() == io::ErrorKind::InvalidInput
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_encode_byte_array() -> io::Result<()> {
+        let mut core_data_writer = BitWriter::new(Vec::new());
+        let mut external_data_writers: HashMap<Itf8, Vec<u8>> = HashMap::new();
+        external_data_writers.insert(1, Vec::new());
+
+        let encoding = Encoding::ByteArrayLen(
+            Box::new(Encoding::External(1)),
+            Box::new(Encoding::Beta(0, 8)),
+        );
+
+        encode_byte_array(
+            &encoding,
+            &mut core_data_writer,
+            &mut external_data_writers,
+            b"nd",
+        )?;
+
+        assert_eq!(core_data_writer.finish()?, b"nd");
+        assert_eq!(external_data_writers[&1], [0x02]);
+
+        Ok(())
     }
 }
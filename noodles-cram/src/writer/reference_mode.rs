@@ -0,0 +1,19 @@
+/// How read bases are encoded relative to a reference sequence.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ReferenceMode {
+    /// Mapped reads are encoded as differences to an external reference sequence.
+    ///
+    /// The reference sequence is required to decode the read bases. This is the default.
+    External,
+    /// Mapped reads are encoded as differences to a reference sequence, and the reference span
+    /// of each slice is stored in an external block of the slice.
+    Embedded,
+    /// Read bases are stored verbatim, and no reference sequence is used.
+    None,
+}
+
+impl Default for ReferenceMode {
+    fn default() -> Self {
+        Self::External
+    }
+}
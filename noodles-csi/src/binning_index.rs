@@ -8,9 +8,13 @@ use std::{io, ops::RangeBounds};
 
 use noodles_bgzf as bgzf;
 
-use super::index::reference_sequence::bin::Chunk;
+use super::index::reference_sequence::{bin::Chunk, Metadata};
 
 /// A binning index.
+///
+/// This is implemented by the indices that use the hierarchical binning scheme, i.e., BAM index
+/// (BAI), coordinate-sorted index (CSI), and tabix. Readers that query using a binning index accept
+/// any index type that implements this trait.
 pub trait BinningIndex<RS>
 where
     RS: ReferenceSequenceExt,
@@ -22,10 +26,75 @@ where
     fn unplaced_unmapped_record_count(&self) -> Option<u64>;
 
     /// Returns the chunks that overlap with the given region.
+    ///
+    /// The chunks are merged and do not start before the minimum offset of the start position
+    /// (see [`Self::min_offset`]).
     fn query<B>(&self, reference_sequence_id: usize, interval: B) -> io::Result<Vec<Chunk>>
     where
         B: RangeBounds<i32> + Clone;
 
+    /// Returns the reference sequence with the given ID.
+    ///
+    /// This returns an [`io::ErrorKind::InvalidInput`] error if the reference sequence ID is out
+    /// of range.
+    fn get_reference_sequence(&self, reference_sequence_id: usize) -> io::Result<&RS> {
+        self.reference_sequences()
+            .get(reference_sequence_id)
+            .ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("invalid reference sequence ID: {}", reference_sequence_id),
+                )
+            })
+    }
+
+    /// Returns the minimum start offset of records that may overlap the given 1-based start
+    /// position.
+    ///
+    /// By default, this is the start of the stream, i.e., no records are excluded. Indices with a
+    /// linear index return the offset of the window that includes the start position.
+    fn min_offset(
+        &self,
+        reference_sequence_id: usize,
+        _start: i32,
+    ) -> io::Result<bgzf::VirtualPosition> {
+        self.get_reference_sequence(reference_sequence_id)
+            .map(|_| bgzf::VirtualPosition::default())
+    }
+
+    /// Returns the names of the indexed reference sequences, if the index stores them.
+    ///
+    /// The names are parallel to [`Self::reference_sequences`]. A BAM index (BAI) does not store
+    /// reference sequence names; they are defined by the header of the associated file.
+    fn reference_sequence_names(&self) -> Option<Vec<&str>> {
+        None
+    }
+
+    /// Returns the ID of the reference sequence with the given name, if the index stores
+    /// reference sequence names.
+    ///
+    /// This returns `None` if the index does not store reference sequence names (see
+    /// [`Self::reference_sequence_names`]) and `Some(None)` if the name is not in the index.
+    fn reference_sequence_id(&self, name: &str) -> Option<Option<usize>> {
+        self.reference_sequence_names()
+            .map(|names| names.iter().position(|&n| n == name))
+    }
+
+    /// Returns the metadata of the reference sequence with the given ID, if present.
+    ///
+    /// The metadata include the number of mapped and unmapped records of the reference sequence.
+    fn reference_sequence_metadata<'a>(
+        &'a self,
+        reference_sequence_id: usize,
+    ) -> Option<&'a Metadata>
+    where
+        RS: 'a,
+    {
+        self.reference_sequences()
+            .get(reference_sequence_id)
+            .and_then(|rs| rs.metadata())
+    }
+
     /// Returns the start position of the first record in the last linear bin.
     ///
     /// This is the closest position to the unplaced, unmapped records, if any, that is available
@@ -139,6 +208,45 @@ pub fn optimize_chunks(chunks: &[Chunk], min_offset: bgzf::VirtualPosition) -> V
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::index::ReferenceSequence;
+
+    struct MinimalIndex(Vec<ReferenceSequence>);
+
+    impl BinningIndex<ReferenceSequence> for MinimalIndex {
+        fn reference_sequences(&self) -> &[ReferenceSequence] {
+            &self.0
+        }
+
+        fn unplaced_unmapped_record_count(&self) -> Option<u64> {
+            None
+        }
+
+        fn query<B>(&self, _: usize, _: B) -> io::Result<Vec<Chunk>>
+        where
+            B: RangeBounds<i32> + Clone,
+        {
+            Ok(Vec::new())
+        }
+    }
+
+    #[test]
+    fn test_provided_methods() -> io::Result<()> {
+        let index = MinimalIndex(vec![ReferenceSequence::new(Vec::new(), None)]);
+
+        assert!(index.get_reference_sequence(0).is_ok());
+        assert!(matches!(
+            index.get_reference_sequence(1),
+            Err(e) if e.kind() == io::ErrorKind::InvalidInput
+        ));
+
+        assert_eq!(index.min_offset(0, 8)?, bgzf::VirtualPosition::default());
+        assert!(index.min_offset(1, 8).is_err());
+
+        assert!(index.reference_sequence_names().is_none());
+        assert!(index.reference_sequence_id("sq0").is_none());
+
+        Ok(())
+    }
 
     fn build_chunks() -> Vec<Chunk> {
         vec![
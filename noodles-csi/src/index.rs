@@ -1,16 +1,19 @@
 //! Coordinate-sorted index and fields.
 
 mod builder;
+mod indexer;
 pub mod reference_sequence;
 
-pub use self::{builder::Builder, reference_sequence::ReferenceSequence};
+pub use self::{builder::Builder, indexer::Indexer, reference_sequence::ReferenceSequence};
 
 use std::{
     io,
     ops::{Bound, RangeBounds},
 };
 
-use super::{index::reference_sequence::bin::Chunk, BinningIndex};
+use noodles_bgzf as bgzf;
+
+use super::{binning_index::optimize_chunks, index::reference_sequence::bin::Chunk, BinningIndex};
 
 /// A coordinate-sorted index (CSI).
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -131,15 +134,7 @@ impl BinningIndex<ReferenceSequence> for Index {
             }
         }
 
-        let reference_sequence = self
-            .reference_sequences()
-            .get(reference_sequence_id)
-            .ok_or_else(|| {
-                io::Error::new(
-                    io::ErrorKind::InvalidInput,
-                    format!("invalid reference sequence ID: {}", reference_sequence_id),
-                )
-            })?;
+        let reference_sequence = self.get_reference_sequence(reference_sequence_id)?;
 
         let query_interval = (
             cast_bound_i32_to_bound_i64(interval.start_bound()),
@@ -156,7 +151,57 @@ impl BinningIndex<ReferenceSequence> for Index {
             .copied()
             .collect();
 
-        Ok(chunks)
+        let start = match query_interval.0 {
+            Bound::Included(s) => s,
+            Bound::Excluded(s) => s + 1,
+            Bound::Unbounded => 1,
+        };
+
+        let min_offset = reference_sequence.min_offset(self.min_shift(), self.depth(), start);
+        let merged_chunks = optimize_chunks(&chunks, min_offset);
+
+        Ok(merged_chunks)
+    }
+
+    /// Returns the minimum start offset of records that may overlap the given start position.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf as bgzf;
+    /// use noodles_csi::{self as csi, index::ReferenceSequence, BinningIndex};
+    ///
+    /// let index = csi::Index::builder()
+    ///     .set_reference_sequences(vec![ReferenceSequence::new(Vec::new(), None)])
+    ///     .build();
+    ///
+    /// assert_eq!(index.min_offset(0, 8)?, bgzf::VirtualPosition::default());
+    /// assert!(index.min_offset(1, 8).is_err());
+    /// # Ok::<_, std::io::Error>(())
+    /// ```
+    fn min_offset(
+        &self,
+        reference_sequence_id: usize,
+        start: i32,
+    ) -> io::Result<bgzf::VirtualPosition> {
+        self.get_reference_sequence(reference_sequence_id)
+            .map(|rs| rs.min_offset(self.min_shift(), self.depth(), i64::from(start)))
+    }
+
+    /// Returns the reference sequence names stored in the auxiliary data.
+    ///
+    /// The auxiliary data of a CSI for a tabix-indexed file (e.g., VCF) is a tabix header, which
+    /// includes the reference sequence names. Otherwise, e.g., for BAM and BCF, this is `None`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_csi::{self as csi, BinningIndex};
+    /// let index = csi::Index::default();
+    /// assert!(index.reference_sequence_names().is_none());
+    /// ```
+    fn reference_sequence_names(&self) -> Option<Vec<&str>> {
+        parse_aux_reference_sequence_names(self.aux())
     }
 }
 
@@ -165,3 +210,50 @@ impl Default for Index {
         Self::builder().build()
     }
 }
+
+// The tabix header is 6 `i32` fields (format, col_seq, col_beg, col_end, meta, and skip) followed
+// by the length of the concatenated NUL-terminated names (`l_nm`) and the names.
+fn parse_aux_reference_sequence_names(aux: &[u8]) -> Option<Vec<&str>> {
+    const NAMES_OFFSET: usize = 28;
+
+    let raw_l_nm = aux.get(NAMES_OFFSET - 4..NAMES_OFFSET)?;
+    let l_nm = i32::from_le_bytes([raw_l_nm[0], raw_l_nm[1], raw_l_nm[2], raw_l_nm[3]]);
+    let l_nm = usize::try_from(l_nm).ok()?;
+
+    let names = aux.get(NAMES_OFFSET..NAMES_OFFSET + l_nm)?;
+    let names = names.strip_suffix(&[0])?;
+
+    if names.is_empty() {
+        return Some(Vec::new());
+    }
+
+    names
+        .split(|&b| b == 0)
+        .map(|name| std::str::from_utf8(name).ok())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_aux_reference_sequence_names() {
+        let mut aux = Vec::new();
+
+        for n in [2, 1, 2, 0, 35, 0] {
+            aux.extend_from_slice(&i32::to_le_bytes(n));
+        }
+
+        aux.extend_from_slice(&i32::to_le_bytes(8));
+        aux.extend_from_slice(b"sq0\x00sq1\x00");
+
+        assert_eq!(
+            parse_aux_reference_sequence_names(&aux),
+            Some(vec!["sq0", "sq1"])
+        );
+
+        assert!(parse_aux_reference_sequence_names(&[]).is_none());
+        assert!(parse_aux_reference_sequence_names(&aux[..30]).is_none());
+    }
+}
@@ -0,0 +1,237 @@
+//! CSI indexer.
+
+use std::io;
+
+use super::{
+    reference_sequence::{self, bin::Chunk},
+    Index, ReferenceSequence,
+};
+
+/// A coordinate-sorted index (CSI) indexer.
+///
+/// The indexer builds an index from coordinate-sorted records. Unlike a BAM index (BAI), the
+/// number of bits for the minimum interval (`min_shift`) and the depth of the binning index can
+/// be set, e.g., to index reference sequences longer than 2^29 - 1 bases.
+#[derive(Debug)]
+pub struct Indexer {
+    min_shift: i32,
+    depth: i32,
+    reference_sequence_builders: Vec<reference_sequence::Builder>,
+    unplaced_unmapped_record_count: u64,
+    last_alignment_context: Option<(usize, i64)>,
+}
+
+impl Indexer {
+    /// Creates a CSI indexer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_csi::index::Indexer;
+    /// let indexer = Indexer::new(14, 6);
+    /// ```
+    pub fn new(min_shift: i32, depth: i32) -> Self {
+        Self {
+            min_shift,
+            depth,
+            reference_sequence_builders: Vec::new(),
+            unplaced_unmapped_record_count: 0,
+            last_alignment_context: None,
+        }
+    }
+
+    /// Adds a record.
+    ///
+    /// The alignment context is the reference sequence ID, the 1-based start and end (inclusive)
+    /// positions, and whether the record is mapped. It is `None` for unplaced, unmapped records,
+    /// which must be added last. The chunk is the start and end position of the record in the
+    /// file.
+    ///
+    /// This returns an error if the records are not coordinate-sorted or a position is out of
+    /// the range addressable by the index.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf as bgzf;
+    /// use noodles_csi::index::{reference_sequence::bin::Chunk, Indexer};
+    ///
+    /// let mut indexer = Indexer::new(14, 5);
+    ///
+    /// indexer.add_record(
+    ///     Some((0, 8, 13, true)),
+    ///     Chunk::new(
+    ///         bgzf::VirtualPosition::from(233),
+    ///         bgzf::VirtualPosition::from(377),
+    ///     ),
+    /// )?;
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn add_record(
+        &mut self,
+        alignment_context: Option<(usize, i64, i64, bool)>,
+        chunk: Chunk,
+    ) -> io::Result<()> {
+        let (reference_sequence_id, start, end, is_mapped) = match alignment_context {
+            Some(context) => context,
+            None => {
+                self.unplaced_unmapped_record_count += 1;
+                return Ok(());
+            }
+        };
+
+        if self.unplaced_unmapped_record_count > 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "unplaced, unmapped records must be last",
+            ));
+        }
+
+        let max_position = ReferenceSequence::max_position(self.min_shift, self.depth);
+
+        if start < 1 || end < start || end > max_position {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!(
+                    "invalid alignment interval: expected 1 <= start <= end <= {}, got [{}, {}]",
+                    max_position, start, end
+                ),
+            ));
+        }
+
+        if let Some(last_alignment_context) = self.last_alignment_context {
+            if (reference_sequence_id, start) < last_alignment_context {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "records must be coordinate-sorted to be indexed",
+                ));
+            }
+        }
+
+        if reference_sequence_id >= self.reference_sequence_builders.len() {
+            self.reference_sequence_builders
+                .resize_with(reference_sequence_id + 1, Default::default);
+        }
+
+        self.reference_sequence_builders[reference_sequence_id].add_record(
+            self.min_shift,
+            self.depth,
+            start,
+            end,
+            is_mapped,
+            chunk,
+        );
+
+        self.last_alignment_context = Some((reference_sequence_id, start));
+
+        Ok(())
+    }
+
+    /// Builds a coordinate-sorted index (CSI).
+    ///
+    /// `reference_sequence_count` is the number of reference sequences in the associated file.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_csi::{index::Indexer, BinningIndex};
+    ///
+    /// let indexer = Indexer::new(14, 5);
+    /// let index = indexer.build(2);
+    ///
+    /// assert_eq!(index.reference_sequences().len(), 2);
+    /// assert_eq!(index.unplaced_unmapped_record_count(), Some(0));
+    /// ```
+    pub fn build(mut self, reference_sequence_count: usize) -> Index {
+        if reference_sequence_count > self.reference_sequence_builders.len() {
+            self.reference_sequence_builders
+                .resize_with(reference_sequence_count, Default::default);
+        }
+
+        let reference_sequences = self
+            .reference_sequence_builders
+            .into_iter()
+            .map(|builder| builder.build(self.depth))
+            .collect();
+
+        Index::builder()
+            .set_min_shift(self.min_shift)
+            .set_depth(self.depth)
+            .set_reference_sequences(reference_sequences)
+            .set_unplaced_unmapped_record_count(self.unplaced_unmapped_record_count)
+            .build()
+    }
+}
+
+impl Default for Indexer {
+    fn default() -> Self {
+        Self::new(14, 5)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use noodles_bgzf as bgzf;
+
+    use super::*;
+    use crate::BinningIndex;
+
+    fn chunk(start: u64, end: u64) -> Chunk {
+        Chunk::new(
+            bgzf::VirtualPosition::from(start),
+            bgzf::VirtualPosition::from(end),
+        )
+    }
+
+    #[test]
+    fn test_add_record() -> io::Result<()> {
+        let mut indexer = Indexer::default();
+        indexer.add_record(Some((0, 8, 13, true)), chunk(5, 8))?;
+
+        assert!(matches!(
+            indexer.add_record(Some((0, 5, 13, true)), chunk(8, 13)),
+            Err(e) if e.kind() == io::ErrorKind::InvalidInput
+        ));
+
+        assert!(matches!(
+            indexer.add_record(Some((0, 0, 13, true)), chunk(8, 13)),
+            Err(e) if e.kind() == io::ErrorKind::InvalidInput
+        ));
+
+        assert!(matches!(
+            indexer.add_record(Some((0, 8, 1 << 29, true)), chunk(8, 13)),
+            Err(e) if e.kind() == io::ErrorKind::InvalidInput
+        ));
+
+        indexer.add_record(None, chunk(8, 13))?;
+
+        assert!(matches!(
+            indexer.add_record(Some((1, 8, 13, true)), chunk(13, 21)),
+            Err(e) if e.kind() == io::ErrorKind::InvalidInput
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_build() -> io::Result<()> {
+        let mut indexer = Indexer::new(14, 6);
+
+        let start = 1 << 30;
+        indexer.add_record(Some((1, start, start + 4, true)), chunk(5, 8))?;
+        indexer.add_record(None, chunk(8, 13))?;
+
+        let index = indexer.build(3);
+
+        assert_eq!(index.min_shift(), 14);
+        assert_eq!(index.depth(), 6);
+        assert_eq!(index.reference_sequences().len(), 3);
+        assert!(index.reference_sequences()[0].bins().is_empty());
+        assert_eq!(index.unplaced_unmapped_record_count(), Some(1));
+
+        let chunks = index.query(1, (start as i32)..=(start as i32 + 1))?;
+        assert_eq!(chunks, [chunk(5, 8)]);
+
+        Ok(())
+    }
+}
@@ -1,11 +1,15 @@
 //! Coordinate-sorted index (CSI) reference sequence and fields.
 
 pub mod bin;
+mod builder;
 mod metadata;
 
 pub use self::{bin::Bin, metadata::Metadata};
 
+pub(crate) use self::builder::Builder;
+
 use std::{
+    collections::HashMap,
     error, fmt,
     ops::{Bound, RangeBounds},
 };
@@ -53,7 +57,7 @@ impl fmt::Display for QueryError {
 }
 
 impl ReferenceSequence {
-    fn max_position(min_shift: i32, depth: i32) -> i64 {
+    pub(crate) fn max_position(min_shift: i32, depth: i32) -> i64 {
         let min_shift = i64::from(min_shift);
         let depth = i64::from(depth);
         (1 << (min_shift + 3 * depth)) - 1
@@ -139,6 +143,57 @@ impl ReferenceSequence {
 
         Ok(query_bins)
     }
+
+    /// Finds the minimum start offset in the linear index for a given start position.
+    ///
+    /// The linear index is not stored in a CSI. Instead, it is recovered from the smallest offset
+    /// of the bins that cover the start position. `start` is 1-based.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf as bgzf;
+    /// use noodles_csi::index::{reference_sequence::Bin, ReferenceSequence};
+    ///
+    /// let bins = vec![Bin::new(4682, bgzf::VirtualPosition::from(8), Vec::new())];
+    /// let reference_sequence = ReferenceSequence::new(bins, None);
+    ///
+    /// assert_eq!(reference_sequence.min_offset(14, 5, 13), bgzf::VirtualPosition::from(0));
+    /// assert_eq!(reference_sequence.min_offset(14, 5, 16385), bgzf::VirtualPosition::from(8));
+    /// ```
+    pub fn min_offset(&self, min_shift: i32, depth: i32, start: i64) -> bgzf::VirtualPosition {
+        // Bins are not necessarily ordered by ID.
+        let loffsets: HashMap<_, _> = self
+            .bins()
+            .iter()
+            .map(|bin| (i64::from(bin.id()), bin.loffset()))
+            .collect();
+
+        let first_bin_id = ((1 << (depth * 3)) - 1) / 7;
+        let mut bin_id = first_bin_id + ((start - 1) >> min_shift);
+
+        // If the bin at the smallest level is not present, the search continues with the
+        // preceding bin on the same level and, at the first bin of a level, with the parent bin.
+        loop {
+            if let Some(&loffset) = loffsets.get(&bin_id) {
+                return loffset;
+            }
+
+            if bin_id == 0 {
+                break;
+            }
+
+            let parent_id = (bin_id - 1) >> 3;
+
+            if bin_id > (parent_id << 3) + 1 {
+                bin_id -= 1;
+            } else {
+                bin_id = parent_id;
+            }
+        }
+
+        bgzf::VirtualPosition::default()
+    }
 }
 
 impl ReferenceSequenceExt for ReferenceSequence {
@@ -183,6 +238,7 @@ impl ReferenceSequenceExt for ReferenceSequence {
     /// let bins = vec![
     ///     Bin::new(0, bgzf::VirtualPosition::from(8), Vec::new()),
     ///     Bin::new(1, bgzf::VirtualPosition::from(13), Vec::new()),
+    ///     Bin::new(4681, bgzf::VirtualPosition::from(5), Vec::new()),
     /// ];
     /// let reference_sequence = ReferenceSequence::new(bins, None);
     /// assert_eq!(
@@ -191,7 +247,9 @@ impl ReferenceSequenceExt for ReferenceSequence {
     /// );
     /// ```
     fn first_record_in_last_linear_bin_start_position(&self) -> Option<bgzf::VirtualPosition> {
-        self.bins().last().map(|bin| bin.loffset())
+        // Bins are not ordered by position, so the last bin does not necessarily cover the last
+        // linear window.
+        self.bins().iter().map(|bin| bin.loffset()).max()
     }
 }
 
@@ -248,6 +306,53 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_min_offset() {
+        let bins = vec![
+            Bin::new(0, bgzf::VirtualPosition::from(2), Vec::new()),
+            Bin::new(4681, bgzf::VirtualPosition::from(5), Vec::new()),
+            Bin::new(4683, bgzf::VirtualPosition::from(13), Vec::new()),
+            Bin::new(586, bgzf::VirtualPosition::from(21), Vec::new()),
+        ];
+        let reference_sequence = ReferenceSequence::new(bins, None);
+
+        // bin 4681
+        assert_eq!(
+            reference_sequence.min_offset(MIN_SHIFT, DEPTH, 1),
+            bgzf::VirtualPosition::from(5)
+        );
+
+        // bin 4682 -> bin 4681
+        assert_eq!(
+            reference_sequence.min_offset(MIN_SHIFT, DEPTH, 16385),
+            bgzf::VirtualPosition::from(5)
+        );
+
+        // bin 4683
+        assert_eq!(
+            reference_sequence.min_offset(MIN_SHIFT, DEPTH, 32769),
+            bgzf::VirtualPosition::from(13)
+        );
+
+        // bin 4689 -> bin 586
+        assert_eq!(
+            reference_sequence.min_offset(MIN_SHIFT, DEPTH, 131073),
+            bgzf::VirtualPosition::from(21)
+        );
+
+        // bin 4697 -> bin 587 -> bin 586
+        assert_eq!(
+            reference_sequence.min_offset(MIN_SHIFT, DEPTH, 262145),
+            bgzf::VirtualPosition::from(21)
+        );
+
+        // bin 9417 -> ... -> bin 1 -> bin 0
+        assert_eq!(
+            reference_sequence.min_offset(MIN_SHIFT, DEPTH, 77594625),
+            bgzf::VirtualPosition::from(2)
+        );
+    }
+
     #[test]
     fn test_reg2bins() {
         // +------------------------------------------------------------------------------------...
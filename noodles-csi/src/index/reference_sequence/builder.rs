@@ -0,0 +1,233 @@
+use std::{cmp, collections::HashMap};
+
+use noodles_bgzf as bgzf;
+
+use super::{bin::Chunk, Bin, Metadata, ReferenceSequence};
+
+// Builds an indexed reference sequence from coordinate-sorted records.
+#[derive(Debug, Default)]
+pub(crate) struct Builder {
+    bins: HashMap<u32, Vec<Chunk>>,
+    linear_index: Vec<Option<bgzf::VirtualPosition>>,
+    start_position: Option<bgzf::VirtualPosition>,
+    end_position: bgzf::VirtualPosition,
+    mapped_record_count: u64,
+    unmapped_record_count: u64,
+}
+
+impl Builder {
+    // `start` and `end` are 1-based, inclusive.
+    pub fn add_record(
+        &mut self,
+        min_shift: i32,
+        depth: i32,
+        start: i64,
+        end: i64,
+        is_mapped: bool,
+        chunk: Chunk,
+    ) {
+        self.update_bins(min_shift, depth, start, end, chunk);
+        self.update_linear_index(min_shift, start, end, chunk);
+        self.update_metadata(is_mapped, chunk);
+    }
+
+    pub fn build(self, depth: i32) -> ReferenceSequence {
+        let start_position = match self.start_position {
+            Some(start_position) => start_position,
+            None => return ReferenceSequence::new(Vec::new(), None),
+        };
+
+        let linear_index = fill_linear_index(self.linear_index);
+
+        let mut bins: Vec<_> = self
+            .bins
+            .into_iter()
+            .map(|(id, chunks)| {
+                let i = first_window_index(id, depth);
+                let loffset = linear_index.get(i).copied().unwrap_or_default();
+                Bin::new(id, loffset, chunks)
+            })
+            .collect();
+
+        bins.sort_unstable_by_key(|bin| bin.id());
+
+        let metadata = Metadata::new(
+            start_position,
+            self.end_position,
+            self.mapped_record_count,
+            self.unmapped_record_count,
+        );
+
+        ReferenceSequence::new(bins, Some(metadata))
+    }
+
+    fn update_bins(&mut self, min_shift: i32, depth: i32, start: i64, end: i64, chunk: Chunk) {
+        let id = reg2bin(start - 1, end, min_shift, depth);
+        let chunks = self.bins.entry(id).or_default();
+
+        // Adjacent records in the same bin are merged into a single chunk.
+        match chunks.last_mut() {
+            Some(last_chunk) if last_chunk.end() == chunk.start() => {
+                *last_chunk = Chunk::new(last_chunk.start(), chunk.end());
+            }
+            _ => chunks.push(chunk),
+        }
+    }
+
+    fn update_linear_index(&mut self, min_shift: i32, start: i64, end: i64, chunk: Chunk) {
+        let start_index = ((start - 1) >> min_shift) as usize;
+        let end_index = ((end - 1) >> min_shift) as usize;
+
+        if end_index >= self.linear_index.len() {
+            self.linear_index.resize(end_index + 1, None);
+        }
+
+        for position in &mut self.linear_index[start_index..=end_index] {
+            position.get_or_insert(chunk.start());
+        }
+    }
+
+    fn update_metadata(&mut self, is_mapped: bool, chunk: Chunk) {
+        if is_mapped {
+            self.mapped_record_count += 1;
+        } else {
+            self.unmapped_record_count += 1;
+        }
+
+        let start_position = self.start_position.get_or_insert(chunk.start());
+        *start_position = cmp::min(*start_position, chunk.start());
+
+        self.end_position = cmp::max(self.end_position, chunk.end());
+    }
+}
+
+// Windows with no records are assigned the position of the previous window.
+fn fill_linear_index(
+    linear_index: Vec<Option<bgzf::VirtualPosition>>,
+) -> Vec<bgzf::VirtualPosition> {
+    let mut last_position = bgzf::VirtualPosition::default();
+
+    linear_index
+        .into_iter()
+        .map(|position| {
+            if let Some(p) = position {
+                last_position = p;
+            }
+
+            last_position
+        })
+        .collect()
+}
+
+// `CSIv1.pdf` (2020-07-21)
+// [beg, end), 0-based
+#[allow(clippy::many_single_char_names)]
+pub(crate) fn reg2bin(beg: i64, mut end: i64, min_shift: i32, depth: i32) -> u32 {
+    end -= 1;
+
+    let mut l = depth;
+    let mut s = min_shift;
+    let mut t = ((1 << (depth * 3)) - 1) / 7;
+
+    while l > 0 {
+        if beg >> s == end >> s {
+            return (t + (beg >> s)) as u32;
+        }
+
+        l -= 1;
+        s += 3;
+        t -= 1 << (l * 3);
+    }
+
+    0
+}
+
+// Returns the index of the first linear index window that is covered by the given bin.
+fn first_window_index(id: u32, depth: i32) -> usize {
+    if id > Bin::max_id(depth) {
+        return usize::MAX;
+    }
+
+    let id = id as usize;
+    let mut level = 0;
+    let mut b = id;
+
+    while b > 0 {
+        level += 1;
+        b = (b - 1) >> 3;
+    }
+
+    let first_id = ((1 << (level * 3)) - 1) / 7;
+
+    (id - first_id) << ((depth as usize - level) * 3)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MIN_SHIFT: i32 = 14;
+    const DEPTH: i32 = 5;
+
+    #[test]
+    fn test_reg2bin() {
+        // These are the same as BAI bins for the default min shift and depth.
+        assert_eq!(reg2bin(0, 1, MIN_SHIFT, DEPTH), 4681);
+        assert_eq!(reg2bin(16383, 16385, MIN_SHIFT, DEPTH), 585);
+        assert_eq!(reg2bin(0, 1 << 29, MIN_SHIFT, DEPTH), 0);
+
+        // A position that is only addressable with a deeper index.
+        let beg = 1 << 30;
+        assert_eq!(
+            reg2bin(beg, beg + 1, MIN_SHIFT, 6),
+            37449 + (beg >> 14) as u32
+        );
+    }
+
+    #[test]
+    fn test_first_window_index() {
+        assert_eq!(first_window_index(0, DEPTH), 0);
+        assert_eq!(first_window_index(2, DEPTH), 1 << 12);
+        assert_eq!(first_window_index(4681, DEPTH), 0);
+        assert_eq!(first_window_index(4682, DEPTH), 1);
+    }
+
+    #[test]
+    fn test_build() {
+        fn chunk(start: u64, end: u64) -> Chunk {
+            Chunk::new(
+                bgzf::VirtualPosition::from(start),
+                bgzf::VirtualPosition::from(end),
+            )
+        }
+
+        let mut builder = Builder::default();
+        builder.add_record(MIN_SHIFT, DEPTH, 1, 4, true, chunk(5, 8));
+        builder.add_record(MIN_SHIFT, DEPTH, 3, 6, true, chunk(8, 13));
+        builder.add_record(MIN_SHIFT, DEPTH, 32769, 32772, false, chunk(13, 21));
+
+        let actual = builder.build(DEPTH);
+
+        let expected = ReferenceSequence::new(
+            vec![
+                Bin::new(4681, bgzf::VirtualPosition::from(5), vec![chunk(5, 13)]),
+                Bin::new(4683, bgzf::VirtualPosition::from(13), vec![chunk(13, 21)]),
+            ],
+            Some(Metadata::new(
+                bgzf::VirtualPosition::from(5),
+                bgzf::VirtualPosition::from(21),
+                2,
+                1,
+            )),
+        );
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_build_with_no_records() {
+        let builder = Builder::default();
+        let actual = builder.build(DEPTH);
+        assert_eq!(actual, ReferenceSequence::new(Vec::new(), None));
+    }
+}
@@ -1,33 +1,62 @@
 //! Queries a FASTA with a given reference sequence name.
 //!
-//! The input FASTA must have an index in the same directory.
+//! The input FASTA must have an index in the same directory. If the input is bgzip-compressed
+//! (`.gz`), it must also have a gzip index (`.gzi`) in the same directory.
 //!
 //! The result is similar to the output of `samtools faidx --length 80 <src>
 //! <reference-sequence-name>`.
 
 use std::{
     env,
+    ffi::OsString,
     fs::File,
-    io::{self, BufReader},
-    path::PathBuf,
+    io::{self, BufRead, BufReader, Seek},
+    path::{Path, PathBuf},
 };
 
+use noodles_bgzf::{self as bgzf, gzi};
 use noodles_fasta::{self as fasta, fai};
 
+fn push_ext<P>(src: P, ext: &str) -> PathBuf
+where
+    P: AsRef<Path>,
+{
+    let mut s = OsString::from(src.as_ref());
+    s.push(".");
+    s.push(ext);
+    PathBuf::from(s)
+}
+
+fn query<R>(
+    reader: R,
+    index: &[fai::Record],
+    raw_region: &str,
+) -> Result<fasta::Record, Box<dyn std::error::Error>>
+where
+    R: BufRead + Seek,
+{
+    let mut reader = fasta::Reader::new(reader);
+    let region = raw_region.parse()?;
+    let record = reader.query(index, &region)?;
+    Ok(record)
+}
+
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     let mut args = env::args();
 
     let src = args.nth(1).map(PathBuf::from).expect("missing src");
     let raw_region = args.next().expect("missing region");
 
-    let mut reader = File::open(&src)
-        .map(BufReader::new)
-        .map(fasta::Reader::new)?;
-
-    let index = fai::read(src.with_extension("fa.fai"))?;
-    let region = raw_region.parse()?;
+    let index = fai::read(push_ext(&src, "fai"))?;
 
-    let record = reader.query(&index, &region)?;
+    let record = if src.extension().map(|ext| ext == "gz").unwrap_or_default() {
+        let gzi_index = gzi::read(push_ext(&src, "gzi"))?;
+        let reader = File::open(&src).map(|f| bgzf::IndexedReader::new(f, gzi_index))?;
+        query(reader, &index, &raw_region)?
+    } else {
+        let reader = File::open(&src).map(BufReader::new)?;
+        query(reader, &index, &raw_region)?
+    };
 
     let stdout = io::stdout();
     let handle = stdout.lock();
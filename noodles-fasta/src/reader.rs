@@ -168,6 +168,10 @@ where
 {
     /// Returns a record of the given region.
     ///
+    /// To query a bgzip-compressed FASTA, wrap the stream in a [`bgzf::IndexedReader`] with the
+    /// associated gzip index (GZI). The FASTA index offsets are then resolved as uncompressed
+    /// positions.
+    ///
     /// # Examples
     ///
     /// ```
@@ -472,6 +476,51 @@ mod tests {
         assert_eq!(buf, b"NNNN");
     }
 
+    #[test]
+    fn test_query_with_bgzf_indexed_reader() -> io::Result<()> {
+        use std::io::Write;
+
+        use noodles_bgzf::gzi;
+
+        use crate::record::{Definition, Sequence};
+
+        let mut writer = bgzf::Writer::new(Vec::new());
+        let mut entries = Vec::new();
+        let mut uncompressed_position = 0;
+
+        for chunk in [&b">sq0\nNNNN\n"[..], b">sq1\nACGT\n", b">sq2\nNNNN\n"] {
+            writer.write_all(chunk)?;
+            writer.flush()?;
+
+            uncompressed_position += chunk.len() as u64;
+            entries.push((writer.position(), uncompressed_position));
+        }
+
+        let data = writer.finish()?;
+        let gzi_index = gzi::Index::from(entries);
+
+        let index = vec![
+            fai::Record::new(String::from("sq0"), 4, 5, 4, 5),
+            fai::Record::new(String::from("sq1"), 4, 15, 4, 5),
+            fai::Record::new(String::from("sq2"), 4, 25, 4, 5),
+        ];
+
+        let mut reader = Reader::new(bgzf::IndexedReader::new(Cursor::new(data), gzi_index));
+
+        let region = Region::mapped("sq1", 2..=3);
+        let record = reader.query(&index, &region)?;
+
+        assert_eq!(
+            record,
+            Record::new(
+                Definition::new("sq1:2-3", None),
+                Sequence::from(b"CG".to_vec())
+            )
+        );
+
+        Ok(())
+    }
+
     #[test]
     fn test_read_line() -> io::Result<()> {
         let mut buf = String::new();
@@ -15,6 +15,7 @@ use std::{
 };
 
 use indexmap::IndexSet;
+use noodles_bgzf as bgzf;
 use noodles_csi::{
     binning_index::optimize_chunks, index::reference_sequence::bin::Chunk, BinningIndex,
 };
@@ -139,15 +140,7 @@ impl BinningIndex<ReferenceSequence> for Index {
     where
         B: RangeBounds<i32> + Clone,
     {
-        let reference_sequence = self
-            .reference_sequences()
-            .get(reference_sequence_id)
-            .ok_or_else(|| {
-                io::Error::new(
-                    io::ErrorKind::InvalidInput,
-                    format!("invalid reference sequence ID: {}", reference_sequence_id),
-                )
-            })?;
+        let reference_sequence = self.get_reference_sequence(reference_sequence_id)?;
 
         let query_bins = reference_sequence
             .query(interval.clone())
@@ -170,6 +163,71 @@ impl BinningIndex<ReferenceSequence> for Index {
 
         Ok(merged_chunks)
     }
+
+    /// Returns the minimum start offset of records that may overlap the given start position.
+    ///
+    /// This is the offset of the linear index window that includes the start position.
+    fn min_offset(
+        &self,
+        reference_sequence_id: usize,
+        start: i32,
+    ) -> io::Result<bgzf::VirtualPosition> {
+        self.get_reference_sequence(reference_sequence_id)
+            .map(|rs| rs.min_offset(start))
+    }
+
+    /// Returns the reference sequence names.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_csi::BinningIndex;
+    /// use noodles_tabix::{self as tabix, index::ReferenceSequenceNames};
+    ///
+    /// let reference_sequence_names: ReferenceSequenceNames = [String::from("sq0")]
+    ///     .into_iter()
+    ///     .collect();
+    ///
+    /// let index = tabix::Index::builder()
+    ///     .set_reference_sequence_names(reference_sequence_names)
+    ///     .build();
+    ///
+    /// assert_eq!(
+    ///     BinningIndex::reference_sequence_names(&index),
+    ///     Some(vec!["sq0"])
+    /// );
+    /// ```
+    fn reference_sequence_names(&self) -> Option<Vec<&str>> {
+        Some(
+            self.reference_sequence_names
+                .iter()
+                .map(|name| name.as_str())
+                .collect(),
+        )
+    }
+
+    /// Returns the ID of the reference sequence with the given name.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_csi::BinningIndex;
+    /// use noodles_tabix::{self as tabix, index::ReferenceSequenceNames};
+    ///
+    /// let reference_sequence_names: ReferenceSequenceNames = [String::from("sq0")]
+    ///     .into_iter()
+    ///     .collect();
+    ///
+    /// let index = tabix::Index::builder()
+    ///     .set_reference_sequence_names(reference_sequence_names)
+    ///     .build();
+    ///
+    /// assert_eq!(index.reference_sequence_id("sq0"), Some(Some(0)));
+    /// assert_eq!(index.reference_sequence_id("sq1"), Some(None));
+    /// ```
+    fn reference_sequence_id(&self, name: &str) -> Option<Option<usize>> {
+        Some(self.reference_sequence_names.get_index_of(name))
+    }
 }
 
 impl Default for Index {
@@ -4,8 +4,7 @@ use futures::{stream, Stream};
 use memchr::memchr;
 use noodles_bgzf as bgzf;
 use noodles_core::Region;
-use noodles_csi::BinningIndex;
-use noodles_tabix as tabix;
+use noodles_csi::{binning_index::ReferenceSequenceExt, BinningIndex};
 use tokio::io::{self, AsyncBufRead, AsyncBufReadExt, AsyncRead, AsyncSeek};
 
 use self::query::query;
@@ -288,14 +287,18 @@ where
     /// # Ok(())
     /// # }
     /// ```
-    pub fn query<'r>(
+    pub fn query<'r, I, RS>(
         &'r mut self,
         header: &'r Header,
-        index: &tabix::Index,
+        index: &I,
         region: &Region,
-    ) -> io::Result<impl Stream<Item = io::Result<Record>> + 'r> {
+    ) -> io::Result<impl Stream<Item = io::Result<Record>> + 'r>
+    where
+        I: BinningIndex<RS>,
+        RS: ReferenceSequenceExt,
+    {
         let (reference_sequence_id, reference_sequence_name, interval) =
-            resolve_region(index, region)?;
+            resolve_region(header, index, region)?;
 
         let chunks = index.query(reference_sequence_id, interval)?;
 
@@ -26,6 +26,7 @@ mod r#async;
 pub mod header;
 pub mod reader;
 pub mod record;
+pub mod subset;
 mod writer;
 
 pub use self::{header::Header, reader::Reader, record::Record, writer::Writer};
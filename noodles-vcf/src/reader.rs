@@ -10,8 +10,7 @@ use std::io::{self, BufRead, Read, Seek};
 use memchr::memchr;
 use noodles_bgzf as bgzf;
 use noodles_core::{region::Interval, Region};
-use noodles_csi::BinningIndex;
-use noodles_tabix as tabix;
+use noodles_csi::{binning_index::ReferenceSequenceExt, BinningIndex};
 
 use super::Header;
 
@@ -288,14 +287,18 @@ where
     /// }
     /// Ok::<_, Box<dyn std::error::Error>>(())
     /// ```
-    pub fn query<'r, 'h>(
+    pub fn query<'r, 'h, I, RS>(
         &'r mut self,
         header: &'h Header,
-        index: &tabix::Index,
+        index: &I,
         region: &Region,
-    ) -> io::Result<Query<'r, 'h, R>> {
+    ) -> io::Result<Query<'r, 'h, R>>
+    where
+        I: BinningIndex<RS>,
+        RS: ReferenceSequenceExt,
+    {
         let (reference_sequence_id, reference_sequence_name, interval) =
-            resolve_region(index, region)?;
+            resolve_region(header, index, region)?;
 
         let chunks = index.query(reference_sequence_id, interval)?;
 
@@ -369,14 +372,21 @@ where
     }
 }
 
-pub(crate) fn resolve_region(
-    index: &tabix::Index,
+// Reference sequence IDs are resolved using the reference sequence names in the index, e.g., in
+// a tabix index, or, if the index does not store names, the contigs in the header.
+pub(crate) fn resolve_region<I, RS>(
+    header: &Header,
+    index: &I,
     region: &Region,
-) -> io::Result<(usize, String, Interval)> {
+) -> io::Result<(usize, String, Interval)>
+where
+    I: BinningIndex<RS>,
+    RS: ReferenceSequenceExt,
+{
     if let Some(r) = region.as_mapped() {
         let i = index
-            .reference_sequence_names()
-            .get_index_of(r.name())
+            .reference_sequence_id(r.name())
+            .unwrap_or_else(|| header.contigs().get_index_of(r.name()))
             .ok_or_else(|| {
                 io::Error::new(
                     io::ErrorKind::InvalidInput,
@@ -512,4 +522,39 @@ sq0\t13
 
         Ok(())
     }
+
+    #[test]
+    fn test_resolve_region() -> Result<(), Box<dyn std::error::Error>> {
+        use noodles_csi as csi;
+        use noodles_tabix as tabix;
+
+        use crate::header::Contig;
+
+        let header = Header::builder()
+            .add_contig(Contig::new("sq0"))
+            .add_contig(Contig::new("sq1"))
+            .build();
+
+        let region = Region::mapped("sq1", 8..=13);
+
+        // The reference sequence names in a tabix index are not necessarily in header order.
+        let index = tabix::Index::builder()
+            .set_reference_sequence_names(["sq1", "sq0"].iter().map(|s| s.to_string()).collect())
+            .build();
+        let (i, name, _) = resolve_region(&header, &index, &region)?;
+        assert_eq!(i, 0);
+        assert_eq!(name, "sq1");
+
+        let index = csi::Index::default();
+        let (i, _, _) = resolve_region(&header, &index, &region)?;
+        assert_eq!(i, 1);
+
+        let region = Region::mapped("sq2", 8..=13);
+        assert!(matches!(
+            resolve_region(&header, &index, &region),
+            Err(e) if e.kind() == io::ErrorKind::InvalidInput
+        ));
+
+        Ok(())
+    }
 }
@@ -28,6 +28,16 @@ impl DerefMut for Genotype {
     }
 }
 
+impl fmt::Display for Genotype {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for allele in self.iter() {
+            write!(f, "{}", allele)?;
+        }
+
+        Ok(())
+    }
+}
+
 /// An error returned when a raw VCF record genotype value fails to parse.
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum ParseError {
@@ -109,6 +119,26 @@ impl fmt::Display for TryFromAllelesError {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_fmt() {
+        use allele::Phasing;
+
+        let genotype = Genotype(vec![
+            Allele::new(Some(0), None),
+            Allele::new(Some(1), Some(Phasing::Unphased)),
+        ]);
+        assert_eq!(genotype.to_string(), "0/1");
+
+        let genotype = Genotype(vec![
+            Allele::new(None, None),
+            Allele::new(Some(2), Some(Phasing::Phased)),
+        ]);
+        assert_eq!(genotype.to_string(), ".|2");
+
+        let genotype = Genotype(vec![Allele::new(Some(1), None)]);
+        assert_eq!(genotype.to_string(), "1");
+    }
+
     #[test]
     fn test_from_str() {
         use allele::Phasing;
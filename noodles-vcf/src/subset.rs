@@ -0,0 +1,779 @@
+//! VCF sample subsetting.
+//!
+//! A [`Subsetter`] projects records onto a subset of the samples in a header. It builds the
+//! subsetted header and rewrites the genotypes of each record to the selected samples, in the
+//! selected order. It can optionally recompute the `AC`, `AN`, and `AF` INFO fields and remove
+//! alternate alleles that are no longer observed.
+//!
+//! BCF records can be subsetted by first converting them to VCF records and then writing them
+//! with the subsetted header.
+//!
+//! # Examples
+//!
+//! ```
+//! use noodles_vcf::{self as vcf, subset};
+//!
+//! let header = vcf::Header::builder()
+//!     .add_sample_name("sample0")
+//!     .add_sample_name("sample1")
+//!     .build();
+//!
+//! let subsetter = subset::Builder::default()
+//!     .set_sample_names(["sample1"])
+//!     .set_trim_alternate_bases(true)
+//!     .build(&header)?;
+//!
+//! let mut record = vcf::Record::try_from_str(
+//!     "sq0\t1\t.\tA\tC,G\t.\tPASS\t.\tGT\t0/1\t0/2",
+//!     &header,
+//! )?;
+//!
+//! subsetter.subset_record(&mut record)?;
+//!
+//! assert_eq!(record.to_string(), "sq0\t1\t.\tA\tG\t.\tPASS\t.\tGT\t0/1");
+//! # Ok::<_, Box<dyn std::error::Error>>(())
+//! ```
+
+mod builder;
+
+pub use self::builder::{BuildError, Builder};
+
+use std::{collections::HashMap, error, fmt};
+
+use crate::{
+    header::{self, Number, SampleNames},
+    record::{
+        alternate_bases::Allele,
+        genotypes::{
+            genotype::{self, field},
+            Genotype,
+        },
+        info, Genotypes, Info,
+    },
+    Header, Record,
+};
+
+/// A VCF sample subsetter.
+#[derive(Debug)]
+pub struct Subsetter {
+    header: Header,
+    sample_indices: Vec<usize>,
+    info_numbers: HashMap<String, Number>,
+    format_numbers: HashMap<String, Number>,
+    update_allele_counts: bool,
+    trim_alternate_bases: bool,
+}
+
+/// An error returned when a VCF record fails to be subsetted.
+#[derive(Clone, Debug, PartialEq)]
+pub enum SubsetError {
+    /// A selected sample is missing from the record.
+    MissingSample(usize),
+    /// A genotype (`GT`) field value is invalid.
+    InvalidGenotype(genotype::GenotypeError),
+    /// A genotype allele position is not a reference or alternate allele.
+    InvalidAllelePosition(usize),
+    /// An INFO field value does not match the number of alleles.
+    InvalidInfoFieldValue(info::field::Key),
+    /// A genotype field value does not match the number of alleles.
+    InvalidGenotypeFieldValue(field::Key),
+}
+
+impl error::Error for SubsetError {}
+
+impl fmt::Display for SubsetError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingSample(i) => write!(f, "missing sample at index {}", i),
+            Self::InvalidGenotype(e) => write!(f, "invalid genotype: {}", e),
+            Self::InvalidAllelePosition(i) => write!(f, "invalid allele position: {}", i),
+            Self::InvalidInfoFieldValue(key) => write!(f, "invalid INFO field value: {}", key),
+            Self::InvalidGenotypeFieldValue(key) => {
+                write!(f, "invalid genotype field value: {}", key)
+            }
+        }
+    }
+}
+
+impl Subsetter {
+    /// Returns a builder to create a VCF sample subsetter.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::subset::Subsetter;
+    /// let builder = Subsetter::builder();
+    /// ```
+    pub fn builder() -> Builder {
+        Builder::default()
+    }
+
+    pub(crate) fn new(
+        header: &Header,
+        sample_names: SampleNames,
+        sample_indices: Vec<usize>,
+        update_allele_counts: bool,
+        trim_alternate_bases: bool,
+    ) -> Self {
+        use info::field::Key;
+
+        let mut header = header.clone();
+
+        header
+            .samples_mut()
+            .retain(|id, _| sample_names.contains(id));
+        *header.sample_names_mut() = sample_names;
+
+        if update_allele_counts {
+            for key in [Key::AlleleCount, Key::TotalAlleleCount] {
+                if !header.infos().contains_key(&key) {
+                    header
+                        .infos_mut()
+                        .insert(key.clone(), header::Info::from(key));
+                }
+            }
+        }
+
+        // Keys of nonstandard fields parsed from records do not carry the header number, so
+        // numbers are looked up by name.
+        let info_numbers = header
+            .infos()
+            .iter()
+            .map(|(key, info)| (key.as_ref().into(), info.number()))
+            .collect();
+
+        let format_numbers = header
+            .formats()
+            .iter()
+            .map(|(key, format)| (key.as_ref().into(), format.number()))
+            .collect();
+
+        Self {
+            header,
+            sample_indices,
+            info_numbers,
+            format_numbers,
+            update_allele_counts,
+            trim_alternate_bases,
+        }
+    }
+
+    /// Returns the subsetted header.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::{self as vcf, subset};
+    ///
+    /// let header = vcf::Header::builder()
+    ///     .add_sample_name("sample0")
+    ///     .add_sample_name("sample1")
+    ///     .build();
+    ///
+    /// let subsetter = subset::Builder::default()
+    ///     .set_sample_indices([1])
+    ///     .build(&header)?;
+    ///
+    /// let sample_names = subsetter.header().sample_names();
+    /// assert_eq!(sample_names.len(), 1);
+    /// assert!(sample_names.contains("sample1"));
+    /// # Ok::<_, subset::BuildError>(())
+    /// ```
+    pub fn header(&self) -> &Header {
+        &self.header
+    }
+
+    /// Returns the indices of the selected samples in the original header.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::{self as vcf, subset};
+    ///
+    /// let header = vcf::Header::builder()
+    ///     .add_sample_name("sample0")
+    ///     .add_sample_name("sample1")
+    ///     .build();
+    ///
+    /// let subsetter = subset::Builder::default()
+    ///     .set_sample_names(["sample1", "sample0"])
+    ///     .build(&header)?;
+    ///
+    /// assert_eq!(subsetter.sample_indices(), [1, 0]);
+    /// # Ok::<_, subset::BuildError>(())
+    /// ```
+    pub fn sample_indices(&self) -> &[usize] {
+        &self.sample_indices
+    }
+
+    /// Subsets the genotypes of a record to the selected samples.
+    ///
+    /// Records without genotypes are not changed. If none of the selected samples have a genotype
+    /// (`GT`) value, alternate alleles are not trimmed and allele counts are not updated. If the
+    /// record fails to be subsetted, it is not modified.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::{self as vcf, subset};
+    ///
+    /// let header = vcf::Header::builder()
+    ///     .add_sample_name("sample0")
+    ///     .add_sample_name("sample1")
+    ///     .build();
+    ///
+    /// let subsetter = subset::Builder::default()
+    ///     .set_sample_names(["sample1"])
+    ///     .set_update_allele_counts(true)
+    ///     .build(&header)?;
+    ///
+    /// let mut record = vcf::Record::try_from_str(
+    ///     "sq0\t1\t.\tA\tC\t.\tPASS\t.\tGT\t0/1\t1/1",
+    ///     &header,
+    /// )?;
+    ///
+    /// subsetter.subset_record(&mut record)?;
+    ///
+    /// assert_eq!(record.to_string(), "sq0\t1\t.\tA\tC\t.\tPASS\tAC=2;AN=2\tGT\t1/1");
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn subset_record(&self, record: &mut Record) -> Result<(), SubsetError> {
+        if record.genotypes().is_empty() {
+            return Ok(());
+        }
+
+        let mut genotypes = self
+            .sample_indices
+            .iter()
+            .map(|&i| {
+                record
+                    .genotypes()
+                    .get(i)
+                    .cloned()
+                    .ok_or(SubsetError::MissingSample(i))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut genotype_values = genotypes
+            .iter()
+            .map(|genotype| genotype.genotype().transpose())
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(SubsetError::InvalidGenotype)?;
+
+        let allele_count = record.alternate_bases().len() + 1;
+        let mut is_observed = vec![false; allele_count];
+        is_observed[0] = true;
+
+        for position in genotype_values
+            .iter()
+            .flatten()
+            .flat_map(|genotype| genotype.iter())
+            .filter_map(|allele| allele.position())
+        {
+            match is_observed.get_mut(position) {
+                Some(is_observed) => *is_observed = true,
+                None => return Err(SubsetError::InvalidAllelePosition(position)),
+            }
+        }
+
+        let mut alternate_bases = record.alternate_bases().clone();
+        let mut info = record.info().clone();
+
+        // Without any genotype values, the observed alleles are unknown, so alleles are not
+        // trimmed and allele counts are not updated.
+        let has_genotype_values = genotype_values.iter().any(Option::is_some);
+
+        if self.trim_alternate_bases && has_genotype_values && is_observed.contains(&false) {
+            let kept_positions: Vec<_> = (0..allele_count).filter(|&i| is_observed[i]).collect();
+
+            info = self.trim_info(&info, &kept_positions, allele_count)?;
+
+            for (genotype, genotype_value) in genotypes.iter_mut().zip(&mut genotype_values) {
+                self.trim_genotype(genotype, genotype_value, &kept_positions, allele_count)?;
+            }
+
+            alternate_bases = kept_positions
+                .iter()
+                .skip(1)
+                .map(|&i| alternate_bases[i - 1].clone())
+                .collect::<Vec<Allele>>()
+                .into();
+        }
+
+        if self.update_allele_counts && has_genotype_values {
+            update_allele_counts(&mut info, alternate_bases.len(), &genotype_values);
+        }
+
+        *record.alternate_bases_mut() = alternate_bases;
+        *record.info_mut() = info;
+
+        *record.genotypes_mut() = if genotypes.is_empty() {
+            Genotypes::default()
+        } else {
+            Genotypes::new(record.genotypes().keys().clone(), genotypes)
+        };
+
+        Ok(())
+    }
+
+    fn trim_info(
+        &self,
+        info: &Info,
+        kept_positions: &[usize],
+        allele_count: usize,
+    ) -> Result<Info, SubsetError> {
+        let mut fields = Vec::with_capacity(info.len());
+
+        for field in info.values() {
+            let number = self
+                .info_numbers
+                .get(field.key().as_ref())
+                .copied()
+                .unwrap_or_else(|| field.key().number());
+
+            let selection = select_alleles(number, kept_positions, allele_count, 2);
+
+            match (selection, field.value()) {
+                (Some((indices, len)), Some(value)) => {
+                    let value = trim_info_field_value(value, &indices, len)
+                        .ok_or_else(|| SubsetError::InvalidInfoFieldValue(field.key().clone()))?;
+
+                    // Fields with no remaining values are removed.
+                    if let Some(value) = value {
+                        fields.push(info::Field::new(field.key().clone(), Some(value)));
+                    }
+                }
+                _ => fields.push(field.clone()),
+            }
+        }
+
+        let mut info = Info::default();
+
+        for field in fields {
+            info.insert(field);
+        }
+
+        Ok(info)
+    }
+
+    fn trim_genotype(
+        &self,
+        genotype: &mut Genotype,
+        genotype_value: &mut Option<field::value::Genotype>,
+        kept_positions: &[usize],
+        allele_count: usize,
+    ) -> Result<(), SubsetError> {
+        let ploidy = genotype_value.as_ref().map(|g| g.len()).unwrap_or(2);
+
+        if let Some(genotype_value) = genotype_value {
+            for allele in genotype_value.iter_mut() {
+                if let Some(position) = allele.position() {
+                    let new_position = kept_positions
+                        .iter()
+                        .position(|&i| i == position)
+                        .ok_or(SubsetError::InvalidAllelePosition(position))?;
+
+                    *allele.position_mut() = Some(new_position);
+                }
+            }
+        }
+
+        for (key, field) in genotype.iter_mut() {
+            if key == &field::Key::Genotype {
+                if let Some(genotype_value) = genotype_value {
+                    *field.value_mut() = Some(field::Value::String(genotype_value.to_string()));
+                }
+
+                continue;
+            }
+
+            let number = self
+                .format_numbers
+                .get(key.as_ref())
+                .copied()
+                .unwrap_or_else(|| key.number());
+
+            let selection = select_alleles(number, kept_positions, allele_count, ploidy);
+
+            if let (Some((indices, len)), Some(value)) = (selection, field.value()) {
+                let value = trim_genotype_field_value(value, &indices, len)
+                    .ok_or_else(|| SubsetError::InvalidGenotypeFieldValue(key.clone()))?;
+
+                *field.value_mut() = value;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+// Returns the indices of the values to keep for a field with the given number and the expected
+// number of values, or `None` if the number does not depend on the alleles.
+fn select_alleles(
+    number: Number,
+    kept_positions: &[usize],
+    allele_count: usize,
+    ploidy: usize,
+) -> Option<(Vec<usize>, usize)> {
+    match number {
+        Number::A => {
+            let indices = kept_positions.iter().skip(1).map(|&i| i - 1).collect();
+            Some((indices, allele_count - 1))
+        }
+        Number::R => Some((kept_positions.to_vec(), allele_count)),
+        Number::G => {
+            let indices = genotype_indices(kept_positions, ploidy);
+            let len = binomial(allele_count + ploidy - 1, ploidy);
+            Some((indices, len))
+        }
+        _ => None,
+    }
+}
+
+// Returns the sorted indices of the genotypes that only contain the given alleles.
+//
+// The index of a genotype with sorted allele positions a_1 <= ... <= a_p is
+// sum_{i=1}^{p} binomial(a_i + i - 1, i) (VCF 4.3 § 1.6.2 "Genotype fields").
+fn genotype_indices(kept_positions: &[usize], ploidy: usize) -> Vec<usize> {
+    fn push_indices(
+        kept_positions: &[usize],
+        start: usize,
+        ploidy: usize,
+        alleles: &mut Vec<usize>,
+        indices: &mut Vec<usize>,
+    ) {
+        if alleles.len() == ploidy {
+            let index = alleles
+                .iter()
+                .enumerate()
+                .map(|(i, &a)| binomial(a + i, i + 1))
+                .sum();
+
+            indices.push(index);
+
+            return;
+        }
+
+        for j in start..kept_positions.len() {
+            alleles.push(kept_positions[j]);
+            push_indices(kept_positions, j, ploidy, alleles, indices);
+            alleles.pop();
+        }
+    }
+
+    let mut indices = Vec::new();
+    let mut alleles = Vec::with_capacity(ploidy);
+    push_indices(kept_positions, 0, ploidy, &mut alleles, &mut indices);
+    indices.sort_unstable();
+    indices
+}
+
+fn binomial(n: usize, k: usize) -> usize {
+    if k > n {
+        return 0;
+    }
+
+    (0..k).fold(1, |acc, i| acc * (n - i) / (i + 1))
+}
+
+// Returns the values at the given indices, or `None` if the number of values is not the expected
+// length. The selected values are `None` if no values remain.
+fn select<T>(values: &[T], indices: &[usize], len: usize) -> Option<Option<Vec<T>>>
+where
+    T: Clone,
+{
+    if values.len() != len {
+        return None;
+    }
+
+    let values: Vec<_> = indices.iter().map(|&i| values[i].clone()).collect();
+
+    if values.is_empty() {
+        Some(None)
+    } else {
+        Some(Some(values))
+    }
+}
+
+fn trim_info_field_value(
+    value: &info::field::Value,
+    indices: &[usize],
+    len: usize,
+) -> Option<Option<info::field::Value>> {
+    use info::field::Value;
+
+    let value = match value {
+        Value::Integer(n) => select(&[*n], indices, len)?.map(|vs| Value::Integer(vs[0])),
+        Value::Float(n) => select(&[*n], indices, len)?.map(|vs| Value::Float(vs[0])),
+        Value::Flag => Some(Value::Flag),
+        Value::Character(c) => select(&[*c], indices, len)?.map(|vs| Value::Character(vs[0])),
+        Value::String(s) => {
+            let values: Vec<_> = s.split(',').collect();
+            select(&values, indices, len)?.map(|vs| Value::String(vs.join(",")))
+        }
+        Value::IntegerArray(values) => select(values, indices, len)?.map(Value::IntegerArray),
+        Value::FloatArray(values) => select(values, indices, len)?.map(Value::FloatArray),
+        Value::CharacterArray(values) => select(values, indices, len)?.map(Value::CharacterArray),
+        Value::StringArray(values) => select(values, indices, len)?.map(Value::StringArray),
+    };
+
+    Some(value)
+}
+
+fn trim_genotype_field_value(
+    value: &field::Value,
+    indices: &[usize],
+    len: usize,
+) -> Option<Option<field::Value>> {
+    use field::Value;
+
+    let value = match value {
+        Value::Integer(n) => select(&[*n], indices, len)?.map(|vs| Value::Integer(vs[0])),
+        Value::Float(n) => select(&[*n], indices, len)?.map(|vs| Value::Float(vs[0])),
+        Value::Character(c) => select(&[*c], indices, len)?.map(|vs| Value::Character(vs[0])),
+        Value::String(s) => {
+            let values: Vec<_> = s.split(',').collect();
+            select(&values, indices, len)?.map(|vs| Value::String(vs.join(",")))
+        }
+        Value::IntegerArray(values) => select(values, indices, len)?.map(Value::IntegerArray),
+        Value::FloatArray(values) => select(values, indices, len)?.map(Value::FloatArray),
+        Value::CharacterArray(values) => select(values, indices, len)?.map(Value::CharacterArray),
+        Value::StringArray(values) => select(values, indices, len)?.map(Value::StringArray),
+    };
+
+    Some(value)
+}
+
+fn update_allele_counts(
+    info: &mut Info,
+    alternate_allele_count: usize,
+    genotype_values: &[Option<field::value::Genotype>],
+) {
+    use info::field::{Key, Value};
+
+    let mut allele_counts = vec![0; alternate_allele_count];
+    let mut total_allele_count = 0;
+
+    for position in genotype_values
+        .iter()
+        .flatten()
+        .flat_map(|genotype| genotype.iter())
+        .filter_map(|allele| allele.position())
+    {
+        total_allele_count += 1;
+
+        if position > 0 {
+            allele_counts[position - 1] += 1;
+        }
+    }
+
+    let has_allele_frequencies = info.get(&Key::AlleleFrequencies).is_some();
+
+    if alternate_allele_count > 0 && total_allele_count > 0 && has_allele_frequencies {
+        let allele_frequencies = allele_counts
+            .iter()
+            .map(|&n| n as f32 / total_allele_count as f32)
+            .collect();
+
+        info.insert(info::Field::new(
+            Key::AlleleFrequencies,
+            Some(Value::FloatArray(allele_frequencies)),
+        ));
+    } else {
+        info.as_mut().shift_remove(&Key::AlleleFrequencies);
+    }
+
+    if alternate_allele_count > 0 {
+        info.insert(info::Field::new(
+            Key::AlleleCount,
+            Some(Value::IntegerArray(allele_counts)),
+        ));
+    } else {
+        info.as_mut().shift_remove(&Key::AlleleCount);
+    }
+
+    info.insert(info::Field::new(
+        Key::TotalAlleleCount,
+        Some(Value::Integer(total_allele_count)),
+    ));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn build_header() -> Header {
+        use crate::header::{Format, Info};
+
+        Header::builder()
+            .add_info(Info::from(info::field::Key::AlleleCount))
+            .add_info(Info::from(info::field::Key::TotalAlleleCount))
+            .add_info(Info::from(info::field::Key::AlleleFrequencies))
+            .add_format(Format::from(field::Key::Genotype))
+            .add_format(Format::from(field::Key::ReadDepths))
+            .add_format(Format::from(field::Key::RoundedGenotypeLikelihoods))
+            .add_sample_name("sample0")
+            .add_sample_name("sample1")
+            .add_sample_name("sample2")
+            .build()
+    }
+
+    #[test]
+    fn test_subset_record() -> Result<(), Box<dyn std::error::Error>> {
+        let header = build_header();
+
+        let subsetter = Subsetter::builder()
+            .set_sample_names(["sample2", "sample0"])
+            .build(&header)?;
+
+        let mut record = Record::try_from_str(
+            "sq0\t1\t.\tA\tC,G\t.\tPASS\tAC=1,2;AN=6\tGT:AD\t0/1:5,3,0\t0/0:8,0,0\t2/2:0,0,9",
+            &header,
+        )?;
+
+        subsetter.subset_record(&mut record)?;
+
+        assert_eq!(
+            record.to_string(),
+            "sq0\t1\t.\tA\tC,G\t.\tPASS\tAC=1,2;AN=6\tGT:AD\t2/2:0,0,9\t0/1:5,3,0"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_subset_record_with_update_allele_counts_and_trim_alternate_bases(
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let header = build_header();
+
+        let subsetter = Subsetter::builder()
+            .set_sample_names(["sample2", "sample1"])
+            .set_update_allele_counts(true)
+            .set_trim_alternate_bases(true)
+            .build(&header)?;
+
+        let mut record = Record::try_from_str(
+            "sq0\t1\t.\tA\tC,G\t.\tPASS\tAC=1,2;AN=6;AF=0.25,0.5\tGT:AD:PL\t0/1:5,3,0:0,1,2,3,4,5\t0|0:8,0,0:0,10,20,30,40,50\t2|0:0,0,9:9,8,7,6,5,4",
+            &header,
+        )?;
+
+        subsetter.subset_record(&mut record)?;
+
+        assert_eq!(
+            record.to_string(),
+            "sq0\t1\t.\tA\tG\t.\tPASS\tAC=1;AN=4;AF=0.25\tGT:AD:PL\t1|0:0,9:9,6,4\t0|0:8,0:0,30,50"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_subset_record_with_no_observed_alternate_alleles(
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let header = build_header();
+
+        let subsetter = Subsetter::builder()
+            .set_sample_indices([1])
+            .set_update_allele_counts(true)
+            .set_trim_alternate_bases(true)
+            .build(&header)?;
+
+        let mut record = Record::try_from_str(
+            "sq0\t1\t.\tA\tC\t.\tPASS\tAC=1;AN=6;AF=0.166667\tGT:AD\t0/1:5,3\t0/.:8,0\t0/0:9,0",
+            &header,
+        )?;
+
+        subsetter.subset_record(&mut record)?;
+
+        assert_eq!(
+            record.to_string(),
+            "sq0\t1\t.\tA\t.\t.\tPASS\tAN=1\tGT:AD\t0/.:8"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_subset_record_with_no_genotype_values() -> Result<(), Box<dyn std::error::Error>> {
+        let header = build_header();
+
+        let subsetter = Subsetter::builder()
+            .set_sample_indices([1])
+            .set_update_allele_counts(true)
+            .set_trim_alternate_bases(true)
+            .build(&header)?;
+
+        let mut record = Record::try_from_str(
+            "sq0\t1\t.\tA\tC,G\t.\tPASS\tAC=1,2;AN=6\tAD\t5,3,0\t8,0,0\t0,0,9",
+            &header,
+        )?;
+
+        subsetter.subset_record(&mut record)?;
+
+        assert_eq!(
+            record.to_string(),
+            "sq0\t1\t.\tA\tC,G\t.\tPASS\tAC=1,2;AN=6\tAD\t8,0,0"
+        );
+
+        let mut record = Record::try_from_str(
+            "sq0\t1\t.\tA\tC\t.\tPASS\tAC=1;AN=6\tGT:AD\t0/1:5,3\t.:8,0\t0/0:9,0",
+            &header,
+        )?;
+
+        subsetter.subset_record(&mut record)?;
+
+        assert_eq!(
+            record.to_string(),
+            "sq0\t1\t.\tA\tC\t.\tPASS\tAC=1;AN=6\tGT:AD\t.:8,0"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_subset_record_with_invalid_values() -> Result<(), Box<dyn std::error::Error>> {
+        let header = build_header();
+
+        let subsetter = Subsetter::builder()
+            .set_sample_indices([0])
+            .set_trim_alternate_bases(true)
+            .build(&header)?;
+
+        let mut record = Record::try_from_str("sq0\t1\t.\tA\tC\t.\tPASS\t.\tGT\t0/2", &header)?;
+        assert_eq!(
+            subsetter.subset_record(&mut record),
+            Err(SubsetError::InvalidAllelePosition(2))
+        );
+
+        let mut record =
+            Record::try_from_str("sq0\t1\t.\tA\tC,G\t.\tPASS\t.\tGT:AD\t0/1:5,3", &header)?;
+        let expected = record.clone();
+        assert_eq!(
+            subsetter.subset_record(&mut record),
+            Err(SubsetError::InvalidGenotypeFieldValue(
+                field::Key::ReadDepths
+            ))
+        );
+        assert_eq!(record, expected);
+
+        let mut record = Record::try_from_str("sq0\t1\t.\tA\tC\t.\tPASS\t.\tGT\t0/1", &header)?;
+        let subsetter = Subsetter::builder()
+            .set_sample_indices([2])
+            .build(&header)?;
+        assert_eq!(
+            subsetter.subset_record(&mut record),
+            Err(SubsetError::MissingSample(2))
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_genotype_indices() {
+        assert_eq!(genotype_indices(&[0, 2], 1), [0, 2]);
+        assert_eq!(genotype_indices(&[0, 2], 2), [0, 3, 5]);
+        assert_eq!(genotype_indices(&[1, 2], 2), [2, 4, 5]);
+        assert_eq!(genotype_indices(&[0, 1], 3), [0, 1, 2, 3]);
+        assert_eq!(genotype_indices(&[0], 3), [0]);
+    }
+}
@@ -0,0 +1,233 @@
+use std::{error, fmt};
+
+use super::Subsetter;
+use crate::{header::SampleNames, Header};
+
+#[derive(Debug)]
+enum Samples {
+    Names(Vec<String>),
+    Indices(Vec<usize>),
+}
+
+/// A VCF sample subsetter builder.
+#[derive(Debug, Default)]
+pub struct Builder {
+    samples: Option<Samples>,
+    update_allele_counts: bool,
+    trim_alternate_bases: bool,
+}
+
+/// An error returned when a VCF sample subsetter fails to build.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum BuildError {
+    /// A sample name is not in the header.
+    MissingSampleName(String),
+    /// A sample index is out of range of the header sample names.
+    InvalidSampleIndex(usize),
+    /// A sample is selected more than once.
+    DuplicateSample(String),
+}
+
+impl error::Error for BuildError {}
+
+impl fmt::Display for BuildError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingSampleName(name) => write!(f, "missing sample name: {}", name),
+            Self::InvalidSampleIndex(i) => write!(f, "invalid sample index: {}", i),
+            Self::DuplicateSample(name) => write!(f, "duplicate sample: {}", name),
+        }
+    }
+}
+
+impl Builder {
+    /// Selects samples by name.
+    ///
+    /// The samples are written in the given order. This replaces any previous selection.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::subset;
+    /// let builder = subset::Builder::default().set_sample_names(["sample1", "sample0"]);
+    /// ```
+    pub fn set_sample_names<I, S>(mut self, sample_names: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let sample_names = sample_names.into_iter().map(|name| name.into()).collect();
+        self.samples = Some(Samples::Names(sample_names));
+        self
+    }
+
+    /// Selects samples by their 0-based index in the header sample names.
+    ///
+    /// The samples are written in the given order. This replaces any previous selection.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::subset;
+    /// let builder = subset::Builder::default().set_sample_indices([1, 0]);
+    /// ```
+    pub fn set_sample_indices<I>(mut self, sample_indices: I) -> Self
+    where
+        I: IntoIterator<Item = usize>,
+    {
+        let sample_indices = sample_indices.into_iter().collect();
+        self.samples = Some(Samples::Indices(sample_indices));
+        self
+    }
+
+    /// Sets whether to recompute the allele count (`AC`), total allele count (`AN`), and allele
+    /// frequencies (`AF`) INFO fields from the genotypes of the selected samples.
+    ///
+    /// `AC` and `AN` are added to the header if they are not defined. `AF` is only updated in
+    /// records that have it. Records where none of the selected samples have a genotype (`GT`)
+    /// value are not updated. By default, INFO fields are not changed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::subset;
+    /// let builder = subset::Builder::default().set_update_allele_counts(true);
+    /// ```
+    pub fn set_update_allele_counts(mut self, update_allele_counts: bool) -> Self {
+        self.update_allele_counts = update_allele_counts;
+        self
+    }
+
+    /// Sets whether to remove alternate alleles that are not observed in the genotypes of the
+    /// selected samples.
+    ///
+    /// Genotype allele positions and `Number=A`, `Number=R`, and `Number=G` INFO and FORMAT field
+    /// values are updated to match the remaining alleles. Records where none of the selected
+    /// samples have a genotype (`GT`) value are not trimmed. By default, all alternate alleles are
+    /// kept.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::subset;
+    /// let builder = subset::Builder::default().set_trim_alternate_bases(true);
+    /// ```
+    pub fn set_trim_alternate_bases(mut self, trim_alternate_bases: bool) -> Self {
+        self.trim_alternate_bases = trim_alternate_bases;
+        self
+    }
+
+    /// Builds a VCF sample subsetter for records described by the given header.
+    ///
+    /// If no samples are selected, all samples are kept in their original order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::{self as vcf, subset};
+    ///
+    /// let header = vcf::Header::builder()
+    ///     .add_sample_name("sample0")
+    ///     .add_sample_name("sample1")
+    ///     .build();
+    ///
+    /// let subsetter = subset::Builder::default()
+    ///     .set_sample_names(["sample1"])
+    ///     .build(&header)?;
+    ///
+    /// assert_eq!(subsetter.sample_indices(), [1]);
+    /// # Ok::<_, subset::BuildError>(())
+    /// ```
+    pub fn build(self, header: &Header) -> Result<Subsetter, BuildError> {
+        let header_sample_names = header.sample_names();
+
+        let sample_indices: Vec<_> = match self.samples {
+            Some(Samples::Names(sample_names)) => sample_names
+                .into_iter()
+                .map(|name| {
+                    header_sample_names
+                        .get_index_of(&name)
+                        .ok_or(BuildError::MissingSampleName(name))
+                })
+                .collect::<Result<_, _>>()?,
+            Some(Samples::Indices(sample_indices)) => sample_indices,
+            None => (0..header_sample_names.len()).collect(),
+        };
+
+        let mut sample_names = SampleNames::with_capacity(sample_indices.len());
+
+        for &i in &sample_indices {
+            let name = header_sample_names
+                .get_index(i)
+                .ok_or(BuildError::InvalidSampleIndex(i))?;
+
+            if !sample_names.insert(name.clone()) {
+                return Err(BuildError::DuplicateSample(name.clone()));
+            }
+        }
+
+        Ok(Subsetter::new(
+            header,
+            sample_names,
+            sample_indices,
+            self.update_allele_counts,
+            self.trim_alternate_bases,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build() -> Result<(), BuildError> {
+        let header = Header::builder()
+            .add_sample_name("sample0")
+            .add_sample_name("sample1")
+            .add_sample_name("sample2")
+            .build();
+
+        let subsetter = Builder::default().build(&header)?;
+        assert_eq!(subsetter.sample_indices(), [0, 1, 2]);
+
+        let subsetter = Builder::default()
+            .set_sample_names(["sample2", "sample0"])
+            .build(&header)?;
+        assert_eq!(subsetter.sample_indices(), [2, 0]);
+
+        let expected: SampleNames = [String::from("sample2"), String::from("sample0")]
+            .into_iter()
+            .collect();
+        assert_eq!(subsetter.header().sample_names(), &expected);
+
+        let subsetter = Builder::default().set_sample_indices([1]).build(&header)?;
+        assert_eq!(subsetter.sample_indices(), [1]);
+
+        assert_eq!(
+            Builder::default()
+                .set_sample_names(["sample3"])
+                .build(&header)
+                .unwrap_err(),
+            BuildError::MissingSampleName(String::from("sample3"))
+        );
+
+        assert_eq!(
+            Builder::default()
+                .set_sample_indices([3])
+                .build(&header)
+                .unwrap_err(),
+            BuildError::InvalidSampleIndex(3)
+        );
+
+        assert_eq!(
+            Builder::default()
+                .set_sample_indices([1, 1])
+                .build(&header)
+                .unwrap_err(),
+            BuildError::DuplicateSample(String::from("sample1"))
+        );
+
+        Ok(())
+    }
+}